@@ -73,6 +73,7 @@ fn main() -> Result<()> {
                     initial_dao_balance: None,
                 },
                 active_threshold: None,
+                power_transform: None,
             })?,
             funds: vec![],
             admin: Some(Admin::CoreModule {}),
@@ -99,6 +100,7 @@ fn main() -> Result<()> {
                                 },
                                 amount: Uint128::new(1000000000),
                                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                                scaling: None,
                             }),
                             submission_policy: PreProposeSubmissionPolicy::Specific {
                                 dao_members: true,
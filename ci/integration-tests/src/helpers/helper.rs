@@ -56,6 +56,7 @@ pub fn create_dao(
                     initial_dao_balance: None,
                 },
                 active_threshold: None,
+                power_transform: None,
             })?,
             admin: Some(Admin::CoreModule {}),
             label: "DAO DAO Voting Module".to_string(),
@@ -83,6 +84,7 @@ pub fn create_dao(
                                 },
                                 amount: DEPOSIT_AMOUNT,
                                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                                scaling: None,
                             }),
                             submission_policy: PreProposeSubmissionPolicy::Specific {
                                 dao_members: true,
@@ -252,6 +254,7 @@ pub fn create_proposal(
                     description: "desc".to_string(),
                     msgs,
                     vote: None,
+                    execution_plan: None,
                 },
             },
             key,
@@ -223,7 +223,8 @@ fn instantiate_with_no_admin(chain: &mut Chain) {
             description: "A DAO that makes DAO tooling".to_string(),
             image_url: None,
             automatically_add_cw20s: false,
-            automatically_add_cw721s: false
+            automatically_add_cw721s: false,
+            guardian: None,
         }
     );
 }
@@ -257,7 +258,8 @@ fn instantiate_with_admin(chain: &mut Chain) {
             description: "A DAO that makes DAO tooling".to_string(),
             image_url: None,
             automatically_add_cw20s: false,
-            automatically_add_cw721s: false
+            automatically_add_cw721s: false,
+            guardian: None,
         }
     );
 
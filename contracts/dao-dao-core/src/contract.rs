@@ -1,30 +1,55 @@
+use cosmwasm_schema::serde::de::IgnoredAny;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
-    Order, Reply, Response, StdError, StdResult, SubMsg, WasmMsg,
+    from_json, to_json_binary, Addr, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env,
+    Event, MessageInfo, Order, Reply, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_denom::UncheckedDenom;
 use cw_paginate_storage::{paginate_map, paginate_map_keys, paginate_map_values};
-use cw_storage_plus::Map;
+use cw_storage_plus::{Bound, Map};
 use cw_utils::{parse_reply_instantiate_data, Duration};
 use dao_interface::{
     msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, QueryMsg},
+    proposal::VetoMsg,
     query::{
         AdminNominationResponse, Cw20BalanceResponse, DaoURIResponse, DumpStateResponse,
-        GetItemResponse, PauseInfoResponse, ProposalModuleCountResponse, SubDao,
+        GetItemResponse, GetNamespacedItemResponse, GuardianAction, PauseInfoResponse,
+        ProposalModuleCountResponse, ProposalModuleVersion, RemainingBudgetResponse, RoleResponse,
+        SubDao,
     },
+    role::Role,
     state::{
-        Admin, Config, ModuleInstantiateCallback, ModuleInstantiateInfo, ProposalModule,
+        Admin, Config, Guardian, ModuleInstantiateCallback, ModuleInstantiateInfo, ProposalModule,
         ProposalModuleStatus,
     },
+    timelock::{QueuedExecution, Timelock},
+    treasury::{SpendingCap, SubDaoBudget, TreasuryAssetType, TreasuryBalanceKey, TreasuryHolding},
     voting,
 };
 
 use crate::error::ContractError;
+use crate::roles::{check_role_msg, nft_spend, record_spend, validate_role};
 use crate::state::{
-    ACTIVE_PROPOSAL_MODULE_COUNT, ADMIN, CONFIG, CW20_LIST, CW721_LIST, ITEMS, NOMINATED_ADMIN,
-    PAUSED, PROPOSAL_MODULES, SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT, VOTING_MODULE,
+    ACTIVE_PROPOSAL_MODULE_COUNT, ADMIN, CONFIG, CW20_LIST, CW721_LIST, DRAINING_SCAN_MARKS,
+    GUARDIAN_PAUSE, GUARDIAN_PAUSE_COOLDOWN, ITEMS, MODULE_SUSPENSIONS, NAMESPACED_ITEMS,
+    NAMESPACE_WRITERS, NOMINATED_ADMIN, PAUSED, PROPOSAL_MODULES, QUEUED_EXECUTIONS,
+    QUEUED_EXECUTION_COUNT, ROLES, ROLE_HOLDERS, ROLE_SPENDING, SPENDING_CAPS, SUBDAO_BUDGETS,
+    SUBDAO_LIST, TIMELOCK, TOTAL_PROPOSAL_MODULE_COUNT, TREASURY_HOLDINGS,
+    UPGRADING_PROPOSAL_MODULE, VOTING_MODULE,
+};
+use crate::subdaos::{
+    sub_dao_budgets, sub_dao_tree, DEFAULT_SUBDAO_TREE_DEPTH, MAX_SUBDAO_TREE_DEPTH,
+};
+use crate::timelock::{is_timelocked, validate_timelock};
+use crate::treasury::{
+    check_spending_caps, holding_balance, msg_spends, spent_in_window, treasury_balances,
+    validate_spending_cap,
+};
+use crate::upgrades::{
+    copy_config_msg, find_open_proposal, ProposalScan, DEFAULT_DRAIN_SCAN_LIMIT,
+    MAX_DRAIN_SCAN_LIMIT,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-dao-core";
@@ -33,6 +58,7 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const PROPOSAL_MODULE_REPLY_ID: u64 = 0;
 const VOTE_MODULE_INSTANTIATE_REPLY_ID: u64 = 1;
 const VOTE_MODULE_UPDATE_REPLY_ID: u64 = 2;
+const PROPOSAL_MODULE_UPGRADE_REPLY_ID: u64 = 3;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -50,6 +76,7 @@ pub fn instantiate(
         automatically_add_cw20s: msg.automatically_add_cw20s,
         automatically_add_cw721s: msg.automatically_add_cw721s,
         dao_uri: msg.dao_uri,
+        guardian: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -119,18 +146,38 @@ pub fn execute(
     }
 
     match msg {
-        ExecuteMsg::ExecuteAdminMsgs { msgs } => {
-            execute_admin_msgs(deps.as_ref(), info.sender, msgs)
-        }
+        ExecuteMsg::ExecuteAdminMsgs { msgs } => execute_admin_msgs(deps, env, info.sender, msgs),
         ExecuteMsg::ExecuteProposalHook { msgs } => {
-            execute_proposal_hook(deps.as_ref(), info.sender, msgs)
+            execute_proposal_hook(deps, env, info.sender, msgs)
         }
         ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
         ExecuteMsg::Unpause {} => execute_unpause(deps, info.sender),
+        ExecuteMsg::GuardianPause { duration } => {
+            execute_guardian_pause(deps, env, info.sender, duration)
+        }
+        ExecuteMsg::GuardianSuspendModule { address, duration } => {
+            execute_guardian_suspend_module(deps, env, info.sender, address, duration)
+        }
+        ExecuteMsg::LiftModuleSuspension { address } => {
+            execute_lift_module_suspension(deps, env, info.sender, address)
+        }
         ExecuteMsg::Receive(_) => execute_receive_cw20(deps, info.sender),
         ExecuteMsg::ReceiveNft(_) => execute_receive_cw721(deps, info.sender),
         ExecuteMsg::RemoveItem { key } => execute_remove_item(deps, env, info.sender, key),
         ExecuteMsg::SetItem { key, value } => execute_set_item(deps, env, info.sender, key, value),
+        ExecuteMsg::SetNamespacedItem {
+            namespace,
+            key,
+            value,
+        } => execute_set_namespaced_item(deps, env, info.sender, namespace, key, value),
+        ExecuteMsg::RemoveNamespacedItem { namespace, key } => {
+            execute_remove_namespaced_item(deps, env, info.sender, namespace, key)
+        }
+        ExecuteMsg::UpdateNamespaceWriters {
+            namespace,
+            to_add,
+            to_remove,
+        } => execute_update_namespace_writers(deps, env, info.sender, namespace, to_add, to_remove),
         ExecuteMsg::UpdateConfig { config } => {
             execute_update_config(deps, env, info.sender, config)
         }
@@ -146,6 +193,23 @@ pub fn execute(
         ExecuteMsg::UpdateProposalModules { to_add, to_disable } => {
             execute_update_proposal_modules(deps, env, info.sender, to_add, to_disable)
         }
+        ExecuteMsg::UpgradeProposalModule {
+            address,
+            new_module,
+            drain_duration,
+            copy_config,
+        } => execute_upgrade_proposal_module(
+            deps,
+            env,
+            info.sender,
+            address,
+            new_module,
+            drain_duration,
+            copy_config,
+        ),
+        ExecuteMsg::FinishDraining { address, limit } => {
+            execute_finish_draining(deps, env, address, limit)
+        }
         ExecuteMsg::NominateAdmin { admin } => {
             execute_nominate_admin(deps, env, info.sender, admin)
         }
@@ -156,7 +220,122 @@ pub fn execute(
         ExecuteMsg::UpdateSubDaos { to_add, to_remove } => {
             execute_update_sub_daos_list(deps, env, info.sender, to_add, to_remove)
         }
+        ExecuteMsg::SetRole { role } => execute_set_role(deps, env, info.sender, role),
+        ExecuteMsg::RemoveRole { name } => execute_remove_role(deps, env, info.sender, name),
+        ExecuteMsg::UpdateRoleHolders {
+            name,
+            to_add,
+            to_remove,
+        } => execute_update_role_holders(deps, env, info.sender, name, to_add, to_remove),
+        ExecuteMsg::ExecuteRoleMsgs { role, msgs } => {
+            execute_role_msgs(deps, env, info.sender, role, msgs)
+        }
+        ExecuteMsg::UpdateSpendingCaps { to_set, to_remove } => {
+            execute_update_spending_caps(deps, env, info.sender, to_set, to_remove)
+        }
+        ExecuteMsg::UpdateTreasuryHoldings { to_add, to_remove } => {
+            execute_update_treasury_holdings(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::SetSubDaoBudget {
+            sub_dao,
+            denom,
+            amount,
+        } => execute_set_sub_dao_budget(deps, env, info.sender, sub_dao, denom, amount),
+        ExecuteMsg::DrawSubDaoBudget { denom, amount } => {
+            execute_draw_sub_dao_budget(deps, info.sender, denom, amount)
+        }
+        ExecuteMsg::VetoSubDaoProposal {
+            sub_dao,
+            proposal_module,
+            proposal_id,
+        } => execute_veto_sub_dao_proposal(
+            deps.as_ref(),
+            env,
+            info.sender,
+            sub_dao,
+            proposal_module,
+            proposal_id,
+        ),
+        ExecuteMsg::UpdateTimelock { timelock } => {
+            execute_update_timelock(deps, env, info.sender, timelock)
+        }
+        ExecuteMsg::ExecuteQueued { id } => execute_queued(deps, env, id),
+        ExecuteMsg::CancelQueued { id } => execute_cancel_queued(deps, env, info.sender, id),
+    }
+}
+
+pub fn execute_update_timelock(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    timelock: Option<Timelock>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &timelock {
+        Some(timelock) => {
+            validate_timelock(timelock)?;
+            TIMELOCK.save(deps.storage, timelock)?;
+        }
+        None => TIMELOCK.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_timelock")
+        .add_attribute(
+            "delay",
+            timelock
+                .map(|timelock| timelock.delay.to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ))
+}
+
+pub fn execute_queued(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let queued = QUEUED_EXECUTIONS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::QueuedExecutionNotFound { id })?;
+    if !queued.ready.is_expired(&env.block) {
+        return Err(ContractError::QueuedExecutionNotReady {
+            id,
+            ready: queued.ready,
+        });
+    }
+    QUEUED_EXECUTIONS.remove(deps.storage, id);
+    if queued.capped {
+        check_spending_caps(deps.storage, &env.block, &queued.msgs, true)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_queued")
+        .add_attribute("id", id.to_string())
+        .add_messages(queued.msgs))
+}
+
+pub fn execute_cancel_queued(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let is_guardian = CONFIG
+        .load(deps.storage)?
+        .guardian
+        .map_or(false, |guardian| guardian.addr == sender.as_str());
+    if sender != env.contract.address && sender != ADMIN.load(deps.storage)? && !is_guardian {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !QUEUED_EXECUTIONS.has(deps.storage, id) {
+        return Err(ContractError::QueuedExecutionNotFound { id });
     }
+    QUEUED_EXECUTIONS.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_cancel_queued")
+        .add_attribute("sender", sender)
+        .add_attribute("id", id.to_string()))
 }
 
 pub fn execute_pause(
@@ -173,6 +352,7 @@ pub fn execute_pause(
     let until = pause_duration.after(&env.block);
 
     PAUSED.save(deps.storage, &until)?;
+    GUARDIAN_PAUSE.remove(deps.storage);
 
     Ok(Response::new()
         .add_attribute("action", "execute_pause")
@@ -189,14 +369,192 @@ pub fn execute_unpause(deps: DepsMut, sender: Addr) -> Result<Response, Contract
     }
 
     PAUSED.remove(deps.storage);
+    GUARDIAN_PAUSE.remove(deps.storage);
 
     Ok(Response::new()
         .add_attribute("action", "execute_unpause")
         .add_attribute("sender", sender))
 }
 
-pub fn execute_admin_msgs(
+/// Errors if `sender` is not the DAO's guardian or `duration` is
+/// longer than the guardian may act for. Returns the guardian.
+fn check_guardian(
     deps: Deps,
+    sender: &Addr,
+    duration: &Duration,
+) -> Result<Guardian, ContractError> {
+    let guardian = CONFIG
+        .load(deps.storage)?
+        .guardian
+        .ok_or(ContractError::NoGuardian {})?;
+    if sender.as_str() != guardian.addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match (guardian.max_duration, duration) {
+        (Duration::Height(max), Duration::Height(blocks)) if *blocks <= max => Ok(guardian),
+        (Duration::Time(max), Duration::Time(seconds)) if *seconds <= max => Ok(guardian),
+        _ => Err(ContractError::GuardianDurationTooLong {}),
+    }
+}
+
+pub fn execute_guardian_pause(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    duration: Duration,
+) -> Result<Response, ContractError> {
+    let guardian = check_guardian(deps.as_ref(), &sender, &duration)?;
+
+    // The guardian may not extend or shorten an existing pause.
+    if let Some(expiration) = PAUSED.may_load(deps.storage)? {
+        if !expiration.is_expired(&env.block) {
+            return Err(ContractError::Paused {});
+        }
+    }
+    // Nor may it pause again straight after a pause ends, as the DAO
+    // can not execute proposals while paused and would otherwise have
+    // no way to remove a guardian that keeps it paused.
+    if let Some(expiration) = GUARDIAN_PAUSE_COOLDOWN.may_load(deps.storage)? {
+        if !expiration.is_expired(&env.block) {
+            return Err(ContractError::GuardianPauseCooldown { expiration });
+        }
+    }
+
+    let until = duration.after(&env.block);
+    PAUSED.save(deps.storage, &until)?;
+    GUARDIAN_PAUSE.save(deps.storage, &until)?;
+    GUARDIAN_PAUSE_COOLDOWN.save(deps.storage, &(until + guardian.max_duration)?)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_guardian_pause")
+        .add_event(
+            Event::new("guardian_pause")
+                .add_attribute("guardian", sender)
+                .add_attribute("until", until.to_string()),
+        ))
+}
+
+pub fn execute_guardian_suspend_module(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    address: String,
+    duration: Duration,
+) -> Result<Response, ContractError> {
+    check_guardian(deps.as_ref(), &sender, &duration)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    let module = PROPOSAL_MODULES
+        .may_load(deps.storage, address.clone())?
+        .ok_or_else(|| ContractError::ProposalModuleDoesNotExist {
+            address: address.clone(),
+        })?;
+    if module.status == ProposalModuleStatus::Disabled {
+        return Err(ContractError::ModuleAlreadyDisabled { address });
+    }
+
+    // As with pauses, the guardian may not extend a suspension.
+    if let Some(expiration) = MODULE_SUSPENSIONS.may_load(deps.storage, address.clone())? {
+        if !expiration.is_expired(&env.block) {
+            return Err(ContractError::ModuleSuspended {
+                address,
+                expiration,
+            });
+        }
+    }
+
+    let until = duration.after(&env.block);
+    MODULE_SUSPENSIONS.save(deps.storage, address.clone(), &until)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_guardian_suspend_module")
+        .add_event(
+            Event::new("guardian_suspend_module")
+                .add_attribute("guardian", sender)
+                .add_attribute("module", address)
+                .add_attribute("until", until.to_string()),
+        ))
+}
+
+pub fn execute_lift_module_suspension(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    address: String,
+) -> Result<Response, ContractError> {
+    if sender != env.contract.address && sender != ADMIN.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = deps.api.addr_validate(&address)?;
+    match MODULE_SUSPENSIONS.may_load(deps.storage, address.clone())? {
+        Some(expiration) if !expiration.is_expired(&env.block) => {
+            MODULE_SUSPENSIONS.remove(deps.storage, address.clone())
+        }
+        _ => return Err(ContractError::ModuleNotSuspended { address }),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_lift_module_suspension")
+        .add_attribute("sender", sender)
+        .add_attribute("module", address))
+}
+
+/// Executes `msgs` on behalf of the DAO, or queues them if the DAO's
+/// timelock delays them. If `capped`, what they spend is charged
+/// against the DAO's spending caps when they are executed.
+fn execute_or_queue(
+    deps: DepsMut,
+    env: &Env,
+    sender: Addr,
+    msgs: Vec<CosmosMsg<Empty>>,
+    action: &str,
+    capped: bool,
+) -> Result<Response, ContractError> {
+    if let Some(timelock) = TIMELOCK.may_load(deps.storage)? {
+        if is_timelocked(&env.contract.address, &timelock, &msgs)? {
+            // Reject messages that could not be executed now, but only
+            // charge the caps once they are, so that cancelled messages
+            // do not use up the budget.
+            if capped {
+                check_spending_caps(deps.storage, &env.block, &msgs, false)?;
+            }
+            let id = QUEUED_EXECUTION_COUNT
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                + 1;
+            QUEUED_EXECUTION_COUNT.save(deps.storage, &id)?;
+            let ready = timelock.delay.after(&env.block);
+            QUEUED_EXECUTIONS.save(
+                deps.storage,
+                id,
+                &QueuedExecution {
+                    id,
+                    sender,
+                    msgs,
+                    ready,
+                    capped,
+                },
+            )?;
+            return Ok(Response::default()
+                .add_attribute("action", action)
+                .add_attribute("queued", id.to_string())
+                .add_attribute("ready", ready.to_string()));
+        }
+    }
+
+    if capped {
+        check_spending_caps(deps.storage, &env.block, &msgs, true)?;
+    }
+    Ok(Response::default()
+        .add_attribute("action", action)
+        .add_messages(msgs))
+}
+
+pub fn execute_admin_msgs(
+    deps: DepsMut,
+    env: Env,
     sender: Addr,
     msgs: Vec<CosmosMsg<Empty>>,
 ) -> Result<Response, ContractError> {
@@ -207,13 +565,12 @@ pub fn execute_admin_msgs(
         return Err(ContractError::Unauthorized {});
     }
 
-    Ok(Response::default()
-        .add_attribute("action", "execute_admin_msgs")
-        .add_messages(msgs))
+    execute_or_queue(deps, &env, sender, msgs, "execute_admin_msgs", false)
 }
 
 pub fn execute_proposal_hook(
-    deps: Deps,
+    deps: DepsMut,
+    env: Env,
     sender: Addr,
     msgs: Vec<CosmosMsg<Empty>>,
 ) -> Result<Response, ContractError> {
@@ -221,14 +578,21 @@ pub fn execute_proposal_hook(
         .may_load(deps.storage, sender.clone())?
         .ok_or(ContractError::Unauthorized {})?;
 
-    // Check that the message has come from an active module
-    if module.status != ProposalModuleStatus::Enabled {
+    // Check that the message has come from an active module, or one
+    // that is draining its open proposals.
+    if module.status == ProposalModuleStatus::Disabled {
         return Err(ContractError::ModuleDisabledCannotExecute { address: sender });
     }
+    if let Some(expiration) = MODULE_SUSPENSIONS.may_load(deps.storage, sender.clone())? {
+        if !expiration.is_expired(&env.block) {
+            return Err(ContractError::ModuleSuspended {
+                address: sender,
+                expiration,
+            });
+        }
+    }
 
-    Ok(Response::default()
-        .add_attribute("action", "execute_proposal_hook")
-        .add_messages(msgs))
+    execute_or_queue(deps, &env, sender, msgs, "execute_proposal_hook", true)
 }
 
 pub fn execute_nominate_admin(
@@ -310,12 +674,16 @@ pub fn execute_update_config(
     deps: DepsMut,
     env: Env,
     sender: Addr,
-    config: Config,
+    mut config: Config,
 ) -> Result<Response, ContractError> {
     if sender != env.contract.address {
         return Err(ContractError::Unauthorized {});
     }
 
+    if let Some(guardian) = config.guardian.as_mut() {
+        guardian.addr = deps.api.addr_validate(&guardian.addr)?.into_string();
+    }
+
     CONFIG.save(deps.storage, &config)?;
     // We incur some gas costs by having the config's fields in the
     // response. This has the benefit that it makes it reasonably
@@ -329,6 +697,12 @@ pub fn execute_update_config(
         .add_attribute(
             "image_url",
             config.image_url.unwrap_or_else(|| "None".to_string()),
+        )
+        .add_attribute(
+            "guardian",
+            config
+                .guardian
+                .map_or_else(|| "None".to_string(), |guardian| guardian.addr),
         ))
 }
 
@@ -360,7 +734,7 @@ pub fn execute_update_proposal_modules(
         return Err(ContractError::Unauthorized {});
     }
 
-    let disable_count = to_disable.len() as u32;
+    let mut disable_count = 0;
     for addr in to_disable {
         let addr = deps.api.addr_validate(&addr)?;
         let mut module = PROPOSAL_MODULES
@@ -375,6 +749,11 @@ pub fn execute_update_proposal_modules(
             });
         }
 
+        // Draining modules are already not counted as active.
+        if module.status == ProposalModuleStatus::Enabled {
+            disable_count += 1;
+        }
+
         module.status = ProposalModuleStatus::Disabled {};
         PROPOSAL_MODULES.save(deps.storage, addr, &module)?;
     }
@@ -400,6 +779,98 @@ pub fn execute_update_proposal_modules(
         .add_submessages(to_add))
 }
 
+pub fn execute_upgrade_proposal_module(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    address: String,
+    new_module: ModuleInstantiateInfo,
+    drain_duration: Duration,
+    copy_config: bool,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = deps.api.addr_validate(&address)?;
+    let module = PROPOSAL_MODULES
+        .may_load(deps.storage, address.clone())?
+        .ok_or_else(|| ContractError::ProposalModuleDoesNotExist {
+            address: address.clone(),
+        })?;
+    if module.status != ProposalModuleStatus::Enabled {
+        return Err(ContractError::ModuleNotEnabled { address });
+    }
+
+    // The old module starts draining once its successor has been
+    // instantiated. See `reply`.
+    let until = drain_duration.after(&env.block);
+    UPGRADING_PROPOSAL_MODULE.save(deps.storage, &(address.clone(), until, copy_config))?;
+
+    let wasm = new_module.into_wasm_msg(env.contract.address);
+    Ok(Response::default()
+        .add_attribute("action", "execute_upgrade_proposal_module")
+        .add_attribute("module", address)
+        .add_attribute("until", until.to_string())
+        .add_attribute("copy_config", copy_config.to_string())
+        .add_submessage(SubMsg::reply_on_success(
+            wasm,
+            PROPOSAL_MODULE_UPGRADE_REPLY_ID,
+        )))
+}
+
+pub fn execute_finish_draining(
+    deps: DepsMut,
+    env: Env,
+    address: String,
+    limit: Option<u64>,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let mut module = PROPOSAL_MODULES
+        .may_load(deps.storage, address.clone())?
+        .ok_or_else(|| ContractError::ProposalModuleDoesNotExist {
+            address: address.clone(),
+        })?;
+    match module.status {
+        ProposalModuleStatus::Draining { until, .. } if !until.is_expired(&env.block) => {
+            return Err(ContractError::ModuleStillDraining { address, until })
+        }
+        ProposalModuleStatus::Draining { .. } => (),
+        _ => return Err(ContractError::ModuleNotDraining { address }),
+    }
+    // Leave the module draining until none of its proposals may be
+    // executed. Closed proposals stay closed, so a scan that runs out
+    // of proposals to look through resumes from where it stopped.
+    let start_after = DRAINING_SCAN_MARKS.may_load(deps.storage, address.clone())?;
+    let limit = limit
+        .unwrap_or(DEFAULT_DRAIN_SCAN_LIMIT)
+        .min(MAX_DRAIN_SCAN_LIMIT);
+    match find_open_proposal(deps.as_ref(), &address, start_after, limit)? {
+        ProposalScan::Open(proposal_id) => Err(ContractError::ModuleHasOpenProposal {
+            address,
+            proposal_id,
+        }),
+        ProposalScan::Incomplete(last) => {
+            DRAINING_SCAN_MARKS.save(deps.storage, address.clone(), &last)?;
+            Ok(Response::default()
+                .add_attribute("action", "execute_finish_draining")
+                .add_attribute("module", address)
+                .add_attribute("scanned_to", last.to_string())
+                .add_attribute("finished", "false"))
+        }
+        ProposalScan::Closed => {
+            DRAINING_SCAN_MARKS.remove(deps.storage, address.clone());
+            module.status = ProposalModuleStatus::Disabled;
+            PROPOSAL_MODULES.save(deps.storage, address.clone(), &module)?;
+
+            Ok(Response::default()
+                .add_attribute("action", "execute_finish_draining")
+                .add_attribute("module", address)
+                .add_attribute("finished", "true"))
+        }
+    }
+}
+
 /// Updates a set of addresses in state applying VERIFY to each item
 /// that will be added.
 fn do_update_addr_list(
@@ -511,6 +982,103 @@ pub fn execute_remove_item(
     }
 }
 
+/// Errors if `sender` may not write to `namespace`. The DAO may write
+/// to every namespace.
+fn check_namespace_writer(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    namespace: &str,
+) -> Result<(), ContractError> {
+    if namespace.is_empty() {
+        return Err(ContractError::EmptyNamespace {});
+    }
+    if *sender != env.contract.address && !NAMESPACE_WRITERS.has(deps.storage, (namespace, sender))
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub fn execute_set_namespaced_item(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    namespace: String,
+    key: String,
+    value: Binary,
+) -> Result<Response, ContractError> {
+    check_namespace_writer(deps.as_ref(), &env, &sender, &namespace)?;
+    from_json::<IgnoredAny>(&value)
+        .map_err(|_| ContractError::InvalidItemValue { key: key.clone() })?;
+
+    NAMESPACED_ITEMS.save(
+        deps.storage,
+        (namespace.as_str(), key.as_str()),
+        &value,
+        env.block.height,
+    )?;
+    Ok(Response::default()
+        .add_attribute("action", "execute_set_namespaced_item")
+        .add_attribute("sender", sender)
+        .add_attribute("namespace", namespace)
+        .add_attribute("key", key))
+}
+
+pub fn execute_remove_namespaced_item(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    namespace: String,
+    key: String,
+) -> Result<Response, ContractError> {
+    check_namespace_writer(deps.as_ref(), &env, &sender, &namespace)?;
+
+    if !NAMESPACED_ITEMS.has(deps.storage, (namespace.as_str(), key.as_str())) {
+        return Err(ContractError::KeyMissing {});
+    }
+    NAMESPACED_ITEMS.remove(
+        deps.storage,
+        (namespace.as_str(), key.as_str()),
+        env.block.height,
+    )?;
+    Ok(Response::default()
+        .add_attribute("action", "execute_remove_namespaced_item")
+        .add_attribute("sender", sender)
+        .add_attribute("namespace", namespace)
+        .add_attribute("key", key))
+}
+
+pub fn execute_update_namespace_writers(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    namespace: String,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if namespace.is_empty() {
+        return Err(ContractError::EmptyNamespace {});
+    }
+
+    for addr in to_remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        NAMESPACE_WRITERS.remove(deps.storage, (namespace.as_str(), &addr));
+    }
+
+    for addr in to_add {
+        let addr = deps.api.addr_validate(&addr)?;
+        NAMESPACE_WRITERS.save(deps.storage, (namespace.as_str(), &addr), &Empty {})?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_namespace_writers")
+        .add_attribute("namespace", namespace))
+}
+
 pub fn execute_update_sub_daos_list(
     deps: DepsMut,
     env: Env,
@@ -525,6 +1093,15 @@ pub fn execute_update_sub_daos_list(
     for addr in to_remove {
         let addr = deps.api.addr_validate(&addr)?;
         SUBDAO_LIST.remove(deps.storage, &addr);
+
+        // Budgets are revoked along with the SubDAO.
+        let denoms = SUBDAO_BUDGETS
+            .prefix(&addr)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<String>>>()?;
+        for denom in denoms {
+            SUBDAO_BUDGETS.remove(deps.storage, (&addr, denom.as_str()));
+        }
     }
 
     for subdao in to_add {
@@ -537,25 +1114,311 @@ pub fn execute_update_sub_daos_list(
         .add_attribute("sender", sender))
 }
 
-pub fn execute_receive_cw20(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    if !config.automatically_add_cw20s {
-        Ok(Response::new())
-    } else {
-        CW20_LIST.save(deps.storage, sender.clone(), &Empty {})?;
-        Ok(Response::new()
-            .add_attribute("action", "receive_cw20")
-            .add_attribute("token", sender))
+pub fn execute_set_role(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    role: Role,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
     }
+
+    let role = validate_role(deps.api, role)?;
+    ROLES.save(deps.storage, &role.name, &role)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_set_role")
+        .add_attribute("role", role.name))
 }
 
-pub fn execute_receive_cw721(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    if !config.automatically_add_cw721s {
-        Ok(Response::new())
-    } else {
-        CW721_LIST.save(deps.storage, sender.clone(), &Empty {})?;
-        Ok(Response::new()
+pub fn execute_remove_role(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    name: String,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !ROLES.has(deps.storage, &name) {
+        return Err(ContractError::RoleNotFound { name });
+    }
+
+    ROLES.remove(deps.storage, &name);
+    let holders = ROLE_HOLDERS
+        .prefix(&name)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for holder in holders {
+        ROLE_HOLDERS.remove(deps.storage, (name.as_str(), &holder));
+    }
+    let denoms = ROLE_SPENDING
+        .prefix(&name)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for denom in denoms {
+        ROLE_SPENDING.remove(deps.storage, (name.as_str(), denom.as_str()));
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_remove_role")
+        .add_attribute("role", name))
+}
+
+pub fn execute_update_role_holders(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    name: String,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !ROLES.has(deps.storage, &name) {
+        return Err(ContractError::RoleNotFound { name });
+    }
+
+    for addr in to_remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        ROLE_HOLDERS.remove(deps.storage, (name.as_str(), &addr));
+    }
+
+    for addr in to_add {
+        let addr = deps.api.addr_validate(&addr)?;
+        ROLE_HOLDERS.save(deps.storage, (name.as_str(), &addr), &Empty {})?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_role_holders")
+        .add_attribute("role", name))
+}
+
+pub fn execute_role_msgs(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    name: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    let role = ROLES
+        .may_load(deps.storage, &name)?
+        .ok_or_else(|| ContractError::RoleNotFound { name: name.clone() })?;
+
+    if !ROLE_HOLDERS.has(deps.storage, (name.as_str(), &sender)) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if role.expiration.is_expired(&env.block) {
+        return Err(ContractError::RoleExpired { name });
+    }
+
+    for msg in msgs.iter() {
+        check_role_msg(&role, &env.contract.address, msg)?;
+        for (denom, amount) in msg_spends(msg).into_iter().chain(nft_spend(msg)) {
+            record_spend(deps.storage, &env.block, &role, &denom, amount)?;
+        }
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_role_msgs")
+        .add_attribute("role", name)
+        .add_attribute("sender", sender)
+        .add_messages(msgs))
+}
+
+pub fn execute_update_spending_caps(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_set: Vec<SpendingCap>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for denom in to_remove {
+        SPENDING_CAPS.remove(deps.storage, &denom);
+    }
+
+    for cap in to_set {
+        validate_spending_cap(&cap)?;
+        SPENDING_CAPS.save(deps.storage, &cap.denom, &cap)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_spending_caps")
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_update_treasury_holdings(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<TreasuryHolding>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for addr in to_remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        TREASURY_HOLDINGS.remove(deps.storage, &addr);
+    }
+
+    for holding in to_add {
+        let addr = deps.api.addr_validate(&holding.addr)?;
+        // Query the balance here as this is the query performed by the
+        // `TreasuryBalances` query.
+        holding_balance(
+            deps.as_ref(),
+            &env.contract.address,
+            addr.clone(),
+            &holding.kind,
+        )?;
+        TREASURY_HOLDINGS.save(deps.storage, &addr, &holding.kind)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_treasury_holdings")
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_set_sub_dao_budget(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    sub_dao: String,
+    denom: UncheckedDenom,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let sub_dao = deps.api.addr_validate(&sub_dao)?;
+    if !SUBDAO_LIST.has(deps.storage, &sub_dao) {
+        return Err(ContractError::NotSubDao { address: sub_dao });
+    }
+
+    let denom = denom.into_checked(deps.as_ref())?;
+    let key = denom.to_string();
+    let drawn = SUBDAO_BUDGETS
+        .may_load(deps.storage, (&sub_dao, key.as_str()))?
+        .map(|budget| budget.drawn)
+        .unwrap_or_default();
+    SUBDAO_BUDGETS.save(
+        deps.storage,
+        (&sub_dao, key.as_str()),
+        &SubDaoBudget {
+            denom,
+            amount,
+            drawn,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_set_sub_dao_budget")
+        .add_attribute("sub_dao", sub_dao)
+        .add_attribute("denom", key)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_draw_sub_dao_budget(
+    deps: DepsMut,
+    sender: Addr,
+    denom: UncheckedDenom,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if !SUBDAO_LIST.has(deps.storage, &sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let key = denom.into_checked(deps.as_ref())?.to_string();
+    let mut budget = SUBDAO_BUDGETS
+        .may_load(deps.storage, (&sender, key.as_str()))?
+        .ok_or_else(|| ContractError::NoSubDaoBudget { denom: key.clone() })?;
+
+    let remaining = budget.amount.saturating_sub(budget.drawn);
+    if amount > remaining {
+        return Err(ContractError::SubDaoBudgetExceeded {
+            denom: key,
+            remaining,
+        });
+    }
+    budget.drawn += amount;
+    SUBDAO_BUDGETS.save(deps.storage, (&sender, key.as_str()), &budget)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_draw_sub_dao_budget")
+        .add_attribute("sub_dao", sender.clone())
+        .add_attribute("denom", key)
+        .add_attribute("amount", amount)
+        .add_attribute("drawn", budget.drawn)
+        .add_message(budget.denom.get_transfer_to_message(&sender, amount)?))
+}
+
+pub fn execute_veto_sub_dao_proposal(
+    deps: Deps,
+    env: Env,
+    sender: Addr,
+    sub_dao: String,
+    proposal_module: String,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let sub_dao = deps.api.addr_validate(&sub_dao)?;
+    if !SUBDAO_LIST.has(deps.storage, &sub_dao) {
+        return Err(ContractError::NotSubDao { address: sub_dao });
+    }
+
+    let proposal_module = deps.api.addr_validate(&proposal_module)?;
+    let dao: Addr = deps
+        .querier
+        .query_wasm_smart(&proposal_module, &dao_interface::proposal::Query::Dao {})?;
+    if dao != sub_dao {
+        return Err(ContractError::NotSubDaoProposalModule {
+            address: proposal_module,
+        });
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_veto_sub_dao_proposal")
+        .add_attribute("sub_dao", sub_dao)
+        .add_attribute("proposal_module", proposal_module.clone())
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_message(WasmMsg::Execute {
+            contract_addr: proposal_module.into_string(),
+            msg: to_json_binary(&VetoMsg::Veto { proposal_id })?,
+            funds: vec![],
+        }))
+}
+
+pub fn execute_receive_cw20(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.automatically_add_cw20s {
+        Ok(Response::new())
+    } else {
+        CW20_LIST.save(deps.storage, sender.clone(), &Empty {})?;
+        Ok(Response::new()
+            .add_attribute("action", "receive_cw20")
+            .add_attribute("token", sender))
+    }
+}
+
+pub fn execute_receive_cw721(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.automatically_add_cw721s {
+        Ok(Response::new())
+    } else {
+        CW721_LIST.save(deps.storage, sender.clone(), &Empty {})?;
+        Ok(Response::new()
             .add_attribute("action", "receive_cw721")
             .add_attribute("token", sender))
     }
@@ -578,6 +1441,21 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetItem { key } => query_get_item(deps, key),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ListItems { start_after, limit } => query_list_items(deps, start_after, limit),
+        QueryMsg::GetNamespacedItem {
+            namespace,
+            key,
+            height,
+        } => query_get_namespaced_item(deps, namespace, key, height),
+        QueryMsg::ListNamespacedItems {
+            namespace,
+            start_after,
+            limit,
+        } => query_list_namespaced_items(deps, namespace, start_after, limit),
+        QueryMsg::ListNamespaceWriters {
+            namespace,
+            start_after,
+            limit,
+        } => query_list_namespace_writers(deps, namespace, start_after, limit),
         QueryMsg::PauseInfo {} => query_paused(deps, env),
         QueryMsg::ProposalModules { start_after, limit } => {
             query_proposal_modules(deps, start_after, limit)
@@ -591,10 +1469,39 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ActiveProposalModules { start_after, limit } => {
             query_active_proposal_modules(deps, start_after, limit)
         }
+        QueryMsg::ProposalModuleVersions { start_after, limit } => {
+            query_proposal_module_versions(deps, start_after, limit)
+        }
         QueryMsg::ListSubDaos { start_after, limit } => {
             query_list_sub_daos(deps, start_after, limit)
         }
+        QueryMsg::SubDaoTree { max_depth } => query_sub_dao_tree(deps, env, max_depth),
+        QueryMsg::SubDaoBudgets { sub_dao } => query_sub_dao_budgets(deps, sub_dao),
         QueryMsg::DaoURI {} => query_dao_uri(deps),
+        QueryMsg::GetRole { name } => query_get_role(deps, env, name),
+        QueryMsg::TreasuryBalances {
+            asset_type,
+            start_after,
+            limit,
+        } => query_treasury_balances(deps, env, asset_type, start_after, limit),
+        QueryMsg::TreasuryHoldings { start_after, limit } => {
+            query_treasury_holdings(deps, start_after, limit)
+        }
+        QueryMsg::SpendingCaps { start_after, limit } => {
+            query_spending_caps(deps, start_after, limit)
+        }
+        QueryMsg::RemainingBudget { denom } => query_remaining_budget(deps, env, denom),
+        QueryMsg::Timelock {} => query_timelock(deps),
+        QueryMsg::QueuedExecution { id } => query_queued_execution(deps, id),
+        QueryMsg::QueuedExecutions { start_after, limit } => {
+            query_queued_executions(deps, start_after, limit)
+        }
+        QueryMsg::ListRoles { start_after, limit } => query_list_roles(deps, start_after, limit),
+        QueryMsg::ListRoleHolders {
+            name,
+            start_after,
+            limit,
+        } => query_list_role_holders(deps, name, start_after, limit),
     }
 }
 
@@ -647,6 +1554,32 @@ pub fn query_proposal_modules(
     )?)
 }
 
+pub fn query_proposal_module_versions(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let versions = paginate_map_values(
+        deps,
+        &PROPOSAL_MODULES,
+        start_after,
+        limit,
+        Order::Ascending,
+    )?
+    .into_iter()
+    .map(|module: ProposalModule| ProposalModuleVersion {
+        current: cw2::query_contract_info(&deps.querier, &module.address).ok(),
+        address: module.address,
+        status: module.status,
+        recorded: module.version,
+    })
+    .collect::<Vec<_>>();
+    to_json_binary(&versions)
+}
+
 pub fn query_active_proposal_modules(
     deps: Deps,
     start_after: Option<String>,
@@ -700,6 +1633,7 @@ pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
         .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
         .map(|kv| Ok(kv?.1))
         .collect::<StdResult<Vec<ProposalModule>>>()?;
+    let guardian_actions = get_guardian_actions(deps, &env.block)?;
     let pause_info = get_pause_info(deps, env)?;
     let version = get_contract_version(deps.storage)?;
     let active_proposal_module_count = ACTIVE_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
@@ -713,9 +1647,30 @@ pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
         voting_module,
         active_proposal_module_count,
         total_proposal_module_count,
+        guardian_actions,
     })
 }
 
+/// Returns the guardian's actions that are still in effect.
+fn get_guardian_actions(deps: Deps, block: &BlockInfo) -> StdResult<Vec<GuardianAction>> {
+    let mut actions = vec![];
+    if let Some(expiration) = GUARDIAN_PAUSE.may_load(deps.storage)? {
+        if !expiration.is_expired(block) {
+            actions.push(GuardianAction::Pause { expiration });
+        }
+    }
+    for suspension in MODULE_SUSPENSIONS.range(deps.storage, None, None, Order::Ascending) {
+        let (address, expiration) = suspension?;
+        if !expiration.is_expired(block) {
+            actions.push(GuardianAction::SuspendModule {
+                address,
+                expiration,
+            });
+        }
+    }
+    Ok(actions)
+}
+
 pub fn query_voting_power_at_height(
     deps: Deps,
     address: String,
@@ -761,6 +1716,61 @@ pub fn query_list_items(
     )?)
 }
 
+pub fn query_get_namespaced_item(
+    deps: Deps,
+    namespace: String,
+    key: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let key = (namespace.as_str(), key.as_str());
+    let value = match height {
+        Some(height) => NAMESPACED_ITEMS.may_load_at_height(deps.storage, key, height)?,
+        None => NAMESPACED_ITEMS.may_load(deps.storage, key)?,
+    };
+    to_json_binary(&GetNamespacedItemResponse { value })
+}
+
+pub fn query_list_namespaced_items(
+    deps: Deps,
+    namespace: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let items = NAMESPACED_ITEMS
+        .prefix(&namespace)
+        .range(
+            deps.storage,
+            start_after.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.map(|limit| limit as usize).unwrap_or(usize::MAX))
+        .collect::<StdResult<Vec<(String, Binary)>>>()?;
+    to_json_binary(&items)
+}
+
+pub fn query_list_namespace_writers(
+    deps: Deps,
+    namespace: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let writers = NAMESPACE_WRITERS
+        .prefix(&namespace)
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.map(|limit| limit as usize).unwrap_or(usize::MAX))
+        .collect::<StdResult<Vec<Addr>>>()?;
+    to_json_binary(&writers)
+}
+
 pub fn query_cw20_list(
     deps: Deps,
     start_after: Option<String>,
@@ -854,6 +1864,174 @@ pub fn query_list_sub_daos(
     to_json_binary(&subdaos)
 }
 
+pub fn query_get_role(deps: Deps, env: Env, name: String) -> StdResult<Binary> {
+    let role = ROLES.load(deps.storage, &name)?;
+    // Spending from periods that have ended no longer counts towards
+    // the role's limits.
+    let spending = ROLE_SPENDING
+        .prefix(&name)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, spending)| spending))
+        .filter(|spending| match spending {
+            Ok(spending) => !spending.resets.is_expired(&env.block),
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&RoleResponse { role, spending })
+}
+
+pub fn query_list_roles(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let roles = paginate_map(
+        deps,
+        &ROLES,
+        start_after.as_deref(),
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+    let roles: Vec<Role> = roles.into_iter().map(|(_, role)| role).collect();
+    to_json_binary(&roles)
+}
+
+pub fn query_list_role_holders(
+    deps: Deps,
+    name: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let holders = ROLE_HOLDERS
+        .prefix(&name)
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.map(|limit| limit as usize).unwrap_or(usize::MAX))
+        .collect::<StdResult<Vec<Addr>>>()?;
+    to_json_binary(&holders)
+}
+
+pub fn query_treasury_balances(
+    deps: Deps,
+    env: Env,
+    asset_type: Option<TreasuryAssetType>,
+    start_after: Option<TreasuryBalanceKey>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    to_json_binary(&treasury_balances(
+        deps,
+        &env.contract.address,
+        asset_type,
+        start_after,
+        limit,
+    )?)
+}
+
+pub fn query_treasury_holdings(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let holdings = TREASURY_HOLDINGS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.map(|limit| limit as usize).unwrap_or(usize::MAX))
+        .map(|item| {
+            let (addr, kind) = item?;
+            Ok(TreasuryHolding {
+                addr: addr.into_string(),
+                kind,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&holdings)
+}
+
+pub fn query_timelock(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&TIMELOCK.may_load(deps.storage)?)
+}
+
+pub fn query_queued_execution(deps: Deps, id: u64) -> StdResult<Binary> {
+    to_json_binary(&QUEUED_EXECUTIONS.load(deps.storage, id)?)
+}
+
+pub fn query_queued_executions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let queued = QUEUED_EXECUTIONS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.map(|limit| limit as usize).unwrap_or(usize::MAX))
+        .map(|item| Ok(item?.1))
+        .collect::<StdResult<Vec<QueuedExecution>>>()?;
+    to_json_binary(&queued)
+}
+
+pub fn query_spending_caps(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let caps = paginate_map(
+        deps,
+        &SPENDING_CAPS,
+        start_after.as_deref(),
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+    let caps: Vec<SpendingCap> = caps.into_iter().map(|(_, cap)| cap).collect();
+    to_json_binary(&caps)
+}
+
+pub fn query_remaining_budget(deps: Deps, env: Env, denom: String) -> StdResult<Binary> {
+    let cap = SPENDING_CAPS.may_load(deps.storage, &denom)?;
+    let per_execution = cap.as_ref().and_then(|cap| cap.per_execution);
+    let window_remaining = cap
+        .and_then(|cap| cap.per_window)
+        .map(|per_window| -> StdResult<_> {
+            let spent = spent_in_window(deps.storage, &env.block, &denom, &per_window.window)?;
+            Ok(per_window.amount.saturating_sub(spent))
+        })
+        .transpose()?;
+    to_json_binary(&RemainingBudgetResponse {
+        denom,
+        per_execution,
+        window_remaining,
+    })
+}
+
+pub fn query_sub_dao_tree(deps: Deps, env: Env, max_depth: Option<u32>) -> StdResult<Binary> {
+    let depth = max_depth
+        .unwrap_or(DEFAULT_SUBDAO_TREE_DEPTH)
+        .min(MAX_SUBDAO_TREE_DEPTH);
+    to_json_binary(&sub_dao_tree(deps, env.contract.address, depth)?)
+}
+
+pub fn query_sub_dao_budgets(deps: Deps, sub_dao: String) -> StdResult<Binary> {
+    let sub_dao = deps.api.addr_validate(&sub_dao)?;
+    to_json_binary(&sub_dao_budgets(deps, &sub_dao)?)
+}
+
 pub fn query_dao_uri(deps: Deps) -> StdResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
     to_json_binary(&DaoURIResponse {
@@ -903,6 +2081,7 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
                         address: address.clone(),
                         status: ProposalModuleStatus::Enabled {},
                         prefix,
+                        version: None,
                     };
                     PROPOSAL_MODULES.save(deps.storage, address, proposal_module)?;
                     Ok(())
@@ -919,6 +2098,7 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
                     automatically_add_cw20s: v1_config.automatically_add_cw20s,
                     automatically_add_cw721s: v1_config.automatically_add_cw721s,
                     dao_uri,
+                    guardian: None,
                 },
             )?;
 
@@ -949,39 +2129,82 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
     }
 }
 
+/// Adds a newly instantiated proposal module to the DAO, returning
+/// the module's instantiation callback messages.
+fn register_proposal_module(
+    deps: DepsMut,
+    prop_module_addr: Addr,
+    data: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let total_module_count = TOTAL_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
+
+    let prefix = derive_proposal_module_prefix(total_module_count as usize)?;
+    let prop_module = ProposalModule {
+        address: prop_module_addr.clone(),
+        status: ProposalModuleStatus::Enabled,
+        prefix,
+        version: cw2::query_contract_info(&deps.querier, &prop_module_addr).ok(),
+    };
+
+    PROPOSAL_MODULES.save(deps.storage, prop_module_addr.clone(), &prop_module)?;
+
+    // Save active and total proposal module counts.
+    ACTIVE_PROPOSAL_MODULE_COUNT.update::<_, StdError>(deps.storage, |count| Ok(count + 1))?;
+    TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &(total_module_count + 1))?;
+
+    // Check for module instantiation callbacks
+    let callback_msgs = match data {
+        Some(data) => from_json::<ModuleInstantiateCallback>(&data)
+            .map(|m| m.msgs)
+            .unwrap_or_else(|_| vec![]),
+        None => vec![],
+    };
+
+    Ok(Response::default()
+        .add_attribute("prop_module".to_string(), prop_module_addr)
+        .add_messages(callback_msgs))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         PROPOSAL_MODULE_REPLY_ID => {
             let res = parse_reply_instantiate_data(msg)?;
             let prop_module_addr = deps.api.addr_validate(&res.contract_address)?;
-            let total_module_count = TOTAL_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
+            register_proposal_module(deps, prop_module_addr, res.data)
+        }
+        PROPOSAL_MODULE_UPGRADE_REPLY_ID => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let prop_module_addr = deps.api.addr_validate(&res.contract_address)?;
 
-            let prefix = derive_proposal_module_prefix(total_module_count as usize)?;
-            let prop_module = ProposalModule {
-                address: prop_module_addr.clone(),
-                status: ProposalModuleStatus::Enabled,
-                prefix,
+            let (old_addr, until, copy_config) = UPGRADING_PROPOSAL_MODULE.load(deps.storage)?;
+            UPGRADING_PROPOSAL_MODULE.remove(deps.storage);
+            let mut old_module = PROPOSAL_MODULES.load(deps.storage, old_addr.clone())?;
+            old_module.status = ProposalModuleStatus::Draining {
+                until,
+                successor: prop_module_addr.clone(),
             };
-
-            PROPOSAL_MODULES.save(deps.storage, prop_module_addr, &prop_module)?;
-
-            // Save active and total proposal module counts.
+            PROPOSAL_MODULES.save(deps.storage, old_addr.clone(), &old_module)?;
+            // The old module is no longer active. Its successor is
+            // counted when it is registered.
             ACTIVE_PROPOSAL_MODULE_COUNT
-                .update::<_, StdError>(deps.storage, |count| Ok(count + 1))?;
-            TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &(total_module_count + 1))?;
-
-            // Check for module instantiation callbacks
-            let callback_msgs = match res.data {
-                Some(data) => from_json::<ModuleInstantiateCallback>(&data)
-                    .map(|m| m.msgs)
-                    .unwrap_or_else(|_| vec![]),
-                None => vec![],
+                .update::<_, StdError>(deps.storage, |count| Ok(count - 1))?;
+
+            // The successor is instantiated by now, so the DAO may
+            // update its config.
+            let copy_config_msg = if copy_config {
+                Some(copy_config_msg(
+                    deps.as_ref(),
+                    &old_addr,
+                    &prop_module_addr,
+                )?)
+            } else {
+                None
             };
 
-            Ok(Response::default()
-                .add_attribute("prop_module".to_string(), res.contract_address)
-                .add_messages(callback_msgs))
+            Ok(register_proposal_module(deps, prop_module_addr, res.data)?
+                .add_messages(copy_config_msg)
+                .add_attribute("draining_module", old_addr))
         }
 
         VOTE_MODULE_INSTANTIATE_REPLY_ID => {
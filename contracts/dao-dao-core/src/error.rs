@@ -1,5 +1,6 @@
-use cosmwasm_std::{Addr, StdError};
-use cw_utils::ParseReplyError;
+use cosmwasm_std::{Addr, StdError, Uint128};
+use cw_denom::DenomError;
+use cw_utils::{Expiration, ParseReplyError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -10,6 +11,9 @@ pub enum ContractError {
     #[error(transparent)]
     ParseReplyError(#[from] ParseReplyError),
 
+    #[error(transparent)]
+    Denom(#[from] DenomError),
+
     #[error("Unauthorized.")]
     Unauthorized {},
 
@@ -51,9 +55,98 @@ pub enum ContractError {
     #[error("Proposal module with address is disabled and cannot execute messages.")]
     ModuleDisabledCannotExecute { address: Addr },
 
+    #[error("Proposal module with address ({address}) is not enabled.")]
+    ModuleNotEnabled { address: Addr },
+
+    #[error("Proposal module with address ({address}) is not draining.")]
+    ModuleNotDraining { address: Addr },
+
+    #[error("Proposal module with address ({address}) is draining until {until}.")]
+    ModuleStillDraining { address: Addr, until: Expiration },
+
+    #[error("Proposal module with address ({address}) has an open proposal ({proposal_id}).")]
+    ModuleHasOpenProposal { address: Addr, proposal_id: u64 },
+
+    #[error("Proposal module with address ({address}) is suspended until {expiration}.")]
+    ModuleSuspended {
+        address: Addr,
+        expiration: Expiration,
+    },
+
+    #[error("Proposal module with address ({address}) is not suspended.")]
+    ModuleNotSuspended { address: Addr },
+
+    #[error("The DAO has no guardian.")]
+    NoGuardian {},
+
+    #[error("Guardian actions must use the units of, and last no longer than, the guardian's max duration.")]
+    GuardianDurationTooLong {},
+
+    #[error("The guardian may not pause the DAO again until {expiration}.")]
+    GuardianPauseCooldown { expiration: Expiration },
+
+    #[error("Namespaces must not be empty.")]
+    EmptyNamespace {},
+
+    #[error("The value of item ({key}) is not valid JSON.")]
+    InvalidItemValue { key: String },
+
     #[error("Duplicate initial item: ({item})")]
     DuplicateInitialItem { item: String },
 
+    #[error("Role ({name}) does not exist.")]
+    RoleNotFound { name: String },
+
+    #[error("Role names must not be empty.")]
+    EmptyRoleName {},
+
+    #[error("Role has more than one spend limit for ({denom}).")]
+    DuplicateSpendLimit { denom: String },
+
+    #[error("The spend limit period for ({denom}) must be non-zero.")]
+    ZeroSpendLimitPeriod { denom: String },
+
+    #[error("Role ({name}) has expired.")]
+    RoleExpired { name: String },
+
+    #[error("Role ({name}) may not execute this message.")]
+    RoleMsgNotAllowed { name: String },
+
+    #[error("Role ({name}) may not execute messages on the DAO.")]
+    RoleMsgTargetsDao { name: String },
+
+    #[error(
+        "Executing these messages would exceed the spend limit of role ({name}) for ({denom})."
+    )]
+    SpendLimitExceeded { name: String, denom: String },
+
+    #[error("Spending cap for ({denom}) must set a per execution cap or a non-zero window.")]
+    InvalidSpendingCap { denom: String },
+
+    #[error("Executing these messages would exceed the spending cap for ({denom}). Remaining budget: {remaining}.")]
+    SpendingCapExceeded { denom: String, remaining: Uint128 },
+
+    #[error("({address}) is not a SubDAO of this DAO.")]
+    NotSubDao { address: Addr },
+
+    #[error("Proposal module ({address}) does not belong to the SubDAO.")]
+    NotSubDaoProposalModule { address: Addr },
+
+    #[error("The SubDAO has no budget for ({denom}).")]
+    NoSubDaoBudget { denom: String },
+
+    #[error("Drawing this amount would exceed the SubDAO's budget for ({denom}). Remaining budget: {remaining}.")]
+    SubDaoBudgetExceeded { denom: String, remaining: Uint128 },
+
+    #[error("The timelock delay must be non-zero.")]
+    ZeroTimelockDelay {},
+
+    #[error("No queued execution with ID ({id}).")]
+    QueuedExecutionNotFound { id: u64 },
+
+    #[error("Queued execution ({id}) may not be executed until {ready}.")]
+    QueuedExecutionNotReady { id: u64, ready: Expiration },
+
     #[error("Can not migrate. Current version is up to date.")]
     AlreadyMigrated {},
 }
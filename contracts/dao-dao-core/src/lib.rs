@@ -2,7 +2,12 @@
 
 pub mod contract;
 mod error;
+mod roles;
 pub mod state;
+mod subdaos;
+mod timelock;
+mod treasury;
+mod upgrades;
 
 #[cfg(test)]
 mod tests;
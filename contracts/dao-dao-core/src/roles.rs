@@ -0,0 +1,154 @@
+use cosmwasm_std::{
+    from_json, Addr, Api, BankMsg, BlockInfo, CosmosMsg, Empty, Storage, Uint128, WasmMsg,
+};
+use cw721::Cw721ExecuteMsg;
+use cw_utils::Duration;
+use dao_interface::role::{Role, RoleMsgType, RoleSpending};
+
+use crate::error::ContractError;
+use crate::state::ROLE_SPENDING;
+
+/// Validates a role before it is saved, normalizing the addresses it
+/// may target.
+pub(crate) fn validate_role(api: &dyn Api, mut role: Role) -> Result<Role, ContractError> {
+    if role.name.is_empty() {
+        return Err(ContractError::EmptyRoleName {});
+    }
+
+    if let Some(targets) = role.targets.as_mut() {
+        for target in targets.iter_mut() {
+            *target = api.addr_validate(target)?.into_string();
+        }
+    }
+
+    for (i, limit) in role.spend_limits.iter().enumerate() {
+        if role.spend_limits[..i]
+            .iter()
+            .any(|other| other.denom == limit.denom)
+        {
+            return Err(ContractError::DuplicateSpendLimit {
+                denom: limit.denom.clone(),
+            });
+        }
+        if let Duration::Height(0) | Duration::Time(0) = limit.period {
+            return Err(ContractError::ZeroSpendLimitPeriod {
+                denom: limit.denom.clone(),
+            });
+        }
+    }
+
+    Ok(role)
+}
+
+/// Checks that `msg` is within the permissions of `role`. Messages
+/// may never target the DAO, as they would be executed with the DAO's
+/// authority over its own configuration.
+pub(crate) fn check_role_msg(
+    role: &Role,
+    dao: &Addr,
+    msg: &CosmosMsg<Empty>,
+) -> Result<(), ContractError> {
+    let (msg_type, target) = match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, .. }) => {
+            (Some(RoleMsgType::BankSend), Some(to_address))
+        }
+        CosmosMsg::Bank(BankMsg::Burn { .. }) => (Some(RoleMsgType::BankBurn), None),
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            (Some(RoleMsgType::WasmExecute), Some(contract_addr))
+        }
+        CosmosMsg::Wasm(WasmMsg::Instantiate { .. }) => (Some(RoleMsgType::WasmInstantiate), None),
+        _ => (None, None),
+    };
+
+    if target.map_or(false, |target| target == dao.as_str()) {
+        return Err(ContractError::RoleMsgTargetsDao {
+            name: role.name.clone(),
+        });
+    }
+    // Approving an operator would let it move all of the DAO's NFTs
+    // in a collection without counting against a spend limit.
+    if let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = msg {
+        if let Ok(Cw721ExecuteMsg::ApproveAll { .. }) = from_json(msg) {
+            return Err(ContractError::RoleMsgNotAllowed {
+                name: role.name.clone(),
+            });
+        }
+    }
+
+    let allowed = msg_type.map_or(false, |msg_type| role.msg_types.contains(&msg_type))
+        && match (&role.targets, target) {
+            (Some(targets), Some(target)) => targets.contains(target),
+            _ => true,
+        };
+    if allowed {
+        Ok(())
+    } else {
+        Err(ContractError::RoleMsgNotAllowed {
+            name: role.name.clone(),
+        })
+    }
+}
+
+/// The cw721 collection `msg` moves one of the DAO's NFTs out of, if
+/// any. Moving an NFT counts as spending one token of the collection,
+/// so holders of a role may only move NFTs within a spend limit on
+/// the collection's address.
+pub(crate) fn nft_spend(msg: &CosmosMsg<Empty>) -> Option<(String, Uint128)> {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => match from_json(msg) {
+            Ok(
+                Cw721ExecuteMsg::TransferNft { .. }
+                | Cw721ExecuteMsg::SendNft { .. }
+                | Cw721ExecuteMsg::Approve { .. }
+                | Cw721ExecuteMsg::Burn { .. },
+            ) => Some((contract_addr.clone(), Uint128::one())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Records `amount` of `denom` as spent under `role`, erroring if
+/// this exceeds the role's spend limit for the current period.
+pub(crate) fn record_spend(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    role: &Role,
+    denom: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let exceeded = || ContractError::SpendLimitExceeded {
+        name: role.name.clone(),
+        denom: denom.to_string(),
+    };
+
+    let limit = role
+        .spend_limits
+        .iter()
+        .find(|limit| limit.denom == denom)
+        .ok_or_else(exceeded)?;
+
+    let mut spending = match ROLE_SPENDING.may_load(storage, (role.name.as_str(), denom))? {
+        Some(spending) if !spending.resets.is_expired(block) => spending,
+        _ => RoleSpending {
+            denom: denom.to_string(),
+            spent: Uint128::zero(),
+            resets: limit.period.after(block),
+        },
+    };
+    spending.spent = spending
+        .spent
+        .checked_add(amount)
+        .map_err(|_| ContractError::Overflow {})?;
+    if spending.spent > limit.amount {
+        return Err(exceeded());
+    }
+
+    ROLE_SPENDING.save(storage, (role.name.as_str(), denom), &spending)?;
+    Ok(())
+}
@@ -1,7 +1,11 @@
-use cosmwasm_std::{Addr, Empty};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Binary, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
 use cw_utils::Expiration;
+use dao_interface::role::{Role, RoleSpending};
 use dao_interface::state::{Config, ProposalModule};
+use dao_interface::timelock::{QueuedExecution, Timelock};
+use dao_interface::treasury::{SpendingCap, SubDaoBudget, TreasuryHoldingKind};
 
 /// The admin of the contract. Typically a DAO. The contract admin may
 /// unilaterally execute messages on this contract.
@@ -27,6 +31,15 @@ pub const CONFIG: Item<Config> = Item::new("config_v2");
 /// the DAO has never been paused.
 pub const PAUSED: Item<Expiration> = Item::new("paused");
 
+/// The time a pause by the guardian ends. Removed when the DAO
+/// pauses or unpauses itself.
+pub const GUARDIAN_PAUSE: Item<Expiration> = Item::new("guardian_pause");
+
+/// The earliest the guardian may pause the DAO again. Gives the DAO
+/// time to execute proposals, for example to replace its guardian,
+/// between guardian pauses.
+pub const GUARDIAN_PAUSE_COOLDOWN: Item<Expiration> = Item::new("guardian_pause_cooldown");
+
 /// The voting module associated with this contract.
 pub const VOTING_MODULE: Item<Addr> = Item::new("voting_module");
 
@@ -35,6 +48,21 @@ pub const VOTING_MODULE: Item<Addr> = Item::new("voting_module");
 /// to create a new namespace for the changed state.
 pub const PROPOSAL_MODULES: Map<Addr, ProposalModule> = Map::new("proposal_modules_v2");
 
+/// The proposal module being upgraded, when it will stop draining,
+/// and whether its config is copied to its successor. Set while its
+/// successor is instantiated.
+pub const UPGRADING_PROPOSAL_MODULE: Item<(Addr, Expiration, bool)> =
+    Item::new("upgrading_proposal_module");
+
+/// Draining proposal modules mapped to the ID of the last proposal
+/// `FinishDraining` has found closed, so that it may resume from there.
+/// Every proposal up to that ID is closed.
+pub const DRAINING_SCAN_MARKS: Map<Addr, u64> = Map::new("draining_scan_marks");
+
+/// Proposal modules suspended by the guardian, mapped to when their
+/// suspension ends.
+pub const MODULE_SUSPENSIONS: Map<Addr, Expiration> = Map::new("module_suspensions");
+
 /// The count of active proposal modules associated with this contract.
 pub const ACTIVE_PROPOSAL_MODULE_COUNT: Item<u32> = Item::new("active_proposal_module_count");
 
@@ -44,6 +72,18 @@ pub const TOTAL_PROPOSAL_MODULE_COUNT: Item<u32> = Item::new("total_proposal_mod
 // General purpose KV store for DAO associated state.
 pub const ITEMS: Map<String, String> = Map::new("items");
 
+/// Items with JSON values, keyed by namespace and key. Changes are
+/// snapshotted so that past values may be queried.
+pub const NAMESPACED_ITEMS: SnapshotMap<(&str, &str), Binary> = SnapshotMap::new(
+    "namespaced_items",
+    "namespaced_items__checkpoints",
+    "namespaced_items__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Addresses other than the DAO that may write to a namespace.
+pub const NAMESPACE_WRITERS: Map<(&str, &Addr), Empty> = Map::new("namespace_writers");
+
 /// Set of cw20 tokens that have been registered with this contract's
 /// treasury.
 pub const CW20_LIST: Map<Addr, Empty> = Map::new("cw20s");
@@ -53,3 +93,45 @@ pub const CW721_LIST: Map<Addr, Empty> = Map::new("cw721s");
 
 /// List of SubDAOs associated to this DAO. Each SubDAO has an optional charter.
 pub const SUBDAO_LIST: Map<&Addr, Option<String>> = Map::new("sub_daos");
+
+/// Budgets delegated to SubDAOs, keyed by SubDAO and by the native
+/// denom or cw20 address of the token.
+pub const SUBDAO_BUDGETS: Map<(&Addr, &str), SubDaoBudget> = Map::new("sub_dao_budgets");
+
+/// Roles the DAO has created, keyed by name.
+pub const ROLES: Map<&str, Role> = Map::new("roles");
+
+/// The addresses holding each role.
+pub const ROLE_HOLDERS: Map<(&str, &Addr), Empty> = Map::new("role_holders");
+
+/// The spending under each role in its current period, keyed by role
+/// name and denom.
+pub const ROLE_SPENDING: Map<(&str, &str), RoleSpending> = Map::new("role_spending");
+
+/// Caps on spending by proposals, keyed by denom.
+pub const SPENDING_CAPS: Map<&str, SpendingCap> = Map::new("spending_caps");
+
+/// The amount of a token spent by proposals in a block.
+#[cw_serde]
+pub struct SpendRecord {
+    pub time: Timestamp,
+    pub amount: Uint128,
+}
+
+/// The DAO's timelock. Not set if the DAO has no timelock.
+pub const TIMELOCK: Item<Timelock> = Item::new("timelock");
+
+/// Messages delayed by the timelock, keyed by ID.
+pub const QUEUED_EXECUTIONS: Map<u64, QueuedExecution> = Map::new("queued_executions");
+
+/// The number of executions that have been queued. Used to assign
+/// IDs.
+pub const QUEUED_EXECUTION_COUNT: Item<u64> = Item::new("queued_execution_count");
+
+/// Contracts that hold tokens on the DAO's behalf.
+pub const TREASURY_HOLDINGS: Map<&Addr, TreasuryHoldingKind> = Map::new("treasury_holdings");
+
+/// Spending by proposals of tokens with a windowed spending cap,
+/// keyed by denom and block height. Only spending within the current
+/// window is kept.
+pub const TREASURY_SPENDS: Map<(&str, u64), SpendRecord> = Map::new("treasury_spends");
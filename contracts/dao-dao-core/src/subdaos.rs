@@ -0,0 +1,124 @@
+use cosmwasm_std::{Addr, Deps, Order, QuerierWrapper, StdResult};
+use dao_interface::{
+    msg::QueryMsg,
+    query::{SubDao, SubDaoNode},
+    treasury::SubDaoBudget,
+};
+
+use crate::state::{SUBDAO_BUDGETS, SUBDAO_LIST};
+
+/// The number of levels below the DAO returned by the `SubDaoTree`
+/// query if no depth is given.
+pub(crate) const DEFAULT_SUBDAO_TREE_DEPTH: u32 = 3;
+/// The most levels below the DAO the `SubDaoTree` query may return.
+/// Each level queries every SubDAO in it, so this bounds the gas the
+/// query may use.
+pub(crate) const MAX_SUBDAO_TREE_DEPTH: u32 = 10;
+
+/// The number of SubDAOs requested from a SubDAO at a time.
+const LIST_LIMIT: u32 = 30;
+
+/// Lists all of the SubDAOs of `dao` by querying it. Returns no
+/// SubDAOs if `dao` is not a DAO.
+fn list_sub_daos(querier: &QuerierWrapper, dao: &Addr) -> Vec<SubDao> {
+    let mut sub_daos: Vec<SubDao> = vec![];
+    loop {
+        let page: Vec<SubDao> = match querier.query_wasm_smart(
+            dao,
+            &QueryMsg::ListSubDaos {
+                start_after: sub_daos.last().map(|sub_dao| sub_dao.addr.clone()),
+                limit: Some(LIST_LIMIT),
+            },
+        ) {
+            Ok(page) => page,
+            Err(_) => break,
+        };
+        let done = page.len() < LIST_LIMIT as usize;
+        sub_daos.extend(page);
+        if done {
+            break;
+        }
+    }
+    sub_daos
+}
+
+/// Builds the tree of SubDAOs below `sub_dao`, whose parent is the
+/// last address in `path`. SubDAOs already in `path` are not
+/// descended into again, so cycles in the hierarchy are cut short.
+fn sub_dao_node(
+    querier: &QuerierWrapper,
+    path: &mut Vec<Addr>,
+    sub_dao: SubDao,
+    budgets: Vec<SubDaoBudget>,
+    depth: u32,
+) -> SubDaoNode {
+    let addr = Addr::unchecked(&sub_dao.addr);
+    let parent = path.last().cloned();
+    let verified = querier
+        .query_wasm_smart::<Addr>(&addr, &QueryMsg::Admin {})
+        .map_or(false, |admin| Some(admin) == parent);
+
+    let children = if depth > 1 && !path.contains(&addr) {
+        path.push(addr.clone());
+        let children = list_sub_daos(querier, &addr)
+            .into_iter()
+            .map(|child| {
+                let budgets = querier
+                    .query_wasm_smart(
+                        &addr,
+                        &QueryMsg::SubDaoBudgets {
+                            sub_dao: child.addr.clone(),
+                        },
+                    )
+                    .unwrap_or_default();
+                sub_dao_node(querier, path, child, budgets, depth - 1)
+            })
+            .collect();
+        path.pop();
+        children
+    } else {
+        vec![]
+    };
+
+    SubDaoNode {
+        addr: sub_dao.addr,
+        charter: sub_dao.charter,
+        verified,
+        budgets,
+        children,
+    }
+}
+
+/// Lists the budgets delegated to `sub_dao`.
+pub(crate) fn sub_dao_budgets(deps: Deps, sub_dao: &Addr) -> StdResult<Vec<SubDaoBudget>> {
+    SUBDAO_BUDGETS
+        .prefix(sub_dao)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Builds the tree of SubDAOs up to `depth` levels below `dao`.
+pub(crate) fn sub_dao_tree(deps: Deps, dao: Addr, depth: u32) -> StdResult<Vec<SubDaoNode>> {
+    if depth == 0 {
+        return Ok(vec![]);
+    }
+    let mut path = vec![dao];
+    SUBDAO_LIST
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (addr, charter) = item?;
+            let budgets = sub_dao_budgets(deps, &addr)?;
+            Ok(sub_dao_node(
+                &deps.querier,
+                &mut path,
+                SubDao {
+                    addr: addr.into_string(),
+                    charter,
+                },
+                budgets,
+                depth,
+            ))
+        })
+        .collect()
+}
@@ -1,25 +1,35 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    from_json,
+    coin, coins, from_json,
     testing::{mock_dependencies, mock_env},
-    to_json_binary, Addr, CosmosMsg, Empty, Storage, Uint128, WasmMsg,
+    to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Empty, Storage, Uint128, WasmMsg,
 };
 use cw2::{set_contract_version, ContractVersion};
-use cw_multi_test::{App, Executor};
+use cw_denom::{CheckedDenom, UncheckedDenom};
+use cw_multi_test::{next_block, App, AppResponse, BankSudo, Executor};
 use cw_storage_plus::{Item, Map};
 use cw_utils::{Duration, Expiration};
 use dao_interface::{
     msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, QueryMsg},
     query::{
         AdminNominationResponse, Cw20BalanceResponse, DaoURIResponse, DumpStateResponse,
-        GetItemResponse, PauseInfoResponse, ProposalModuleCountResponse, SubDao,
+        GetItemResponse, GetNamespacedItemResponse, GuardianAction, PauseInfoResponse,
+        ProposalModuleCountResponse, ProposalModuleVersion, RemainingBudgetResponse, RoleResponse,
+        SubDao, SubDaoNode,
+    },
+    role::{Role, RoleMsgType, SpendLimit},
+    state::{Admin, Config, Guardian, ModuleInstantiateInfo, ProposalModule, ProposalModuleStatus},
+    timelock::{QueuedExecution, Timelock, TimelockCriterion},
+    treasury::{
+        SpendingCap, SubDaoBudget, TreasuryAssetType, TreasuryBalance, TreasuryBalanceKey,
+        TreasuryHolding, TreasuryHoldingKind, WindowCap, SPENDING_CAP_EXCEEDED_EVENT,
     },
-    state::{Admin, Config, ModuleInstantiateInfo, ProposalModule, ProposalModuleStatus},
     voting::{InfoResponse, VotingPowerAtHeightResponse},
 };
 use dao_testing::contracts::{
-    cw20_base_contract, cw721_base_contract, dao_dao_core_contract, dao_proposal_sudo_contract,
-    dao_voting_cw20_balance_contract, v1::cw_core_v1_contract,
+    cw20_base_contract, cw20_stake_contract, cw721_base_contract, dao_dao_core_contract,
+    dao_proposal_single_contract, dao_proposal_sudo_contract, dao_voting_cw20_balance_contract,
+    v1::cw_core_v1_contract,
 };
 
 use crate::{
@@ -97,6 +107,7 @@ fn test_instantiate_with_n_gov_modules(n: usize) {
             image_url: None,
             automatically_add_cw20s: true,
             automatically_add_cw721s: true,
+            guardian: None,
         }
     );
 
@@ -250,6 +261,7 @@ fn test_update_config() {
         automatically_add_cw20s: false,
         automatically_add_cw721s: true,
         dao_uri: Some("https://daostar.one/EIP".to_string()),
+        guardian: None,
     };
 
     app.execute_contract(
@@ -913,6 +925,7 @@ fn test_permissions() {
                 image_url: None,
                 automatically_add_cw20s: true,
                 automatically_add_cw721s: true,
+                guardian: None,
             },
         },
     );
@@ -920,6 +933,11 @@ fn test_permissions() {
 
 fn do_standard_instantiate(auto_add: bool, admin: Option<String>) -> (Addr, App) {
     let mut app = App::default();
+    let gov_addr = instantiate_standard_dao(&mut app, auto_add, admin);
+    (gov_addr, app)
+}
+
+fn instantiate_standard_dao(app: &mut App, auto_add: bool, admin: Option<String>) -> Addr {
     let govmod_id = app.store_code(dao_proposal_sudo_contract());
     let voting_id = app.store_code(dao_voting_cw20_balance_contract());
     let gov_id = app.store_code(dao_dao_core_contract());
@@ -968,18 +986,79 @@ fn do_standard_instantiate(auto_add: bool, admin: Option<String>) -> (Addr, App)
         initial_items: None,
     };
 
-    let gov_addr = app
-        .instantiate_contract(
-            gov_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &gov_instantiate,
-            &[],
-            "cw-governance",
-            None,
+    app.instantiate_contract(
+        gov_id,
+        Addr::unchecked(CREATOR_ADDR),
+        &gov_instantiate,
+        &[],
+        "cw-governance",
+        None,
+    )
+    .unwrap()
+}
+
+/// Instantiate info for a single choice proposal module that anyone
+/// may propose to.
+fn single_choice_module(code_id: u64) -> ModuleInstantiateInfo {
+    ModuleInstantiateInfo {
+        code_id,
+        msg: to_json_binary(&dao_proposal_single::msg::InstantiateMsg {
+            threshold: dao_voting::threshold::Threshold::AbsolutePercentage {
+                percentage: dao_voting::threshold::PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            pre_propose_info: dao_voting::pre_propose::PreProposeInfo::AnyoneMayPropose {},
+            close_proposal_on_execution_failure: true,
+            veto: None,
+        })
+        .unwrap(),
+        admin: Some(Admin::CoreModule {}),
+        funds: vec![],
+        label: "single choice proposal module".to_string(),
+    }
+}
+
+/// Creates a proposal in a single choice proposal module and passes
+/// it with the standard DAO's only member. Returns the proposal's ID.
+fn pass_single_choice_proposal(app: &mut App, module: &Addr, msgs: Vec<CosmosMsg>) -> u64 {
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        module.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::Propose(
+            dao_voting::proposal::SingleChoiceProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs,
+                proposer: None,
+                vote: None,
+                execution_plan: None,
+            },
+        ),
+        &[],
+    )
+    .unwrap();
+    let proposal_id: u64 = app
+        .wrap()
+        .query_wasm_smart(
+            module.clone(),
+            &dao_proposal_single::msg::QueryMsg::ProposalCount {},
         )
         .unwrap();
-
-    (gov_addr, app)
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        module.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id,
+            vote: dao_voting::voting::Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    proposal_id
 }
 
 #[test]
@@ -2382,6 +2461,7 @@ fn test_pause() {
                 image_url: None,
                 automatically_add_cw20s: true,
                 automatically_add_cw721s: true,
+                guardian: None,
             },
         },
         &[],
@@ -2458,6 +2538,7 @@ fn test_pause() {
                 image_url: None,
                 automatically_add_cw20s: true,
                 automatically_add_cw721s: true,
+                guardian: None,
             },
         },
         &[],
@@ -3118,6 +3199,2402 @@ fn test_add_remove_subdaos() {
     assert_eq!(res, full_result_set);
 }
 
+#[test]
+fn test_roles() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: core_addr.to_string(),
+            amount: coins(1000, "ujuno"),
+        },
+    ))
+    .unwrap();
+
+    let role = Role {
+        name: "ops".to_string(),
+        msg_types: vec![RoleMsgType::BankSend],
+        targets: Some(vec!["vendor".to_string()]),
+        spend_limits: vec![SpendLimit {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(100),
+            period: Duration::Height(10),
+        }],
+        expiration: Expiration::AtHeight(app.block_info().height + 20),
+    };
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::SetRole { role: role.clone() },
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::SetRole { role: role.clone() },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateRoleHolders {
+            name: "ops".to_string(),
+            to_add: vec!["operator".to_string()],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let holders: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListRoleHolders {
+                name: "ops".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(holders, vec![Addr::unchecked("operator")]);
+
+    let pay = |amount: u128, to: &str| ExecuteMsg::ExecuteRoleMsgs {
+        role: "ops".to_string(),
+        msgs: vec![BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()],
+    };
+
+    // Only holders of the role may use it.
+    test_unauthorized(&mut app, core_addr.clone(), pay(60, "vendor"));
+
+    app.execute_contract(
+        Addr::unchecked("operator"),
+        core_addr.clone(),
+        &pay(60, "vendor"),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance("vendor", "ujuno").unwrap().amount,
+        Uint128::new(60)
+    );
+
+    // Spending is limited per period.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("operator"),
+            core_addr.clone(),
+            &pay(60, "vendor"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::SpendLimitExceeded {
+            name: "ops".to_string(),
+            denom: "ujuno".to_string(),
+        }
+    );
+
+    let res: RoleResponse = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::GetRole {
+                name: "ops".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.role, role);
+    assert_eq!(res.spending[0].spent, Uint128::new(60));
+
+    // Messages may only target the role's targets.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("operator"),
+            core_addr.clone(),
+            &pay(10, "someone"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::RoleMsgNotAllowed {
+            name: "ops".to_string(),
+        }
+    );
+
+    // The limit resets once the period ends.
+    app.update_block(|block| block.height += 10);
+    app.execute_contract(
+        Addr::unchecked("operator"),
+        core_addr.clone(),
+        &pay(60, "vendor"),
+        &[],
+    )
+    .unwrap();
+
+    // Expired roles may not be used.
+    app.update_block(|block| block.height += 10);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("operator"),
+            core_addr.clone(),
+            &pay(10, "vendor"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::RoleExpired {
+            name: "ops".to_string(),
+        }
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::RemoveRole {
+            name: "ops".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let roles: Vec<Role> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListRoles {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(roles.is_empty());
+    let holders: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &QueryMsg::ListRoleHolders {
+                name: "ops".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(holders.is_empty());
+}
+
+#[test]
+fn test_role_msgs_may_not_take_over_dao() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let cw721_id = app.store_code(cw721_base_contract());
+    let cw721_addr = app
+        .instantiate_contract(
+            cw721_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw721_base::msg::InstantiateMsg {
+                name: "ekez".to_string(),
+                symbol: "ekez".to_string(),
+                minter: CREATOR_ADDR.to_string(),
+            },
+            &[],
+            "cw721",
+            None,
+        )
+        .unwrap();
+    for token_id in ["1", "2"] {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            cw721_addr.clone(),
+            &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::Mint {
+                token_id: token_id.to_string(),
+                owner: core_addr.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // A role that may execute any contract.
+    let role = Role {
+        name: "ops".to_string(),
+        msg_types: vec![RoleMsgType::WasmExecute],
+        targets: None,
+        spend_limits: vec![],
+        expiration: Expiration::Never {},
+    };
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::SetRole { role: role.clone() },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateRoleHolders {
+            name: "ops".to_string(),
+            to_add: vec!["operator".to_string()],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let execute = |app: &mut App, contract: &Addr, msg: Binary| -> Result<(), ContractError> {
+        app.execute_contract(
+            Addr::unchecked("operator"),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteRoleMsgs {
+                role: "ops".to_string(),
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: contract.to_string(),
+                    msg,
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    };
+
+    // Holders may not execute messages on the DAO, which would let
+    // them grant themselves any role.
+    let err = execute(
+        &mut app,
+        &core_addr,
+        to_json_binary(&ExecuteMsg::SetRole {
+            role: Role {
+                name: "admin".to_string(),
+                msg_types: vec![RoleMsgType::BankSend],
+                spend_limits: vec![SpendLimit {
+                    denom: "ujuno".to_string(),
+                    amount: Uint128::MAX,
+                    period: Duration::Height(1),
+                }],
+                ..role.clone()
+            },
+        })
+        .unwrap(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RoleMsgTargetsDao {
+            name: "ops".to_string()
+        }
+    );
+
+    // Moving the DAO's NFTs spends from the collection's limit, so a
+    // role without one may not move them.
+    let transfer = |token_id: &str| {
+        to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+            recipient: "operator".to_string(),
+            token_id: token_id.to_string(),
+        })
+        .unwrap()
+    };
+    let err = execute(&mut app, &cw721_addr, transfer("1")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SpendLimitExceeded {
+            name: "ops".to_string(),
+            denom: cw721_addr.to_string(),
+        }
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::SetRole {
+            role: Role {
+                spend_limits: vec![SpendLimit {
+                    denom: cw721_addr.to_string(),
+                    amount: Uint128::one(),
+                    period: Duration::Height(10),
+                }],
+                ..role
+            },
+        },
+        &[],
+    )
+    .unwrap();
+    execute(&mut app, &cw721_addr, transfer("1")).unwrap();
+    let err = execute(&mut app, &cw721_addr, transfer("2")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SpendLimitExceeded {
+            name: "ops".to_string(),
+            denom: cw721_addr.to_string(),
+        }
+    );
+
+    // Approving an operator for the whole collection is never allowed.
+    let err = execute(
+        &mut app,
+        &cw721_addr,
+        to_json_binary(&cw721::Cw721ExecuteMsg::ApproveAll {
+            operator: "operator".to_string(),
+            expires: None,
+        })
+        .unwrap(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RoleMsgNotAllowed {
+            name: "ops".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_spending_caps() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: core_addr.to_string(),
+            amount: coins(1000, "ujuno"),
+        },
+    ))
+    .unwrap();
+    let proposal_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let cap = SpendingCap {
+        denom: "ujuno".to_string(),
+        per_execution: Some(Uint128::new(100)),
+        per_window: Some(WindowCap {
+            amount: Uint128::new(150),
+            window: Duration::Height(10),
+        }),
+    };
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::UpdateSpendingCaps {
+            to_set: vec![cap.clone()],
+            to_remove: vec![],
+        },
+    );
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateSpendingCaps {
+            to_set: vec![cap.clone()],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let caps: Vec<SpendingCap> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::SpendingCaps {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(caps, vec![cap]);
+
+    let execute = |app: &mut App, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![BankMsg::Send {
+                    to_address: "recipient".to_string(),
+                    amount: coins(amount, "ujuno"),
+                }
+                .into()],
+            },
+            &[],
+        )
+    };
+
+    let err: ContractError = execute(&mut app, 120).unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err,
+        ContractError::SpendingCapExceeded {
+            denom: "ujuno".to_string(),
+            remaining: Uint128::new(100),
+        }
+    );
+
+    execute(&mut app, 100).unwrap();
+    app.update_block(next_block);
+    let budget: RemainingBudgetResponse = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::RemainingBudget {
+                denom: "ujuno".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(budget.per_execution, Some(Uint128::new(100)));
+    assert_eq!(budget.window_remaining, Some(Uint128::new(50)));
+
+    let err: ContractError = execute(&mut app, 60).unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err,
+        ContractError::SpendingCapExceeded {
+            denom: "ujuno".to_string(),
+            remaining: Uint128::new(50),
+        }
+    );
+
+    // Spending leaves the window as it rolls forward.
+    app.update_block(|block| block.height += 9);
+    execute(&mut app, 60).unwrap();
+    assert_eq!(
+        app.wrap()
+            .query_balance("recipient", "ujuno")
+            .unwrap()
+            .amount,
+        Uint128::new(160)
+    );
+
+    // Tokens without a cap are not limited.
+    let budget: RemainingBudgetResponse = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &QueryMsg::RemainingBudget {
+                denom: "uatom".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(budget.per_execution, None);
+    assert_eq!(budget.window_remaining, None);
+}
+
+#[test]
+fn test_spending_cap_closes_proposal() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: core_addr.to_string(),
+            amount: coins(1000, "ujuno"),
+        },
+    ))
+    .unwrap();
+    let sudo_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let single_id = app.store_code(dao_proposal_single_contract());
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateProposalModules {
+            to_add: vec![single_choice_module(single_id)],
+            to_disable: vec![sudo_module.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateSpendingCaps {
+            to_set: vec![SpendingCap {
+                denom: "ujuno".to_string(),
+                per_execution: Some(Uint128::new(100)),
+                per_window: None,
+            }],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal_module = get_active_modules(&app, core_addr)
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let proposal_id = pass_single_choice_proposal(
+        &mut app,
+        &proposal_module,
+        vec![BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(101, "ujuno"),
+        }
+        .into()],
+    );
+
+    // The proposal module closes the proposal and reports the cap as
+    // the reason its execution failed.
+    let res = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &dao_proposal_single::msg::ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .unwrap();
+    let attribute = |key: &str| {
+        res.events
+            .iter()
+            .flat_map(|event| event.attributes.iter())
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.clone())
+    };
+    assert_eq!(
+        attribute("proposal_execution_failed"),
+        Some(proposal_id.to_string())
+    );
+    let blocked = ContractError::SpendingCapExceeded {
+        denom: "ujuno".to_string(),
+        remaining: Uint128::new(100),
+    };
+    assert!(attribute("error").unwrap().contains(&blocked.to_string()));
+
+    // A dedicated event names the token and its remaining budget.
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == format!("wasm-{SPENDING_CAP_EXCEEDED_EVENT}"))
+        .unwrap();
+    let event_attribute = |key: &str| {
+        event
+            .attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_str())
+    };
+    assert_eq!(
+        event_attribute("proposal_id"),
+        Some(proposal_id.to_string().as_str())
+    );
+    assert_eq!(event_attribute("denom"), Some("ujuno"));
+    assert_eq!(event_attribute("remaining"), Some("100"));
+
+    let proposal: dao_proposal_single::query::ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module,
+            &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id },
+        )
+        .unwrap();
+    assert_eq!(
+        proposal.proposal.status,
+        dao_voting::status::Status::ExecutionFailed
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance("recipient", "ujuno")
+            .unwrap()
+            .amount,
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_guardian() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let proposal_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let guardian = Addr::unchecked("guardian");
+
+    let guardian_execute = |app: &mut App, msg: &ExecuteMsg| {
+        app.execute_contract(guardian.clone(), core_addr.clone(), msg, &[])
+    };
+    let execute_proposal = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute { msgs: vec![] },
+            &[],
+        )
+    };
+    let update_guardian = |app: &mut App, new_guardian: Option<Guardian>| {
+        let config: Config = app
+            .wrap()
+            .query_wasm_smart(core_addr.clone(), &QueryMsg::Config {})
+            .unwrap();
+        app.execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                config: Config {
+                    guardian: new_guardian,
+                    ..config
+                },
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    let pause = ExecuteMsg::GuardianPause {
+        duration: Duration::Height(10),
+    };
+    let err: ContractError = guardian_execute(&mut app, &pause)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoGuardian {});
+
+    update_guardian(
+        &mut app,
+        Some(Guardian {
+            addr: guardian.to_string(),
+            max_duration: Duration::Height(10),
+        }),
+    );
+
+    test_unauthorized(&mut app, core_addr.clone(), pause.clone());
+    for duration in [Duration::Height(11), Duration::Time(5)] {
+        let err: ContractError =
+            guardian_execute(&mut app, &ExecuteMsg::GuardianPause { duration })
+                .unwrap_err()
+                .downcast()
+                .unwrap();
+        assert_eq!(err, ContractError::GuardianDurationTooLong {});
+    }
+
+    // The guardian may not execute messages on the DAO's behalf.
+    let err: ContractError = guardian_execute(
+        &mut app,
+        &ExecuteMsg::ExecuteAdminMsgs {
+            msgs: vec![BankMsg::Burn {
+                amount: coins(1, "ujuno"),
+            }
+            .into()],
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let suspend = ExecuteMsg::GuardianSuspendModule {
+        address: proposal_module.to_string(),
+        duration: Duration::Height(5),
+    };
+    let res = guardian_execute(&mut app, &suspend).unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-guardian_suspend_module"));
+    let expiration = Expiration::AtHeight(app.block_info().height + 5);
+
+    let suspended = ContractError::ModuleSuspended {
+        address: proposal_module.clone(),
+        expiration,
+    };
+    let err: ContractError = execute_proposal(&mut app).unwrap_err().downcast().unwrap();
+    assert_eq!(err, suspended);
+    let err: ContractError = guardian_execute(&mut app, &suspend)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, suspended);
+
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(
+        state.guardian_actions,
+        vec![GuardianAction::SuspendModule {
+            address: proposal_module.clone(),
+            expiration,
+        }]
+    );
+
+    // The DAO may lift the suspension early.
+    let lift = ExecuteMsg::LiftModuleSuspension {
+        address: proposal_module.to_string(),
+    };
+    test_unauthorized(&mut app, core_addr.clone(), lift.clone());
+    app.execute_contract(core_addr.clone(), core_addr.clone(), &lift, &[])
+        .unwrap();
+    execute_proposal(&mut app).unwrap();
+    let err: ContractError = app
+        .execute_contract(core_addr.clone(), core_addr.clone(), &lift, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ModuleNotSuspended {
+            address: proposal_module.clone()
+        }
+    );
+
+    let res = guardian_execute(&mut app, &pause).unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-guardian_pause"));
+    let expiration = Expiration::AtHeight(app.block_info().height + 10);
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(state.pause_info, PauseInfoResponse::Paused { expiration });
+    assert_eq!(
+        state.guardian_actions,
+        vec![GuardianAction::Pause { expiration }]
+    );
+
+    // The guardian may not extend its pause.
+    let err: ContractError = guardian_execute(&mut app, &pause)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Paused {});
+
+    app.update_block(|block| block.height += 10);
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(state.pause_info, PauseInfoResponse::Unpaused {});
+    assert_eq!(state.guardian_actions, vec![]);
+
+    // The guardian may not pause again until the DAO has had
+    // `max_duration` to execute proposals.
+    let cooldown = (expiration + Duration::Height(10)).unwrap();
+    let err: ContractError = guardian_execute(&mut app, &pause)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::GuardianPauseCooldown {
+            expiration: cooldown
+        }
+    );
+    execute_proposal(&mut app).unwrap();
+
+    app.update_block(|block| block.height += 10);
+    guardian_execute(&mut app, &pause).unwrap();
+    let err: ContractError = execute_proposal(&mut app).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::Paused {});
+
+    // The DAO may remove its guardian.
+    update_guardian(&mut app, None);
+    let err: ContractError = guardian_execute(&mut app, &pause)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoGuardian {});
+}
+
+#[test]
+fn test_sub_dao_hierarchy() {
+    let (parent, mut app) = do_standard_instantiate(false, None);
+    let child = instantiate_standard_dao(&mut app, false, Some(parent.to_string()));
+    let grandchild = instantiate_standard_dao(&mut app, false, Some(child.to_string()));
+    // Listed as a SubDAO, but not controlled by the parent.
+    let rogue = instantiate_standard_dao(&mut app, false, None);
+
+    app.execute_contract(
+        parent.clone(),
+        parent.clone(),
+        &ExecuteMsg::UpdateSubDaos {
+            to_add: vec![
+                SubDao {
+                    addr: child.to_string(),
+                    charter: Some("council".to_string()),
+                },
+                SubDao {
+                    addr: rogue.to_string(),
+                    charter: None,
+                },
+            ],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    // The child lists its parent too, forming a cycle.
+    app.execute_contract(
+        child.clone(),
+        child.clone(),
+        &ExecuteMsg::UpdateSubDaos {
+            to_add: vec![
+                SubDao {
+                    addr: grandchild.to_string(),
+                    charter: None,
+                },
+                SubDao {
+                    addr: parent.to_string(),
+                    charter: None,
+                },
+            ],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let find = |nodes: &[SubDaoNode], addr: &Addr| -> SubDaoNode {
+        nodes
+            .iter()
+            .find(|node| node.addr == addr.as_str())
+            .unwrap()
+            .clone()
+    };
+    let query_tree = |app: &App, max_depth: Option<u32>| -> Vec<SubDaoNode> {
+        app.wrap()
+            .query_wasm_smart(parent.clone(), &QueryMsg::SubDaoTree { max_depth })
+            .unwrap()
+    };
+
+    let tree = query_tree(&app, None);
+    assert_eq!(tree.len(), 2);
+    assert!(!find(&tree, &rogue).verified);
+    let child_node = find(&tree, &child);
+    assert!(child_node.verified);
+    assert_eq!(child_node.charter, Some("council".to_string()));
+    assert_eq!(child_node.children.len(), 2);
+    assert!(find(&child_node.children, &grandchild).verified);
+    let cycle = find(&child_node.children, &parent);
+    assert!(!cycle.verified);
+    assert!(cycle.children.is_empty());
+
+    let tree = query_tree(&app, Some(1));
+    assert!(find(&tree, &child).children.is_empty());
+
+    // Budgets.
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: parent.to_string(),
+            amount: coins(1000, "ujuno"),
+        },
+    ))
+    .unwrap();
+    let set_budget = |sub_dao: &Addr, amount: u128| ExecuteMsg::SetSubDaoBudget {
+        sub_dao: sub_dao.to_string(),
+        denom: UncheckedDenom::Native("ujuno".to_string()),
+        amount: Uint128::new(amount),
+    };
+    let draw = ExecuteMsg::DrawSubDaoBudget {
+        denom: UncheckedDenom::Native("ujuno".to_string()),
+        amount: Uint128::new(60),
+    };
+
+    test_unauthorized(&mut app, parent.clone(), set_budget(&child, 100));
+    let err: ContractError = app
+        .execute_contract(
+            parent.clone(),
+            parent.clone(),
+            &set_budget(&grandchild, 100),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NotSubDao {
+            address: grandchild.clone()
+        }
+    );
+    app.execute_contract(
+        parent.clone(),
+        parent.clone(),
+        &set_budget(&child, 100),
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(grandchild.clone(), parent.clone(), &draw, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err: ContractError = app
+        .execute_contract(rogue.clone(), parent.clone(), &draw, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NoSubDaoBudget {
+            denom: "ujuno".to_string()
+        }
+    );
+
+    app.execute_contract(child.clone(), parent.clone(), &draw, &[])
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&child, "ujuno").unwrap().amount,
+        Uint128::new(60)
+    );
+    let err: ContractError = app
+        .execute_contract(child.clone(), parent.clone(), &draw, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::SubDaoBudgetExceeded {
+            denom: "ujuno".to_string(),
+            remaining: Uint128::new(40)
+        }
+    );
+
+    // Raising a budget keeps what has been drawn.
+    app.execute_contract(
+        parent.clone(),
+        parent.clone(),
+        &set_budget(&child, 150),
+        &[],
+    )
+    .unwrap();
+    let budget = SubDaoBudget {
+        denom: CheckedDenom::Native("ujuno".to_string()),
+        amount: Uint128::new(150),
+        drawn: Uint128::new(60),
+    };
+    let budgets: Vec<SubDaoBudget> = app
+        .wrap()
+        .query_wasm_smart(
+            parent.clone(),
+            &QueryMsg::SubDaoBudgets {
+                sub_dao: child.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(budgets, vec![budget.clone()]);
+    assert_eq!(find(&query_tree(&app, None), &child).budgets, vec![budget]);
+
+    // Vetoes.
+    let veto = |sub_dao: &Addr, proposal_module: &Addr| ExecuteMsg::VetoSubDaoProposal {
+        sub_dao: sub_dao.to_string(),
+        proposal_module: proposal_module.to_string(),
+        proposal_id: 1,
+    };
+    let parent_module = get_active_modules(&app, parent.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    test_unauthorized(&mut app, parent.clone(), veto(&child, &parent_module));
+    let err: ContractError = app
+        .execute_contract(
+            parent.clone(),
+            parent.clone(),
+            &veto(&grandchild, &parent_module),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NotSubDao {
+            address: grandchild.clone()
+        }
+    );
+    let err: ContractError = app
+        .execute_contract(
+            parent.clone(),
+            parent.clone(),
+            &veto(&child, &parent_module),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NotSubDaoProposalModule {
+            address: parent_module
+        }
+    );
+
+    // Removing a SubDAO revokes its budgets.
+    app.execute_contract(
+        parent.clone(),
+        parent.clone(),
+        &ExecuteMsg::UpdateSubDaos {
+            to_add: vec![],
+            to_remove: vec![child.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    let budgets: Vec<SubDaoBudget> = app
+        .wrap()
+        .query_wasm_smart(
+            parent,
+            &QueryMsg::SubDaoBudgets {
+                sub_dao: child.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(budgets.is_empty());
+}
+
+#[test]
+fn test_namespaced_items() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let writer = Addr::unchecked("frontend");
+
+    let set = |namespace: &str, value: &[u8]| ExecuteMsg::SetNamespacedItem {
+        namespace: namespace.to_string(),
+        key: "theme".to_string(),
+        value: Binary::from(value),
+    };
+    let get = |app: &App, height: Option<u64>| -> Option<Binary> {
+        let res: GetNamespacedItemResponse = app
+            .wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &QueryMsg::GetNamespacedItem {
+                    namespace: "frontend".to_string(),
+                    key: "theme".to_string(),
+                    height,
+                },
+            )
+            .unwrap();
+        res.value
+    };
+    let blue = br#"{"color":"blue"}"#;
+    let red = br#"{"color":"red"}"#;
+
+    let err: ContractError = app
+        .execute_contract(
+            writer.clone(),
+            core_addr.clone(),
+            &set("frontend", blue),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let update_writers = |namespace: &str| ExecuteMsg::UpdateNamespaceWriters {
+        namespace: namespace.to_string(),
+        to_add: vec![writer.to_string()],
+        to_remove: vec![],
+    };
+    test_unauthorized(&mut app, core_addr.clone(), update_writers("frontend"));
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &update_writers(""),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EmptyNamespace {});
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &update_writers("frontend"),
+        &[],
+    )
+    .unwrap();
+    let writers: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListNamespaceWriters {
+                namespace: "frontend".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(writers, vec![writer.clone()]);
+
+    // Writers may only write valid JSON to their own namespace.
+    let err: ContractError = app
+        .execute_contract(
+            writer.clone(),
+            core_addr.clone(),
+            &set("metadata", blue),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err: ContractError = app
+        .execute_contract(
+            writer.clone(),
+            core_addr.clone(),
+            &set("frontend", b"blue"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidItemValue {
+            key: "theme".to_string()
+        }
+    );
+
+    let start = app.block_info().height;
+    app.execute_contract(
+        writer.clone(),
+        core_addr.clone(),
+        &set("frontend", blue),
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    // The DAO may write to every namespace.
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &set("frontend", red),
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(get(&app, None), Some(Binary::from(red)));
+    assert_eq!(get(&app, Some(start + 1)), Some(Binary::from(blue)));
+    assert_eq!(get(&app, Some(start)), None);
+
+    let items: Vec<(String, Binary)> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListNamespacedItems {
+                namespace: "frontend".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(items, vec![("theme".to_string(), Binary::from(red))]);
+
+    // Namespaced items are separate from untyped ones.
+    let item: GetItemResponse = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::GetItem {
+                key: "theme".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(item.item, None);
+
+    let remove = ExecuteMsg::RemoveNamespacedItem {
+        namespace: "frontend".to_string(),
+        key: "theme".to_string(),
+    };
+    app.update_block(next_block);
+    app.execute_contract(writer.clone(), core_addr.clone(), &remove, &[])
+        .unwrap();
+    assert_eq!(get(&app, None), None);
+    assert_eq!(get(&app, Some(start + 2)), Some(Binary::from(red)));
+    let err: ContractError = app
+        .execute_contract(writer, core_addr, &remove, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::KeyMissing {});
+}
+
+#[test]
+fn test_upgrade_proposal_module() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let old_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap();
+    assert_eq!(
+        old_module.version.clone().unwrap().contract,
+        "crates.io:cw-govmod-sudo"
+    );
+    let code_id = app
+        .wrap()
+        .query_wasm_contract_info(old_module.address.clone())
+        .unwrap()
+        .code_id;
+
+    let upgrade = |address: &Addr| ExecuteMsg::UpgradeProposalModule {
+        address: address.to_string(),
+        new_module: ModuleInstantiateInfo {
+            code_id,
+            msg: to_json_binary(&dao_proposal_sudo::msg::InstantiateMsg {
+                root: CREATOR_ADDR.to_string(),
+            })
+            .unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            funds: vec![],
+            label: "upgraded governance module".to_string(),
+        },
+        drain_duration: Duration::Height(10),
+        copy_config: false,
+    };
+    let execute_proposal = |app: &mut App, module: &Addr| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            module.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute { msgs: vec![] },
+            &[],
+        )
+    };
+    let finish_draining = ExecuteMsg::FinishDraining {
+        address: old_module.address.to_string(),
+        limit: None,
+    };
+
+    test_unauthorized(&mut app, core_addr.clone(), upgrade(&old_module.address));
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &upgrade(&old_module.address),
+        &[],
+    )
+    .unwrap();
+    let until = Expiration::AtHeight(app.block_info().height + 10);
+
+    let new_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap();
+    assert_ne!(new_module.address, old_module.address);
+    assert_eq!(new_module.prefix, "B");
+    assert_eq!(new_module.version, old_module.version);
+
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(state.active_proposal_module_count, 1);
+    assert_eq!(state.total_proposal_module_count, 2);
+    let draining = state
+        .proposal_modules
+        .iter()
+        .find(|module| module.address == old_module.address)
+        .unwrap();
+    assert_eq!(
+        draining.status,
+        ProposalModuleStatus::Draining {
+            until,
+            successor: new_module.address.clone()
+        }
+    );
+
+    // The old module may execute proposals while draining.
+    execute_proposal(&mut app, &old_module.address).unwrap();
+    execute_proposal(&mut app, &new_module.address).unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &upgrade(&old_module.address),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ModuleNotEnabled {
+            address: old_module.address.clone()
+        }
+    );
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &finish_draining,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ModuleStillDraining {
+            address: old_module.address.clone(),
+            until
+        }
+    );
+
+    // The sudo module does not list its proposals, so whether they
+    // have closed can not be checked and the DAO must disable it.
+    app.update_block(|block| block.height += 10);
+    execute_proposal(&mut app, &old_module.address).unwrap();
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        core_addr.clone(),
+        &finish_draining,
+        &[],
+    )
+    .unwrap_err();
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateProposalModules {
+            to_add: vec![],
+            to_disable: vec![old_module.address.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = execute_proposal(&mut app, &old_module.address)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ModuleDisabledCannotExecute {
+            address: old_module.address.clone()
+        }
+    );
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &finish_draining,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ModuleNotDraining {
+            address: old_module.address.clone()
+        }
+    );
+
+    let versions: Vec<ProposalModuleVersion> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &QueryMsg::ProposalModuleVersions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(versions.len(), 2);
+    for version in versions {
+        assert_eq!(version.recorded, old_module.version);
+        assert_eq!(version.current, old_module.version);
+        let expected = if version.address == old_module.address {
+            ProposalModuleStatus::Disabled
+        } else {
+            ProposalModuleStatus::Enabled
+        };
+        assert_eq!(version.status, expected);
+    }
+}
+
+#[test]
+fn test_finish_draining_waits_for_open_proposals() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let sudo_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let single_id = app.store_code(dao_proposal_single_contract());
+
+    let upgrade = |app: &mut App, address: &Addr| {
+        app.execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpgradeProposalModule {
+                address: address.to_string(),
+                new_module: single_choice_module(single_id),
+                drain_duration: Duration::Height(5),
+                copy_config: false,
+            },
+            &[],
+        )
+        .unwrap();
+        get_active_modules(app, core_addr.clone())
+            .into_iter()
+            .next()
+            .unwrap()
+            .address
+    };
+    let finish_draining = |app: &mut App, address: &Addr| {
+        app.execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::FinishDraining {
+                address: address.to_string(),
+                limit: None,
+            },
+            &[],
+        )
+    };
+
+    let old_module = upgrade(&mut app, &sudo_module);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        old_module.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::Propose(
+            dao_voting::proposal::SingleChoiceProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                vote: None,
+                execution_plan: None,
+            },
+        ),
+        &[],
+    )
+    .unwrap();
+    upgrade(&mut app, &old_module);
+
+    // The drain period has passed, but the proposal is still open.
+    app.update_block(|block| block.height += 5);
+    let open = ContractError::ModuleHasOpenProposal {
+        address: old_module.clone(),
+        proposal_id: 1,
+    };
+    let err: ContractError = finish_draining(&mut app, &old_module)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, open);
+
+    // Passed proposals may still be executed.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        old_module.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: dao_voting::voting::Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = finish_draining(&mut app, &old_module)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, open);
+
+    // The draining module may execute after the drain period.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        old_module.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    finish_draining(&mut app, &old_module).unwrap();
+
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let module = modules
+        .into_iter()
+        .find(|module| module.address == old_module)
+        .unwrap();
+    assert_eq!(module.status, ProposalModuleStatus::Disabled);
+}
+
+#[test]
+fn test_finish_draining_resumes() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let sudo_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let single_id = app.store_code(dao_proposal_single_contract());
+
+    let upgrade = |app: &mut App, address: &Addr| {
+        app.execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpgradeProposalModule {
+                address: address.to_string(),
+                new_module: single_choice_module(single_id),
+                drain_duration: Duration::Height(5),
+                copy_config: false,
+            },
+            &[],
+        )
+        .unwrap();
+        get_active_modules(app, core_addr.clone())
+            .into_iter()
+            .next()
+            .unwrap()
+            .address
+    };
+    let finish_draining = |app: &mut App, address: &Addr| {
+        app.execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::FinishDraining {
+                address: address.to_string(),
+                limit: Some(2),
+            },
+            &[],
+        )
+    };
+    let propose = |app: &mut App, module: &Addr| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            module.clone(),
+            &dao_proposal_single::msg::ExecuteMsg::Propose(
+                dao_voting::proposal::SingleChoiceProposeMsg {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    proposer: None,
+                    vote: None,
+                    execution_plan: None,
+                },
+            ),
+            &[],
+        )
+        .unwrap();
+    };
+    let reject = |app: &mut App, module: &Addr, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            module.clone(),
+            &dao_proposal_single::msg::ExecuteMsg::Vote {
+                proposal_id,
+                vote: dao_voting::voting::Vote::No,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let status = |app: &App, address: &Addr| {
+        let modules: Vec<ProposalModule> = app
+            .wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &QueryMsg::ProposalModules {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        modules
+            .into_iter()
+            .find(|module| &module.address == address)
+            .unwrap()
+            .status
+    };
+    let attribute = |res: &AppResponse, key: &str| {
+        res.events
+            .iter()
+            .flat_map(|event| event.attributes.iter())
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.clone())
+    };
+
+    let old_module = upgrade(&mut app, &sudo_module);
+    for proposal_id in 1..=3 {
+        propose(&mut app, &old_module);
+        reject(&mut app, &old_module, proposal_id);
+    }
+    propose(&mut app, &old_module);
+    upgrade(&mut app, &old_module);
+    app.update_block(|block| block.height += 5);
+
+    // The first call finds proposals 1 and 2 closed and stops there.
+    let res = finish_draining(&mut app, &old_module).unwrap();
+    assert_eq!(attribute(&res, "scanned_to"), Some("2".to_string()));
+    assert_eq!(attribute(&res, "finished"), Some("false".to_string()));
+    assert!(matches!(
+        status(&app, &old_module),
+        ProposalModuleStatus::Draining { .. }
+    ));
+
+    // The next call resumes after proposal 2 and finds 4 open.
+    let err: ContractError = finish_draining(&mut app, &old_module)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ModuleHasOpenProposal {
+            address: old_module.clone(),
+            proposal_id: 4,
+        }
+    );
+
+    reject(&mut app, &old_module, 4);
+    let res = finish_draining(&mut app, &old_module).unwrap();
+    assert_eq!(attribute(&res, "scanned_to"), Some("4".to_string()));
+    assert!(matches!(
+        status(&app, &old_module),
+        ProposalModuleStatus::Draining { .. }
+    ));
+
+    let res = finish_draining(&mut app, &old_module).unwrap();
+    assert_eq!(attribute(&res, "finished"), Some("true".to_string()));
+    assert_eq!(status(&app, &old_module), ProposalModuleStatus::Disabled);
+}
+
+#[test]
+fn test_upgrade_proposal_module_copies_config() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let sudo_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let single_id = app.store_code(dao_proposal_single_contract());
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateProposalModules {
+            to_add: vec![single_choice_module(single_id)],
+            to_disable: vec![sudo_module.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    let old_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let query_config = |app: &App, module: &Addr| -> dao_proposal_single::state::Config {
+        app.wrap()
+            .query_wasm_smart(module, &dao_proposal_single::msg::QueryMsg::Config {})
+            .unwrap()
+    };
+
+    // The DAO changes the old module's config after instantiating it.
+    app.execute_contract(
+        core_addr.clone(),
+        old_module.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::UpdateConfig {
+            threshold: dao_voting::threshold::Threshold::AbsoluteCount {
+                threshold: Uint128::new(5),
+            },
+            max_voting_period: Duration::Height(20),
+            min_voting_period: Some(Duration::Height(2)),
+            only_members_execute: false,
+            allow_revoting: true,
+            dao: core_addr.to_string(),
+            close_proposal_on_execution_failure: false,
+            veto: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let old_config = query_config(&app, &old_module);
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpgradeProposalModule {
+            address: old_module.to_string(),
+            new_module: single_choice_module(single_id),
+            drain_duration: Duration::Height(5),
+            copy_config: true,
+        },
+        &[],
+    )
+    .unwrap();
+    let new_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    assert_ne!(new_module, old_module);
+
+    // The new module has the old module's config rather than the
+    // one it was instantiated with.
+    assert_eq!(query_config(&app, &new_module), old_config);
+
+    // Modules that do not report their config like the single and
+    // multiple choice proposal modules may not have it copied.
+    let sudo_id = app
+        .wrap()
+        .query_wasm_contract_info(sudo_module)
+        .unwrap()
+        .code_id;
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateProposalModules {
+            to_add: vec![ModuleInstantiateInfo {
+                code_id: sudo_id,
+                msg: to_json_binary(&dao_proposal_sudo::msg::InstantiateMsg {
+                    root: CREATOR_ADDR.to_string(),
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "sudo governance module".to_string(),
+            }],
+            to_disable: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let sudo_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .find(|module| module.address != new_module)
+        .unwrap()
+        .address;
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr,
+        &ExecuteMsg::UpgradeProposalModule {
+            address: sudo_module.to_string(),
+            new_module: single_choice_module(single_id),
+            drain_duration: Duration::Height(5),
+            copy_config: true,
+        },
+        &[],
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_treasury_balances() {
+    let (gov_addr, mut app) = do_standard_instantiate(false, None);
+
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: gov_addr.to_string(),
+        amount: vec![coin(100, "ujuno"), coin(50, "uatom")],
+    }))
+    .unwrap();
+
+    let cw20_id = app.store_code(cw20_base_contract());
+    let cw20_addr = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![cw20::Cw20Coin {
+                    address: gov_addr.to_string(),
+                    amount: Uint128::new(100),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "cw20",
+            None,
+        )
+        .unwrap();
+
+    let cw721_id = app.store_code(cw721_base_contract());
+    let cw721_addr = app
+        .instantiate_contract(
+            cw721_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw721_base::msg::InstantiateMsg {
+                name: "ekez".to_string(),
+                symbol: "ekez".to_string(),
+                minter: CREATOR_ADDR.to_string(),
+            },
+            &[],
+            "cw721",
+            None,
+        )
+        .unwrap();
+    for token_id in ["1", "2"] {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            cw721_addr.clone(),
+            &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::Mint {
+                token_id: token_id.to_string(),
+                owner: gov_addr.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let stake_id = app.store_code(cw20_stake_contract());
+    let stake_addr = app
+        .instantiate_contract(
+            stake_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_stake::msg::InstantiateMsg {
+                owner: None,
+                token_address: cw20_addr.to_string(),
+                unstaking_duration: None,
+            },
+            &[],
+            "cw20-stake",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        gov_addr.clone(),
+        cw20_addr.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: stake_addr.to_string(),
+            amount: Uint128::new(20),
+            msg: to_json_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    test_unauthorized(
+        &mut app,
+        gov_addr.clone(),
+        ExecuteMsg::UpdateTreasuryHoldings {
+            to_add: vec![],
+            to_remove: vec![],
+        },
+    );
+
+    // A staking contract may not be added as a vesting contract.
+    let err: ContractError = app
+        .execute_contract(
+            gov_addr.clone(),
+            gov_addr.clone(),
+            &ExecuteMsg::UpdateTreasuryHoldings {
+                to_add: vec![TreasuryHolding {
+                    addr: stake_addr.to_string(),
+                    kind: TreasuryHoldingKind::CwVesting,
+                }],
+                to_remove: vec![],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Std(_)));
+
+    for msg in [
+        ExecuteMsg::UpdateCw20List {
+            to_add: vec![cw20_addr.to_string()],
+            to_remove: vec![],
+        },
+        ExecuteMsg::UpdateCw721List {
+            to_add: vec![cw721_addr.to_string()],
+            to_remove: vec![],
+        },
+        ExecuteMsg::UpdateTreasuryHoldings {
+            to_add: vec![TreasuryHolding {
+                addr: stake_addr.to_string(),
+                kind: TreasuryHoldingKind::Cw20Stake,
+            }],
+            to_remove: vec![],
+        },
+    ] {
+        app.execute_contract(gov_addr.clone(), gov_addr.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let holdings: Vec<TreasuryHolding> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::TreasuryHoldings {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        holdings,
+        vec![TreasuryHolding {
+            addr: stake_addr.to_string(),
+            kind: TreasuryHoldingKind::Cw20Stake,
+        }]
+    );
+
+    let query_balances = |app: &App,
+                          asset_type: Option<TreasuryAssetType>,
+                          start_after: Option<TreasuryBalanceKey>,
+                          limit: Option<u32>|
+     -> Vec<TreasuryBalance> {
+        app.wrap()
+            .query_wasm_smart(
+                gov_addr.clone(),
+                &QueryMsg::TreasuryBalances {
+                    asset_type,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    };
+    let balance = |asset_type, id: &str, denom: &str, amount| TreasuryBalance {
+        asset_type,
+        id: id.to_string(),
+        denom: denom.to_string(),
+        amount: Uint128::new(amount),
+    };
+
+    let all = vec![
+        balance(TreasuryAssetType::Native, "uatom", "uatom", 50),
+        balance(TreasuryAssetType::Native, "ujuno", "ujuno", 100),
+        balance(
+            TreasuryAssetType::Cw20,
+            cw20_addr.as_str(),
+            cw20_addr.as_str(),
+            80,
+        ),
+        balance(
+            TreasuryAssetType::Cw721,
+            cw721_addr.as_str(),
+            cw721_addr.as_str(),
+            2,
+        ),
+        balance(
+            TreasuryAssetType::Staked,
+            stake_addr.as_str(),
+            cw20_addr.as_str(),
+            20,
+        ),
+    ];
+    assert_eq!(query_balances(&app, None, None, None), all);
+
+    // Filter by asset type.
+    assert_eq!(
+        query_balances(&app, Some(TreasuryAssetType::Native), None, None),
+        all[..2]
+    );
+    assert_eq!(
+        query_balances(&app, Some(TreasuryAssetType::Staked), None, None),
+        all[4..]
+    );
+    assert_eq!(
+        query_balances(&app, Some(TreasuryAssetType::Vesting), None, None),
+        vec![]
+    );
+
+    // Paginate across asset types.
+    let first_page = query_balances(&app, None, None, Some(3));
+    assert_eq!(first_page, all[..3]);
+    let last = first_page.last().unwrap();
+    assert_eq!(
+        query_balances(
+            &app,
+            None,
+            Some(TreasuryBalanceKey {
+                asset_type: last.asset_type,
+                id: last.id.clone(),
+            }),
+            Some(3)
+        ),
+        all[3..]
+    );
+    assert_eq!(
+        query_balances(
+            &app,
+            None,
+            Some(TreasuryBalanceKey {
+                asset_type: TreasuryAssetType::Native,
+                id: "uatom".to_string(),
+            }),
+            Some(1)
+        ),
+        all[1..2]
+    );
+
+    app.execute_contract(
+        gov_addr.clone(),
+        gov_addr.clone(),
+        &ExecuteMsg::UpdateTreasuryHoldings {
+            to_add: vec![],
+            to_remove: vec![stake_addr.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_balances(&app, None, None, None), all[..4]);
+}
+
+#[test]
+fn test_timelock() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: coins(1000, "ujuno"),
+    }))
+    .unwrap();
+    let proposal_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let timelock = Timelock {
+        delay: Duration::Height(10),
+        criteria: vec![
+            TimelockCriterion::UpdateVotingModule {},
+            TimelockCriterion::Transfer {
+                denom: "ujuno".to_string(),
+                amount: Uint128::new(100),
+            },
+        ],
+    };
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::UpdateTimelock {
+            timelock: Some(timelock.clone()),
+        },
+    );
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpdateTimelock {
+                timelock: Some(Timelock {
+                    delay: Duration::Height(0),
+                    criteria: vec![],
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroTimelockDelay {});
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateTimelock {
+            timelock: Some(timelock.clone()),
+        },
+        &[],
+    )
+    .unwrap();
+    let res: Option<Timelock> = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::Timelock {})
+        .unwrap();
+    assert_eq!(res, Some(timelock));
+
+    let execute = |app: &mut App, msgs: Vec<CosmosMsg>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute { msgs },
+            &[],
+        )
+        .unwrap();
+    };
+    let send = |amount: u128| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()
+    };
+    let recipient_balance = |app: &App| {
+        app.wrap()
+            .query_balance("recipient", "ujuno")
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    // Transfers within the limit are not delayed.
+    execute(&mut app, vec![send(50)]);
+    assert_eq!(recipient_balance(&app), 50);
+
+    // Spends are totalled across the messages executed together.
+    execute(&mut app, vec![send(60), send(60)]);
+    assert_eq!(recipient_balance(&app), 50);
+    let queued: QueuedExecution = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::QueuedExecution { id: 1 })
+        .unwrap();
+    assert_eq!(
+        queued,
+        QueuedExecution {
+            id: 1,
+            sender: proposal_module.clone(),
+            msgs: vec![send(60), send(60)],
+            ready: Expiration::AtHeight(app.block_info().height + 10),
+            capped: true,
+        }
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteQueued { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::QueuedExecutionNotReady {
+            id: 1,
+            ready: queued.ready,
+        }
+    );
+
+    // Removing the timelock is always delayed.
+    execute(
+        &mut app,
+        vec![WasmMsg::Execute {
+            contract_addr: core_addr.to_string(),
+            msg: to_json_binary(&ExecuteMsg::UpdateTimelock { timelock: None }).unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    );
+    let queued: Vec<QueuedExecution> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::QueuedExecutions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        queued.iter().map(|queued| queued.id).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    // Only the DAO, its admin and its guardian may cancel.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::CancelQueued { id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::CancelQueued { id: 2 },
+        &[],
+    )
+    .unwrap();
+    let queued: Vec<QueuedExecution> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::QueuedExecutions {
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(queued, vec![]);
+
+    app.update_block(|block| block.height += 10);
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteQueued { id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(recipient_balance(&app), 170);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteQueued { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::QueuedExecutionNotFound { id: 1 });
+    let res: Option<Timelock> = app
+        .wrap()
+        .query_wasm_smart(core_addr, &QueryMsg::Timelock {})
+        .unwrap();
+    assert!(res.is_some());
+}
+
+#[test]
+fn test_timelock_spending_caps() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: coins(1000, "ujuno"),
+    }))
+    .unwrap();
+    let proposal_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    for msg in [
+        ExecuteMsg::UpdateSpendingCaps {
+            to_set: vec![SpendingCap {
+                denom: "ujuno".to_string(),
+                per_execution: None,
+                per_window: Some(WindowCap {
+                    amount: Uint128::new(150),
+                    window: Duration::Height(100),
+                }),
+            }],
+            to_remove: vec![],
+        },
+        ExecuteMsg::UpdateTimelock {
+            timelock: Some(Timelock {
+                delay: Duration::Height(10),
+                criteria: vec![TimelockCriterion::Transfer {
+                    denom: "ujuno".to_string(),
+                    amount: Uint128::new(100),
+                }],
+            }),
+        },
+    ] {
+        app.execute_contract(core_addr.clone(), core_addr.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let execute = |app: &mut App, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![BankMsg::Send {
+                    to_address: "recipient".to_string(),
+                    amount: coins(amount, "ujuno"),
+                }
+                .into()],
+            },
+            &[],
+        )
+    };
+    let window_remaining = |app: &App| {
+        let budget: RemainingBudgetResponse = app
+            .wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &QueryMsg::RemainingBudget {
+                    denom: "ujuno".to_string(),
+                },
+            )
+            .unwrap();
+        budget.window_remaining.unwrap().u128()
+    };
+
+    // Queued spending is not charged, so cancelling it does not use
+    // up the budget.
+    execute(&mut app, 120).unwrap();
+    assert_eq!(window_remaining(&app), 150);
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::CancelQueued { id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(window_remaining(&app), 150);
+
+    execute(&mut app, 120).unwrap();
+    execute(&mut app, 50).unwrap();
+    assert_eq!(window_remaining(&app), 100);
+
+    // Spending that could not be executed now is not queued.
+    let err: ContractError = execute(&mut app, 101).unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err,
+        ContractError::SpendingCapExceeded {
+            denom: "ujuno".to_string(),
+            remaining: Uint128::new(100),
+        }
+    );
+
+    // Queued spending is charged when executed.
+    app.update_block(|block| block.height += 10);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteQueued { id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::SpendingCapExceeded {
+            denom: "ujuno".to_string(),
+            remaining: Uint128::new(100),
+        }
+    );
+    app.update_block(|block| block.height += 100);
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        core_addr,
+        &ExecuteMsg::ExecuteQueued { id: 2 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(window_remaining(&app), 30);
+    assert_eq!(
+        app.wrap()
+            .query_balance("recipient", "ujuno")
+            .unwrap()
+            .amount,
+        Uint128::new(170)
+    );
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
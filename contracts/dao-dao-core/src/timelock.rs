@@ -0,0 +1,83 @@
+use cosmwasm_std::{from_json, Addr, CosmosMsg, Empty, Uint128, WasmMsg};
+use cw_utils::Duration;
+use dao_interface::{
+    msg::ExecuteMsg,
+    timelock::{Timelock, TimelockCriterion},
+};
+
+use crate::error::ContractError;
+use crate::treasury::msg_spends;
+
+/// Parses `msg` if it is executed by the DAO on itself.
+fn self_msg(dao: &Addr, msg: &CosmosMsg<Empty>) -> Option<ExecuteMsg> {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) if contract_addr == dao.as_str() => from_json(msg).ok(),
+        _ => None,
+    }
+}
+
+fn matches_criterion(
+    dao: &Addr,
+    criterion: &TimelockCriterion,
+    msgs: &[CosmosMsg<Empty>],
+) -> Result<bool, ContractError> {
+    Ok(match criterion {
+        TimelockCriterion::Migrate {} => msgs
+            .iter()
+            .any(|msg| matches!(msg, CosmosMsg::Wasm(WasmMsg::Migrate { .. }))),
+        TimelockCriterion::UpdateAdmin {} => msgs.iter().any(|msg| {
+            matches!(
+                msg,
+                CosmosMsg::Wasm(WasmMsg::UpdateAdmin { .. } | WasmMsg::ClearAdmin { .. })
+            ) || matches!(self_msg(dao, msg), Some(ExecuteMsg::NominateAdmin { .. }))
+        }),
+        TimelockCriterion::UpdateVotingModule {} => msgs.iter().any(|msg| {
+            matches!(
+                self_msg(dao, msg),
+                Some(ExecuteMsg::UpdateVotingModule { .. })
+            )
+        }),
+        TimelockCriterion::Transfer { denom, amount } => {
+            let spent = msgs
+                .iter()
+                .flat_map(msg_spends)
+                .filter(|(spent_denom, _)| spent_denom == denom)
+                .try_fold(Uint128::zero(), |total, (_, spent)| {
+                    total.checked_add(spent)
+                })
+                .map_err(|_| ContractError::Overflow {})?;
+            spent > *amount
+        }
+    })
+}
+
+pub(crate) fn validate_timelock(timelock: &Timelock) -> Result<(), ContractError> {
+    if let Duration::Height(0) | Duration::Time(0) = timelock.delay {
+        return Err(ContractError::ZeroTimelockDelay {});
+    }
+    Ok(())
+}
+
+/// Whether `timelock` delays executing `msgs` on behalf of the DAO.
+/// Messages updating the timelock are always delayed, so that the
+/// timelock may not be removed to skip it.
+pub(crate) fn is_timelocked(
+    dao: &Addr,
+    timelock: &Timelock,
+    msgs: &[CosmosMsg<Empty>],
+) -> Result<bool, ContractError> {
+    if msgs
+        .iter()
+        .any(|msg| matches!(self_msg(dao, msg), Some(ExecuteMsg::UpdateTimelock { .. })))
+    {
+        return Ok(true);
+    }
+    for criterion in &timelock.criteria {
+        if matches_criterion(dao, criterion, msgs)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
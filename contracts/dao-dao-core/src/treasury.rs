@@ -0,0 +1,399 @@
+use cosmwasm_std::{
+    from_json, BankMsg, BlockInfo, CosmosMsg, Empty, Order, StdResult, Storage, Timestamp, Uint128,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use cw_utils::Duration;
+use dao_interface::treasury::{SpendingCap, WindowCap};
+
+use crate::error::ContractError;
+use crate::state::{SpendRecord, SPENDING_CAPS, TREASURY_SPENDS};
+
+/// Returns the amount of each token that executing `msg` would spend
+/// from the DAO's treasury. Native tokens are spent by bank sends and
+/// burns and by funds sent along with wasm messages. cw20 tokens are
+/// spent by `Transfer`, `Send`, `IncreaseAllowance` and `Burn`
+/// messages, and are identified by their address.
+pub(crate) fn msg_spends(msg: &CosmosMsg<Empty>) -> Vec<(String, Uint128)> {
+    let mut spends = vec![];
+    let funds = match msg {
+        CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount,
+        CosmosMsg::Bank(BankMsg::Burn { amount }) => amount,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        }) => {
+            if let Ok(msg) = from_json::<Cw20ExecuteMsg>(msg) {
+                match msg {
+                    Cw20ExecuteMsg::Transfer { amount, .. }
+                    | Cw20ExecuteMsg::Send { amount, .. }
+                    | Cw20ExecuteMsg::IncreaseAllowance { amount, .. }
+                    | Cw20ExecuteMsg::Burn { amount } => {
+                        spends.push((contract_addr.clone(), amount))
+                    }
+                    _ => (),
+                }
+            }
+            funds
+        }
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => funds,
+        _ => return spends,
+    };
+    spends.extend(funds.iter().map(|coin| (coin.denom.clone(), coin.amount)));
+    spends
+}
+
+pub(crate) fn validate_spending_cap(cap: &SpendingCap) -> Result<(), ContractError> {
+    let invalid = || ContractError::InvalidSpendingCap {
+        denom: cap.denom.clone(),
+    };
+    match &cap.per_window {
+        None if cap.per_execution.is_none() => Err(invalid()),
+        Some(WindowCap {
+            window: Duration::Height(0) | Duration::Time(0),
+            ..
+        }) => Err(invalid()),
+        _ => Ok(()),
+    }
+}
+
+/// Whether spending recorded at `height` and `time` falls within a
+/// window of length `window` ending at `block`.
+fn in_window(window: &Duration, height: u64, time: Timestamp, block: &BlockInfo) -> bool {
+    match window {
+        Duration::Height(blocks) => height.saturating_add(*blocks) > block.height,
+        Duration::Time(seconds) => time.plus_seconds(*seconds) > block.time,
+    }
+}
+
+/// The amount of `denom` spent by proposals within `window`.
+pub(crate) fn spent_in_window(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    denom: &str,
+    window: &Duration,
+) -> StdResult<Uint128> {
+    TREASURY_SPENDS
+        .prefix(denom)
+        .range(storage, None, None, Order::Descending)
+        .take_while(|item| match item {
+            Ok((height, record)) => in_window(window, *height, record.time, block),
+            Err(_) => true,
+        })
+        .try_fold(Uint128::zero(), |spent, item| {
+            let (_, record) = item?;
+            Ok(spent.checked_add(record.amount)?)
+        })
+}
+
+/// Checks the messages of a single proposal execution against the
+/// DAO's spending caps. If `charge` is set, records what they spend.
+pub(crate) fn check_spending_caps(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    msgs: &[CosmosMsg<Empty>],
+    charge: bool,
+) -> Result<(), ContractError> {
+    let mut totals: Vec<(String, Uint128)> = vec![];
+    for (denom, amount) in msgs.iter().flat_map(msg_spends) {
+        match totals.iter_mut().find(|(d, _)| *d == denom) {
+            Some((_, total)) => {
+                *total = total
+                    .checked_add(amount)
+                    .map_err(|_| ContractError::Overflow {})?
+            }
+            None => totals.push((denom, amount)),
+        }
+    }
+
+    for (denom, amount) in totals {
+        let cap = match SPENDING_CAPS.may_load(storage, &denom)? {
+            Some(cap) => cap,
+            None => continue,
+        };
+        let exceeded = |remaining: Uint128| ContractError::SpendingCapExceeded {
+            denom: denom.clone(),
+            remaining,
+        };
+
+        if let Some(per_execution) = cap.per_execution {
+            if amount > per_execution {
+                return Err(exceeded(per_execution));
+            }
+        }
+
+        if let Some(WindowCap {
+            amount: window_cap,
+            window,
+        }) = cap.per_window
+        {
+            // Spending that has left the window no longer counts
+            // towards the cap and may be forgotten.
+            let stale = TREASURY_SPENDS
+                .prefix(&denom)
+                .range(storage, None, None, Order::Ascending)
+                .take_while(|item| match item {
+                    Ok((height, record)) => !in_window(&window, *height, record.time, block),
+                    Err(_) => true,
+                })
+                .map(|item| item.map(|(height, _)| height))
+                .collect::<StdResult<Vec<u64>>>()?;
+            for height in stale {
+                TREASURY_SPENDS.remove(storage, (denom.as_str(), height));
+            }
+
+            let remaining =
+                window_cap.saturating_sub(spent_in_window(storage, block, &denom, &window)?);
+            if amount > remaining {
+                return Err(exceeded(remaining));
+            }
+            if !charge {
+                continue;
+            }
+
+            TREASURY_SPENDS.update(
+                storage,
+                (denom.as_str(), block.height),
+                |record| -> StdResult<_> {
+                    Ok(SpendRecord {
+                        time: block.time,
+                        amount: record.map(|r| r.amount).unwrap_or_default() + amount,
+                    })
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The queries of cw-vesting and cw20-stake contracts used to report
+/// the tokens they hold for the DAO.
+#[cw_serde]
+enum HoldingQuery {
+    Info {},
+    TotalToVest {},
+    StakedValue { address: String },
+    GetConfig {},
+}
+
+/// The fields of a cw-vesting contract's `Info` response that are
+/// needed. Other fields are ignored.
+#[derive(Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct VestInfo {
+    recipient: Addr,
+    denom: CheckedDenom,
+    claimed: Uint128,
+    slashed: Uint128,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct StakeConfig {
+    token_address: Addr,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct StakedValue {
+    value: Uint128,
+}
+
+/// The number of NFTs in `collection` owned by `owner`.
+fn count_nfts(deps: Deps, collection: &Addr, owner: &Addr) -> StdResult<Uint128> {
+    let mut count = 0u128;
+    let mut start_after = None;
+    loop {
+        let page: cw721::TokensResponse = deps.querier.query_wasm_smart(
+            collection,
+            &cw721::Cw721QueryMsg::Tokens {
+                owner: owner.to_string(),
+                start_after,
+                limit: Some(CW721_PAGE_LIMIT),
+            },
+        )?;
+        count += page.tokens.len() as u128;
+        if page.tokens.len() < CW721_PAGE_LIMIT as usize {
+            return Ok(Uint128::new(count));
+        }
+        start_after = page.tokens.last().cloned();
+    }
+}
+
+/// The balance of the tokens `contract` holds for `dao`.
+pub(crate) fn holding_balance(
+    deps: Deps,
+    dao: &Addr,
+    contract: Addr,
+    kind: &TreasuryHoldingKind,
+) -> StdResult<TreasuryBalance> {
+    Ok(match kind {
+        TreasuryHoldingKind::CwVesting => {
+            let vest: VestInfo = deps
+                .querier
+                .query_wasm_smart(&contract, &HoldingQuery::Info {})?;
+            let amount = if vest.recipient == *dao {
+                let total: Uint128 = deps
+                    .querier
+                    .query_wasm_smart(&contract, &HoldingQuery::TotalToVest {})?;
+                total.saturating_sub(vest.claimed + vest.slashed)
+            } else {
+                Uint128::zero()
+            };
+            TreasuryBalance {
+                asset_type: TreasuryAssetType::Vesting,
+                id: contract.into_string(),
+                denom: vest.denom.to_string(),
+                amount,
+            }
+        }
+        TreasuryHoldingKind::Cw20Stake => {
+            let config: StakeConfig = deps
+                .querier
+                .query_wasm_smart(&contract, &HoldingQuery::GetConfig {})?;
+            let staked: StakedValue = deps.querier.query_wasm_smart(
+                &contract,
+                &HoldingQuery::StakedValue {
+                    address: dao.to_string(),
+                },
+            )?;
+            TreasuryBalance {
+                asset_type: TreasuryAssetType::Staked,
+                id: contract.into_string(),
+                denom: config.token_address.into_string(),
+                amount: staked.value,
+            }
+        }
+    })
+}
+
+/// Up to `limit` balances of assets of type `asset_type` with IDs
+/// after `start_after`.
+fn balances_of_type(
+    deps: Deps,
+    dao: &Addr,
+    asset_type: TreasuryAssetType,
+    start_after: Option<&str>,
+    limit: usize,
+) -> StdResult<Vec<TreasuryBalance>> {
+    let start_addr = start_after
+        .map(|addr| deps.api.addr_validate(addr))
+        .transpose()?;
+    match asset_type {
+        TreasuryAssetType::Native => Ok(deps
+            .querier
+            .query_all_balances(dao)?
+            .into_iter()
+            .filter(|coin| start_after.map_or(true, |after| coin.denom.as_str() > after))
+            .take(limit)
+            .map(|coin| TreasuryBalance {
+                asset_type,
+                id: coin.denom.clone(),
+                denom: coin.denom,
+                amount: coin.amount,
+            })
+            .collect()),
+        TreasuryAssetType::Cw20 => CW20_LIST
+            .keys(
+                deps.storage,
+                start_addr.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|addr| {
+                let addr = addr?;
+                let balance: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+                    &addr,
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: dao.to_string(),
+                    },
+                )?;
+                Ok(TreasuryBalance {
+                    asset_type,
+                    id: addr.to_string(),
+                    denom: addr.into_string(),
+                    amount: balance.balance,
+                })
+            })
+            .collect(),
+        TreasuryAssetType::Cw721 => CW721_LIST
+            .keys(
+                deps.storage,
+                start_addr.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|addr| {
+                let addr = addr?;
+                Ok(TreasuryBalance {
+                    asset_type,
+                    id: addr.to_string(),
+                    amount: count_nfts(deps, &addr, dao)?,
+                    denom: addr.into_string(),
+                })
+            })
+            .collect(),
+        TreasuryAssetType::Vesting | TreasuryAssetType::Staked => TREASURY_HOLDINGS
+            .range(
+                deps.storage,
+                start_addr.as_ref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .filter(|item| match item {
+                Ok((_, TreasuryHoldingKind::CwVesting)) => asset_type == TreasuryAssetType::Vesting,
+                Ok((_, TreasuryHoldingKind::Cw20Stake)) => asset_type == TreasuryAssetType::Staked,
+                Err(_) => true,
+            })
+            .take(limit)
+            .map(|item| {
+                let (contract, kind) = item?;
+                holding_balance(deps, dao, contract, &kind)
+            })
+            .collect(),
+    }
+}
+
+/// Reports up to `limit` of the DAO's balances, ordered by asset type
+/// and then ID.
+pub(crate) fn treasury_balances(
+    deps: Deps,
+    dao: &Addr,
+    asset_type: Option<TreasuryAssetType>,
+    start_after: Option<TreasuryBalanceKey>,
+    limit: Option<u32>,
+) -> StdResult<Vec<TreasuryBalance>> {
+    let limit = limit.map(|limit| limit as usize).unwrap_or(usize::MAX);
+    let mut balances = vec![];
+    for kind in [
+        TreasuryAssetType::Native,
+        TreasuryAssetType::Cw20,
+        TreasuryAssetType::Cw721,
+        TreasuryAssetType::Vesting,
+        TreasuryAssetType::Staked,
+    ] {
+        if asset_type.map_or(false, |asset_type| asset_type != kind) {
+            continue;
+        }
+        let after = match &start_after {
+            Some(key) if key.asset_type > kind => continue,
+            Some(key) if key.asset_type == kind => Some(key.id.as_str()),
+            _ => None,
+        };
+        if balances.len() == limit {
+            break;
+        }
+        balances.extend(balances_of_type(
+            deps,
+            dao,
+            kind,
+            after,
+            limit - balances.len(),
+        )?);
+    }
+    Ok(balances)
+}
@@ -0,0 +1,136 @@
+use cosmwasm_schema::{cw_serde, serde::Deserialize};
+use cosmwasm_std::{to_json_binary, Addr, Deps, StdResult, WasmMsg};
+use cw_utils::Duration;
+use dao_voting::{
+    multiple_choice::VotingStrategy, status::Status, threshold::Threshold, veto::VetoConfig,
+};
+
+/// The number of proposals requested per page when looking for open
+/// proposals.
+const PAGE_LIMIT: u64 = 30;
+/// The default and maximum number of proposals `FinishDraining` looks
+/// through in one call.
+pub(crate) const DEFAULT_DRAIN_SCAN_LIMIT: u64 = 300;
+pub(crate) const MAX_DRAIN_SCAN_LIMIT: u64 = 1500;
+
+/// The queries shared by the single and multiple choice proposal
+/// modules.
+#[cw_serde]
+enum ProposalModuleQuery {
+    Config {},
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+}
+
+/// The config of the single and multiple choice proposal modules,
+/// which differ only in how proposals pass. Their `UpdateConfig`
+/// messages take the same fields their `Config` query returns.
+#[cw_serde]
+struct ProposalModuleConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    threshold: Option<Threshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    voting_strategy: Option<VotingStrategy>,
+    max_voting_period: Duration,
+    min_voting_period: Option<Duration>,
+    only_members_execute: bool,
+    allow_revoting: bool,
+    dao: Addr,
+    close_proposal_on_execution_failure: bool,
+    veto: Option<VetoConfig>,
+}
+
+#[cw_serde]
+enum ProposalModuleExecuteMsg {
+    UpdateConfig(ProposalModuleConfig),
+}
+
+/// Returns a message giving `successor` the config of `module`.
+/// Errors if `module` does not report its config like the single and
+/// multiple choice proposal modules.
+pub(crate) fn copy_config_msg(deps: Deps, module: &Addr, successor: &Addr) -> StdResult<WasmMsg> {
+    let config: ProposalModuleConfig = deps
+        .querier
+        .query_wasm_smart(module, &ProposalModuleQuery::Config {})?;
+    Ok(WasmMsg::Execute {
+        contract_addr: successor.to_string(),
+        msg: to_json_binary(&ProposalModuleExecuteMsg::UpdateConfig(config))?,
+        funds: vec![],
+    })
+}
+
+/// The parts of a `ListProposals` response needed to tell if a
+/// proposal is still open. Other fields differ between proposal
+/// modules and are ignored.
+#[derive(Deserialize)]
+#[serde(crate = "::cosmwasm_schema::serde")]
+struct ProposalList {
+    proposals: Vec<ListedProposal>,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "::cosmwasm_schema::serde")]
+struct ListedProposal {
+    id: u64,
+    proposal: ProposalStatus,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "::cosmwasm_schema::serde")]
+struct ProposalStatus {
+    status: Status,
+}
+
+/// The outcome of looking through a proposal module's proposals for
+/// one that may still be executed.
+#[derive(Debug, PartialEq)]
+pub(crate) enum ProposalScan {
+    /// The proposal with this ID may still be executed.
+    Open(u64),
+    /// Every proposal up to and including this ID is closed, but the
+    /// scan stopped before reaching the module's last proposal.
+    Incomplete(u64),
+    /// None of the module's proposals may be executed.
+    Closed,
+}
+
+/// Looks through at most `limit` of the proposals in `module` after
+/// `start_after` for one that may still be executed. Errors if the
+/// module does not list its proposals like the single and multiple
+/// choice proposal modules.
+pub(crate) fn find_open_proposal(
+    deps: Deps,
+    module: &Addr,
+    mut start_after: Option<u64>,
+    limit: u64,
+) -> StdResult<ProposalScan> {
+    let mut remaining = limit.max(1);
+    while remaining > 0 {
+        let page_limit = remaining.min(PAGE_LIMIT);
+        let page: ProposalList = deps.querier.query_wasm_smart(
+            module,
+            &ProposalModuleQuery::ListProposals {
+                start_after,
+                limit: Some(page_limit),
+            },
+        )?;
+        if let Some(open) = page.proposals.iter().find(|listed| {
+            matches!(
+                listed.proposal.status,
+                Status::Open | Status::Passed | Status::VetoTimelock { .. }
+            )
+        }) {
+            return Ok(ProposalScan::Open(open.id));
+        }
+        match page.proposals.last() {
+            Some(last) if page.proposals.len() as u64 == page_limit => {
+                start_after = Some(last.id);
+                remaining -= page_limit;
+            }
+            _ => return Ok(ProposalScan::Closed),
+        }
+    }
+    Ok(start_after.map_or(ProposalScan::Closed, ProposalScan::Incomplete))
+}
@@ -0,0 +1,614 @@
+use cosmwasm_schema::cw_serde;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response,
+    StdResult, Uint128,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw4::MemberChangedHookMsg;
+use cw_paginate_storage::paginate_map_keys;
+use cw_utils::nonpayable;
+use dao_hooks::{nft_stake::NftStakeChangedHookMsg, stake::StakeChangedHookMsg};
+use dao_interface::voting::InfoResponse;
+use dao_voting::voting::get_voting_power;
+
+use crate::error::ContractError;
+use crate::msg::{
+    DelegatedVotingPowerResponse, DelegatesResponse, Delegation, DelegationsResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use crate::state::{
+    Config, CONFIG, DELEGATED_VP, DELEGATES, DELEGATIONS, DELEGATORS, UNREGISTERING,
+    VP_HOOK_CALLERS,
+};
+
+pub(crate) const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const DEFAULT_MAX_DELEGATIONS: u32 = 50;
+/// The number of delegations removed by a single `Unregister` call if
+/// no limit is given.
+pub const DEFAULT_UNREGISTER_LIMIT: u32 = 50;
+
+/// Queries of the voting modules whose voting power change hooks are
+/// sent by another contract.
+#[cw_serde]
+enum VotingModuleQuery {
+    /// `dao-voting-cw4`'s cw4-group contract.
+    GroupContract {},
+    /// `dao-voting-cw20-staked`'s cw20-stake contract.
+    StakingContract {},
+    /// `dao-voting-cw721-roles`'s config, which names its NFT contract.
+    Config {},
+}
+
+#[cw_serde]
+struct NftConfig {
+    nft_address: Addr,
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let dao = msg
+        .dao
+        .map(|dao| deps.api.addr_validate(&dao))
+        .transpose()?
+        .unwrap_or(info.sender);
+
+    let max_delegations = msg.max_delegations.unwrap_or(DEFAULT_MAX_DELEGATIONS);
+    if max_delegations == 0 {
+        return Err(ContractError::ZeroMaxDelegations {});
+    }
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            dao: dao.clone(),
+            max_delegations,
+        },
+    )?;
+
+    for caller in msg.vp_hook_callers.unwrap_or_default() {
+        let caller = deps.api.addr_validate(&caller)?;
+        VP_HOOK_CALLERS.save(deps.storage, &caller, &Empty {})?;
+    }
+    check_voting_module_hooked(deps.as_ref(), &dao)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", dao))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    match msg {
+        ExecuteMsg::Register {} => execute_register(deps, env, info),
+        ExecuteMsg::Unregister { limit } => execute_unregister(deps, env, info, limit),
+        ExecuteMsg::Delegate { delegate, percent } => {
+            execute_delegate(deps, env, info, delegate, percent)
+        }
+        ExecuteMsg::Undelegate { delegate } => execute_undelegate(deps, env, info, delegate),
+        ExecuteMsg::UpdateVotingPowerHookCallers { add, remove } => {
+            execute_update_voting_power_hook_callers(deps, info, add, remove)
+        }
+        ExecuteMsg::UpdateConfig { max_delegations } => {
+            execute_update_config(deps, info, max_delegations)
+        }
+        ExecuteMsg::MemberChangedHook(msg) => execute_membership_changed(deps, env, info, msg),
+        ExecuteMsg::NftStakeChangeHook(msg) => execute_nft_stake_changed(deps, env, info, msg),
+        ExecuteMsg::StakeChangeHook(msg) => execute_stake_changed(deps, env, info, msg),
+    }
+}
+
+pub fn execute_register(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if DELEGATES.has(deps.storage, &info.sender) {
+        return Err(ContractError::AlreadyRegistered {});
+    }
+
+    // Delegates may not delegate their own voting power, so that
+    // delegated voting power never needs to be followed through a
+    // chain of delegates.
+    let delegations = DELEGATIONS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if !delegations.is_empty() {
+        return Err(ContractError::DelegatorCannotRegister {});
+    }
+
+    DELEGATES.save(deps.storage, &info.sender, &Empty {})?;
+    DELEGATED_VP.save(
+        deps.storage,
+        &info.sender,
+        &Uint128::zero(),
+        env.block.height,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register")
+        .add_attribute("delegate", info.sender))
+}
+
+pub fn execute_unregister(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    if !DELEGATES.has(deps.storage, &info.sender) {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    // Remove up to `limit` delegations to the delegate. The delegate
+    // stays registered, but accepts no new delegations, until all of
+    // them have been removed.
+    UNREGISTERING.save(deps.storage, &info.sender, &Empty {})?;
+    let limit = limit.unwrap_or(DEFAULT_UNREGISTER_LIMIT) as usize;
+    let delegators = DELEGATORS
+        .prefix(&info.sender)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    let complete = delegators.len() <= limit;
+
+    let mut removed_power = Uint128::zero();
+    for delegator in delegators.iter().take(limit) {
+        let mut delegations = DELEGATIONS
+            .may_load(deps.storage, delegator)?
+            .unwrap_or_default();
+        if let Some(index) = delegations.iter().position(|d| d.delegate == info.sender) {
+            removed_power = removed_power.checked_add(delegations.remove(index).power)?;
+        }
+        DELEGATIONS.save(deps.storage, delegator, &delegations, env.block.height)?;
+        DELEGATORS.remove(deps.storage, (&info.sender, delegator));
+    }
+
+    if complete {
+        DELEGATES.remove(deps.storage, &info.sender);
+        UNREGISTERING.remove(deps.storage, &info.sender);
+        DELEGATED_VP.save(
+            deps.storage,
+            &info.sender,
+            &Uint128::zero(),
+            env.block.height,
+        )?;
+    } else {
+        update_delegated_vp(deps, &env, &info.sender, removed_power, Uint128::zero())?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "unregister")
+        .add_attribute("delegate", info.sender)
+        .add_attribute(
+            "removed_delegations",
+            delegators.len().min(limit).to_string(),
+        )
+        .add_attribute("complete", complete.to_string()))
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+    percent: Decimal,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let delegate = deps.api.addr_validate(&delegate)?;
+
+    if !DELEGATES.has(deps.storage, &delegate) {
+        return Err(ContractError::NotRegistered {});
+    }
+    if UNREGISTERING.has(deps.storage, &delegate) {
+        return Err(ContractError::DelegateUnregistering {});
+    }
+    if DELEGATES.has(deps.storage, &info.sender) {
+        return Err(ContractError::DelegateCannotDelegate {});
+    }
+    if percent.is_zero() || percent > Decimal::one() {
+        return Err(ContractError::InvalidPercent {});
+    }
+    // The DAO may have changed its voting module since the hook
+    // callers were last checked.
+    check_voting_module_hooked(deps.as_ref(), &config.dao)?;
+
+    let mut delegations = DELEGATIONS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+
+    // Replace any existing delegation to the same delegate.
+    let previous_power = match delegations.iter().position(|d| d.delegate == delegate) {
+        Some(index) => delegations.remove(index).power,
+        None => Uint128::zero(),
+    };
+
+    if delegations.len() as u32 >= config.max_delegations {
+        return Err(ContractError::TooManyDelegations {
+            max: config.max_delegations,
+        });
+    }
+
+    let total_percent = delegations
+        .iter()
+        .try_fold(percent, |total, d| total.checked_add(d.percent))?;
+    if total_percent > Decimal::one() {
+        return Err(ContractError::TotalPercentExceeded {});
+    }
+
+    let voting_power = current_voting_power(deps.as_ref(), &env, &config, &info.sender)?;
+    let power = voting_power.mul_floor(percent);
+
+    delegations.push(Delegation {
+        delegate: delegate.clone(),
+        percent,
+        power,
+    });
+    DELEGATIONS.save(deps.storage, &info.sender, &delegations, env.block.height)?;
+    DELEGATORS.save(deps.storage, (&delegate, &info.sender), &Empty {})?;
+    update_delegated_vp(deps, &env, &delegate, previous_power, power)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate)
+        .add_attribute("percent", percent.to_string())
+        .add_attribute("power", power))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+
+    let mut delegations = DELEGATIONS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let index = delegations
+        .iter()
+        .position(|d| d.delegate == delegate)
+        .ok_or_else(|| ContractError::DelegationNotFound {
+            delegate: delegate.to_string(),
+        })?;
+    let removed = delegations.remove(index);
+
+    DELEGATIONS.save(deps.storage, &info.sender, &delegations, env.block.height)?;
+    DELEGATORS.remove(deps.storage, (&delegate, &info.sender));
+    update_delegated_vp(deps, &env, &delegate, removed.power, Uint128::zero())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "undelegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate))
+}
+
+pub fn execute_update_voting_power_hook_callers(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for caller in add.unwrap_or_default() {
+        let caller = deps.api.addr_validate(&caller)?;
+        VP_HOOK_CALLERS.save(deps.storage, &caller, &Empty {})?;
+    }
+    for caller in remove.unwrap_or_default() {
+        let caller = deps.api.addr_validate(&caller)?;
+        VP_HOOK_CALLERS.remove(deps.storage, &caller);
+    }
+    check_voting_module_hooked(deps.as_ref(), &config.dao)?;
+
+    Ok(Response::new().add_attribute("action", "update_voting_power_hook_callers"))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_delegations: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(max_delegations) = max_delegations {
+        if max_delegations == 0 {
+            return Err(ContractError::ZeroMaxDelegations {});
+        }
+        config.max_delegations = max_delegations;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("max_delegations", config.max_delegations.to_string()))
+}
+
+pub fn execute_membership_changed(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: MemberChangedHookMsg,
+) -> Result<Response, ContractError> {
+    check_hook_caller(deps.as_ref(), &info)?;
+
+    for member in msg.diffs {
+        let addr = deps.api.addr_validate(&member.key)?;
+        sync_delegations(deps.branch(), &env, &addr)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "membership_changed"))
+}
+
+pub fn execute_nft_stake_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: NftStakeChangedHookMsg,
+) -> Result<Response, ContractError> {
+    check_hook_caller(deps.as_ref(), &info)?;
+
+    let addr = match msg {
+        NftStakeChangedHookMsg::Stake { addr, .. } => addr,
+        NftStakeChangedHookMsg::Unstake { addr, .. } => addr,
+    };
+    sync_delegations(deps, &env, &addr)?;
+
+    Ok(Response::new().add_attribute("action", "nft_stake_changed"))
+}
+
+pub fn execute_stake_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: StakeChangedHookMsg,
+) -> Result<Response, ContractError> {
+    check_hook_caller(deps.as_ref(), &info)?;
+
+    let addr = match msg {
+        StakeChangedHookMsg::Stake { addr, .. } => addr,
+        StakeChangedHookMsg::Unstake { addr, .. } => addr,
+    };
+    sync_delegations(deps, &env, &addr)?;
+
+    Ok(Response::new().add_attribute("action", "stake_changed"))
+}
+
+/// Ensures hooks that update voting power are only called by a
+/// registered voting power hook caller.
+fn check_hook_caller(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    if !VP_HOOK_CALLERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::InvalidHookSender {});
+    }
+    Ok(())
+}
+
+/// Ensures that the contract sending the voting power change hooks of
+/// `dao`'s voting module is a voting power hook caller. Without its
+/// hooks, delegated voting power would not follow changes to
+/// delegators' voting power, so a delegator could, for example,
+/// unstake and have their delegates keep voting with it.
+fn check_voting_module_hooked(deps: Deps, dao: &Addr) -> Result<(), ContractError> {
+    let voting_module: Addr = deps
+        .querier
+        .query_wasm_smart(dao, &dao_interface::msg::QueryMsg::VotingModule {})?;
+    if VP_HOOK_CALLERS.has(deps.storage, &voting_module) {
+        return Ok(());
+    }
+
+    // Voting modules that do not send hooks themselves answer one of
+    // these queries with the contract that does. Others fail to parse
+    // them, which is ignored.
+    let querier = deps.querier;
+    let hook_senders: [Option<Addr>; 3] = [
+        querier
+            .query_wasm_smart(&voting_module, &VotingModuleQuery::GroupContract {})
+            .ok(),
+        querier
+            .query_wasm_smart(&voting_module, &VotingModuleQuery::StakingContract {})
+            .ok(),
+        querier
+            .query_wasm_smart(&voting_module, &VotingModuleQuery::Config {})
+            .ok()
+            .map(|config: NftConfig| config.nft_address),
+    ];
+    if hook_senders
+        .into_iter()
+        .flatten()
+        .any(|sender| VP_HOOK_CALLERS.has(deps.storage, &sender))
+    {
+        return Ok(());
+    }
+
+    Err(ContractError::VotingModuleNotHooked { voting_module })
+}
+
+/// Returns the voting power of `addr` including any changes made
+/// earlier in the current block. Voting modules snapshot changes at
+/// the height they happen, so those changes are only visible when
+/// querying the next height.
+fn current_voting_power(deps: Deps, env: &Env, config: &Config, addr: &Addr) -> StdResult<Uint128> {
+    get_voting_power(deps, addr.clone(), &config.dao, Some(env.block.height + 1))
+}
+
+/// Recomputes the voting power contributed by each of `delegator`'s
+/// delegations after their voting power changed.
+fn sync_delegations(deps: DepsMut, env: &Env, delegator: &Addr) -> Result<(), ContractError> {
+    let delegations = DELEGATIONS
+        .may_load(deps.storage, delegator)?
+        .unwrap_or_default();
+    if delegations.is_empty() {
+        return Ok(());
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let voting_power = current_voting_power(deps.as_ref(), env, &config, delegator)?;
+
+    let mut updated = Vec::with_capacity(delegations.len());
+    for delegation in delegations {
+        let power = voting_power.mul_floor(delegation.percent);
+        if power != delegation.power {
+            let delegated_vp = DELEGATED_VP
+                .may_load(deps.storage, &delegation.delegate)?
+                .unwrap_or_default();
+            DELEGATED_VP.save(
+                deps.storage,
+                &delegation.delegate,
+                &delegated_vp
+                    .checked_sub(delegation.power)?
+                    .checked_add(power)?,
+                env.block.height,
+            )?;
+        }
+        updated.push(Delegation {
+            power,
+            ..delegation
+        });
+    }
+    DELEGATIONS.save(deps.storage, delegator, &updated, env.block.height)?;
+
+    Ok(())
+}
+
+/// Replaces `previous` voting power delegated to `delegate` with
+/// `new`.
+fn update_delegated_vp(
+    deps: DepsMut,
+    env: &Env,
+    delegate: &Addr,
+    previous: Uint128,
+    new: Uint128,
+) -> Result<(), ContractError> {
+    let delegated_vp = DELEGATED_VP
+        .may_load(deps.storage, delegate)?
+        .unwrap_or_default();
+    DELEGATED_VP.save(
+        deps.storage,
+        delegate,
+        &delegated_vp.checked_sub(previous)?.checked_add(new)?,
+        env.block.height,
+    )?;
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Info {} => to_json_binary(&query_info(deps)?),
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Delegates { start_after, limit } => {
+            to_json_binary(&query_delegates(deps, start_after, limit)?)
+        }
+        QueryMsg::DelegatedVotingPower { delegate, height } => {
+            to_json_binary(&query_delegated_voting_power(deps, env, delegate, height)?)
+        }
+        QueryMsg::Delegations { delegator, height } => {
+            to_json_binary(&query_delegations(deps, env, delegator, height)?)
+        }
+        QueryMsg::VotingPowerHookCallers { start_after, limit } => {
+            to_json_binary(&query_voting_power_hook_callers(deps, start_after, limit)?)
+        }
+    }
+}
+
+fn query_info(deps: Deps) -> StdResult<InfoResponse> {
+    let info = get_contract_version(deps.storage)?;
+    Ok(InfoResponse { info })
+}
+
+fn query_delegates(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DelegatesResponse> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let delegates = paginate_map_keys(
+        deps,
+        &DELEGATES,
+        start_after.as_ref(),
+        limit,
+        Order::Ascending,
+    )?;
+    Ok(DelegatesResponse { delegates })
+}
+
+fn query_delegated_voting_power(
+    deps: Deps,
+    env: Env,
+    delegate: String,
+    height: Option<u64>,
+) -> StdResult<DelegatedVotingPowerResponse> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    let height = height.unwrap_or(env.block.height);
+    let power = DELEGATED_VP
+        .may_load_at_height(deps.storage, &delegate, height)?
+        .unwrap_or_default();
+    Ok(DelegatedVotingPowerResponse { power, height })
+}
+
+fn query_delegations(
+    deps: Deps,
+    env: Env,
+    delegator: String,
+    height: Option<u64>,
+) -> StdResult<DelegationsResponse> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let height = height.unwrap_or(env.block.height);
+    let delegations = DELEGATIONS
+        .may_load_at_height(deps.storage, &delegator, height)?
+        .unwrap_or_default();
+    Ok(DelegationsResponse {
+        delegations,
+        height,
+    })
+}
+
+fn query_voting_power_hook_callers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    paginate_map_keys(
+        deps,
+        &VP_HOOK_CALLERS,
+        start_after.as_ref(),
+        limit,
+        Order::Ascending,
+    )
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
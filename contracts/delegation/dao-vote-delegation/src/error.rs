@@ -0,0 +1,50 @@
+use cosmwasm_std::{Addr, OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Voting power changed hook sender incorrect")]
+    InvalidHookSender {},
+
+    #[error("Address is already registered as a delegate")]
+    AlreadyRegistered {},
+
+    #[error("Address is not registered as a delegate")]
+    NotRegistered {},
+
+    #[error("Delegate is unregistering and accepts no new delegations")]
+    DelegateUnregistering {},
+
+    #[error("Delegates cannot delegate their voting power")]
+    DelegateCannotDelegate {},
+
+    #[error("Cannot delegate voting power while registered as a delegate")]
+    DelegatorCannotRegister {},
+
+    #[error("Delegation percent must be greater than zero and at most one")]
+    InvalidPercent {},
+
+    #[error("Cannot delegate more than 100% of voting power")]
+    TotalPercentExceeded {},
+
+    #[error("Cannot delegate to more than {max} delegates")]
+    TooManyDelegations { max: u32 },
+
+    #[error("No delegation to {delegate}")]
+    DelegationNotFound { delegate: String },
+
+    #[error("Max delegations must be greater than zero")]
+    ZeroMaxDelegations {},
+
+    #[error("The contract sending voting power change hooks for voting module {voting_module} must be a voting power hook caller")]
+    VotingModuleNotHooked { voting_module: Addr },
+}
@@ -0,0 +1,105 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal};
+use cw4::MemberChangedHookMsg;
+use dao_hooks::{nft_stake::NftStakeChangedHookMsg, stake::StakeChangedHookMsg};
+use dao_interface::voting::InfoResponse;
+
+pub use dao_interface::delegation::{
+    DelegatedVotingPowerResponse, Delegation, DelegationsResponse,
+};
+
+use crate::state::Config;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The DAO this module belongs to. If not provided, the
+    /// instantiator is used.
+    pub dao: Option<String>,
+    /// Contracts allowed to call the voting power change hooks. These
+    /// must also be configured to send hooks to this contract, and
+    /// must include the contract that sends the hooks of the DAO's
+    /// voting module.
+    pub vp_hook_callers: Option<Vec<String>>,
+    /// The maximum number of delegates a single delegator may delegate
+    /// to. Defaults to 50.
+    pub max_delegations: Option<u32>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Register the sender as a delegate that may receive delegations.
+    Register {},
+    /// Unregister the sender as a delegate. All delegations to the
+    /// sender are removed, at most `limit` per call (50 by
+    /// default). Until all have been removed the sender remains
+    /// registered but accepts no new delegations, and must call this
+    /// again to finish unregistering.
+    Unregister { limit: Option<u32> },
+    /// Delegate `percent` of the sender's voting power to
+    /// `delegate`. Replaces any existing delegation to the same
+    /// delegate.
+    Delegate { delegate: String, percent: Decimal },
+    /// Remove the sender's delegation to `delegate`.
+    Undelegate { delegate: String },
+    /// Updates the contracts allowed to call the voting power change
+    /// hooks. Only callable by the DAO. Fails if this would leave out
+    /// the contract that sends the hooks of the DAO's voting module.
+    UpdateVotingPowerHookCallers {
+        add: Option<Vec<String>>,
+        remove: Option<Vec<String>>,
+    },
+    /// Updates the config. Only callable by the DAO.
+    UpdateConfig { max_delegations: Option<u32> },
+    /// Called when a member is added or removed
+    /// to a cw4-groups or cw721-roles contract.
+    MemberChangedHook(MemberChangedHookMsg),
+    /// Called when NFTs are staked or unstaked.
+    NftStakeChangeHook(NftStakeChangedHookMsg),
+    /// Called when tokens are staked or unstaked.
+    StakeChangeHook(StakeChangedHookMsg),
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns contract version info.
+    #[returns(InfoResponse)]
+    Info {},
+    /// Returns the config.
+    #[returns(Config)]
+    Config {},
+    /// Returns the registered delegates.
+    #[returns(DelegatesResponse)]
+    Delegates {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the voting power delegated to a delegate at a given
+    /// height. This does not include the delegate's own voting power.
+    #[returns(DelegatedVotingPowerResponse)]
+    DelegatedVotingPower {
+        delegate: String,
+        height: Option<u64>,
+    },
+    /// Returns the delegations made by a delegator at a given height.
+    #[returns(DelegationsResponse)]
+    Delegations {
+        delegator: String,
+        height: Option<u64>,
+    },
+    /// Returns the contracts allowed to call the voting power change
+    /// hooks.
+    #[returns(Vec<Addr>)]
+    VotingPowerHookCallers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct DelegatesResponse {
+    pub delegates: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,50 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
+use dao_interface::delegation::Delegation;
+
+#[cw_serde]
+pub struct Config {
+    /// The DAO this delegation module belongs to. Voting power is
+    /// queried from the DAO and only the DAO may update the config.
+    pub dao: Addr,
+    /// The maximum number of delegates a single delegator may
+    /// delegate to.
+    pub max_delegations: u32,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Contracts allowed to call the voting power change hooks. This is
+/// usually the voting module, or the cw4-group contract for cw4 DAOs.
+pub const VP_HOOK_CALLERS: Map<&Addr, Empty> = Map::new("vp_hook_callers");
+
+/// Addresses that have registered to receive delegations.
+pub const DELEGATES: Map<&Addr, Empty> = Map::new("delegates");
+
+/// The delegations made by each delegator, including the voting power
+/// each delegation contributes to its delegate. Snapshotted so that
+/// proposal modules can look up delegations as of a proposal's start
+/// height.
+pub const DELEGATIONS: SnapshotMap<&Addr, Vec<Delegation>> = SnapshotMap::new(
+    "delegations",
+    "delegations__checkpoints",
+    "delegations__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The total voting power delegated to each delegate.
+pub const DELEGATED_VP: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "delegated_vp",
+    "delegated_vp__checkpoints",
+    "delegated_vp__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Delegates that have started unregistering but still have
+/// delegations left to remove. They accept no new delegations.
+pub const UNREGISTERING: Map<&Addr, Empty> = Map::new("unregistering");
+
+/// Reverse index of (delegate, delegator) pairs so that delegations can
+/// be cleaned up when a delegate unregisters.
+pub const DELEGATORS: Map<(&Addr, &Addr), Empty> = Map::new("delegators");
@@ -0,0 +1,573 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+use dao_interface::delegation::{DelegatedVotingPowerResponse, Delegation, DelegationsResponse};
+use dao_proposal_single::query::{ProposalResponse, VoteResponse};
+use dao_testing::{
+    contracts::dao_vote_delegation_contract, Cw4TestDao, DaoTestingSuite, DaoTestingSuiteBase,
+    MEMBER1, MEMBER2, MEMBER3, MEMBER4, MEMBER5,
+};
+use dao_voting::{pre_propose::PreProposeInfo, proposal::SingleChoiceProposeMsg, voting::Vote};
+
+use crate::{
+    msg::{DelegatesResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
+    ContractError,
+};
+
+struct Setup {
+    suite: DaoTestingSuiteBase,
+    dao: Cw4TestDao,
+    delegation: Addr,
+    proposal_single: Addr,
+}
+
+/// Creates a cw4 DAO with members of weight 1, 2, 3, 3, and 1, a
+/// delegation module receiving membership change hooks from the
+/// group, and a single choice proposal module that uses it.
+fn setup() -> Setup {
+    let mut suite = DaoTestingSuiteBase::base();
+    let dao = suite.cw4().dao();
+
+    let code_id = suite.app.store_code(dao_vote_delegation_contract());
+    let delegation = suite
+        .app
+        .instantiate_contract(
+            code_id,
+            dao.core_addr.clone(),
+            &InstantiateMsg {
+                dao: None,
+                vp_hook_callers: Some(vec![dao.x.group_addr.to_string()]),
+                max_delegations: None,
+            },
+            &[],
+            "delegation",
+            None,
+        )
+        .unwrap();
+
+    suite
+        .app
+        .execute_contract(
+            dao.core_addr.clone(),
+            dao.x.group_addr.clone(),
+            &cw4_group::msg::ExecuteMsg::AddHook {
+                addr: delegation.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let proposal_single = dao.proposal_modules[0].address.clone();
+    for msg in [
+        dao_proposal_single::msg::ExecuteMsg::UpdatePreProposeInfo {
+            info: PreProposeInfo::AnyoneMayPropose {},
+        },
+        dao_proposal_single::msg::ExecuteMsg::UpdateDelegationModule {
+            module: Some(delegation.to_string()),
+        },
+    ] {
+        suite
+            .app
+            .execute_contract(dao.core_addr.clone(), proposal_single.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    Setup {
+        suite,
+        dao,
+        delegation,
+        proposal_single,
+    }
+}
+
+impl Setup {
+    fn execute(&mut self, sender: &str, msg: ExecuteMsg) -> anyhow::Result<AppResponse> {
+        self.suite
+            .app
+            .execute_contract(Addr::unchecked(sender), self.delegation.clone(), &msg, &[])
+    }
+
+    fn register(&mut self, delegate: &str) {
+        self.execute(delegate, ExecuteMsg::Register {}).unwrap();
+    }
+
+    fn delegate(&mut self, delegator: &str, delegate: &str, percent: Decimal) {
+        self.execute(
+            delegator,
+            ExecuteMsg::Delegate {
+                delegate: delegate.to_string(),
+                percent,
+            },
+        )
+        .unwrap();
+    }
+
+    fn delegated_power(&self, delegate: &str, height: Option<u64>) -> Uint128 {
+        let res: DelegatedVotingPowerResponse = self
+            .suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.delegation,
+                &QueryMsg::DelegatedVotingPower {
+                    delegate: delegate.to_string(),
+                    height,
+                },
+            )
+            .unwrap();
+        res.power
+    }
+
+    fn delegations(&self, delegator: &str) -> Vec<Delegation> {
+        let res: DelegationsResponse = self
+            .suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.delegation,
+                &QueryMsg::Delegations {
+                    delegator: delegator.to_string(),
+                    height: None,
+                },
+            )
+            .unwrap();
+        res.delegations
+    }
+
+    fn propose(&mut self) -> u64 {
+        self.suite
+            .app
+            .execute_contract(
+                Addr::unchecked(MEMBER1),
+                self.proposal_single.clone(),
+                &dao_proposal_single::msg::ExecuteMsg::Propose(SingleChoiceProposeMsg {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    proposer: None,
+                    vote: None,
+                    execution_plan: None,
+                }),
+                &[],
+            )
+            .unwrap();
+        self.suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.proposal_single,
+                &dao_proposal_single::msg::QueryMsg::ProposalCount {},
+            )
+            .unwrap()
+    }
+
+    fn vote(&mut self, voter: &str, proposal_id: u64, vote: Vote) {
+        self.suite
+            .app
+            .execute_contract(
+                Addr::unchecked(voter),
+                self.proposal_single.clone(),
+                &dao_proposal_single::msg::ExecuteMsg::Vote {
+                    proposal_id,
+                    vote,
+                    rationale: None,
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    fn ballot_power(&self, proposal_id: u64, voter: &str) -> Uint128 {
+        let res: VoteResponse = self
+            .suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.proposal_single,
+                &dao_proposal_single::msg::QueryMsg::GetVote {
+                    proposal_id,
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap();
+        res.vote.unwrap().power
+    }
+
+    fn proposal(&self, proposal_id: u64) -> ProposalResponse {
+        self.suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.proposal_single,
+                &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id },
+            )
+            .unwrap()
+    }
+}
+
+#[test]
+fn test_delegate_and_undelegate() {
+    let mut setup = setup();
+    setup.register(MEMBER3);
+    setup.delegate(MEMBER2, MEMBER3, Decimal::percent(50));
+    setup.delegate(MEMBER5, MEMBER3, Decimal::one());
+    setup.suite.advance_block();
+
+    assert_eq!(setup.delegated_power(MEMBER3, None), Uint128::new(2));
+    assert_eq!(
+        setup.delegations(MEMBER2),
+        vec![Delegation {
+            delegate: Addr::unchecked(MEMBER3),
+            percent: Decimal::percent(50),
+            power: Uint128::one(),
+        }]
+    );
+
+    // Delegating to the same delegate again replaces the delegation.
+    setup.delegate(MEMBER2, MEMBER3, Decimal::one());
+    setup.suite.advance_block();
+    assert_eq!(setup.delegated_power(MEMBER3, None), Uint128::new(3));
+    assert_eq!(setup.delegations(MEMBER2).len(), 1);
+
+    setup
+        .execute(
+            MEMBER5,
+            ExecuteMsg::Undelegate {
+                delegate: MEMBER3.to_string(),
+            },
+        )
+        .unwrap();
+    setup.suite.advance_block();
+    assert_eq!(setup.delegated_power(MEMBER3, None), Uint128::new(2));
+    assert!(setup.delegations(MEMBER5).is_empty());
+
+    let height = setup.suite.app.block_info().height;
+    assert_eq!(
+        setup.delegated_power(MEMBER3, Some(height - 2)),
+        Uint128::new(2)
+    );
+    assert_eq!(
+        setup.delegated_power(MEMBER3, Some(height - 1)),
+        Uint128::new(3)
+    );
+}
+
+#[test]
+fn test_delegation_errors() {
+    let mut setup = setup();
+
+    let err: ContractError = setup
+        .execute(
+            MEMBER1,
+            ExecuteMsg::Delegate {
+                delegate: MEMBER3.to_string(),
+                percent: Decimal::one(),
+            },
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotRegistered {});
+
+    setup.register(MEMBER3);
+    setup.register(MEMBER4);
+
+    let err: ContractError = setup
+        .execute(
+            MEMBER4,
+            ExecuteMsg::Delegate {
+                delegate: MEMBER3.to_string(),
+                percent: Decimal::one(),
+            },
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::DelegateCannotDelegate {});
+
+    let err: ContractError = setup
+        .execute(
+            MEMBER1,
+            ExecuteMsg::Delegate {
+                delegate: MEMBER3.to_string(),
+                percent: Decimal::percent(101),
+            },
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidPercent {});
+
+    setup.delegate(MEMBER1, MEMBER3, Decimal::percent(60));
+    let err: ContractError = setup
+        .execute(
+            MEMBER1,
+            ExecuteMsg::Delegate {
+                delegate: MEMBER4.to_string(),
+                percent: Decimal::percent(50),
+            },
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::TotalPercentExceeded {});
+
+    let err: ContractError = setup
+        .execute(MEMBER1, ExecuteMsg::Register {})
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::DelegatorCannotRegister {});
+
+    let err: ContractError = setup
+        .execute(
+            MEMBER1,
+            ExecuteMsg::StakeChangeHook(dao_hooks::stake::StakeChangedHookMsg::Stake {
+                addr: Addr::unchecked(MEMBER1),
+                amount: Uint128::one(),
+            }),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidHookSender {});
+
+    let err: ContractError = setup
+        .execute(
+            MEMBER1,
+            ExecuteMsg::UpdateConfig {
+                max_delegations: Some(1),
+            },
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_voting_module_must_be_hooked() {
+    let mut setup = setup();
+    let code_id = setup.suite.app.store_code(dao_vote_delegation_contract());
+    let voting_module = setup.dao.voting_module_addr.clone();
+
+    // The cw4 voting module's hooks are sent by its group contract.
+    let err: ContractError = setup
+        .suite
+        .app
+        .instantiate_contract(
+            code_id,
+            setup.dao.core_addr.clone(),
+            &InstantiateMsg {
+                dao: None,
+                vp_hook_callers: Some(vec![voting_module.to_string()]),
+                max_delegations: None,
+            },
+            &[],
+            "delegation",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::VotingModuleNotHooked {
+            voting_module: voting_module.clone()
+        }
+    );
+
+    let err: ContractError = setup
+        .suite
+        .app
+        .execute_contract(
+            setup.dao.core_addr.clone(),
+            setup.delegation.clone(),
+            &ExecuteMsg::UpdateVotingPowerHookCallers {
+                add: Some(vec![voting_module.to_string()]),
+                remove: Some(vec![setup.dao.x.group_addr.to_string()]),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::VotingModuleNotHooked { voting_module });
+}
+
+#[test]
+fn test_membership_change_updates_delegated_power() {
+    let mut setup = setup();
+    setup.register(MEMBER3);
+    setup.delegate(MEMBER5, MEMBER3, Decimal::one());
+    setup.suite.advance_block();
+    assert_eq!(setup.delegated_power(MEMBER3, None), Uint128::one());
+
+    setup
+        .suite
+        .app
+        .execute_contract(
+            setup.dao.core_addr.clone(),
+            setup.dao.x.group_addr.clone(),
+            &cw4_group::msg::ExecuteMsg::UpdateMembers {
+                remove: vec![],
+                add: vec![cw4::Member {
+                    addr: MEMBER5.to_string(),
+                    weight: 5,
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+    setup.suite.advance_block();
+
+    let height = setup.suite.app.block_info().height;
+    assert_eq!(setup.delegated_power(MEMBER3, None), Uint128::new(5));
+    assert_eq!(
+        setup.delegated_power(MEMBER3, Some(height - 1)),
+        Uint128::one()
+    );
+    assert_eq!(setup.delegations(MEMBER5)[0].power, Uint128::new(5));
+}
+
+#[test]
+fn test_unregister_removes_delegations() {
+    let mut setup = setup();
+    setup.register(MEMBER3);
+    setup.delegate(MEMBER1, MEMBER3, Decimal::one());
+    setup.delegate(MEMBER2, MEMBER3, Decimal::one());
+    setup.suite.advance_block();
+    assert_eq!(setup.delegated_power(MEMBER3, None), Uint128::new(3));
+
+    setup
+        .execute(MEMBER3, ExecuteMsg::Unregister { limit: None })
+        .unwrap();
+    setup.suite.advance_block();
+
+    assert_eq!(setup.delegated_power(MEMBER3, None), Uint128::zero());
+    assert!(setup.delegations(MEMBER1).is_empty());
+    assert!(setup.delegations(MEMBER2).is_empty());
+
+    let delegates: DelegatesResponse = setup
+        .suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.delegation,
+            &QueryMsg::Delegates {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(delegates.delegates.is_empty());
+}
+
+#[test]
+fn test_unregister_paginated() {
+    let mut setup = setup();
+    setup.register(MEMBER3);
+    setup.delegate(MEMBER1, MEMBER3, Decimal::one());
+    setup.delegate(MEMBER2, MEMBER3, Decimal::one());
+    setup.suite.advance_block();
+
+    // Only one delegation is removed. The delegate stays registered
+    // with the power of the remaining delegation.
+    setup
+        .execute(MEMBER3, ExecuteMsg::Unregister { limit: Some(1) })
+        .unwrap();
+    setup.suite.advance_block();
+    assert!(setup.delegations(MEMBER1).is_empty());
+    assert_eq!(setup.delegations(MEMBER2).len(), 1);
+    assert_eq!(setup.delegated_power(MEMBER3, None), Uint128::new(2));
+
+    // No new delegations are accepted while unregistering.
+    let err: ContractError = setup
+        .execute(
+            MEMBER4,
+            ExecuteMsg::Delegate {
+                delegate: MEMBER3.to_string(),
+                percent: Decimal::one(),
+            },
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::DelegateUnregistering {});
+
+    setup
+        .execute(MEMBER3, ExecuteMsg::Unregister { limit: Some(1) })
+        .unwrap();
+    setup.suite.advance_block();
+    assert!(setup.delegations(MEMBER2).is_empty());
+    assert_eq!(setup.delegated_power(MEMBER3, None), Uint128::zero());
+
+    let err: ContractError = setup
+        .execute(MEMBER3, ExecuteMsg::Unregister { limit: None })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotRegistered {});
+}
+
+#[test]
+fn test_delegate_votes_with_delegated_power() {
+    let mut setup = setup();
+    setup.register(MEMBER3);
+    setup.delegate(MEMBER1, MEMBER3, Decimal::one());
+    setup.delegate(MEMBER5, MEMBER3, Decimal::one());
+    setup.suite.advance_block();
+
+    let proposal_id = setup.propose();
+    setup.vote(MEMBER3, proposal_id, Vote::Yes);
+
+    // 3 of the delegate's own voting power plus 2 delegated.
+    assert_eq!(setup.ballot_power(proposal_id, MEMBER3), Uint128::new(5));
+    assert_eq!(
+        setup.proposal(proposal_id).proposal.votes.yes,
+        Uint128::new(5)
+    );
+}
+
+#[test]
+fn test_delegator_overrides_delegate_vote() {
+    let mut setup = setup();
+    setup.register(MEMBER3);
+    setup.delegate(MEMBER1, MEMBER3, Decimal::one());
+    setup.delegate(MEMBER5, MEMBER3, Decimal::one());
+    setup.suite.advance_block();
+
+    // Delegator votes after their delegate.
+    let proposal_id = setup.propose();
+    setup.vote(MEMBER3, proposal_id, Vote::Yes);
+    setup.vote(MEMBER1, proposal_id, Vote::No);
+
+    assert_eq!(setup.ballot_power(proposal_id, MEMBER3), Uint128::new(4));
+    assert_eq!(setup.ballot_power(proposal_id, MEMBER1), Uint128::one());
+    let votes = setup.proposal(proposal_id).proposal.votes;
+    assert_eq!(votes.yes, Uint128::new(4));
+    assert_eq!(votes.no, Uint128::one());
+
+    // Delegator votes before their delegate.
+    let proposal_id = setup.propose();
+    setup.vote(MEMBER5, proposal_id, Vote::No);
+    setup.vote(MEMBER3, proposal_id, Vote::Yes);
+
+    assert_eq!(setup.ballot_power(proposal_id, MEMBER3), Uint128::new(4));
+    let votes = setup.proposal(proposal_id).proposal.votes;
+    assert_eq!(votes.yes, Uint128::new(4));
+    assert_eq!(votes.no, Uint128::one());
+}
+
+#[test]
+fn test_delegation_after_proposal_creation_ignored() {
+    let mut setup = setup();
+    setup.register(MEMBER3);
+    setup.suite.advance_block();
+
+    let proposal_id = setup.propose();
+    setup.delegate(MEMBER2, MEMBER3, Decimal::one());
+    setup.suite.advance_block();
+
+    setup.vote(MEMBER3, proposal_id, Vote::Yes);
+    assert_eq!(setup.ballot_power(proposal_id, MEMBER3), Uint128::new(3));
+}
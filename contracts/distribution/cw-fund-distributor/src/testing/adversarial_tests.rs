@@ -64,6 +64,7 @@ fn setup_test(initial_balances: Vec<Cw20Coin>) -> BaseTest {
                     unstaking_duration: None,
                     initial_dao_balance: None,
                 },
+                power_transform: None,
             },
             &[],
             "voting contract",
@@ -65,6 +65,7 @@ fn test_issue_fantoken() -> anyhow::Result<()> {
                 )?),
                 unstaking_duration: None,
                 active_threshold: None,
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -167,6 +168,7 @@ fn test_initial_fantoken_balances() -> anyhow::Result<()> {
                 )?),
                 unstaking_duration: None,
                 active_threshold: None,
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -274,6 +276,7 @@ fn test_fantoken_minter_and_authority_set_to_dao() -> anyhow::Result<()> {
                 )?),
                 unstaking_duration: None,
                 active_threshold: None,
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -431,6 +434,7 @@ fn test_fantoken_can_be_staked() -> anyhow::Result<()> {
                 )?),
                 unstaking_duration: None,
                 active_threshold: None,
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -71,6 +71,7 @@ fn test_set_self_admin_instantiate2() {
                         weight: 1,
                     }],
                 },
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -0,0 +1,430 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdResult, Storage, Uint128,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw_denom::CheckedDenom;
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, nonpayable, one_coin, Duration};
+use dao_hooks::stream::{stream_hook_msgs, StreamChangedHookMsg};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, StreamParams, StreamResponse};
+use crate::state::{HOOKS, RECIPIENT_STREAMS, STREAMS, STREAM_COUNT};
+use crate::stream::{validate_terms, Stream};
+
+const CONTRACT_NAME: &str = "crates.io:cw-payment-streams";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let owner = msg.owner.unwrap_or_else(|| info.sender.to_string());
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(&owner))?;
+    STREAM_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "instantiate")
+        .add_attribute("owner", owner))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => execute_receive_cw20(deps, env, info, msg),
+        ExecuteMsg::CreateStream(params) => {
+            let coin = one_coin(&info)?;
+            create_stream(
+                deps,
+                env,
+                &info.sender,
+                params,
+                CheckedDenom::Native(coin.denom),
+                coin.amount,
+            )
+        }
+        ExecuteMsg::FundStream { id } => {
+            let stream = load_stream(deps.storage, id)?;
+            let amount = match &stream.denom {
+                CheckedDenom::Native(denom) => must_pay(&info, denom)?,
+                CheckedDenom::Cw20(_) => return Err(ContractError::WrongDenom),
+            };
+            fund_stream(deps, env, id, stream, amount)
+        }
+        ExecuteMsg::UpdateStream {
+            id,
+            recipient,
+            amount_per_period,
+            period,
+        } => execute_update_stream(deps, env, info, id, recipient, amount_per_period, period),
+        ExecuteMsg::PauseStream { id } => execute_pause_stream(deps, env, info, id),
+        ExecuteMsg::ResumeStream { id } => execute_resume_stream(deps, env, info, id),
+        ExecuteMsg::CancelStream { id } => execute_cancel_stream(deps, env, info, id),
+        ExecuteMsg::Claim { id } => execute_claim(deps, env, info, id),
+        ExecuteMsg::AddHook { addr } => execute_add_hook(deps, info, addr),
+        ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, info, addr),
+        ExecuteMsg::UpdateOwnership(action) => {
+            let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+            Ok(Response::default().add_attributes(ownership.into_attributes()))
+        }
+    }
+}
+
+fn load_stream(storage: &dyn Storage, id: u64) -> Result<Stream, ContractError> {
+    STREAMS
+        .may_load(storage, id)?
+        .ok_or(ContractError::StreamNotFound { id })
+}
+
+pub fn execute_receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // Only accepts cw20 tokens
+    nonpayable(&info)?;
+
+    match from_json(&receive_msg.msg)? {
+        ReceiveMsg::CreateStream(params) => {
+            let sender = deps.api.addr_validate(&receive_msg.sender)?;
+            create_stream(
+                deps,
+                env,
+                &sender,
+                params,
+                CheckedDenom::Cw20(info.sender),
+                receive_msg.amount,
+            )
+        }
+        ReceiveMsg::FundStream { id } => {
+            let stream = load_stream(deps.storage, id)?;
+            if !stream.denom.is_cw20(&info.sender) {
+                return Err(ContractError::WrongDenom);
+            }
+            fund_stream(deps, env, id, stream, receive_msg.amount)
+        }
+    }
+}
+
+pub fn create_stream(
+    deps: DepsMut,
+    env: Env,
+    sender: &Addr,
+    params: StreamParams,
+    denom: CheckedDenom,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, sender)?;
+
+    let recipient = deps.api.addr_validate(&params.recipient)?;
+    let stream = Stream::new(
+        recipient.clone(),
+        denom,
+        params.amount_per_period,
+        params.period,
+        amount,
+        &env.block,
+    )?;
+
+    let id = STREAM_COUNT.load(deps.storage)? + 1;
+    STREAM_COUNT.save(deps.storage, &id)?;
+    STREAMS.save(deps.storage, id, &stream)?;
+    RECIPIENT_STREAMS.save(deps.storage, (&recipient, id), &Empty {})?;
+
+    let hooks = stream_hook_msgs(
+        HOOKS,
+        deps.storage,
+        StreamChangedHookMsg::Created {
+            id,
+            recipient: recipient.clone(),
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "create_stream")
+        .add_attribute("id", id.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount)
+        .add_submessages(hooks))
+}
+
+pub fn fund_stream(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+    mut stream: Stream,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // Settle first so that periods which passed while the stream was
+    // out of funds are not paid for with the new funds.
+    stream.settle(&env.block);
+    stream.funded += amount;
+    STREAMS.save(deps.storage, id, &stream)?;
+
+    let hooks = stream_hook_msgs(
+        HOOKS,
+        deps.storage,
+        StreamChangedHookMsg::Funded { id, amount },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "fund_stream")
+        .add_attribute("id", id.to_string())
+        .add_attribute("amount", amount)
+        .add_submessages(hooks))
+}
+
+pub fn execute_update_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    recipient: Option<String>,
+    amount_per_period: Option<Uint128>,
+    period: Option<Duration>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let mut stream = load_stream(deps.storage, id)?;
+    stream.settle(&env.block);
+
+    if let Some(recipient) = recipient {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        RECIPIENT_STREAMS.remove(deps.storage, (&stream.recipient, id));
+        RECIPIENT_STREAMS.save(deps.storage, (&recipient, id), &Empty {})?;
+        stream.recipient = recipient;
+    }
+    if let Some(amount_per_period) = amount_per_period {
+        stream.amount_per_period = amount_per_period;
+    }
+    if let Some(period) = period {
+        stream.set_period(period, &env.block);
+    }
+    validate_terms(stream.amount_per_period, &stream.period)?;
+    STREAMS.save(deps.storage, id, &stream)?;
+
+    let hooks = stream_hook_msgs(HOOKS, deps.storage, StreamChangedHookMsg::Updated { id })?;
+
+    Ok(Response::default()
+        .add_attribute("method", "update_stream")
+        .add_attribute("id", id.to_string())
+        .add_submessages(hooks))
+}
+
+pub fn execute_pause_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let mut stream = load_stream(deps.storage, id)?;
+    if stream.paused.is_some() {
+        return Err(ContractError::StreamPaused { id });
+    }
+    stream.pause(&env.block);
+    STREAMS.save(deps.storage, id, &stream)?;
+
+    let hooks = stream_hook_msgs(HOOKS, deps.storage, StreamChangedHookMsg::Paused { id })?;
+
+    Ok(Response::default()
+        .add_attribute("method", "pause_stream")
+        .add_attribute("id", id.to_string())
+        .add_submessages(hooks))
+}
+
+pub fn execute_resume_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let mut stream = load_stream(deps.storage, id)?;
+    if stream.paused.is_none() {
+        return Err(ContractError::StreamNotPaused { id });
+    }
+    stream.resume(&env.block);
+    STREAMS.save(deps.storage, id, &stream)?;
+
+    let hooks = stream_hook_msgs(HOOKS, deps.storage, StreamChangedHookMsg::Resumed { id })?;
+
+    Ok(Response::default()
+        .add_attribute("method", "resume_stream")
+        .add_attribute("id", id.to_string())
+        .add_submessages(hooks))
+}
+
+pub fn execute_cancel_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let mut stream = load_stream(deps.storage, id)?;
+    stream.settle(&env.block);
+    STREAMS.remove(deps.storage, id);
+    RECIPIENT_STREAMS.remove(deps.storage, (&stream.recipient, id));
+
+    let mut msgs = vec![];
+    let claimable = stream.claimable();
+    if !claimable.is_zero() {
+        msgs.push(
+            stream
+                .denom
+                .get_transfer_to_message(&stream.recipient, claimable)?,
+        );
+    }
+    let refund = stream.unaccrued();
+    if !refund.is_zero() {
+        msgs.push(stream.denom.get_transfer_to_message(&info.sender, refund)?);
+    }
+
+    let hooks = stream_hook_msgs(HOOKS, deps.storage, StreamChangedHookMsg::Cancelled { id })?;
+
+    Ok(Response::default()
+        .add_attribute("method", "cancel_stream")
+        .add_attribute("id", id.to_string())
+        .add_attribute("paid", claimable)
+        .add_attribute("refunded", refund)
+        .add_messages(msgs)
+        .add_submessages(hooks))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let mut stream = load_stream(deps.storage, id)?;
+    if info.sender != stream.recipient {
+        return Err(ContractError::NotRecipient);
+    }
+    stream.settle(&env.block);
+    let amount = stream.claimable();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim);
+    }
+    stream.claimed += amount;
+    STREAMS.save(deps.storage, id, &stream)?;
+
+    let msg = stream
+        .denom
+        .get_transfer_to_message(&stream.recipient, amount)?;
+    let hooks = stream_hook_msgs(
+        HOOKS,
+        deps.storage,
+        StreamChangedHookMsg::Claimed {
+            id,
+            recipient: stream.recipient,
+            amount,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "claim")
+        .add_attribute("id", id.to_string())
+        .add_attribute("amount", amount)
+        .add_message(msg)
+        .add_submessages(hooks))
+}
+
+pub fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let hook = deps.api.addr_validate(&addr)?;
+    HOOKS.add_hook(deps.storage, hook)?;
+    Ok(Response::default()
+        .add_attribute("method", "add_hook")
+        .add_attribute("hook", addr))
+}
+
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let hook = deps.api.addr_validate(&addr)?;
+    HOOKS.remove_hook(deps.storage, hook)?;
+    Ok(Response::default()
+        .add_attribute("method", "remove_hook")
+        .add_attribute("hook", addr))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
+        QueryMsg::Stream { id } => {
+            let stream = STREAMS.load(deps.storage, id)?;
+            to_json_binary(&stream_response(&env, id, stream))
+        }
+        QueryMsg::ListStreams { start_after, limit } => {
+            let streams = STREAMS
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.map(|l| l as usize).unwrap_or(usize::MAX))
+                .map(|item| {
+                    let (id, stream) = item?;
+                    Ok(stream_response(&env, id, stream))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&streams)
+        }
+        QueryMsg::RecipientStreams {
+            recipient,
+            start_after,
+            limit,
+        } => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            let streams = RECIPIENT_STREAMS
+                .prefix(&recipient)
+                .keys(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.map(|l| l as usize).unwrap_or(usize::MAX))
+                .map(|id| {
+                    let id = id?;
+                    Ok(stream_response(&env, id, STREAMS.load(deps.storage, id)?))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&streams)
+        }
+        QueryMsg::Hooks {} => to_json_binary(&HOOKS.query_hooks(deps)?),
+    }
+}
+
+fn stream_response(env: &Env, id: u64, mut stream: Stream) -> StreamResponse {
+    stream.settle(&env.block);
+    StreamResponse {
+        id,
+        claimable: stream.claimable(),
+        stream,
+    }
+}
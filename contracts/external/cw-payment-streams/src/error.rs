@@ -0,0 +1,48 @@
+use cosmwasm_std::StdError;
+use cw_denom::DenomError;
+use cw_hooks::HookError;
+use cw_ownable::OwnershipError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Denom(#[from] DenomError),
+
+    #[error(transparent)]
+    Hook(#[from] HookError),
+
+    #[error(transparent)]
+    Ownable(#[from] OwnershipError),
+
+    #[error("{0}")]
+    PaymentError(#[from] PaymentError),
+
+    #[error("only the stream's recipient may claim from it")]
+    NotRecipient,
+
+    #[error("streams must pay a non-zero amount each period")]
+    ZeroAmount,
+
+    #[error("streams must have a non-zero period")]
+    ZeroPeriod,
+
+    #[error("stream ({id}) does not exist")]
+    StreamNotFound { id: u64 },
+
+    #[error("stream ({id}) is paused")]
+    StreamPaused { id: u64 },
+
+    #[error("stream ({id}) is not paused")]
+    StreamNotPaused { id: u64 },
+
+    #[error("nothing to claim")]
+    NothingToClaim,
+
+    #[error("funds sent do not match the stream's denom")]
+    WrongDenom,
+}
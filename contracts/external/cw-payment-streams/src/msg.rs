@@ -0,0 +1,114 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+use cw_hooks::HooksResponse;
+use cw_ownable::cw_ownable_execute;
+use cw_utils::Duration;
+
+use crate::stream::Stream;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The owner of the contract, usually a DAO. The owner may
+    /// create, edit, pause and cancel streams. Defaults to the
+    /// instantiator.
+    pub owner: Option<String>,
+}
+
+/// The terms of a new stream.
+#[cw_serde]
+pub struct StreamParams {
+    /// The address that may claim from the stream.
+    pub recipient: String,
+    /// The number of tokens paid each period.
+    pub amount_per_period: Uint128,
+    /// The length of a period, in blocks or seconds.
+    pub period: Duration,
+}
+
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Receives cw20 tokens. The `msg` field must be a `ReceiveMsg`.
+    Receive(Cw20ReceiveMsg),
+    /// Creates a stream paying in the native token sent with this
+    /// message, funded with the amount sent. Only callable by the
+    /// owner.
+    CreateStream(StreamParams),
+    /// Adds the native tokens sent with this message to a stream's
+    /// funds. Anyone may call this method.
+    FundStream { id: u64 },
+    /// Edits the terms of a stream. Payment for periods that have
+    /// already passed is unaffected. Changing the period restarts
+    /// the current period. Only callable by the owner.
+    UpdateStream {
+        id: u64,
+        recipient: Option<String>,
+        amount_per_period: Option<Uint128>,
+        period: Option<Duration>,
+    },
+    /// Stops a stream from accruing payment until it is resumed.
+    /// Tokens already accrued may still be claimed. Only callable by
+    /// the owner.
+    PauseStream { id: u64 },
+    /// Resumes a paused stream from where it was paused. Only
+    /// callable by the owner.
+    ResumeStream { id: u64 },
+    /// Removes a stream, paying the recipient what they have accrued
+    /// and returning the rest of its funds to the owner. Only
+    /// callable by the owner.
+    CancelStream { id: u64 },
+    /// Claims the tokens accrued by a stream. Only callable by the
+    /// stream's recipient.
+    Claim { id: u64 },
+    /// Adds an address to be notified when streams change. Only
+    /// callable by the owner.
+    AddHook { addr: String },
+    /// Removes a hook. Only callable by the owner.
+    RemoveHook { addr: String },
+}
+
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Creates a stream paying in the cw20 token sent, funded with
+    /// the amount sent. The cw20 sender must be the owner.
+    CreateStream(StreamParams),
+    /// Adds the cw20 tokens sent to a stream's funds.
+    FundStream { id: u64 },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Get the current ownership.
+    #[returns(::cw_ownable::Ownership<::cosmwasm_std::Addr>)]
+    Ownership {},
+    /// Gets a stream.
+    #[returns(StreamResponse)]
+    Stream { id: u64 },
+    /// Lists streams in order of ID.
+    #[returns(Vec<StreamResponse>)]
+    ListStreams {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists the streams paying a recipient in order of ID.
+    #[returns(Vec<StreamResponse>)]
+    RecipientStreams {
+        recipient: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists the addresses notified when streams change.
+    #[returns(HooksResponse)]
+    Hooks {},
+}
+
+#[cw_serde]
+pub struct StreamResponse {
+    pub id: u64,
+    /// The stream, settled as of the current block.
+    pub stream: Stream,
+    /// The number of tokens the recipient may claim now.
+    pub claimable: Uint128,
+}
@@ -0,0 +1,15 @@
+use cosmwasm_std::{Addr, Empty};
+use cw_hooks::Hooks;
+use cw_storage_plus::{Item, Map};
+
+use crate::stream::Stream;
+
+/// Streams by ID.
+pub const STREAMS: Map<u64, Stream> = Map::new("streams");
+/// The number of streams that have been created. Used to assign
+/// stream IDs.
+pub const STREAM_COUNT: Item<u64> = Item::new("stream_count");
+/// An index of streams by their recipient.
+pub const RECIPIENT_STREAMS: Map<(&Addr, u64), Empty> = Map::new("recipient_streams");
+/// Addresses notified when streams change.
+pub const HOOKS: Hooks = Hooks::new("hooks");
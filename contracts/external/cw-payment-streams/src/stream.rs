@@ -0,0 +1,139 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, Uint128};
+use cw_denom::CheckedDenom;
+use cw_utils::Duration;
+
+use crate::ContractError;
+
+/// A stream of payments of `amount_per_period` tokens to a recipient
+/// each time a period passes, for as long as the stream has funds.
+#[cw_serde]
+pub struct Stream {
+    /// The address that may claim from the stream.
+    pub recipient: Addr,
+    /// The token the stream pays in.
+    pub denom: CheckedDenom,
+    /// The number of tokens paid each period.
+    pub amount_per_period: Uint128,
+    /// The length of a period, in blocks or seconds.
+    pub period: Duration,
+    /// The number of tokens that have ever been deposited into the
+    /// stream.
+    pub funded: Uint128,
+    /// The number of tokens the recipient has earned, as of the last
+    /// time the stream was settled. Never exceeds `funded`.
+    pub accrued: Uint128,
+    /// The number of tokens the recipient has claimed.
+    pub claimed: Uint128,
+    /// The height or time, in the unit of `period`, that the current
+    /// period began.
+    pub period_start: u64,
+    /// If the stream is paused, how far into the current period it
+    /// was when it was paused.
+    pub paused: Option<u64>,
+}
+
+/// The current height or time, in the unit of `period`.
+fn clock(period: &Duration, block: &BlockInfo) -> u64 {
+    match period {
+        Duration::Height(_) => block.height,
+        Duration::Time(_) => block.time.seconds(),
+    }
+}
+
+fn length(period: &Duration) -> u64 {
+    match period {
+        Duration::Height(blocks) => *blocks,
+        Duration::Time(seconds) => *seconds,
+    }
+}
+
+pub(crate) fn validate_terms(
+    amount_per_period: Uint128,
+    period: &Duration,
+) -> Result<(), ContractError> {
+    if amount_per_period.is_zero() {
+        return Err(ContractError::ZeroAmount);
+    }
+    if length(period) == 0 {
+        return Err(ContractError::ZeroPeriod);
+    }
+    Ok(())
+}
+
+impl Stream {
+    pub(crate) fn new(
+        recipient: Addr,
+        denom: CheckedDenom,
+        amount_per_period: Uint128,
+        period: Duration,
+        funded: Uint128,
+        block: &BlockInfo,
+    ) -> Result<Self, ContractError> {
+        validate_terms(amount_per_period, &period)?;
+        Ok(Self {
+            recipient,
+            denom,
+            amount_per_period,
+            period_start: clock(&period, block),
+            period,
+            funded,
+            accrued: Uint128::zero(),
+            claimed: Uint128::zero(),
+            paused: None,
+        })
+    }
+
+    /// Accrues payment for every period that has passed since the
+    /// stream was last settled. Periods that pass while the stream
+    /// is out of funds are not paid for if it is funded later, so
+    /// streams must be settled before funds are added.
+    pub fn settle(&mut self, block: &BlockInfo) {
+        if self.paused.is_some() {
+            return;
+        }
+        let length = length(&self.period);
+        let periods = clock(&self.period, block).saturating_sub(self.period_start) / length;
+        self.period_start += periods * length;
+        self.accrued = self
+            .accrued
+            .saturating_add(self.amount_per_period.saturating_mul(periods.into()))
+            .min(self.funded);
+    }
+
+    /// The number of tokens the recipient may claim.
+    pub fn claimable(&self) -> Uint128 {
+        self.accrued - self.claimed
+    }
+
+    /// The number of tokens that have not been earned by the
+    /// recipient.
+    pub fn unaccrued(&self) -> Uint128 {
+        self.funded - self.accrued
+    }
+
+    /// Stops the stream from accruing, remembering how far into the
+    /// current period it is.
+    pub(crate) fn pause(&mut self, block: &BlockInfo) {
+        self.settle(block);
+        self.paused = Some(clock(&self.period, block) - self.period_start);
+    }
+
+    /// Restarts a paused stream from where it was paused.
+    pub(crate) fn resume(&mut self, block: &BlockInfo) {
+        if let Some(progress) = self.paused.take() {
+            self.period_start = clock(&self.period, block) - progress;
+        }
+    }
+
+    /// Changes the length of the stream's periods. The current
+    /// period restarts, as progress in a period of one length may
+    /// not be meaningful in a period of another.
+    pub(crate) fn set_period(&mut self, period: Duration, block: &BlockInfo) {
+        self.period_start = clock(&period, block);
+        self.period = period;
+        if self.paused.is_some() {
+            self.paused = Some(0);
+        }
+    }
+}
@@ -0,0 +1,461 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, to_json_binary, Addr, CosmosMsg, Uint128, WasmMsg};
+use cw20::{Cw20Coin, Cw20ExecuteMsg};
+use cw_multi_test::{App, BankSudo, Executor, SudoMsg};
+use cw_utils::Duration;
+use dao_hooks::stream::{StreamChangedExecuteMsg, StreamChangedHookMsg};
+use dao_testing::contracts::{cw20_base_contract, cw_payment_streams_contract};
+
+use crate::contract::{execute, instantiate};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, StreamParams, StreamResponse};
+use crate::ContractError;
+
+const OWNER: &str = "owner";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+const NATIVE_DENOM: &str = "ujuno";
+
+fn setup_app() -> (App, Addr) {
+    let mut app = App::default();
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: OWNER.to_string(),
+        amount: coins(1000, NATIVE_DENOM),
+    }))
+    .unwrap();
+    let code_id = app.store_code(cw_payment_streams_contract());
+    let streams = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg { owner: None },
+            &[],
+            "streams",
+            None,
+        )
+        .unwrap();
+    (app, streams)
+}
+
+fn create_native_stream(app: &mut App, streams: &Addr, amount: u128, period: Duration) {
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        streams.clone(),
+        &ExecuteMsg::CreateStream(StreamParams {
+            recipient: ALICE.to_string(),
+            amount_per_period: Uint128::new(10),
+            period,
+        }),
+        &coins(amount, NATIVE_DENOM),
+    )
+    .unwrap();
+}
+
+fn query_stream(app: &App, streams: &Addr, id: u64) -> StreamResponse {
+    app.wrap()
+        .query_wasm_smart(streams, &QueryMsg::Stream { id })
+        .unwrap()
+}
+
+fn claim(app: &mut App, streams: &Addr, id: u64) {
+    app.execute_contract(
+        Addr::unchecked(ALICE),
+        streams.clone(),
+        &ExecuteMsg::Claim { id },
+        &[],
+    )
+    .unwrap();
+}
+
+fn native_balance(app: &App, addr: &str) -> Uint128 {
+    app.wrap().query_balance(addr, NATIVE_DENOM).unwrap().amount
+}
+
+fn advance_blocks(app: &mut App, blocks: u64) {
+    app.update_block(|block| {
+        block.height += blocks;
+        block.time = block.time.plus_seconds(5 * blocks);
+    });
+}
+
+#[test]
+fn test_native_block_stream() {
+    let (mut app, streams) = setup_app();
+    create_native_stream(&mut app, &streams, 100, Duration::Height(5));
+
+    advance_blocks(&mut app, 12);
+    assert_eq!(query_stream(&app, &streams, 1).claimable, Uint128::new(20));
+    claim(&mut app, &streams, 1);
+    assert_eq!(native_balance(&app, ALICE), Uint128::new(20));
+
+    // Nothing new has accrued.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ALICE),
+            streams.clone(),
+            &ExecuteMsg::Claim { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim);
+
+    // Accrual stops once the stream runs out of funds.
+    advance_blocks(&mut app, 100);
+    assert_eq!(query_stream(&app, &streams, 1).claimable, Uint128::new(80));
+    claim(&mut app, &streams, 1);
+    assert_eq!(native_balance(&app, ALICE), Uint128::new(100));
+
+    // Funding the stream again does not pay for periods that passed
+    // while it was empty.
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        streams.clone(),
+        &ExecuteMsg::FundStream { id: 1 },
+        &coins(50, NATIVE_DENOM),
+    )
+    .unwrap();
+    assert_eq!(query_stream(&app, &streams, 1).claimable, Uint128::zero());
+    advance_blocks(&mut app, 5);
+    assert_eq!(query_stream(&app, &streams, 1).claimable, Uint128::new(10));
+}
+
+#[test]
+fn test_cw20_time_stream() {
+    let (mut app, streams) = setup_app();
+    let cw20_code = app.store_code(cw20_base_contract());
+    let cw20 = app
+        .instantiate_contract(
+            cw20_code,
+            Addr::unchecked(OWNER),
+            &cw20_base::msg::InstantiateMsg {
+                name: "token".to_string(),
+                symbol: "ttt".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: OWNER.to_string(),
+                    amount: Uint128::new(1000),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "cw20",
+            None,
+        )
+        .unwrap();
+
+    let send = |msg: &ReceiveMsg| Cw20ExecuteMsg::Send {
+        contract: streams.to_string(),
+        amount: Uint128::new(100),
+        msg: to_json_binary(msg).unwrap(),
+    };
+    let params = StreamParams {
+        recipient: ALICE.to_string(),
+        amount_per_period: Uint128::new(10),
+        period: Duration::Time(60),
+    };
+
+    // Only the owner may create streams.
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        cw20.clone(),
+        &Cw20ExecuteMsg::Transfer {
+            recipient: BOB.to_string(),
+            amount: Uint128::new(100),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(BOB),
+        cw20.clone(),
+        &send(&ReceiveMsg::CreateStream(params.clone())),
+        &[],
+    )
+    .unwrap_err();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        cw20.clone(),
+        &send(&ReceiveMsg::CreateStream(params)),
+        &[],
+    )
+    .unwrap();
+
+    // Anyone may fund a stream.
+    app.execute_contract(
+        Addr::unchecked(BOB),
+        cw20.clone(),
+        &send(&ReceiveMsg::FundStream { id: 1 }),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_stream(&app, &streams, 1).stream.funded,
+        Uint128::new(200)
+    );
+
+    // Native funds may not be added to a cw20 stream.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            streams.clone(),
+            &ExecuteMsg::FundStream { id: 1 },
+            &coins(10, NATIVE_DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::WrongDenom);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(150));
+    claim(&mut app, &streams, 1);
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &cw20,
+            &cw20::Cw20QueryMsg::Balance {
+                address: ALICE.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(20));
+}
+
+#[test]
+fn test_pause_and_resume() {
+    let (mut app, streams) = setup_app();
+    create_native_stream(&mut app, &streams, 100, Duration::Height(10));
+
+    // Pause 15 blocks in, halfway through the second period.
+    advance_blocks(&mut app, 15);
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        streams.clone(),
+        &ExecuteMsg::PauseStream { id: 1 },
+        &[],
+    )
+    .unwrap();
+    advance_blocks(&mut app, 100);
+    let paused = query_stream(&app, &streams, 1);
+    assert_eq!(paused.claimable, Uint128::new(10));
+    assert_eq!(paused.stream.paused, Some(5));
+
+    // Paused streams may still be claimed from.
+    claim(&mut app, &streams, 1);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            streams.clone(),
+            &ExecuteMsg::PauseStream { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::StreamPaused { id: 1 });
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        streams.clone(),
+        &ExecuteMsg::ResumeStream { id: 1 },
+        &[],
+    )
+    .unwrap();
+    advance_blocks(&mut app, 4);
+    assert_eq!(query_stream(&app, &streams, 1).claimable, Uint128::zero());
+    advance_blocks(&mut app, 1);
+    assert_eq!(query_stream(&app, &streams, 1).claimable, Uint128::new(10));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            streams.clone(),
+            &ExecuteMsg::ResumeStream { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::StreamNotPaused { id: 1 });
+}
+
+#[test]
+fn test_update_stream() {
+    let (mut app, streams) = setup_app();
+    create_native_stream(&mut app, &streams, 100, Duration::Height(5));
+    advance_blocks(&mut app, 7);
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        streams.clone(),
+        &ExecuteMsg::UpdateStream {
+            id: 1,
+            recipient: Some(BOB.to_string()),
+            amount_per_period: Some(Uint128::new(20)),
+            period: Some(Duration::Height(10)),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Earlier accrual is kept and the new period starts now.
+    let stream = query_stream(&app, &streams, 1);
+    assert_eq!(stream.claimable, Uint128::new(10));
+    assert_eq!(stream.stream.recipient, Addr::unchecked(BOB));
+    advance_blocks(&mut app, 10);
+    assert_eq!(query_stream(&app, &streams, 1).claimable, Uint128::new(30));
+
+    let alice: Vec<StreamResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &streams,
+            &QueryMsg::RecipientStreams {
+                recipient: ALICE.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(alice.is_empty());
+    let bob: Vec<StreamResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &streams,
+            &QueryMsg::RecipientStreams {
+                recipient: BOB.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(bob.len(), 1);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            streams.clone(),
+            &ExecuteMsg::UpdateStream {
+                id: 1,
+                recipient: None,
+                amount_per_period: None,
+                period: Some(Duration::Time(0)),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroPeriod);
+}
+
+#[test]
+fn test_cancel_stream() {
+    let (mut app, streams) = setup_app();
+    create_native_stream(&mut app, &streams, 100, Duration::Height(5));
+    advance_blocks(&mut app, 10);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ALICE),
+            streams.clone(),
+            &ExecuteMsg::CancelStream { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Ownable(_)));
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        streams.clone(),
+        &ExecuteMsg::CancelStream { id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(native_balance(&app, ALICE), Uint128::new(20));
+    assert_eq!(native_balance(&app, OWNER), Uint128::new(980));
+
+    let list: Vec<StreamResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &streams,
+            &QueryMsg::ListStreams {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(list.is_empty());
+}
+
+#[test]
+fn test_only_recipient_claims() {
+    let (mut app, streams) = setup_app();
+    create_native_stream(&mut app, &streams, 100, Duration::Height(5));
+    advance_blocks(&mut app, 5);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(BOB),
+            streams.clone(),
+            &ExecuteMsg::Claim { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotRecipient);
+}
+
+#[test]
+fn test_hooks() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        InstantiateMsg { owner: None },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::AddHook {
+            addr: "hook".to_string(),
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OWNER, &coins(100, NATIVE_DENOM)),
+        ExecuteMsg::CreateStream(StreamParams {
+            recipient: ALICE.to_string(),
+            amount_per_period: Uint128::new(10),
+            period: Duration::Height(5),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "hook".to_string(),
+            msg: to_json_binary(&StreamChangedExecuteMsg::StreamChangeHook(
+                StreamChangedHookMsg::Created {
+                    id: 1,
+                    recipient: Addr::unchecked(ALICE),
+                }
+            ))
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
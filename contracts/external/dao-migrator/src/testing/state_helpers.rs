@@ -55,6 +55,8 @@ pub fn query_proposal_v1(
         votes: v1_votes_to_v2(proposal.votes),
         allow_revoting: proposal.allow_revoting,
         veto: None,
+        execution_plan: None,
+        amendable_until: None,
     };
 
     (proposal_count, proposal)
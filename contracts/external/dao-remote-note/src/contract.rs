@@ -0,0 +1,177 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, IbcMsg, IbcTimeout,
+    MessageInfo, Order, Response, StdResult,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use dao_interface::remote::RemotePacket;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, ProposalRef, QueryMsg};
+use crate::state::{
+    Execution, ExecutionStatus, CHANNEL, DAO, EXECUTIONS, EXECUTION_COUNT, PAIR,
+    PROPOSAL_EXECUTIONS, REMOTE_ADDRESS, TIMEOUT_SECONDS,
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-remote-note";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    if msg.timeout_seconds == 0 {
+        return Err(ContractError::ZeroTimeout {});
+    }
+    // The DAO instantiates the note.
+    DAO.save(deps.storage, &info.sender)?;
+    TIMEOUT_SECONDS.save(deps.storage, &msg.timeout_seconds)?;
+    if let Some(pair) = &msg.pair {
+        PAIR.save(deps.storage, pair)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("dao", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Execute {
+            msgs,
+            proposal,
+            timeout_seconds,
+        } => execute_execute(deps, env, info, msgs, proposal, timeout_seconds),
+    }
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<CosmosMsg<Empty>>,
+    proposal: Option<ProposalRef>,
+    timeout_seconds: Option<u64>,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let channel = CHANNEL
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoChannel {})?;
+    let timeout_seconds = match timeout_seconds {
+        Some(0) => return Err(ContractError::ZeroTimeout {}),
+        Some(timeout_seconds) => timeout_seconds,
+        None => TIMEOUT_SECONDS.load(deps.storage)?,
+    };
+    let proposal = proposal
+        .map(|proposal| -> StdResult<_> {
+            Ok((deps.api.addr_validate(&proposal.module)?, proposal.id))
+        })
+        .transpose()?;
+
+    let id = EXECUTION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    EXECUTION_COUNT.save(deps.storage, &id)?;
+    if let Some((module, proposal_id)) = &proposal {
+        PROPOSAL_EXECUTIONS.save(deps.storage, (module, *proposal_id, id), &Empty {})?;
+    }
+    EXECUTIONS.save(
+        deps.storage,
+        id,
+        &Execution {
+            id,
+            proposal,
+            msgs: msgs.clone(),
+            status: ExecutionStatus::Pending,
+        },
+    )?;
+
+    let packet = RemotePacket {
+        id,
+        sender: info.sender.into_string(),
+        msgs,
+    };
+    Ok(Response::new()
+        .add_attribute("method", "execute")
+        .add_attribute("execution_id", id.to_string())
+        .add_message(IbcMsg::SendPacket {
+            channel_id: channel,
+            data: to_json_binary(&packet)?,
+            timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(timeout_seconds)),
+        }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Pair {} => to_json_binary(&PAIR.may_load(deps.storage)?),
+        QueryMsg::Channel {} => to_json_binary(&CHANNEL.may_load(deps.storage)?),
+        QueryMsg::RemoteAddress {} => to_json_binary(&REMOTE_ADDRESS.may_load(deps.storage)?),
+        QueryMsg::Execution { id } => to_json_binary(&EXECUTIONS.load(deps.storage, id)?),
+        QueryMsg::ListExecutions { start_after, limit } => {
+            query_list_executions(deps, start_after, limit)
+        }
+        QueryMsg::ProposalExecutions {
+            module,
+            proposal_id,
+            start_after,
+            limit,
+        } => query_proposal_executions(deps, module, proposal_id, start_after, limit),
+    }
+}
+
+pub fn query_list_executions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let executions = EXECUTIONS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize)
+        .map(|item| Ok(item?.1))
+        .collect::<StdResult<Vec<Execution>>>()?;
+    to_json_binary(&executions)
+}
+
+pub fn query_proposal_executions(
+    deps: Deps,
+    module: String,
+    proposal_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let module: Addr = deps.api.addr_validate(&module)?;
+    let executions = PROPOSAL_EXECUTIONS
+        .prefix((&module, proposal_id))
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize)
+        .map(|id| EXECUTIONS.load(deps.storage, id?))
+        .collect::<StdResult<Vec<Execution>>>()?;
+    to_json_binary(&executions)
+}
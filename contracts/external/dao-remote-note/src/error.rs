@@ -0,0 +1,35 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The note has no open channel.")]
+    NoChannel {},
+
+    #[error("The note already has an open channel ({channel}).")]
+    ChannelAlreadyOpen { channel: String },
+
+    #[error("Only unordered channels are supported.")]
+    OrderedChannel {},
+
+    #[error("Expected channel version ({expected}), got ({actual}).")]
+    InvalidVersion { expected: String, actual: String },
+
+    #[error("Channels must be over ({connection_id}) to port ({remote_port}).")]
+    InvalidPair {
+        connection_id: String,
+        remote_port: String,
+    },
+
+    #[error("Timeout must be non-zero.")]
+    ZeroTimeout {},
+
+    #[error("The note does not accept packets.")]
+    PacketsNotAccepted {},
+}
@@ -0,0 +1,153 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, DepsMut, Env, Ibc3ChannelOpenResponse, IbcBasicResponse, IbcChannel,
+    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcOrder,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, StdError,
+    StdResult, Storage,
+};
+use dao_interface::remote::{RemoteAck, RemotePacket, REMOTE_VERSION};
+
+use crate::error::ContractError;
+use crate::state::{ExecutionStatus, CHANNEL, EXECUTIONS, PAIR, REMOTE_ADDRESS};
+
+fn validate_channel(
+    storage: &dyn Storage,
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if let Some(pair) = PAIR.may_load(storage)? {
+        if channel.connection_id != pair.connection_id
+            || channel.counterparty_endpoint.port_id != pair.remote_port
+        {
+            return Err(ContractError::InvalidPair {
+                connection_id: pair.connection_id,
+                remote_port: pair.remote_port,
+            });
+        }
+    }
+    if channel.order != IbcOrder::Unordered {
+        return Err(ContractError::OrderedChannel {});
+    }
+    for version in [Some(channel.version.as_str()), counterparty_version]
+        .into_iter()
+        .flatten()
+    {
+        if version != REMOTE_VERSION {
+            return Err(ContractError::InvalidVersion {
+                expected: REMOTE_VERSION.to_string(),
+                actual: version.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Sets the status of the execution sent in `packet`.
+fn set_status(storage: &mut dyn Storage, packet: &[u8], status: ExecutionStatus) -> StdResult<u64> {
+    let packet: RemotePacket = from_json(packet)?;
+    EXECUTIONS.update(storage, packet.id, |execution| -> StdResult<_> {
+        let mut execution =
+            execution.ok_or_else(|| StdError::not_found(format!("execution {}", packet.id)))?;
+        execution.status = status;
+        Ok(execution)
+    })?;
+    Ok(packet.id)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    // A note controls a single proxy, so only one channel may be
+    // open at a time.
+    if let Some(channel) = CHANNEL.may_load(deps.storage)? {
+        return Err(ContractError::ChannelAlreadyOpen { channel });
+    }
+    validate_channel(deps.storage, msg.channel(), msg.counterparty_version())?;
+    Ok(Some(Ibc3ChannelOpenResponse {
+        version: REMOTE_VERSION.to_string(),
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    if let Some(channel) = CHANNEL.may_load(deps.storage)? {
+        return Err(ContractError::ChannelAlreadyOpen { channel });
+    }
+    validate_channel(deps.storage, msg.channel(), msg.counterparty_version())?;
+    let channel = msg.channel().endpoint.channel_id.clone();
+    CHANNEL.save(deps.storage, &channel)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_channel_connect")
+        .add_attribute("channel", channel))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    CHANNEL.remove(deps.storage);
+    REMOTE_ADDRESS.remove(deps.storage);
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_channel_close")
+        .add_attribute("channel", &msg.channel().endpoint.channel_id))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    Err(ContractError::PacketsNotAccepted {})
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    // Acknowledgements that are not a `RemoteAck`, for example those
+    // written by the chain if the voice fails, are recorded as errors.
+    let status = match from_json(&msg.acknowledgement.data) {
+        Ok(RemoteAck::Success { proxy }) => {
+            if !REMOTE_ADDRESS.exists(deps.storage) {
+                REMOTE_ADDRESS.save(deps.storage, &proxy)?;
+            }
+            ExecutionStatus::Success
+        }
+        Ok(RemoteAck::Error { error }) => ExecutionStatus::Error { error },
+        Err(_) => ExecutionStatus::Error {
+            error: String::from_utf8_lossy(msg.acknowledgement.data.as_slice()).into_owned(),
+        },
+    };
+    let id = set_status(deps.storage, &msg.original_packet.data, status)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_packet_ack")
+        .add_attribute("execution_id", id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let id = set_status(deps.storage, &msg.packet.data, ExecutionStatus::Timeout)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_packet_timeout")
+        .add_attribute("execution_id", id.to_string()))
+}
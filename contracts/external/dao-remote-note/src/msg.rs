@@ -0,0 +1,69 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The number of seconds packets may take to be relayed before
+    /// they time out, if not set when executing.
+    pub timeout_seconds: u64,
+    /// If set, channels may only be opened over this connection and
+    /// with a voice bound to this port. Otherwise channels may be
+    /// opened with any voice.
+    pub pair: Option<crate::state::Pair>,
+}
+
+/// A proposal that executed messages on the remote chain.
+#[cw_serde]
+pub struct ProposalRef {
+    /// The address of the proposal module.
+    pub module: String,
+    pub id: u64,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Callable by the DAO. Sends `msgs` to be executed by the DAO's
+    /// proxy on the remote chain. If `proposal` is set, the outcome is
+    /// recorded against that proposal.
+    Execute {
+        msgs: Vec<CosmosMsg<Empty>>,
+        proposal: Option<ProposalRef>,
+        timeout_seconds: Option<u64>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the DAO that controls the note.
+    #[returns(cosmwasm_std::Addr)]
+    Dao {},
+    /// Gets the connection and voice port channels are restricted to,
+    /// if set.
+    #[returns(Option<crate::state::Pair>)]
+    Pair {},
+    /// Gets the channel packets are sent over, if one is open.
+    #[returns(Option<String>)]
+    Channel {},
+    /// Gets the address of the DAO's proxy on the remote chain. Known
+    /// once the first packet has been acknowledged.
+    #[returns(Option<String>)]
+    RemoteAddress {},
+    /// Gets an execution sent by the note.
+    #[returns(crate::state::Execution)]
+    Execution { id: u64 },
+    /// Lists the executions sent by the note.
+    #[returns(Vec<crate::state::Execution>)]
+    ListExecutions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists the executions sent by a proposal.
+    #[returns(Vec<crate::state::Execution>)]
+    ProposalExecutions {
+        module: String,
+        proposal_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
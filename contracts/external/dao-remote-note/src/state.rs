@@ -0,0 +1,63 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CosmosMsg, Empty};
+use cw_storage_plus::{Item, Map};
+
+/// The DAO that controls the note.
+pub const DAO: Item<Addr> = Item::new("dao");
+
+/// A connection and the port of a voice on the other end of it.
+#[cw_serde]
+pub struct Pair {
+    pub connection_id: String,
+    pub remote_port: String,
+}
+
+/// If set, the only connection and voice port channels may be opened
+/// with.
+pub const PAIR: Item<Pair> = Item::new("pair");
+
+/// The default number of seconds before packets time out.
+pub const TIMEOUT_SECONDS: Item<u64> = Item::new("timeout_seconds");
+
+/// The channel packets are sent over. Not set until a channel has
+/// been opened, and removed if it is closed.
+pub const CHANNEL: Item<String> = Item::new("channel");
+
+/// The DAO's proxy on the remote chain, as reported by the first
+/// successful acknowledgement over the open channel. Removed if the
+/// channel is closed, as a new channel may be to another voice.
+pub const REMOTE_ADDRESS: Item<String> = Item::new("remote_address");
+
+/// The number of executions sent. Used to assign IDs.
+pub const EXECUTION_COUNT: Item<u64> = Item::new("execution_count");
+
+#[cw_serde]
+pub enum ExecutionStatus {
+    /// The packet has not been acknowledged or timed out yet.
+    Pending,
+    /// The messages were executed on the remote chain.
+    Success,
+    /// Executing the messages failed on the remote chain.
+    Error { error: String },
+    /// The packet was not relayed in time. None of the messages were
+    /// executed.
+    Timeout,
+}
+
+/// Messages sent to be executed on the remote chain, and their
+/// outcome.
+#[cw_serde]
+pub struct Execution {
+    pub id: u64,
+    /// The proposal module and proposal ID that sent the messages, if
+    /// given.
+    pub proposal: Option<(Addr, u64)>,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    pub status: ExecutionStatus,
+}
+
+pub const EXECUTIONS: Map<u64, Execution> = Map::new("executions");
+
+/// The executions of each proposal, keyed by proposal module,
+/// proposal ID and execution ID.
+pub const PROPOSAL_EXECUTIONS: Map<(&Addr, u64, u64), Empty> = Map::new("proposal_executions");
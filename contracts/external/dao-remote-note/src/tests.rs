@@ -0,0 +1,477 @@
+use cosmwasm_std::{
+    coins, from_json,
+    testing::{
+        mock_dependencies, mock_env, mock_ibc_channel, mock_ibc_channel_close_init,
+        mock_ibc_channel_connect_ack, mock_ibc_channel_open_init, mock_ibc_packet_ack,
+        mock_ibc_packet_timeout, mock_info, MockApi, MockQuerier, MockStorage,
+    },
+    to_json_binary, Addr, BankMsg, Binary, CosmosMsg, IbcAcknowledgement, IbcChannel,
+    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcMsg, IbcOrder, IbcTimeout, OwnedDeps,
+};
+use cw_multi_test::{App, BankSudo, Executor, SudoMsg};
+use dao_interface::remote::{RemoteAck, RemotePacket, REMOTE_VERSION};
+use dao_testing::contracts::{dao_remote_proxy_contract, dao_remote_voice_contract};
+
+use crate::{
+    contract::{execute, instantiate, query},
+    ibc::{
+        ibc_channel_close, ibc_channel_connect, ibc_channel_open, ibc_packet_ack,
+        ibc_packet_timeout,
+    },
+    msg::{ExecuteMsg, InstantiateMsg, ProposalRef, QueryMsg},
+    state::{Execution, ExecutionStatus, Pair},
+    ContractError,
+};
+
+const DAO: &str = "dao";
+const CHANNEL: &str = "channel-0";
+const CONNECTION: &str = "connection-0";
+const NOTE_PORT: &str = "wasm.note";
+const VOICE_PORT: &str = "wasm.voice";
+
+fn setup(pair: Option<Pair>) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(DAO, &[]),
+        InstantiateMsg {
+            timeout_seconds: 60,
+            pair,
+        },
+    )
+    .unwrap();
+    deps
+}
+
+/// A channel from the note to a voice bound to `remote_port` over
+/// `connection_id`.
+fn channel_to(connection_id: &str, remote_port: &str) -> IbcChannel {
+    let mut channel = mock_ibc_channel(CHANNEL, IbcOrder::Unordered, REMOTE_VERSION);
+    channel.endpoint.port_id = NOTE_PORT.to_string();
+    channel.counterparty_endpoint.port_id = remote_port.to_string();
+    channel.connection_id = connection_id.to_string();
+    channel
+}
+
+/// Opens `channel` through the note's handshake entry points.
+fn open_channel(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    channel: IbcChannel,
+) -> Result<(), ContractError> {
+    ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        IbcChannelOpenMsg::new_init(channel.clone()),
+    )?;
+    ibc_channel_connect(
+        deps.as_mut(),
+        mock_env(),
+        IbcChannelConnectMsg::new_ack(channel, REMOTE_VERSION),
+    )?;
+    Ok(())
+}
+
+fn send(amount: u128) -> CosmosMsg {
+    BankMsg::Send {
+        to_address: "recipient".to_string(),
+        amount: coins(amount, "uatom"),
+    }
+    .into()
+}
+
+fn execute_remote(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    sender: &str,
+    msgs: Vec<CosmosMsg>,
+    proposal: Option<ProposalRef>,
+) -> Result<RemotePacket, ContractError> {
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::Execute {
+            msgs,
+            proposal,
+            timeout_seconds: None,
+        },
+    )?;
+    match &res.messages[0].msg {
+        CosmosMsg::Ibc(IbcMsg::SendPacket {
+            channel_id,
+            data,
+            timeout,
+        }) => {
+            assert_eq!(channel_id, CHANNEL);
+            assert_eq!(
+                timeout,
+                &IbcTimeout::with_timestamp(mock_env().block.time.plus_seconds(60))
+            );
+            Ok(from_json(data).unwrap())
+        }
+        msg => panic!("expected a packet, got {msg:?}"),
+    }
+}
+
+fn query_execution(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, id: u64) -> Execution {
+    from_json(query(deps.as_ref(), mock_env(), QueryMsg::Execution { id }).unwrap()).unwrap()
+}
+
+#[test]
+fn test_channel_handshake() {
+    let mut deps = setup(None);
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(CHANNEL, IbcOrder::Ordered, REMOTE_VERSION),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderedChannel {});
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(CHANNEL, IbcOrder::Unordered, "ics20-1"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidVersion {
+            expected: REMOTE_VERSION.to_string(),
+            actual: "ics20-1".to_string(),
+        }
+    );
+
+    ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(CHANNEL, IbcOrder::Unordered, REMOTE_VERSION),
+    )
+    .unwrap();
+    ibc_channel_connect(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_connect_ack(CHANNEL, IbcOrder::Unordered, REMOTE_VERSION),
+    )
+    .unwrap();
+    let channel: Option<String> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Channel {}).unwrap()).unwrap();
+    assert_eq!(channel, Some(CHANNEL.to_string()));
+
+    // Only one channel may be open.
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init("channel-1", IbcOrder::Unordered, REMOTE_VERSION),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ChannelAlreadyOpen {
+            channel: CHANNEL.to_string()
+        }
+    );
+
+    ibc_channel_close(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_close_init(CHANNEL, IbcOrder::Unordered, REMOTE_VERSION),
+    )
+    .unwrap();
+    let err = execute_remote(&mut deps, DAO, vec![send(10)], None).unwrap_err();
+    assert_eq!(err, ContractError::NoChannel {});
+}
+
+#[test]
+fn test_execution_outcomes() {
+    let mut deps = setup(None);
+    let err = execute_remote(&mut deps, DAO, vec![send(10)], None).unwrap_err();
+    assert_eq!(err, ContractError::NoChannel {});
+    ibc_channel_connect(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_connect_ack(CHANNEL, IbcOrder::Unordered, REMOTE_VERSION),
+    )
+    .unwrap();
+
+    let err = execute_remote(&mut deps, "ekez", vec![send(10)], None).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let proposal = ProposalRef {
+        module: "proposal_module".to_string(),
+        id: 4,
+    };
+    let packet = execute_remote(&mut deps, DAO, vec![send(10)], Some(proposal.clone())).unwrap();
+    assert_eq!(
+        packet,
+        RemotePacket {
+            id: 1,
+            sender: DAO.to_string(),
+            msgs: vec![send(10)],
+        }
+    );
+    assert_eq!(
+        query_execution(&deps, 1),
+        Execution {
+            id: 1,
+            proposal: Some((Addr::unchecked("proposal_module"), 4)),
+            msgs: vec![send(10)],
+            status: ExecutionStatus::Pending,
+        }
+    );
+
+    // A successful acknowledgement records the proxy's address.
+    ibc_packet_ack(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_packet_ack(
+            CHANNEL,
+            &packet,
+            IbcAcknowledgement::new(
+                to_json_binary(&RemoteAck::Success {
+                    proxy: "proxy".to_string(),
+                })
+                .unwrap(),
+            ),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(query_execution(&deps, 1).status, ExecutionStatus::Success);
+    let remote: Option<String> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::RemoteAddress {}).unwrap()).unwrap();
+    assert_eq!(remote, Some("proxy".to_string()));
+
+    // Acknowledgements written by the chain are recorded as errors.
+    let packet = execute_remote(&mut deps, DAO, vec![send(10)], Some(proposal.clone())).unwrap();
+    ibc_packet_ack(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_packet_ack(
+            CHANNEL,
+            &packet,
+            IbcAcknowledgement::new(Binary::from(b"ABCI code: 5".to_vec())),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        query_execution(&deps, 2).status,
+        ExecutionStatus::Error {
+            error: "ABCI code: 5".to_string()
+        }
+    );
+
+    let packet = execute_remote(&mut deps, DAO, vec![send(10)], None).unwrap();
+    ibc_packet_timeout(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_packet_timeout(CHANNEL, &packet).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(query_execution(&deps, 3).status, ExecutionStatus::Timeout);
+
+    // Only the executions of the proposal are listed against it.
+    let executions: Vec<Execution> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ProposalExecutions {
+                module: proposal.module.clone(),
+                proposal_id: proposal.id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        executions.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    let executions: Vec<Execution> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ProposalExecutions {
+                module: proposal.module,
+                proposal_id: proposal.id,
+                start_after: Some(1),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(executions.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2]);
+    let executions: Vec<Execution> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ListExecutions {
+                start_after: Some(1),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(executions.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2]);
+
+    // A new channel may be to another voice, so the proxy's address
+    // is forgotten when the channel closes.
+    ibc_channel_close(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_close_init(CHANNEL, IbcOrder::Unordered, REMOTE_VERSION),
+    )
+    .unwrap();
+    let remote: Option<String> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::RemoteAddress {}).unwrap()).unwrap();
+    assert_eq!(remote, None);
+}
+
+#[test]
+fn test_pair() {
+    let pair = Pair {
+        connection_id: CONNECTION.to_string(),
+        remote_port: VOICE_PORT.to_string(),
+    };
+    let mut deps = setup(Some(pair.clone()));
+    let queried: Option<Pair> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Pair {}).unwrap()).unwrap();
+    assert_eq!(queried, Some(pair));
+
+    let invalid_pair = ContractError::InvalidPair {
+        connection_id: CONNECTION.to_string(),
+        remote_port: VOICE_PORT.to_string(),
+    };
+    for channel in [
+        channel_to("connection-1", VOICE_PORT),
+        channel_to(CONNECTION, "wasm.other_voice"),
+    ] {
+        let err = open_channel(&mut deps, channel.clone()).unwrap_err();
+        assert_eq!(err, invalid_pair);
+        // The pair is also checked when the voice opens the channel.
+        let err = ibc_channel_connect(
+            deps.as_mut(),
+            mock_env(),
+            IbcChannelConnectMsg::new_confirm(channel),
+        )
+        .unwrap_err();
+        assert_eq!(err, invalid_pair);
+    }
+
+    open_channel(&mut deps, channel_to(CONNECTION, VOICE_PORT)).unwrap();
+    let channel: Option<String> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Channel {}).unwrap()).unwrap();
+    assert_eq!(channel, Some(CHANNEL.to_string()));
+}
+
+/// Sends messages from a DAO through a note, voice and proxy.
+/// cw-multi-test can not relay packets, so the note's entry points are
+/// called directly, and packets are relayed by executing them on the
+/// voice as the voice does when it receives one.
+#[test]
+fn test_remote_execution() {
+    let mut app = App::default();
+    let voice_id = app.store_code(dao_remote_voice_contract());
+    let proxy_id = app.store_code(dao_remote_proxy_contract());
+    let voice = app
+        .instantiate_contract(
+            voice_id,
+            Addr::unchecked("relayer"),
+            &dao_remote_voice::msg::InstantiateMsg {
+                proxy_code_id: proxy_id,
+            },
+            &[],
+            "voice",
+            None,
+        )
+        .unwrap();
+
+    let mut note = setup(Some(Pair {
+        connection_id: CONNECTION.to_string(),
+        remote_port: VOICE_PORT.to_string(),
+    }));
+    open_channel(&mut note, channel_to(CONNECTION, VOICE_PORT)).unwrap();
+
+    // Sends `msgs` through the note, relays the packet to the voice,
+    // and acknowledges it with the voice's acknowledgement.
+    let mut send_remote = |app: &mut App, msgs: Vec<CosmosMsg>| {
+        let packet = execute_remote(&mut note, DAO, msgs, None).unwrap();
+        let rx = dao_remote_voice::msg::ExecuteMsg::Rx {
+            connection_id: CONNECTION.to_string(),
+            counterparty_port: NOTE_PORT.to_string(),
+            packet: packet.clone(),
+        };
+        // The voice's reply writes the error of a failed execution to
+        // the acknowledgement.
+        let ack = match app.execute_contract(voice.clone(), voice.clone(), &rx, &[]) {
+            Ok(res) => res.data.unwrap(),
+            Err(err) => to_json_binary(&RemoteAck::Error {
+                error: err.root_cause().to_string(),
+            })
+            .unwrap(),
+        };
+        ibc_packet_ack(
+            note.as_mut(),
+            mock_env(),
+            mock_ibc_packet_ack(CHANNEL, &packet, IbcAcknowledgement::new(ack)).unwrap(),
+        )
+        .unwrap();
+        query_execution(&note, packet.id)
+    };
+
+    // The first packet instantiates the DAO's proxy, and its
+    // acknowledgement tells the note the proxy's address.
+    let execution = send_remote(&mut app, vec![]);
+    assert_eq!(execution.status, ExecutionStatus::Success);
+    let proxy: Option<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            &voice,
+            &dao_remote_voice::msg::QueryMsg::Proxy {
+                connection_id: CONNECTION.to_string(),
+                counterparty_port: NOTE_PORT.to_string(),
+                sender: DAO.to_string(),
+            },
+        )
+        .unwrap();
+    let proxy = proxy.unwrap();
+
+    // Later packets are executed by the same proxy.
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: proxy.to_string(),
+        amount: coins(100, "uatom"),
+    }))
+    .unwrap();
+    let execution = send_remote(&mut app, vec![send(60)]);
+    assert_eq!(execution.status, ExecutionStatus::Success);
+    assert_eq!(
+        app.wrap()
+            .query_balance("recipient", "uatom")
+            .unwrap()
+            .amount
+            .u128(),
+        60
+    );
+
+    // A failing message fails the whole packet, and the error is
+    // recorded against the execution.
+    let execution = send_remote(&mut app, vec![send(10), send(100)]);
+    assert!(matches!(execution.status, ExecutionStatus::Error { .. }));
+    assert_eq!(
+        app.wrap()
+            .query_balance("recipient", "uatom")
+            .unwrap()
+            .amount
+            .u128(),
+        60
+    );
+
+    let remote: Option<String> =
+        from_json(query(note.as_ref(), mock_env(), QueryMsg::RemoteAddress {}).unwrap()).unwrap();
+    assert_eq!(remote, Some(proxy.to_string()));
+}
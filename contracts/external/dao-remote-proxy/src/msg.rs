@@ -0,0 +1,19 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Callable by the owner. Executes `msgs` from the proxy.
+    Execute { msgs: Vec<CosmosMsg<Empty>> },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the contract that may execute messages from the proxy.
+    #[returns(cosmwasm_std::Addr)]
+    Owner {},
+}
@@ -0,0 +1,6 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+
+/// The contract that instantiated the proxy, and that may execute
+/// messages from it.
+pub const OWNER: Item<Addr> = Item::new("owner");
@@ -0,0 +1,166 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply,
+    Response, StdResult, SubMsg, SubMsgResult, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_utils::parse_reply_instantiate_data;
+use dao_interface::remote::{RemoteAck, RemotePacket};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{PendingProxy, PENDING_PROXY, PROXIES, PROXY_CODE_ID};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-remote-voice";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Reply to the voice executing a received packet.
+pub const RX_REPLY_ID: u64 = 0;
+/// Reply to instantiating a proxy.
+pub const PROXY_INSTANTIATE_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    PROXY_CODE_ID.save(deps.storage, &msg.proxy_code_id)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("creator", info.sender)
+        .add_attribute("proxy_code_id", msg.proxy_code_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Rx {
+            connection_id,
+            counterparty_port,
+            packet,
+        } => execute_rx(deps, env, info, connection_id, counterparty_port, packet),
+    }
+}
+
+/// Executes `msgs` with `proxy`, setting the acknowledgement for a
+/// successful execution as the response's data.
+fn execute_with_proxy(proxy: Addr, msgs: Vec<CosmosMsg<Empty>>) -> StdResult<Response> {
+    Ok(Response::new()
+        .add_attribute("proxy", proxy.as_str())
+        .add_message(WasmMsg::Execute {
+            contract_addr: proxy.to_string(),
+            msg: to_json_binary(&dao_remote_proxy::msg::ExecuteMsg::Execute { msgs })?,
+            funds: vec![],
+        })
+        .set_data(to_json_binary(&RemoteAck::Success {
+            proxy: proxy.into_string(),
+        })?))
+}
+
+pub fn execute_rx(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    connection_id: String,
+    counterparty_port: String,
+    packet: RemotePacket,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(proxy) = PROXIES.may_load(
+        deps.storage,
+        (&connection_id, &counterparty_port, &packet.sender),
+    )? {
+        return Ok(execute_with_proxy(proxy, packet.msgs)?.add_attribute("method", "rx"));
+    }
+
+    let instantiate = WasmMsg::Instantiate {
+        admin: Some(env.contract.address.to_string()),
+        code_id: PROXY_CODE_ID.load(deps.storage)?,
+        msg: to_json_binary(&dao_remote_proxy::msg::InstantiateMsg {})?,
+        funds: vec![],
+        label: format!(
+            "remote account of {} on {} over {}",
+            packet.sender, counterparty_port, connection_id
+        ),
+    };
+    PENDING_PROXY.save(
+        deps.storage,
+        &PendingProxy {
+            connection_id,
+            counterparty_port,
+            sender: packet.sender,
+            msgs: packet.msgs,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "rx")
+        .add_submessage(SubMsg::reply_on_success(
+            instantiate,
+            PROXY_INSTANTIATE_REPLY_ID,
+        )))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        RX_REPLY_ID => {
+            // The data of a successful execution is its
+            // acknowledgement. Replacing the data of the receive
+            // response replaces the acknowledgement written for the
+            // packet.
+            let ack = match msg.result {
+                SubMsgResult::Ok(res) => res.data.unwrap_or_default(),
+                SubMsgResult::Err(error) => to_json_binary(&RemoteAck::Error { error })?,
+            };
+            Ok(Response::new().set_data(ack))
+        }
+        PROXY_INSTANTIATE_REPLY_ID => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let proxy = deps.api.addr_validate(&res.contract_address)?;
+            let pending = PENDING_PROXY.load(deps.storage)?;
+            PENDING_PROXY.remove(deps.storage);
+            PROXIES.save(
+                deps.storage,
+                (
+                    &pending.connection_id,
+                    &pending.counterparty_port,
+                    &pending.sender,
+                ),
+                &proxy,
+            )?;
+
+            Ok(execute_with_proxy(proxy, pending.msgs)?
+                .add_attribute("method", "instantiate_proxy")
+                .add_attribute("sender", pending.sender))
+        }
+        _ => Err(ContractError::UnknownReplyID {}),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Proxy {
+            connection_id,
+            counterparty_port,
+            sender,
+        } => to_json_binary(
+            &PROXIES.may_load(deps.storage, (&connection_id, &counterparty_port, &sender))?,
+        ),
+        QueryMsg::ProxyCodeId {} => to_json_binary(&PROXY_CODE_ID.load(deps.storage)?),
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, DepsMut, Env, Ibc3ChannelOpenResponse, IbcBasicResponse, IbcChannel,
+    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcOrder,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, SubMsg, WasmMsg,
+};
+use dao_interface::remote::{RemoteAck, RemotePacket, REMOTE_VERSION};
+
+use crate::contract::RX_REPLY_ID;
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::CHANNEL_TO_CONNECTION;
+
+fn validate_channel(
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if channel.order != IbcOrder::Unordered {
+        return Err(ContractError::OrderedChannel {});
+    }
+    for version in [Some(channel.version.as_str()), counterparty_version]
+        .into_iter()
+        .flatten()
+    {
+        if version != REMOTE_VERSION {
+            return Err(ContractError::InvalidVersion {
+                expected: REMOTE_VERSION.to_string(),
+                actual: version.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    validate_channel(msg.channel(), msg.counterparty_version())?;
+    Ok(Some(Ibc3ChannelOpenResponse {
+        version: REMOTE_VERSION.to_string(),
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    validate_channel(msg.channel(), msg.counterparty_version())?;
+    CHANNEL_TO_CONNECTION.save(
+        deps.storage,
+        &msg.channel().endpoint.channel_id,
+        &msg.channel().connection_id,
+    )?;
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_channel_connect")
+        .add_attribute("channel", &msg.channel().endpoint.channel_id))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    // Proxies are kept. They are keyed by connection and the note's
+    // port rather than by channel, so a DAO regains control of its
+    // proxy over a new channel between the same note and voice.
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_channel_close")
+        .add_attribute("channel", &msg.channel().endpoint.channel_id))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let packet: RemotePacket = match from_json(&msg.packet.data) {
+        Ok(packet) => packet,
+        Err(err) => {
+            return Ok(IbcReceiveResponse::new()
+                .add_attribute("method", "ibc_packet_receive")
+                .set_ack(to_json_binary(&RemoteAck::Error {
+                    error: err.to_string(),
+                })?))
+        }
+    };
+
+    // Execute the packet in a submessage so that if it fails its
+    // error is written to the acknowledgement rather than failing
+    // the transaction that relays it.
+    let rx = WasmMsg::Execute {
+        contract_addr: env.contract.address.into_string(),
+        msg: to_json_binary(&ExecuteMsg::Rx {
+            connection_id: CHANNEL_TO_CONNECTION.load(deps.storage, &msg.packet.dest.channel_id)?,
+            counterparty_port: msg.packet.src.port_id,
+            packet,
+        })?,
+        funds: vec![],
+    };
+    Ok(IbcReceiveResponse::new()
+        .add_attribute("method", "ibc_packet_receive")
+        .add_submessage(SubMsg::reply_always(rx, RX_REPLY_ID)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    // The voice never sends packets.
+    Ok(IbcBasicResponse::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new())
+}
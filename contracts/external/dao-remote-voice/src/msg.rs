@@ -0,0 +1,41 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use dao_interface::remote::RemotePacket;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The code ID of `dao-remote-proxy`, instantiated for each DAO
+    /// that sends messages to the voice.
+    pub proxy_code_id: u64,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Callable only by the voice itself. Executes the messages in a
+    /// packet received over `connection_id` from the note bound to
+    /// `counterparty_port` with the sender's proxy, instantiating the
+    /// proxy if it does not exist yet. Packets are executed this way
+    /// so that if any message fails, the failure can be caught and
+    /// written to the acknowledgement.
+    Rx {
+        connection_id: String,
+        counterparty_port: String,
+        packet: RemotePacket,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the proxy of `sender`, a DAO sending packets over
+    /// `connection_id` from the note bound to `counterparty_port`, if
+    /// it has been instantiated.
+    #[returns(Option<cosmwasm_std::Addr>)]
+    Proxy {
+        connection_id: String,
+        counterparty_port: String,
+        sender: String,
+    },
+    /// Gets the code ID proxies are instantiated with.
+    #[returns(u64)]
+    ProxyCodeId {},
+}
@@ -0,0 +1,28 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CosmosMsg, Empty};
+use cw_storage_plus::{Item, Map};
+
+/// The code ID of `dao-remote-proxy`.
+pub const PROXY_CODE_ID: Item<u64> = Item::new("proxy_code_id");
+
+/// The connection each channel to the voice was opened over.
+pub const CHANNEL_TO_CONNECTION: Map<&str, String> = Map::new("channel_to_connection");
+
+/// The proxy of each DAO, keyed by the connection its packets arrive
+/// over, the port of the note that sent them, and the DAO's address on
+/// its own chain.
+pub const PROXIES: Map<(&str, &str, &str), Addr> = Map::new("proxies");
+
+/// Messages waiting to be executed by a proxy that is being
+/// instantiated.
+#[cw_serde]
+pub struct PendingProxy {
+    pub connection_id: String,
+    pub counterparty_port: String,
+    pub sender: String,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+}
+
+/// Set while a proxy is being instantiated. Removed once the proxy
+/// has been instantiated and its messages dispatched.
+pub const PENDING_PROXY: Item<PendingProxy> = Item::new("pending_proxy");
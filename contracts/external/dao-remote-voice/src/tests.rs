@@ -0,0 +1,272 @@
+use cosmwasm_std::{
+    from_json,
+    testing::{
+        mock_dependencies, mock_env, mock_ibc_channel, mock_ibc_channel_close_init,
+        mock_ibc_channel_open_init, mock_ibc_channel_open_try, mock_ibc_packet_recv,
+    },
+    to_json_binary, Addr, DepsMut, Ibc3ChannelOpenResponse, IbcChannelConnectMsg, IbcOrder, Reply,
+    SubMsg, SubMsgResult, WasmMsg,
+};
+use cw_multi_test::{App, Executor};
+use dao_interface::remote::{RemoteAck, RemotePacket, REMOTE_VERSION};
+use dao_testing::contracts::{dao_remote_proxy_contract, dao_remote_voice_contract};
+
+use crate::{
+    contract::{reply, RX_REPLY_ID},
+    ibc::{ibc_channel_close, ibc_channel_connect, ibc_channel_open, ibc_packet_receive},
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    ContractError,
+};
+
+const CHANNEL: &str = "channel-0";
+const CONNECTION: &str = "connection-0";
+const NOTE_PORT: &str = "wasm.note";
+
+#[test]
+fn test_channel_open() {
+    let mut deps = mock_dependencies();
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(CHANNEL, IbcOrder::Ordered, REMOTE_VERSION),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderedChannel {});
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_try(CHANNEL, IbcOrder::Unordered, "ics20-1"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidVersion {
+            expected: REMOTE_VERSION.to_string(),
+            actual: "ics20-1".to_string(),
+        }
+    );
+
+    let res = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_try(CHANNEL, IbcOrder::Unordered, REMOTE_VERSION),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Some(Ibc3ChannelOpenResponse {
+            version: REMOTE_VERSION.to_string()
+        })
+    );
+}
+
+#[test]
+fn test_packet_receive() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let packet = RemotePacket {
+        id: 1,
+        sender: "dao".to_string(),
+        msgs: vec![],
+    };
+    let connect = |deps: DepsMut, channel: &str| {
+        let mut channel = mock_ibc_channel(channel, IbcOrder::Unordered, REMOTE_VERSION);
+        channel.connection_id = CONNECTION.to_string();
+        ibc_channel_connect(
+            deps,
+            mock_env(),
+            IbcChannelConnectMsg::new_ack(channel, REMOTE_VERSION),
+        )
+        .unwrap();
+    };
+    let receive = |deps: DepsMut, channel: &str| {
+        let mut recv = mock_ibc_packet_recv(channel, &packet).unwrap();
+        recv.packet.src.port_id = NOTE_PORT.to_string();
+        ibc_packet_receive(deps, mock_env(), recv)
+    };
+    let rx = SubMsg::reply_always(
+        WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Rx {
+                connection_id: CONNECTION.to_string(),
+                counterparty_port: NOTE_PORT.to_string(),
+                packet: packet.clone(),
+            })
+            .unwrap(),
+            funds: vec![],
+        },
+        RX_REPLY_ID,
+    );
+
+    // Packets are executed by the voice in a submessage, along with
+    // the connection and note port they arrived from.
+    connect(deps.as_mut(), CHANNEL);
+    let res = receive(deps.as_mut(), CHANNEL).unwrap();
+    assert_eq!(res.messages, vec![rx.clone()]);
+
+    // A new channel between the same note and voice executes packets
+    // with the same proxy.
+    ibc_channel_close(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_close_init(CHANNEL, IbcOrder::Unordered, REMOTE_VERSION),
+    )
+    .unwrap();
+    connect(deps.as_mut(), "channel-1");
+    let res = receive(deps.as_mut(), "channel-1").unwrap();
+    assert_eq!(res.messages, vec![rx]);
+
+    // Packets that can not be parsed are acknowledged with an error.
+    let res = ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_packet_recv(CHANNEL, &"hello").unwrap(),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    let ack: RemoteAck = from_json(&res.acknowledgement).unwrap();
+    assert!(matches!(ack, RemoteAck::Error { .. }));
+
+    // If executing the packet fails, its error is acknowledged.
+    let res = reply(
+        deps.as_mut(),
+        env,
+        Reply {
+            id: RX_REPLY_ID,
+            result: SubMsgResult::Err("insufficient funds".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.data,
+        Some(
+            to_json_binary(&RemoteAck::Error {
+                error: "insufficient funds".to_string()
+            })
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn test_proxies() {
+    let mut app = App::default();
+    let proxy_id = app.store_code(dao_remote_proxy_contract());
+    let voice_id = app.store_code(dao_remote_voice_contract());
+    let voice = app
+        .instantiate_contract(
+            voice_id,
+            Addr::unchecked("relayer"),
+            &InstantiateMsg {
+                proxy_code_id: proxy_id,
+            },
+            &[],
+            "voice",
+            None,
+        )
+        .unwrap();
+    let rx = |connection_id: &str, port: &str, sender: &str| ExecuteMsg::Rx {
+        connection_id: connection_id.to_string(),
+        counterparty_port: port.to_string(),
+        packet: RemotePacket {
+            id: 1,
+            sender: sender.to_string(),
+            msgs: vec![],
+        },
+    };
+    let query_proxy = |app: &App, connection_id: &str, port: &str, sender: &str| -> Option<Addr> {
+        app.wrap()
+            .query_wasm_smart(
+                &voice,
+                &QueryMsg::Proxy {
+                    connection_id: connection_id.to_string(),
+                    counterparty_port: port.to_string(),
+                    sender: sender.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    // Only the voice may execute packets.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("dao"),
+            voice.clone(),
+            &rx(CONNECTION, NOTE_PORT, "dao"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    assert_eq!(query_proxy(&app, CONNECTION, NOTE_PORT, "dao"), None);
+    app.execute_contract(
+        voice.clone(),
+        voice.clone(),
+        &rx(CONNECTION, NOTE_PORT, "dao"),
+        &[],
+    )
+    .unwrap();
+    let proxy = query_proxy(&app, CONNECTION, NOTE_PORT, "dao").unwrap();
+    let owner: Addr = app
+        .wrap()
+        .query_wasm_smart(&proxy, &dao_remote_proxy::msg::QueryMsg::Owner {})
+        .unwrap();
+    assert_eq!(owner, voice);
+
+    // The same sender keeps its proxy.
+    let res = app
+        .execute_contract(
+            voice.clone(),
+            voice.clone(),
+            &rx(CONNECTION, NOTE_PORT, "dao"),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.data,
+        Some(
+            to_json_binary(&RemoteAck::Success {
+                proxy: proxy.to_string()
+            })
+            .unwrap()
+        )
+    );
+    assert_eq!(
+        query_proxy(&app, CONNECTION, NOTE_PORT, "dao"),
+        Some(proxy.clone())
+    );
+
+    // Other senders, and the same sender over another connection or
+    // from another note, get their own proxies.
+    for (connection_id, port, sender) in [
+        (CONNECTION, NOTE_PORT, "other"),
+        ("connection-1", NOTE_PORT, "dao"),
+        (CONNECTION, "wasm.other_note", "dao"),
+    ] {
+        app.execute_contract(
+            voice.clone(),
+            voice.clone(),
+            &rx(connection_id, port, sender),
+            &[],
+        )
+        .unwrap();
+        let other = query_proxy(&app, connection_id, port, sender).unwrap();
+        assert_ne!(other, proxy);
+    }
+
+    // Only the voice may execute messages from a proxy.
+    let err: dao_remote_proxy::ContractError = app
+        .execute_contract(
+            Addr::unchecked("dao"),
+            proxy,
+            &dao_remote_proxy::msg::ExecuteMsg::Execute { msgs: vec![] },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, dao_remote_proxy::ContractError::Unauthorized {});
+}
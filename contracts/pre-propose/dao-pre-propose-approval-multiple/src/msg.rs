@@ -1,10 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Empty;
+use cw_utils::Duration;
 use dao_pre_propose_base::msg::{
     ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, MigrateMsg as MigrateBase,
     QueryMsg as QueryBase,
 };
 use dao_voting::{
+    approval::ApproverSet,
     multiple_choice::{MultipleChoiceAutoVote, MultipleChoiceOptions},
     proposal::MultipleChoiceProposeMsg as ProposeMsg,
 };
@@ -28,6 +30,12 @@ pub enum ProposeMessage {
 #[cw_serde]
 pub struct InstantiateExt {
     pub approver: String,
+    /// An optional committee that may approve or reject proposals
+    /// together, in addition to the approver.
+    pub approver_set: Option<ApproverSet>,
+    /// How long proposals may remain pending before they expire. If
+    /// unset, proposals never expire.
+    pub pending_duration: Option<Duration>,
 }
 
 #[cw_serde]
@@ -36,6 +44,12 @@ pub enum QueryExt {
     /// List the approver address
     #[returns(cosmwasm_std::Addr)]
     Approver {},
+    /// The committee that may approve or reject new proposals, if any
+    #[returns(::std::option::Option<dao_voting::approval::CheckedApproverSet>)]
+    ApproverSet {},
+    /// How long new proposals may remain pending, if they expire
+    #[returns(::std::option::Option<cw_utils::Duration>)]
+    PendingDuration {},
     /// Return whether or not the proposal is pending
     #[returns(bool)]
     IsPending { id: u64 },
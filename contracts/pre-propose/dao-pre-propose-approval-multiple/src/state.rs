@@ -1,11 +1,19 @@
 use cosmwasm_std::{Addr, StdResult, Storage};
 use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
 
-use dao_voting::{approval::ApprovalProposal, proposal::MultipleChoiceProposeMsg};
+use dao_voting::{
+    approval::{ApprovalProposal, CheckedApproverSet},
+    proposal::MultipleChoiceProposeMsg,
+};
 
 pub type Proposal = ApprovalProposal<MultipleChoiceProposeMsg>;
 
 pub const APPROVER: Item<Addr> = Item::new("approver");
+/// A committee that may approve or reject new proposals together.
+pub const APPROVER_SET: Item<CheckedApproverSet> = Item::new("approver_set");
+/// How long new proposals may remain pending before they expire.
+pub const PENDING_DURATION: Item<Duration> = Item::new("pending_duration");
 pub const PENDING_PROPOSALS: Map<u64, Proposal> = Map::new("pending_proposals");
 pub const COMPLETED_PROPOSALS: Map<u64, Proposal> = Map::new("completed_proposals");
 pub const CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL: Map<u64, u64> =
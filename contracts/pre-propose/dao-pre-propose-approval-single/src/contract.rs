@@ -9,12 +9,18 @@ use cw2::set_contract_version;
 use cw_denom::CheckedDenom;
 use cw_paginate_storage::paginate_map_values;
 use cw_storage_plus::Map;
+use cw_utils::{Duration, Expiration};
 use dao_pre_propose_base::{
-    error::PreProposeError, msg::ExecuteMsg as ExecuteBase, state::PreProposeContract,
+    error::PreProposeError,
+    msg::ExecuteMsg as ExecuteBase,
+    state::{OpenProposal, PreProposeContract},
+};
+use dao_voting::approval::{
+    ApprovalError, ApprovalProposalStatus, ApproverProposeMessage, ApproverSet,
 };
-use dao_voting::approval::{ApprovalProposalStatus, ApproverProposeMessage};
 use dao_voting::deposit::{CheckedDepositInfo, DepositRefundPolicy};
 use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+use dao_voting::status::Status;
 use dao_voting::voting::{SingleChoiceAutoVote, Vote};
 
 use crate::msg::{
@@ -22,8 +28,8 @@ use crate::msg::{
     ProposeMessageInternal, QueryExt, QueryMsg,
 };
 use crate::state::{
-    advance_approval_id, Proposal, APPROVER, COMPLETED_PROPOSALS,
-    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL, PENDING_PROPOSALS,
+    advance_approval_id, Proposal, APPROVER, APPROVER_SET, COMPLETED_PROPOSALS,
+    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL, PENDING_DURATION, PENDING_PROPOSALS,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-approval-single";
@@ -40,6 +46,12 @@ pub fn instantiate(
 ) -> Result<Response, PreProposeError> {
     let approver = deps.api.addr_validate(&msg.extension.approver)?;
     APPROVER.save(deps.storage, &approver)?;
+    if let Some(approver_set) = msg.extension.approver_set.clone() {
+        APPROVER_SET.save(deps.storage, &approver_set.into_checked(deps.api)?)?;
+    }
+    if let Some(pending_duration) = msg.extension.pending_duration {
+        PENDING_DURATION.save(deps.storage, &pending_duration)?;
+    }
 
     let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -64,9 +76,14 @@ pub fn execute(
         }
 
         ExecuteMsg::Extension { msg } => match msg {
-            ExecuteExt::Approve { id } => execute_approve(deps, info, id),
-            ExecuteExt::Reject { id } => execute_reject(deps, info, id),
+            ExecuteExt::Approve { id } => execute_approve(deps, env, info, id),
+            ExecuteExt::Reject { id } => execute_reject(deps, env, info, id),
             ExecuteExt::UpdateApprover { address } => execute_update_approver(deps, info, address),
+            ExecuteExt::UpdateApprovalConfig {
+                approver_set,
+                pending_duration,
+            } => execute_update_approval_config(deps, info, approver_set, pending_duration),
+            ExecuteExt::Expire { id } => execute_expire(deps, env, id),
         },
         // Default pre-propose-base behavior for all other messages
         _ => PrePropose::default().execute(deps, env, info, msg),
@@ -80,12 +97,12 @@ pub fn execute_propose(
     msg: ProposeMessage,
 ) -> Result<Response, PreProposeError> {
     let pre_propose_base = PrePropose::default();
-    let config = pre_propose_base.config.load(deps.storage)?;
 
-    pre_propose_base.check_can_submit(deps.as_ref(), info.sender.clone())?;
+    pre_propose_base.check_can_submit(deps.as_ref(), &env.block, info.sender.clone())?;
 
     // Take deposit, if configured.
-    let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
+    let deposit_info = pre_propose_base.resolve_deposit(deps.as_ref(), &info.sender)?;
+    let deposit_messages = if let Some(ref deposit_info) = deposit_info {
         deposit_info.check_native_deposit_paid(&info)?;
         deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
     } else {
@@ -93,6 +110,12 @@ pub fn execute_propose(
     };
 
     let approval_id = advance_approval_id(deps.storage)?;
+    pre_propose_base.add_open_proposal(
+        deps.storage,
+        &env.block,
+        &info.sender,
+        OpenProposal::Pending(approval_id),
+    )?;
 
     let propose_msg_internal = match msg {
         ProposeMessage::Propose {
@@ -106,6 +129,7 @@ pub fn execute_propose(
             msgs,
             proposer: Some(info.sender.to_string()),
             vote,
+            execution_plan: None,
         },
     };
 
@@ -141,7 +165,12 @@ pub fn execute_propose(
             approver: approver.clone(),
             proposer: info.sender,
             msg: propose_msg_internal,
-            deposit: config.deposit_info,
+            deposit: deposit_info,
+            approver_set: APPROVER_SET.may_load(deps.storage)?,
+            decisions: vec![],
+            expiration: PENDING_DURATION
+                .may_load(deps.storage)?
+                .map_or(Expiration::Never {}, |duration| duration.after(&env.block)),
         },
     )?;
 
@@ -155,122 +184,173 @@ pub fn execute_propose(
 
 pub fn execute_approve(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     id: u64,
 ) -> Result<Response, PreProposeError> {
-    // Load proposal and send propose message to the proposal module
-    let proposal = PENDING_PROPOSALS.may_load(deps.storage, id)?;
-    match proposal {
-        Some(proposal) => {
-            // Check sender is the approver
-            if proposal.approver != info.sender {
-                return Err(PreProposeError::Unauthorized {});
-            }
+    let mut proposal = PENDING_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
 
-            let proposal_module = PrePropose::default().proposal_module.load(deps.storage)?;
-
-            // Snapshot the deposit for the proposal that we're about
-            // to create.
-            let proposal_id = deps.querier.query_wasm_smart(
-                &proposal_module,
-                &dao_interface::proposal::Query::NextProposalId {},
-            )?;
-            PrePropose::default().deposits.save(
-                deps.storage,
-                proposal_id,
-                &(proposal.deposit.clone(), proposal.proposer.clone()),
-            )?;
-
-            let propose_messsage = WasmMsg::Execute {
-                contract_addr: proposal_module.into_string(),
-                msg: to_json_binary(&ProposeMessageInternal::Propose(proposal.msg.clone()))?,
-                funds: vec![],
-            };
-
-            COMPLETED_PROPOSALS.save(
-                deps.storage,
-                id,
-                &Proposal {
-                    status: ApprovalProposalStatus::Approved {
-                        created_proposal_id: proposal_id,
-                    },
-                    approval_id: proposal.approval_id,
-                    approver: proposal.approver,
-                    proposer: proposal.proposer,
-                    msg: proposal.msg,
-                    deposit: proposal.deposit,
-                },
-            )?;
-            CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL.save(deps.storage, proposal_id, &id)?;
-            PENDING_PROPOSALS.remove(deps.storage, id);
-
-            Ok(Response::default()
-                .add_message(propose_messsage)
-                .add_attribute("method", "proposal_approved")
-                .add_attribute("approval_id", id.to_string())
-                .add_attribute("proposal_id", proposal_id.to_string()))
-        }
-        None => Err(PreProposeError::ProposalNotFound {}),
+    // Check sender is the approver or a member of the approver set
+    if !proposal.is_approver(&info.sender) {
+        return Err(PreProposeError::Unauthorized {});
+    }
+    if proposal.expiration.is_expired(&env.block) {
+        return Err(ApprovalError::Expired {}.into());
     }
+
+    // Wait for more members of the approver set to approve.
+    if !proposal.decide(&info.sender, true)? {
+        PENDING_PROPOSALS.save(deps.storage, id, &proposal)?;
+        return Ok(Response::default()
+            .add_attribute("method", "approval_recorded")
+            .add_attribute("approval_id", id.to_string())
+            .add_attribute("approver", info.sender));
+    }
+
+    // Send propose message to the proposal module
+    let proposal_module = PrePropose::default().proposal_module.load(deps.storage)?;
+
+    // Snapshot the deposit for the proposal that we're about
+    // to create.
+    let proposal_id = deps.querier.query_wasm_smart(
+        &proposal_module,
+        &dao_interface::proposal::Query::NextProposalId {},
+    )?;
+    PrePropose::default().deposits.save(
+        deps.storage,
+        proposal_id,
+        &(proposal.deposit.clone(), proposal.proposer.clone()),
+    )?;
+
+    let propose_messsage = WasmMsg::Execute {
+        contract_addr: proposal_module.into_string(),
+        msg: to_json_binary(&ProposeMessageInternal::Propose(proposal.msg.clone()))?,
+        funds: vec![],
+    };
+
+    COMPLETED_PROPOSALS.save(
+        deps.storage,
+        id,
+        &Proposal {
+            status: ApprovalProposalStatus::Approved {
+                created_proposal_id: proposal_id,
+            },
+            ..proposal
+        },
+    )?;
+    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL.save(deps.storage, proposal_id, &id)?;
+    PrePropose::default().create_pending_proposal(deps.storage, id, proposal_id)?;
+    PENDING_PROPOSALS.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_message(propose_messsage)
+        .add_attribute("method", "proposal_approved")
+        .add_attribute("approval_id", id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
 pub fn execute_reject(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     id: u64,
 ) -> Result<Response, PreProposeError> {
-    let Proposal {
-        approval_id,
-        approver,
-        proposer,
-        msg,
-        deposit,
-        ..
-    } = PENDING_PROPOSALS
+    let mut proposal = PENDING_PROPOSALS
         .may_load(deps.storage, id)?
         .ok_or(PreProposeError::ProposalNotFound {})?;
 
-    // Check sender is the approver
-    if approver != info.sender {
+    // Check sender is the approver or a member of the approver set
+    if !proposal.is_approver(&info.sender) {
         return Err(PreProposeError::Unauthorized {});
     }
+    if proposal.expiration.is_expired(&env.block) {
+        return Err(ApprovalError::Expired {}.into());
+    }
 
-    COMPLETED_PROPOSALS.save(
+    // Wait for more members of the approver set to reject.
+    if !proposal.decide(&info.sender, false)? {
+        PENDING_PROPOSALS.save(deps.storage, id, &proposal)?;
+        return Ok(Response::default()
+            .add_attribute("method", "rejection_recorded")
+            .add_attribute("approval_id", id.to_string())
+            .add_attribute("approver", info.sender));
+    }
+
+    proposal.status = ApprovalProposalStatus::Rejected {};
+    COMPLETED_PROPOSALS.save(deps.storage, id, &proposal)?;
+    PENDING_PROPOSALS.remove(deps.storage, id);
+    PrePropose::default().remove_open_proposal(
         deps.storage,
-        id,
-        &Proposal {
-            status: ApprovalProposalStatus::Rejected {},
-            approval_id,
-            approver,
-            proposer: proposer.clone(),
-            msg: msg.clone(),
-            deposit: deposit.clone(),
-        },
+        &proposal.proposer,
+        OpenProposal::Pending(id),
     )?;
-    PENDING_PROPOSALS.remove(deps.storage, id);
 
-    let messages = if let Some(ref deposit_info) = deposit {
-        // Refund can be issued if proposal if deposits are always
-        // refunded. `OnlyPassed` and `Never` refund deposit policies
-        // do not apply here.
-        if deposit_info.refund_policy == DepositRefundPolicy::Always {
-            deposit_info.get_return_deposit_message(&proposer)?
-        } else {
-            // If the proposer doesn't get the deposit, the DAO does.
-            let dao = PrePropose::default().dao.load(deps.storage)?;
-            deposit_info.get_return_deposit_message(&dao)?
-        }
-    } else {
-        vec![]
-    };
+    let messages = get_unapproved_deposit_messages(deps.as_ref(), &proposal)?;
 
     Ok(Response::default()
         .add_attribute("method", "proposal_rejected")
         .add_attribute("proposal", id.to_string())
-        .add_attribute("deposit_info", to_json_binary(&deposit)?.to_string())
+        .add_attribute(
+            "deposit_info",
+            to_json_binary(&proposal.deposit)?.to_string(),
+        )
+        .add_messages(messages))
+}
+
+/// Completes a pending proposal that was neither approved nor
+/// rejected before it expired.
+pub fn execute_expire(deps: DepsMut, env: Env, id: u64) -> Result<Response, PreProposeError> {
+    let mut proposal = PENDING_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if !proposal.expiration.is_expired(&env.block) {
+        return Err(ApprovalError::NotExpired {}.into());
+    }
+
+    proposal.status = ApprovalProposalStatus::Expired {};
+    COMPLETED_PROPOSALS.save(deps.storage, id, &proposal)?;
+    PENDING_PROPOSALS.remove(deps.storage, id);
+    PrePropose::default().remove_open_proposal(
+        deps.storage,
+        &proposal.proposer,
+        OpenProposal::Pending(id),
+    )?;
+
+    let messages = get_unapproved_deposit_messages(deps.as_ref(), &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "proposal_expired")
+        .add_attribute("proposal", id.to_string())
+        .add_attribute(
+            "deposit_info",
+            to_json_binary(&proposal.deposit)?.to_string(),
+        )
         .add_messages(messages))
 }
 
+/// Returns the deposit of a proposal that was rejected or expired.
+fn get_unapproved_deposit_messages(
+    deps: Deps,
+    proposal: &Proposal,
+) -> Result<Vec<CosmosMsg>, PreProposeError> {
+    Ok(if let Some(ref deposit_info) = proposal.deposit {
+        // Proposals that were never approved are settled like
+        // proposals that were rejected by a vote, so they are only
+        // refunded in full if deposits are always refunded.
+        let dao = PrePropose::default().dao.load(deps.storage)?;
+        deposit_info.get_completed_deposit_messages(
+            &proposal.proposer,
+            &dao,
+            Status::Closed,
+            false,
+        )?
+    } else {
+        vec![]
+    })
+}
+
 pub fn execute_update_approver(
     deps: DepsMut,
     info: MessageInfo,
@@ -289,6 +369,34 @@ pub fn execute_update_approver(
     Ok(Response::default())
 }
 
+pub fn execute_update_approval_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    approver_set: Option<ApproverSet>,
+    pending_duration: Option<Duration>,
+) -> Result<Response, PreProposeError> {
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    let approver = APPROVER.load(deps.storage)?;
+
+    // Check sender is the approver or the parent DAO
+    if approver != info.sender && dao != info.sender {
+        return Err(PreProposeError::Unauthorized {});
+    }
+
+    match approver_set {
+        Some(approver_set) => {
+            APPROVER_SET.save(deps.storage, &approver_set.into_checked(deps.api)?)?
+        }
+        None => APPROVER_SET.remove(deps.storage),
+    }
+    match pending_duration {
+        Some(pending_duration) => PENDING_DURATION.save(deps.storage, &pending_duration)?,
+        None => PENDING_DURATION.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "update_approval_config"))
+}
+
 pub fn execute_add_approver_hook(
     deps: DepsMut,
     info: MessageInfo,
@@ -343,6 +451,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::QueryExtension { msg } => match msg {
             QueryExt::Approver {} => to_json_binary(&APPROVER.load(deps.storage)?),
+            QueryExt::ApproverSet {} => to_json_binary(&APPROVER_SET.may_load(deps.storage)?),
+            QueryExt::PendingDuration {} => {
+                to_json_binary(&PENDING_DURATION.may_load(deps.storage)?)
+            }
             QueryExt::IsPending { id } => {
                 let pending = PENDING_PROPOSALS.may_load(deps.storage, id)?.is_some();
                 // Force load completed proposal if not pending, throwing error
@@ -561,6 +673,7 @@ pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response
                                 },
                                 rationale: vote.rationale,
                             }),
+                            execution_plan: None,
                         },
                         deposit: proposal.deposit.map(|deposit| CheckedDepositInfo {
                             denom: match deposit.denom {
@@ -575,7 +688,11 @@ pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response
                                 }
                                 DepositRefundPolicyV241::Never => DepositRefundPolicy::Never,
                             },
+                            scaling: None,
                         }),
+                        approver_set: None,
+                        decisions: vec![],
+                        expiration: Expiration::Never {},
                     },
                 )?;
             }
@@ -619,6 +736,7 @@ pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response
                                 },
                                 rationale: vote.rationale,
                             }),
+                            execution_plan: None,
                         },
                         deposit: proposal.deposit.map(|deposit| CheckedDepositInfo {
                             denom: match deposit.denom {
@@ -633,7 +751,11 @@ pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response
                                 }
                                 DepositRefundPolicyV241::Never => DepositRefundPolicy::Never,
                             },
+                            scaling: None,
                         }),
+                        approver_set: None,
+                        decisions: vec![],
+                        expiration: Expiration::Never {},
                     },
                 )?;
             }
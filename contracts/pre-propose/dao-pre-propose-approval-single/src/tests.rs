@@ -22,7 +22,7 @@ use dao_testing::{
 };
 use dao_voting::pre_propose::{PreProposeSubmissionPolicy, PreProposeSubmissionPolicyError};
 use dao_voting::{
-    approval::ApprovalProposalStatus,
+    approval::{ApprovalError, ApprovalProposalStatus, ApproverSet, CheckedApproverSet},
     deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     status::Status,
@@ -73,6 +73,8 @@ fn get_default_proposal_module_instantiate(
                     submission_policy,
                     extension: InstantiateExt {
                         approver: "approver".to_string(),
+                        approver_set: None,
+                        pending_duration: None,
                     },
                 })
                 .unwrap(),
@@ -483,6 +485,7 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -557,6 +560,7 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -810,6 +814,7 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -897,6 +902,7 @@ fn test_pending_proposal_queries() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -980,6 +986,7 @@ fn test_completed_proposal_queries() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -1122,6 +1129,7 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -1158,6 +1166,7 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false, // no open proposal submission.
     );
@@ -1169,6 +1178,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                failed_quorum: false,
             },
             &[],
         )
@@ -1217,6 +1227,7 @@ fn test_approval_and_rejection_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -1349,6 +1360,238 @@ fn test_approval_and_rejection_permissions() {
     .unwrap();
 }
 
+fn get_proposal(app: &App, module: Addr, id: u64) -> Proposal {
+    app.wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Proposal { id },
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_approver_set() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        pre_propose,
+        ..
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
+        }),
+        true,
+    );
+
+    let update = |threshold: u32| ExecuteMsg::Extension {
+        msg: ExecuteExt::UpdateApprovalConfig {
+            approver_set: Some(ApproverSet {
+                members: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                threshold,
+            }),
+            pending_duration: None,
+        },
+    };
+
+    // Only the approver or the DAO may update the approver set.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("nonapprover"),
+            pre_propose.clone(),
+            &update(2),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("approver"),
+            pre_propose.clone(),
+            &update(4),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::Approval(ApprovalError::InvalidThreshold {})
+    );
+
+    app.execute_contract(core_addr, pre_propose.clone(), &update(2), &[])
+        .unwrap();
+    let approver_set: Option<CheckedApproverSet> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::ApproverSet {},
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        approver_set,
+        Some(CheckedApproverSet {
+            members: vec![
+                Addr::unchecked("a"),
+                Addr::unchecked("b"),
+                Addr::unchecked("c")
+            ],
+            threshold: 2,
+        })
+    );
+
+    mint_natives(&mut app, "ekez", coins(20, "ujuno"));
+    let approved_id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    let rejected_id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+
+    // One approval of two is recorded but does not approve.
+    app.execute_contract(
+        Addr::unchecked("a"),
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::Approve { id: approved_id },
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal = get_proposal(&app, pre_propose.clone(), approved_id);
+    assert_eq!(proposal.status, ApprovalProposalStatus::Pending {});
+    assert_eq!(proposal.decisions.len(), 1);
+
+    // Members may only decide once.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("a"),
+            pre_propose.clone(),
+            &ExecuteMsg::Extension {
+                msg: ExecuteExt::Reject { id: approved_id },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::Approval(ApprovalError::AlreadyDecided {
+            approver: Addr::unchecked("a")
+        })
+    );
+
+    let created_id = approve_proposal(&mut app, pre_propose.clone(), "b", approved_id);
+    assert_eq!(
+        get_proposal(&app, pre_propose.clone(), approved_id).status,
+        ApprovalProposalStatus::Approved {
+            created_proposal_id: created_id
+        }
+    );
+
+    // Two rejections of three make approval impossible.
+    reject_proposal(&mut app, pre_propose.clone(), "a", rejected_id);
+    assert_eq!(
+        get_proposal(&app, pre_propose.clone(), rejected_id).status,
+        ApprovalProposalStatus::Pending {}
+    );
+    reject_proposal(&mut app, pre_propose.clone(), "c", rejected_id);
+    assert_eq!(
+        get_proposal(&app, pre_propose, rejected_id).status,
+        ApprovalProposalStatus::Rejected {}
+    );
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+}
+
+#[test]
+fn test_pending_proposal_expiration() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        pre_propose,
+        ..
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            scaling: None,
+        }),
+        true,
+    );
+
+    app.execute_contract(
+        Addr::unchecked("approver"),
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::UpdateApprovalConfig {
+                approver_set: None,
+                pending_duration: Some(Duration::Height(10)),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    let expire = ExecuteMsg::Extension {
+        msg: ExecuteExt::Expire { id },
+    };
+
+    let err: PreProposeError = app
+        .execute_contract(Addr::unchecked("keze"), pre_propose.clone(), &expire, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::Approval(ApprovalError::NotExpired {}));
+
+    app.update_block(|block| block.height += 10);
+
+    // Expired proposals may not be approved.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("approver"),
+            pre_propose.clone(),
+            &ExecuteMsg::Extension {
+                msg: ExecuteExt::Approve { id },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::Approval(ApprovalError::Expired {}));
+
+    // Anyone may expire them.
+    app.execute_contract(Addr::unchecked("keze"), pre_propose.clone(), &expire, &[])
+        .unwrap();
+    assert_eq!(
+        get_proposal(&app, pre_propose, id).status,
+        ApprovalProposalStatus::Expired {}
+    );
+
+    // Deposits are only refunded for passed proposals, so the DAO
+    // keeps it.
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::zero());
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(10)
+    );
+}
+
 #[test]
 fn test_propose_open_proposal_submission() {
     let mut app = App::default();
@@ -1364,6 +1607,7 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -1684,6 +1928,7 @@ fn test_instantiate_with_zero_native_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            scaling: None,
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
@@ -1692,6 +1937,8 @@ fn test_instantiate_with_zero_native_deposit() {
                         },
                         extension: InstantiateExt {
                             approver: "approver".to_string(),
+                            approver_set: None,
+                            pending_duration: None,
                         },
                     })
                     .unwrap(),
@@ -1753,6 +2000,7 @@ fn test_instantiate_with_zero_cw20_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            scaling: None,
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
@@ -1761,6 +2009,8 @@ fn test_instantiate_with_zero_cw20_deposit() {
                         },
                         extension: InstantiateExt {
                             approver: "approver".to_string(),
+                            approver_set: None,
+                            pending_duration: None,
                         },
                     })
                     .unwrap(),
@@ -1829,6 +2079,7 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
     );
@@ -1840,7 +2091,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
         }
@@ -1876,7 +2128,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             proposer: Addr::unchecked("ekez"),
         }
@@ -2431,6 +2684,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2482,6 +2736,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -96,6 +96,7 @@ pub fn execute(
         ExecuteMsg::ProposalCompletedHook {
             proposal_id,
             new_status,
+            ..
         } => execute_proposal_completed(deps, info, proposal_id, new_status),
         ExecuteMsg::Extension { msg } => match msg {
             ExecuteExt::ResetApprover {} => execute_reset_approver(deps, env, info),
@@ -103,6 +104,7 @@ pub fn execute(
         // Override config updates since they don't apply.
         ExecuteMsg::UpdateConfig { .. } => Err(PreProposeError::Unsupported {}),
         ExecuteMsg::UpdateSubmissionPolicy { .. } => Err(PreProposeError::Unsupported {}),
+        ExecuteMsg::UpdateRateLimits { .. } => Err(PreProposeError::Unsupported {}),
         _ => PrePropose::default().execute(deps, env, info, msg),
     }
 }
@@ -179,7 +181,8 @@ pub fn execute_proposal_completed(
             })?,
             funds: vec![],
         }),
-        Status::Executed => Some(WasmMsg::Execute {
+        // A proposal whose execution failed was still approved.
+        Status::Executed | Status::ExecutionFailed => Some(WasmMsg::Execute {
             contract_addr: approval_contract.into_string(),
             msg: to_json_binary(&PreProposeApprovalExecuteMsg::Extension {
                 msg: ApprovalExecuteExt::Approve { id: pre_propose_id },
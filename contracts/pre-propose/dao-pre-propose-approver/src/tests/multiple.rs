@@ -123,6 +123,8 @@ fn get_proposal_module_approval_multiple_instantiate(
                     submission_policy,
                     extension: InstantiateExt {
                         approver: APPROVER.to_string(),
+                        approver_set: None,
+                        pending_duration: None,
                     },
                 })
                 .unwrap(),
@@ -689,6 +691,7 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -798,6 +801,7 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -1091,6 +1095,7 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -1176,6 +1181,7 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -1218,6 +1224,7 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false, // no open proposal submission.
     );
@@ -1229,6 +1236,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                failed_quorum: false,
             },
             &[],
         )
@@ -1297,6 +1305,7 @@ fn test_approval_and_rejection_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -1363,6 +1372,7 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -1458,6 +1468,7 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
     );
@@ -1469,7 +1480,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
         }
@@ -1507,7 +1519,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             proposer: Addr::unchecked("ekez"),
         }
@@ -1704,6 +1717,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -1757,6 +1771,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -1841,6 +1856,7 @@ fn test_reset_approver() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -73,6 +73,8 @@ fn get_proposal_module_approval_single_instantiate(
                     submission_policy,
                     extension: InstantiateExt {
                         approver: APPROVER.to_string(),
+                        approver_set: None,
+                        pending_duration: None,
                     },
                 })
                 .unwrap(),
@@ -587,6 +589,7 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -695,6 +698,7 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -987,6 +991,7 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -1084,6 +1089,7 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -1126,6 +1132,7 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false, // no open proposal submission.
     );
@@ -1137,6 +1144,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                failed_quorum: false,
             },
             &[],
         )
@@ -1192,6 +1200,7 @@ fn test_approval_and_rejection_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -1258,6 +1267,7 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -1353,6 +1363,7 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
     );
@@ -1364,7 +1375,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
         }
@@ -1402,7 +1414,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             proposer: Addr::unchecked("ekez"),
         }
@@ -1599,6 +1612,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -1652,6 +1666,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -1748,6 +1763,7 @@ fn test_reset_approver() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -89,6 +89,9 @@ pub fn execute(
         },
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
         ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
+        ExecuteMsg::UpdateRateLimits { rate_limits } => {
+            ExecuteInternal::UpdateRateLimits { rate_limits }
+        }
         ExecuteMsg::UpdateConfig {
             deposit_info,
             submission_policy,
@@ -118,9 +121,11 @@ pub fn execute(
         ExecuteBase::ProposalCompletedHook {
             proposal_id,
             new_status,
+            failed_quorum,
         } => ExecuteInternal::ProposalCompletedHook {
             proposal_id,
             new_status,
+            failed_quorum,
         },
     };
 
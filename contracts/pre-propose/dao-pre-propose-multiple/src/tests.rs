@@ -502,6 +502,7 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -574,6 +575,7 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -750,6 +752,7 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -832,6 +835,7 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -868,6 +872,7 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false, // no open proposal submission.
     );
@@ -879,6 +884,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                failed_quorum: false,
             },
             &[],
         )
@@ -933,6 +939,7 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -1352,6 +1359,7 @@ fn test_instantiate_with_zero_native_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            scaling: None,
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
@@ -1419,6 +1427,7 @@ fn test_instantiate_with_zero_cw20_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            scaling: None,
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
@@ -1496,6 +1505,7 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
     );
@@ -1507,7 +1517,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
         }
@@ -1539,7 +1550,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             proposer: Addr::unchecked("ekez"),
         }
@@ -2106,6 +2118,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2154,6 +2167,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
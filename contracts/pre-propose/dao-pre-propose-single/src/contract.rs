@@ -14,7 +14,10 @@ use dao_pre_propose_base::{
     },
     state::PreProposeContract,
 };
-use dao_voting::{proposal::SingleChoiceProposeMsg as ProposeMsg, voting::SingleChoiceAutoVote};
+use dao_voting::{
+    execution_plan::ExecutionBatch, proposal::SingleChoiceProposeMsg as ProposeMsg,
+    voting::SingleChoiceAutoVote,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -30,6 +33,8 @@ pub enum ProposeMessage {
         description: String,
         msgs: Vec<CosmosMsg<Empty>>,
         vote: Option<SingleChoiceAutoVote>,
+        #[serde(default)]
+        execution_plan: Option<Vec<ExecutionBatch>>,
     },
 }
 
@@ -80,6 +85,7 @@ pub fn execute(
                     description,
                     msgs,
                     vote,
+                    execution_plan,
                 },
         } => ExecuteInternal::Propose {
             msg: ProposeMessageInternal::Propose(ProposeMsg {
@@ -89,10 +95,14 @@ pub fn execute(
                 description,
                 msgs,
                 vote,
+                execution_plan,
             }),
         },
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
         ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
+        ExecuteMsg::UpdateRateLimits { rate_limits } => {
+            ExecuteInternal::UpdateRateLimits { rate_limits }
+        }
         ExecuteMsg::UpdateConfig {
             deposit_info,
             submission_policy,
@@ -122,9 +132,11 @@ pub fn execute(
         ExecuteMsg::ProposalCompletedHook {
             proposal_id,
             new_status,
+            failed_quorum,
         } => ExecuteInternal::ProposalCompletedHook {
             proposal_id,
             new_status,
+            failed_quorum,
         },
     };
 
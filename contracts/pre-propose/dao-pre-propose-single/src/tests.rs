@@ -1,5 +1,5 @@
 use cosmwasm_std::{
-    coins, from_json, to_json_binary, Addr, Coin, CosmosMsg, Empty, Uint128, WasmMsg,
+    coins, from_json, to_json_binary, Addr, Coin, CosmosMsg, Decimal, Empty, Uint128, WasmMsg,
 };
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
@@ -24,7 +24,10 @@ use dao_testing::{
 };
 use dao_voting::pre_propose::{PreProposeSubmissionPolicy, PreProposeSubmissionPolicyError};
 use dao_voting::{
-    deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    deposit::{
+        CheckedDepositInfo, DepositRefundPolicy, DepositScaling, DepositToken, SlashDestination,
+        UncheckedDepositInfo,
+    },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     status::Status,
     threshold::{PercentageThreshold, Threshold},
@@ -204,6 +207,7 @@ fn make_proposal(
                 description: "description".to_string(),
                 msgs: vec![],
                 vote: None,
+                execution_plan: None,
             },
         },
         funds,
@@ -353,6 +357,21 @@ fn get_deposit_info(app: &App, module: Addr, id: u64) -> DepositInfoResponse {
         .unwrap()
 }
 
+fn get_required_deposit(
+    app: &App,
+    module: Addr,
+    proposer: impl Into<String>,
+) -> Option<CheckedDepositInfo> {
+    app.wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::RequiredDeposit {
+                proposer: proposer.into(),
+            },
+        )
+        .unwrap()
+}
+
 fn query_can_propose(app: &App, module: Addr, address: impl Into<String>) -> bool {
     app.wrap()
         .query_wasm_smart(
@@ -481,6 +500,7 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -545,6 +565,7 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            scaling: None,
         }),
         false,
     );
@@ -698,6 +719,157 @@ fn test_cw20_failed_passed_refund() {
 }
 
 // See: <https://github.com/DA0-DA0/dao-contracts/pull/465#discussion_r960092321>
+#[test]
+fn test_native_failed_by_outcome_refund() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::ByOutcome {
+                rejected: Decimal::percent(50),
+                failed_quorum: Decimal::zero(),
+                vetoed: Decimal::zero(),
+                execution_failed: Decimal::one(),
+                slash_destination: SlashDestination::Address {
+                    address: "community_pool".to_string(),
+                },
+            },
+            scaling: None,
+        }),
+        false,
+    );
+
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+    let id = make_proposal(
+        &mut app,
+        pre_propose,
+        proposal_single.clone(),
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+    let new_status = vote(&mut app, proposal_single.clone(), "ekez", id, Vote::No);
+    assert_eq!(new_status, Status::Rejected);
+    close_proposal(&mut app, proposal_single, "ekez", id);
+
+    // Half of the deposit is refunded and the rest is slashed to the
+    // slash destination rather than the DAO.
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(5));
+    assert_eq!(
+        get_balance_native(&app, "community_pool", "ujuno"),
+        Uint128::new(5)
+    );
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_deposit_scales_with_open_proposals() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr: _,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+            scaling: Some(DepositScaling::OpenProposals {
+                increment: Uint128::new(5),
+            }),
+        }),
+        false,
+    );
+
+    let required = get_required_deposit(&app, pre_propose.clone(), "ekez").unwrap();
+    assert_eq!(required.amount, Uint128::new(10));
+    assert_eq!(required.scaling, None);
+
+    mint_natives(&mut app, "ekez", coins(25, "ujuno"));
+    let first_id = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+
+    // The second proposal costs more while the first is open.
+    let required = get_required_deposit(&app, pre_propose.clone(), "ekez").unwrap();
+    assert_eq!(required.amount, Uint128::new(15));
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    vote: None,
+                    execution_plan: None,
+                },
+            },
+            &coins(10, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, PreProposeError::Deposit(_)));
+    let second_id = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(15, "ujuno"),
+    );
+
+    // Other proposers are unaffected.
+    let required = get_required_deposit(&app, pre_propose.clone(), "keze").unwrap();
+    assert_eq!(required.amount, Uint128::new(10));
+
+    // Refunds return the amount that was paid.
+    let new_status = vote(
+        &mut app,
+        proposal_single.clone(),
+        "ekez",
+        second_id,
+        Vote::No,
+    );
+    assert_eq!(new_status, Status::Rejected);
+    close_proposal(&mut app, proposal_single.clone(), "ekez", second_id);
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(15));
+
+    let required = get_required_deposit(&app, pre_propose.clone(), "ekez").unwrap();
+    assert_eq!(required.amount, Uint128::new(15));
+    let new_status = vote(
+        &mut app,
+        proposal_single.clone(),
+        "ekez",
+        first_id,
+        Vote::No,
+    );
+    assert_eq!(new_status, Status::Rejected);
+    close_proposal(&mut app, proposal_single, "ekez", first_id);
+    let required = get_required_deposit(&app, pre_propose, "ekez").unwrap();
+    assert_eq!(required.amount, Uint128::new(10));
+}
+
 #[test]
 fn test_multiple_open_proposals() {
     let mut app = App::default();
@@ -714,6 +886,7 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -796,6 +969,7 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -832,6 +1006,7 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false, // no open proposal submission.
     );
@@ -843,6 +1018,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                failed_quorum: false,
             },
             &[],
         )
@@ -863,6 +1039,7 @@ fn test_permissions() {
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
                     vote: None,
+                    execution_plan: None,
                 },
             },
             &[],
@@ -891,6 +1068,7 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -955,6 +1133,7 @@ fn test_no_deposit_required_members_submission() {
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
                     vote: None,
+                    execution_plan: None,
                 },
             },
             &[],
@@ -1023,6 +1202,7 @@ fn test_anyone_denylist() {
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
                     vote: None,
+                    execution_plan: None,
                 },
             },
             &[],
@@ -1085,6 +1265,7 @@ fn test_specific_allowlist_denylist() {
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
                     vote: None,
+                    execution_plan: None,
                 },
             },
             &[],
@@ -1143,6 +1324,7 @@ fn test_specific_allowlist_denylist() {
                     description: "I am a member!!!".to_string(),
                     msgs: vec![],
                     vote: None,
+                    execution_plan: None,
                 },
             },
             &[],
@@ -1179,6 +1361,7 @@ fn test_specific_allowlist_denylist() {
                     description: "I am a member!!!".to_string(),
                     msgs: vec![],
                     vote: None,
+                    execution_plan: None,
                 },
             },
             &[],
@@ -1262,6 +1445,7 @@ fn test_instantiate_with_zero_native_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            scaling: None,
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
@@ -1329,6 +1513,7 @@ fn test_instantiate_with_zero_cw20_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            scaling: None,
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
@@ -1406,6 +1591,7 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
     );
@@ -1417,7 +1603,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
         }
@@ -1449,7 +1636,8 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                scaling: None,
             }),
             proposer: Addr::unchecked("ekez"),
         }
@@ -2025,6 +2213,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2073,6 +2262,7 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2441,6 +2631,7 @@ fn test_migrate_from_v241() {
                     vote: Vote::Yes,
                     rationale: None,
                 }),
+                execution_plan: None,
             },
         },
         &[],
@@ -2743,6 +2934,7 @@ fn test_migrate_from_v241_with_policy_update() {
                     description: "d".to_string(),
                     msgs: vec![],
                     vote: None,
+                    execution_plan: None,
                 },
             },
             &[],
@@ -2764,6 +2956,7 @@ fn test_migrate_from_v241_with_policy_update() {
                 description: "d".to_string(),
                 msgs: vec![],
                 vote: None,
+                execution_plan: None,
             },
         },
         &[],
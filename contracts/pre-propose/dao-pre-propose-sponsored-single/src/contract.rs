@@ -0,0 +1,352 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, to_json_binary, BankMsg, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_denom::CheckedDenom;
+use cw_paginate_storage::paginate_map_values;
+use cw_utils::Duration;
+use dao_interface::voting::{
+    Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_pre_propose_base::state::{OpenProposal, PreProposeContract};
+use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, MigrateMsg, ProposeMessage,
+    ProposeMessageInternal, QueryExt, QueryMsg, SponsorshipConfig, UnsponsoredDepositPolicy,
+};
+use crate::state::{
+    advance_proposal_id, Proposal, SponsorshipStatus, COMPLETED_PROPOSALS, PENDING_PROPOSALS,
+    SPONSORSHIP_CONFIG,
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-sponsored-single";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+type PrePropose = PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, Empty, ProposeMessage>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    validate_config(&msg.extension)?;
+    SPONSORSHIP_CONFIG.save(deps.storage, &msg.extension)?;
+
+    let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose { msg } => execute_propose(deps, env, info, msg),
+        ExecuteMsg::Extension { msg } => match msg {
+            ExecuteExt::Sponsor { id } => execute_sponsor(deps, env, info, id),
+            ExecuteExt::Expire { id } => execute_expire(deps, env, id),
+            ExecuteExt::UpdateSponsorshipConfig { config } => {
+                execute_update_sponsorship_config(deps, info, config)
+            }
+        },
+        // Default pre-propose-base behavior for all other messages
+        _ => Ok(PrePropose::default().execute(deps, env, info, msg)?),
+    }
+}
+
+fn validate_config(config: &SponsorshipConfig) -> Result<(), ContractError> {
+    if config.threshold.is_zero() || config.threshold > Decimal::one() {
+        return Err(ContractError::InvalidThreshold {});
+    }
+    if let Duration::Height(0) | Duration::Time(0) = config.period {
+        return Err(ContractError::ZeroPeriod {});
+    }
+    Ok(())
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ProposeMessage,
+) -> Result<Response, ContractError> {
+    let pre_propose_base = PrePropose::default();
+
+    pre_propose_base.check_can_submit(deps.as_ref(), &env.block, info.sender.clone())?;
+
+    // Take deposit, if configured.
+    let deposit_info = pre_propose_base.resolve_deposit(deps.as_ref(), &info.sender)?;
+    let deposit_messages = if let Some(ref deposit_info) = deposit_info {
+        deposit_info.check_native_deposit_paid(&info)?;
+        deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
+    } else {
+        vec![]
+    };
+
+    let sponsorship = SPONSORSHIP_CONFIG.load(deps.storage)?;
+    let dao = pre_propose_base.dao.load(deps.storage)?;
+    let total_power: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        dao,
+        &VotingQuery::TotalPowerAtHeight {
+            height: Some(env.block.height),
+        },
+    )?;
+    let required_power = total_power.power.mul_ceil(sponsorship.threshold);
+
+    let id = advance_proposal_id(deps.storage)?;
+    pre_propose_base.add_open_proposal(
+        deps.storage,
+        &env.block,
+        &info.sender,
+        OpenProposal::Pending(id),
+    )?;
+
+    let msg = match msg {
+        ProposeMessage::Propose {
+            title,
+            description,
+            msgs,
+            vote,
+        } => ProposeMsg {
+            title,
+            description,
+            msgs,
+            proposer: Some(info.sender.to_string()),
+            vote,
+            execution_plan: None,
+        },
+    };
+
+    PENDING_PROPOSALS.save(
+        deps.storage,
+        id,
+        &Proposal {
+            status: SponsorshipStatus::SeekingSponsors {},
+            id,
+            proposer: info.sender.clone(),
+            msg,
+            deposit: deposit_info,
+            unsponsored_deposit: sponsorship.unsponsored_deposit,
+            start_height: env.block.height,
+            expiration: sponsorship.period.after(&env.block),
+            required_power,
+            sponsor_power: Uint128::zero(),
+            sponsors: vec![],
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_messages(deposit_messages)
+        .add_attribute("method", "pre-propose")
+        .add_attribute("id", id.to_string())
+        .add_attribute("proposer", info.sender)
+        .add_attribute("required_power", required_power))
+}
+
+pub fn execute_sponsor(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PENDING_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotSeekingSponsors { id })?;
+    if proposal.expiration.is_expired(&env.block) {
+        return Err(ContractError::Expired { id });
+    }
+    if proposal.sponsors.contains(&info.sender) {
+        return Err(ContractError::AlreadySponsored { id });
+    }
+
+    let pre_propose_base = PrePropose::default();
+    let dao = pre_propose_base.dao.load(deps.storage)?;
+    let power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        dao,
+        &VotingQuery::VotingPowerAtHeight {
+            address: info.sender.to_string(),
+            height: Some(proposal.start_height),
+        },
+    )?;
+    if power.power.is_zero() {
+        return Err(ContractError::NoVotingPower {});
+    }
+    proposal.sponsors.push(info.sender.clone());
+    proposal.sponsor_power += power.power;
+
+    if proposal.sponsor_power < proposal.required_power {
+        PENDING_PROPOSALS.save(deps.storage, id, &proposal)?;
+        return Ok(Response::default()
+            .add_attribute("method", "sponsor")
+            .add_attribute("id", id.to_string())
+            .add_attribute("sponsor", info.sender)
+            .add_attribute("sponsor_power", proposal.sponsor_power));
+    }
+
+    let proposal_module = pre_propose_base.proposal_module.load(deps.storage)?;
+
+    // Snapshot the deposit for the proposal that we're about to
+    // create.
+    let proposal_id = deps.querier.query_wasm_smart(
+        &proposal_module,
+        &dao_interface::proposal::Query::NextProposalId {},
+    )?;
+    pre_propose_base.deposits.save(
+        deps.storage,
+        proposal_id,
+        &(proposal.deposit.clone(), proposal.proposer.clone()),
+    )?;
+    pre_propose_base.create_pending_proposal(deps.storage, id, proposal_id)?;
+
+    let msg = to_json_binary(&ProposeMessageInternal::Propose(proposal.msg.clone()))?;
+    let propose_message = WasmMsg::Execute {
+        contract_addr: proposal_module.into_string(),
+        msg: msg.clone(),
+        funds: vec![],
+    };
+    let hooks_msgs =
+        pre_propose_base
+            .proposal_submitted_hooks
+            .prepare_hooks(deps.storage, |a| {
+                let execute = WasmMsg::Execute {
+                    contract_addr: a.into_string(),
+                    msg: msg.clone(),
+                    funds: vec![],
+                };
+                Ok(SubMsg::new(execute))
+            })?;
+
+    proposal.status = SponsorshipStatus::Sponsored {
+        created_proposal_id: proposal_id,
+    };
+    COMPLETED_PROPOSALS.save(deps.storage, id, &proposal)?;
+    PENDING_PROPOSALS.remove(deps.storage, id);
+
+    Ok(Response::default()
+        // The propose message must come before the hooks so that a
+        // hook receiver can not invalidate our `NextProposalId {}`
+        // query.
+        .add_message(propose_message)
+        .add_submessages(hooks_msgs)
+        .add_attribute("method", "proposal_sponsored")
+        .add_attribute("id", id.to_string())
+        .add_attribute("sponsor", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_expire(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let mut proposal = PENDING_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotSeekingSponsors { id })?;
+    if !proposal.expiration.is_expired(&env.block) {
+        return Err(ContractError::NotExpired { id });
+    }
+
+    proposal.status = SponsorshipStatus::Expired {};
+    COMPLETED_PROPOSALS.save(deps.storage, id, &proposal)?;
+    PENDING_PROPOSALS.remove(deps.storage, id);
+    PrePropose::default().remove_open_proposal(
+        deps.storage,
+        &proposal.proposer,
+        OpenProposal::Pending(id),
+    )?;
+
+    let messages = match proposal.deposit {
+        Some(ref deposit) => match proposal.unsponsored_deposit {
+            UnsponsoredDepositPolicy::Refund => {
+                deposit.get_return_deposit_message(&proposal.proposer)?
+            }
+            UnsponsoredDepositPolicy::Burn => vec![match &deposit.denom {
+                CheckedDenom::Native(denom) => CosmosMsg::Bank(BankMsg::Burn {
+                    amount: coins(deposit.amount.u128(), denom),
+                }),
+                CheckedDenom::Cw20(address) => CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: address.to_string(),
+                    msg: to_json_binary(&cw20::Cw20ExecuteMsg::Burn {
+                        amount: deposit.amount,
+                    })?,
+                    funds: vec![],
+                }),
+            }],
+        },
+        None => vec![],
+    };
+
+    Ok(Response::default()
+        .add_attribute("method", "proposal_expired")
+        .add_attribute("id", id.to_string())
+        .add_messages(messages))
+}
+
+pub fn execute_update_sponsorship_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: SponsorshipConfig,
+) -> Result<Response, ContractError> {
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::NotDao {});
+    }
+    validate_config(&config)?;
+    SPONSORSHIP_CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "update_sponsorship_config")
+        .add_attribute("threshold", config.threshold.to_string())
+        .add_attribute("period", config.period.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::QueryExtension { msg } => match msg {
+            QueryExt::SponsorshipConfig {} => {
+                to_json_binary(&SPONSORSHIP_CONFIG.load(deps.storage)?)
+            }
+            QueryExt::Proposal { id } => {
+                if let Some(pending) = PENDING_PROPOSALS.may_load(deps.storage, id)? {
+                    to_json_binary(&pending)
+                } else {
+                    to_json_binary(&COMPLETED_PROPOSALS.load(deps.storage, id)?)
+                }
+            }
+            QueryExt::PendingProposals { start_after, limit } => {
+                to_json_binary(&paginate_map_values(
+                    deps,
+                    &PENDING_PROPOSALS,
+                    start_after,
+                    limit,
+                    Order::Ascending,
+                )?)
+            }
+            QueryExt::CompletedProposals { start_after, limit } => {
+                to_json_binary(&paginate_map_values(
+                    deps,
+                    &COMPLETED_PROPOSALS,
+                    start_after,
+                    limit,
+                    Order::Ascending,
+                )?)
+            }
+        },
+        _ => PrePropose::default().query(deps, env, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let res = PrePropose::default().migrate(deps.branch(), msg);
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(res?)
+}
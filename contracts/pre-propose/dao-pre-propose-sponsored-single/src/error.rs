@@ -0,0 +1,40 @@
+use cosmwasm_std::StdError;
+use dao_pre_propose_base::error::PreProposeError;
+use dao_voting::deposit::DepositError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    PrePropose(#[from] PreProposeError),
+
+    #[error(transparent)]
+    Deposit(#[from] DepositError),
+
+    #[error("sponsorship threshold must be greater than zero and at most one")]
+    InvalidThreshold {},
+
+    #[error("sponsorship period must be non-zero")]
+    ZeroPeriod {},
+
+    #[error("only the DAO may update the sponsorship config")]
+    NotDao {},
+
+    #[error("proposal ({id}) is not seeking sponsors")]
+    NotSeekingSponsors { id: u64 },
+
+    #[error("proposal ({id}) has expired")]
+    Expired { id: u64 },
+
+    #[error("proposal ({id}) has not expired")]
+    NotExpired { id: u64 },
+
+    #[error("sponsors must have had voting power when the proposal was submitted")]
+    NoVotingPower {},
+
+    #[error("proposal ({id}) has already been sponsored by this address")]
+    AlreadySponsored { id: u64 },
+}
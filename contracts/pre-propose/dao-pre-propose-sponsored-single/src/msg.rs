@@ -0,0 +1,99 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Decimal, Empty};
+use cw_utils::Duration;
+use dao_pre_propose_base::msg::{
+    ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, MigrateMsg as MigrateBase,
+    QueryMsg as QueryBase,
+};
+use dao_voting::{proposal::SingleChoiceProposeMsg as ProposeMsg, voting::SingleChoiceAutoVote};
+
+#[cw_serde]
+pub enum ProposeMessage {
+    /// The propose message used to make a proposal to this
+    /// module. Note that this is identical to the propose message
+    /// used by dao-proposal-single, except that it omits the
+    /// `proposer` field which it fills in for the sender.
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        vote: Option<SingleChoiceAutoVote>,
+    },
+}
+
+/// What happens to the deposit of a proposal that expires before it
+/// is sponsored.
+#[cw_serde]
+pub enum UnsponsoredDepositPolicy {
+    /// The deposit is returned to the proposer.
+    Refund,
+    /// The deposit is burned.
+    Burn,
+}
+
+#[cw_serde]
+pub struct SponsorshipConfig {
+    /// The share of the DAO's total voting power at the time a
+    /// proposal is submitted that must sponsor it before it is
+    /// created on the proposal module.
+    pub threshold: Decimal,
+    /// How long a proposal may seek sponsors before it expires.
+    pub period: Duration,
+    /// What happens to the deposit of a proposal that expires before
+    /// it is sponsored.
+    pub unsponsored_deposit: UnsponsoredDepositPolicy,
+}
+
+pub type InstantiateExt = SponsorshipConfig;
+
+#[cw_serde]
+pub enum ExecuteExt {
+    /// Sponsors a proposal that is seeking sponsors. The sender's
+    /// voting power when the proposal was submitted counts towards
+    /// the proposal's threshold. Once the threshold is met, the
+    /// proposal is created on the proposal module.
+    Sponsor { id: u64 },
+    /// Completes a proposal that expired before it was sponsored,
+    /// handling its deposit according to the unsponsored deposit
+    /// policy it was submitted with. Callable by anyone.
+    Expire { id: u64 },
+    /// Updates the sponsorship config. Proposals that are already
+    /// seeking sponsors are unaffected. Only callable by the DAO.
+    UpdateSponsorshipConfig { config: SponsorshipConfig },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryExt {
+    /// The sponsorship config for new proposals.
+    #[returns(SponsorshipConfig)]
+    SponsorshipConfig {},
+    /// A proposal, seeking sponsors or completed.
+    #[returns(crate::state::Proposal)]
+    Proposal { id: u64 },
+    /// List of proposals seeking sponsors.
+    #[returns(Vec<crate::state::Proposal>)]
+    PendingProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// List of proposals that were sponsored or expired.
+    #[returns(Vec<crate::state::Proposal>)]
+    CompletedProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+pub type InstantiateMsg = InstantiateBase<InstantiateExt>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+pub type QueryMsg = QueryBase<QueryExt>;
+pub type MigrateMsg = MigrateBase<Empty>;
+
+/// Internal version of the propose message that includes the
+/// `proposer` field. The module will fill this in based on the sender
+/// of the external message.
+#[cw_serde]
+pub(crate) enum ProposeMessageInternal {
+    Propose(ProposeMsg),
+}
@@ -0,0 +1,62 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
+use dao_voting::{deposit::CheckedDepositInfo, proposal::SingleChoiceProposeMsg};
+
+use crate::msg::{SponsorshipConfig, UnsponsoredDepositPolicy};
+
+#[cw_serde]
+pub enum SponsorshipStatus {
+    /// The proposal is waiting for enough voting power to sponsor it.
+    SeekingSponsors {},
+    /// The proposal was sponsored and created on the proposal module.
+    Sponsored {
+        /// The created proposal ID.
+        created_proposal_id: u64,
+    },
+    /// The proposal expired before it was sponsored.
+    Expired {},
+}
+
+#[cw_serde]
+pub struct Proposal {
+    pub status: SponsorshipStatus,
+    /// The ID used to identify this proposal in this module.
+    pub id: u64,
+    /// The address that created the proposal.
+    pub proposer: Addr,
+    /// The propose message sent to the proposal module once this
+    /// proposal is sponsored.
+    pub msg: SingleChoiceProposeMsg,
+    /// Snapshot of the deposit info at the time of proposal
+    /// submission.
+    pub deposit: Option<CheckedDepositInfo>,
+    /// What happens to the deposit if the proposal expires, as of
+    /// submission.
+    pub unsponsored_deposit: UnsponsoredDepositPolicy,
+    /// The height the proposal was submitted at. Sponsors' voting
+    /// power is measured at this height.
+    pub start_height: u64,
+    /// When the proposal stops seeking sponsors.
+    pub expiration: Expiration,
+    /// The voting power that must sponsor the proposal.
+    pub required_power: Uint128,
+    /// The voting power that has sponsored the proposal so far.
+    pub sponsor_power: Uint128,
+    /// The addresses that have sponsored the proposal.
+    pub sponsors: Vec<Addr>,
+}
+
+pub const SPONSORSHIP_CONFIG: Item<SponsorshipConfig> = Item::new("sponsorship_config");
+pub const PENDING_PROPOSALS: Map<u64, Proposal> = Map::new("pending_proposals");
+pub const COMPLETED_PROPOSALS: Map<u64, Proposal> = Map::new("completed_proposals");
+
+/// Used internally to track the current proposal ID.
+const CURRENT_ID: Item<u64> = Item::new("current_id");
+
+pub(crate) fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = CURRENT_ID.may_load(store)?.unwrap_or_default() + 1;
+    CURRENT_ID.save(store, &id)?;
+    Ok(id)
+}
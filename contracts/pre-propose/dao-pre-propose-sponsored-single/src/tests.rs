@@ -0,0 +1,369 @@
+use cosmwasm_std::{coins, to_json_binary, Addr, Coin, Decimal, Uint128};
+use cw_denom::UncheckedDenom;
+use cw_multi_test::{next_block, App, BankSudo, Executor};
+use cw_utils::Duration;
+use dao_interface::state::{Admin, ModuleInstantiateInfo, ProposalModule};
+use dao_pre_propose_base::msg::DepositInfoResponse;
+use dao_proposal_single::query::ProposalResponse;
+use dao_testing::{
+    contracts::{dao_pre_propose_sponsored_single_contract, dao_proposal_single_contract},
+    helpers::instantiate_with_cw4_groups_governance,
+};
+use dao_voting::{
+    deposit::{DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    pre_propose::{PreProposeInfo, PreProposeSubmissionPolicy, ProposalCreationPolicy},
+    threshold::{PercentageThreshold, Threshold},
+};
+
+use crate::msg::*;
+use crate::state::{Proposal, SponsorshipStatus};
+use crate::ContractError;
+
+struct DefaultTestSetup {
+    core_addr: Addr,
+    proposal_single: Addr,
+    pre_propose: Addr,
+}
+
+fn setup_default_test(app: &mut App, config: SponsorshipConfig) -> DefaultTestSetup {
+    let pre_propose_id = app.store_code(dao_pre_propose_sponsored_single_contract());
+    let dao_proposal_single_id = app.store_code(dao_proposal_single_contract());
+
+    let proposal_module_instantiate = dao_proposal_single::msg::InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Time(86400),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        pre_propose_info: PreProposeInfo::ModuleMayPropose {
+            info: ModuleInstantiateInfo {
+                code_id: pre_propose_id,
+                msg: to_json_binary(&InstantiateMsg {
+                    deposit_info: Some(UncheckedDepositInfo {
+                        denom: DepositToken::Token {
+                            denom: UncheckedDenom::Native("ujuno".to_string()),
+                        },
+                        amount: Uint128::new(10),
+                        refund_policy: DepositRefundPolicy::Always,
+                        scaling: None,
+                    }),
+                    submission_policy: PreProposeSubmissionPolicy::Specific {
+                        dao_members: true,
+                        allowlist: vec![],
+                        denylist: vec![],
+                    },
+                    extension: config,
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "sponsored pre-propose".to_string(),
+            },
+        },
+        close_proposal_on_execution_failure: false,
+        veto: None,
+    };
+
+    let core_addr = instantiate_with_cw4_groups_governance(
+        app,
+        dao_proposal_single_id,
+        to_json_binary(&proposal_module_instantiate).unwrap(),
+        Some(vec![
+            cw20::Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(9),
+            },
+            cw20::Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(8),
+            },
+        ]),
+    );
+    // Members' voting power is measured as of the block before a
+    // proposal is submitted.
+    app.update_block(next_block);
+
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &dao_interface::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+    let proposal_creation_policy = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &dao_proposal_single::msg::QueryMsg::ProposalCreationPolicy {},
+        )
+        .unwrap();
+    let pre_propose = match proposal_creation_policy {
+        ProposalCreationPolicy::Module { addr } => addr,
+        _ => panic!("expected a module for the proposal creation policy"),
+    };
+
+    DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    }
+}
+
+fn default_config(unsponsored_deposit: UnsponsoredDepositPolicy) -> SponsorshipConfig {
+    SponsorshipConfig {
+        threshold: Decimal::percent(50),
+        period: Duration::Height(10),
+        unsponsored_deposit,
+    }
+}
+
+fn mint_natives(app: &mut App, receiver: &str, coins: Vec<Coin>) {
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: receiver.to_string(),
+        amount: coins,
+    }))
+    .unwrap();
+}
+
+fn make_pre_proposal(app: &mut App, pre_propose: Addr, proposer: &str) -> u64 {
+    mint_natives(app, proposer, coins(10, "ujuno"));
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        pre_propose.clone(),
+        &ExecuteMsg::Propose {
+            msg: ProposeMessage::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                vote: None,
+            },
+        },
+        &coins(10, "ujuno"),
+    )
+    .unwrap();
+
+    let mut pending: Vec<Proposal> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::PendingProposals {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap();
+    pending.pop().unwrap().id
+}
+
+fn get_proposal(app: &App, pre_propose: Addr, id: u64) -> Proposal {
+    app.wrap()
+        .query_wasm_smart(
+            pre_propose,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Proposal { id },
+            },
+        )
+        .unwrap()
+}
+
+fn sponsor(app: &mut App, pre_propose: Addr, sender: &str, id: u64) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        pre_propose,
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::Sponsor { id },
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn expire(app: &mut App, pre_propose: Addr, id: u64) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        pre_propose,
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::Expire { id },
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn get_balance_native(app: &App, who: &str, denom: &str) -> Uint128 {
+    app.wrap().query_balance(who, denom).unwrap().amount
+}
+
+#[test]
+fn test_sponsorship() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        proposal_single,
+        pre_propose,
+        ..
+    } = setup_default_test(&mut app, default_config(UnsponsoredDepositPolicy::Refund));
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "keze");
+    let proposal = get_proposal(&app, pre_propose.clone(), id);
+    // Half of 17 total voting power, rounded up.
+    assert_eq!(proposal.required_power, Uint128::new(9));
+    assert_eq!(proposal.status, SponsorshipStatus::SeekingSponsors {});
+
+    // 8 of 9 required voting power.
+    sponsor(&mut app, pre_propose.clone(), "keze", id).unwrap();
+    let proposal = get_proposal(&app, pre_propose.clone(), id);
+    assert_eq!(proposal.sponsor_power, Uint128::new(8));
+    assert_eq!(proposal.status, SponsorshipStatus::SeekingSponsors {});
+
+    let err = sponsor(&mut app, pre_propose.clone(), "keze", id).unwrap_err();
+    assert_eq!(err, ContractError::AlreadySponsored { id });
+    let err = sponsor(&mut app, pre_propose.clone(), "nonmember", id).unwrap_err();
+    assert_eq!(err, ContractError::NoVotingPower {});
+
+    // No proposal has been created yet.
+    let next_id: u64 = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &dao_proposal_single::msg::QueryMsg::NextProposalId {},
+        )
+        .unwrap();
+    assert_eq!(next_id, 1);
+
+    sponsor(&mut app, pre_propose.clone(), "ekez", id).unwrap();
+    assert_eq!(
+        get_proposal(&app, pre_propose.clone(), id).status,
+        SponsorshipStatus::Sponsored {
+            created_proposal_id: 1
+        }
+    );
+
+    let created: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(created.proposal.proposer, Addr::unchecked("keze"));
+
+    // The deposit is tracked for the created proposal.
+    let deposit: DepositInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::DepositInfo { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(deposit.proposer, Addr::unchecked("keze"));
+
+    let err = sponsor(&mut app, pre_propose, "keze", id).unwrap_err();
+    assert_eq!(err, ContractError::NotSeekingSponsors { id });
+}
+
+#[test]
+fn test_unsponsored_proposals_expire() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        pre_propose,
+        ..
+    } = setup_default_test(&mut app, default_config(UnsponsoredDepositPolicy::Burn));
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez");
+    let err = expire(&mut app, pre_propose.clone(), id).unwrap_err();
+    assert_eq!(err, ContractError::NotExpired { id });
+
+    app.update_block(|block| block.height += 10);
+    let err = sponsor(&mut app, pre_propose.clone(), "ekez", id).unwrap_err();
+    assert_eq!(err, ContractError::Expired { id });
+
+    // The deposit is burned.
+    expire(&mut app, pre_propose.clone(), id).unwrap();
+    assert_eq!(
+        get_proposal(&app, pre_propose.clone(), id).status,
+        SponsorshipStatus::Expired {}
+    );
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::zero());
+    assert_eq!(
+        get_balance_native(&app, pre_propose.as_str(), "ujuno"),
+        Uint128::zero()
+    );
+
+    // Only the DAO may update the config.
+    let update = ExecuteMsg::Extension {
+        msg: ExecuteExt::UpdateSponsorshipConfig {
+            config: default_config(UnsponsoredDepositPolicy::Refund),
+        },
+    };
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked("ekez"), pre_propose.clone(), &update, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotDao {});
+    app.execute_contract(core_addr, pre_propose.clone(), &update, &[])
+        .unwrap();
+
+    // The deposit is refunded.
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez");
+    app.update_block(|block| block.height += 10);
+    expire(&mut app, pre_propose, id).unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+}
+
+#[test]
+fn test_invalid_config() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        pre_propose,
+        ..
+    } = setup_default_test(&mut app, default_config(UnsponsoredDepositPolicy::Refund));
+
+    let mut update = |config: SponsorshipConfig| -> ContractError {
+        app.execute_contract(
+            core_addr.clone(),
+            pre_propose.clone(),
+            &ExecuteMsg::Extension {
+                msg: ExecuteExt::UpdateSponsorshipConfig { config },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+
+    assert_eq!(
+        update(SponsorshipConfig {
+            threshold: Decimal::zero(),
+            ..default_config(UnsponsoredDepositPolicy::Refund)
+        }),
+        ContractError::InvalidThreshold {}
+    );
+    assert_eq!(
+        update(SponsorshipConfig {
+            threshold: Decimal::percent(101),
+            ..default_config(UnsponsoredDepositPolicy::Refund)
+        }),
+        ContractError::InvalidThreshold {}
+    );
+    assert_eq!(
+        update(SponsorshipConfig {
+            period: Duration::Time(0),
+            ..default_config(UnsponsoredDepositPolicy::Refund)
+        }),
+        ContractError::ZeroPeriod {}
+    );
+}
@@ -94,6 +94,7 @@ impl SuiteBuilder {
                         cw4_group_code_id: cw4_id,
                         initial_members,
                     },
+                    power_transform: None,
                 })
                 .unwrap(),
                 admin: Some(Admin::CoreModule {}),
@@ -0,0 +1,928 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
+    Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw4::MemberChangedHookMsg;
+use cw_denom::UncheckedDenom;
+use cw_hooks::Hooks;
+use cw_paginate_storage::paginate_map_keys;
+use cw_storage_plus::Bound;
+use cw_utils::parse_reply_instantiate_data;
+use dao_hooks::nft_stake::NftStakeChangedHookMsg;
+use dao_hooks::proposal::{
+    new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
+};
+use dao_hooks::stake::StakeChangedHookMsg;
+use dao_interface::voting::IsActiveResponse;
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::proposal::{ConvictionProposeMsg as ProposeMsg, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
+use dao_voting::reply::{
+    failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
+};
+use dao_voting::status::Status;
+use dao_voting::voting::get_voting_power;
+
+use crate::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    proposal::{advance_proposal_id, next_proposal_id, ConvictionProposal},
+    query::{ProposalListResponse, ProposalResponse, SupportInfo, SupportListResponse},
+    state::{
+        Config, CONFIG, CREATION_POLICY, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, SUPPORT,
+        VP_HOOK_CALLERS,
+    },
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-conviction";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let dao = info.sender;
+
+    validate_params(msg.decay, msg.max_ratio, msg.weight)?;
+    let denom = msg.denom.into_checked(deps.as_ref())?;
+
+    let (initial_policy, pre_propose_messages) = msg
+        .pre_propose_info
+        .into_initial_policy_and_messages(dao.clone())?;
+
+    let config = Config {
+        dao: dao.clone(),
+        denom,
+        decay: msg.decay,
+        max_ratio: msg.max_ratio,
+        weight: msg.weight,
+        close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+    };
+
+    // Initialize proposal count to zero so that queries return zero
+    // instead of None.
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    CONFIG.save(deps.storage, &config)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(pre_propose_messages)
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", dao))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose(propose_msg) => execute_propose(deps, env, info.sender, propose_msg),
+        ExecuteMsg::Support {
+            proposal_id,
+            amount,
+        } => execute_support(deps, env, info.sender, proposal_id, amount),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::UpdateConfig {
+            denom,
+            decay,
+            max_ratio,
+            weight,
+            close_proposal_on_execution_failure,
+        } => execute_update_config(
+            deps,
+            info,
+            denom,
+            decay,
+            max_ratio,
+            weight,
+            close_proposal_on_execution_failure,
+        ),
+        ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
+            execute_update_proposal_creation_policy(deps, info, new_info)
+        }
+        ExecuteMsg::AddProposalHook { address } => execute_add_proposal_hook(deps, info, address),
+        ExecuteMsg::RemoveProposalHook { address } => {
+            execute_remove_proposal_hook(deps, info, address)
+        }
+        ExecuteMsg::UpdateVotingPowerHookCallers { add, remove } => {
+            execute_update_voting_power_hook_callers(deps, info, add, remove)
+        }
+        ExecuteMsg::MemberChangedHook(msg) => execute_membership_changed(deps, env, info, msg),
+        ExecuteMsg::NftStakeChangeHook(msg) => execute_nft_stake_changed(deps, env, info, msg),
+        ExecuteMsg::StakeChangeHook(msg) => execute_stake_changed(deps, env, info, msg),
+    }
+}
+
+/// Checks that conviction parameters are in range.
+fn validate_params(
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+) -> Result<(), ContractError> {
+    if decay.is_zero() || decay >= Decimal::one() {
+        return Err(ContractError::InvalidDecay {});
+    }
+    if max_ratio.is_zero() || max_ratio > Decimal::one() {
+        return Err(ContractError::InvalidMaxRatio {});
+    }
+    if weight.is_zero() {
+        return Err(ContractError::ZeroWeight {});
+    }
+    Ok(())
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    ProposeMsg {
+        title,
+        description,
+        beneficiary,
+        amount,
+        proposer,
+    }: ProposeMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+
+    // Check that the sender is permitted to create proposals.
+    if !proposal_creation_policy.is_permitted(&sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Determine the appropriate proposer. If this is coming from our
+    // pre-propose module, it must be specified. Otherwise, the
+    // proposer should not be specified.
+    let proposer = match (proposer, &proposal_creation_policy) {
+        (None, ProposalCreationPolicy::Anyone {}) => sender.clone(),
+        // `is_permitted` above checks that an allowed module is
+        // actually sending the propose message.
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+            deps.api.addr_validate(&proposer)?
+        }
+        _ => return Err(ContractError::InvalidProposer {}),
+    };
+
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+
+    let voting_module: Addr = deps.querier.query_wasm_smart(
+        config.dao.clone(),
+        &dao_interface::msg::QueryMsg::VotingModule {},
+    )?;
+
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let active_resp: IsActiveResponse = deps
+        .querier
+        .query_wasm_smart(voting_module, &dao_interface::voting::Query::IsActive {})
+        .unwrap_or(IsActiveResponse { active: true });
+
+    if !active_resp.active {
+        return Err(ContractError::InactiveDao {});
+    }
+
+    let proposal = ConvictionProposal {
+        title,
+        description,
+        proposer: proposer.clone(),
+        beneficiary,
+        amount,
+        start_height: env.block.height,
+        status: Status::Open,
+        support: Uint128::zero(),
+        conviction: Uint128::zero(),
+        last_updated: env.block.height,
+    };
+    let id = advance_proposal_id(deps.storage)?;
+
+    // Limit the size of proposals. See `dao-proposal-single` for why.
+    let proposal_size = cosmwasm_std::to_json_vec(&proposal)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+
+    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "propose")
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("status", proposal.status.to_string()))
+}
+
+pub fn execute_support(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    proposal_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Accrue conviction under the old support before changing
+    // it. Proposals that have passed no longer accept support.
+    let threshold = prop.threshold(deps.as_ref(), &config, &env.block)?;
+    prop.update_status(&config, &env.block, threshold)?;
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    // Sum the sender's support for other open proposals, forgetting
+    // support for proposals that have completed as it no longer
+    // counts against the sender's voting power.
+    let mut committed = Uint128::zero();
+    let mut completed = vec![];
+    for entry in SUPPORT
+        .prefix(&sender)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (id, support) = entry?;
+        if id == proposal_id {
+            continue;
+        }
+        if PROPOSALS.load(deps.storage, id)?.status == Status::Open {
+            committed = committed.checked_add(support)?;
+        } else {
+            completed.push(id);
+        }
+    }
+    for id in completed {
+        SUPPORT.remove(deps.storage, (&sender, id));
+    }
+
+    let total = committed.checked_add(amount)?;
+    let power = get_voting_power(
+        deps.as_ref(),
+        sender.clone(),
+        &config.dao,
+        Some(env.block.height),
+    )?;
+    if total > power {
+        return Err(ContractError::InsufficientVotingPower {
+            support: total,
+            available: power,
+        });
+    }
+
+    let previous = SUPPORT
+        .may_load(deps.storage, (&sender, proposal_id))?
+        .unwrap_or_default();
+    prop.support = prop.support.checked_sub(previous)?.checked_add(amount)?;
+    if amount.is_zero() {
+        SUPPORT.remove(deps.storage, (&sender, proposal_id));
+    } else {
+        SUPPORT.save(deps.storage, (&sender, proposal_id), &amount)?;
+    }
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "support")
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("conviction", prop.conviction))
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Conviction is the DAO's approval, so anyone may execute a
+    // proposal once it has passed.
+    let threshold = prop.threshold(deps.as_ref(), &config, &env.block)?;
+    prop.update_status(&config, &env.block, threshold)?;
+    if prop.status != Status::Passed {
+        return Err(ContractError::NotPassed {});
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Executed;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let execute_message = WasmMsg::Execute {
+        contract_addr: config.dao.to_string(),
+        msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![config
+                .denom
+                .get_transfer_to_message(&prop.beneficiary, prop.amount)?],
+        })?,
+        funds: vec![],
+    };
+    let response = match config.close_proposal_on_execution_failure {
+        // The reply reports whether execution failed to the
+        // pre-propose module, so it is needed on success too.
+        true => {
+            let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
+            Response::default()
+                .add_submessage(SubMsg::reply_always(execute_message, masked_proposal_id))
+        }
+        false => Response::default().add_message(execute_message),
+    };
+
+    // Add proposal status change hooks
+    let proposal_status_changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit
+    // refunds. If the execution reply was requested it fires the hook
+    // once the outcome of execution is known.
+    let proposal_completed_hooks = if config.close_proposal_on_execution_failure {
+        vec![]
+    } else {
+        let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status, false)?
+    };
+
+    Ok(response
+        .add_submessages(proposal_status_changed_hooks)
+        .add_submessages(proposal_completed_hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", config.dao))
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    if info.sender != prop.proposer && info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let threshold = prop.threshold(deps.as_ref(), &config, &env.block)?;
+    prop.update_status(&config, &env.block, threshold)?;
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Closed;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    // Add proposal status change hooks
+    let proposal_status_changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let proposal_completed_hooks =
+        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status, false)?;
+
+    Ok(Response::default()
+        .add_submessages(proposal_status_changed_hooks)
+        .add_submessages(proposal_completed_hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: UncheckedDenom,
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+    close_proposal_on_execution_failure: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_params(decay, max_ratio, weight)?;
+    let denom = denom.into_checked(deps.as_ref())?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            dao: config.dao,
+            denom,
+            decay,
+            max_ratio,
+            weight,
+            close_proposal_on_execution_failure,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_update_proposal_creation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_info: PreProposeInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (initial_policy, messages) = new_info.into_initial_policy_and_messages(config.dao)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_attribute("action", "update_proposal_creation_policy")
+        .add_attribute("sender", info.sender)
+        .add_attribute("new_policy", format!("{initial_policy:?}")))
+}
+
+pub fn add_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validated_address: Addr,
+) -> Result<(), ContractError> {
+    hooks
+        .add_hook(storage, validated_address)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn remove_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validate_address: Addr,
+) -> Result<(), ContractError> {
+    hooks
+        .remove_hook(storage, validate_address)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn execute_add_proposal_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_proposal_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_proposal_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    remove_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_proposal_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_update_voting_power_hook_callers(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for caller in add.unwrap_or_default() {
+        let caller = deps.api.addr_validate(&caller)?;
+        VP_HOOK_CALLERS.save(deps.storage, &caller, &Empty {})?;
+    }
+    for caller in remove.unwrap_or_default() {
+        let caller = deps.api.addr_validate(&caller)?;
+        VP_HOOK_CALLERS.remove(deps.storage, &caller);
+    }
+
+    Ok(Response::default().add_attribute("action", "update_voting_power_hook_callers"))
+}
+
+pub fn execute_membership_changed(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: MemberChangedHookMsg,
+) -> Result<Response, ContractError> {
+    check_hook_caller(deps.as_ref(), &info)?;
+
+    for member in msg.diffs {
+        let addr = deps.api.addr_validate(&member.key)?;
+        cap_support(deps.branch(), &env, &addr)?;
+    }
+
+    Ok(Response::default().add_attribute("action", "membership_changed"))
+}
+
+pub fn execute_nft_stake_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: NftStakeChangedHookMsg,
+) -> Result<Response, ContractError> {
+    check_hook_caller(deps.as_ref(), &info)?;
+
+    let addr = match msg {
+        NftStakeChangedHookMsg::Stake { addr, .. } => addr,
+        NftStakeChangedHookMsg::Unstake { addr, .. } => addr,
+    };
+    cap_support(deps, &env, &addr)?;
+
+    Ok(Response::default().add_attribute("action", "nft_stake_changed"))
+}
+
+pub fn execute_stake_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: StakeChangedHookMsg,
+) -> Result<Response, ContractError> {
+    check_hook_caller(deps.as_ref(), &info)?;
+
+    let addr = match msg {
+        StakeChangedHookMsg::Stake { addr, .. } => addr,
+        StakeChangedHookMsg::Unstake { addr, .. } => addr,
+    };
+    cap_support(deps, &env, &addr)?;
+
+    Ok(Response::default().add_attribute("action", "stake_changed"))
+}
+
+/// Ensures hooks that update voting power are only called by a
+/// registered voting power hook caller.
+fn check_hook_caller(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    if !VP_HOOK_CALLERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::InvalidHookSender {});
+    }
+    Ok(())
+}
+
+/// Scales down `voter`'s support for open proposals so that it does
+/// not exceed their voting power. Without this, a member could
+/// support proposals, move their stake to another address, and
+/// support them again. Conviction accrued under the old support is
+/// kept, but stops growing from the moment the voting power is gone.
+fn cap_support(deps: DepsMut, env: &Env, voter: &Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut open = vec![];
+    let mut completed = vec![];
+    let mut committed = Uint128::zero();
+    for entry in SUPPORT
+        .prefix(voter)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (id, support) = entry?;
+        if PROPOSALS.load(deps.storage, id)?.status == Status::Open {
+            committed = committed.checked_add(support)?;
+            open.push((id, support));
+        } else {
+            completed.push(id);
+        }
+    }
+    for id in completed {
+        SUPPORT.remove(deps.storage, (voter, id));
+    }
+
+    // Voting modules snapshot changes at the height they happen, so
+    // changes made in this block are only visible at the next one.
+    let power = get_voting_power(
+        deps.as_ref(),
+        voter.clone(),
+        &config.dao,
+        Some(env.block.height + 1),
+    )?;
+    if committed <= power {
+        return Ok(());
+    }
+
+    for (id, support) in open {
+        let mut prop = PROPOSALS.load(deps.storage, id)?;
+        let threshold = prop.threshold(deps.as_ref(), &config, &env.block)?;
+        prop.update_status(&config, &env.block, threshold)?;
+        if prop.status == Status::Open {
+            let capped = support.multiply_ratio(power, committed);
+            prop.support = prop.support.checked_sub(support)?.checked_add(capped)?;
+            if capped.is_zero() {
+                SUPPORT.remove(deps.storage, (voter, id));
+            } else {
+                SUPPORT.save(deps.storage, (voter, id), &capped)?;
+            }
+        } else {
+            SUPPORT.remove(deps.storage, (voter, id));
+        }
+        PROPOSALS.save(deps.storage, id, &prop)?;
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::Dao {} => query_dao(deps),
+        QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
+        QueryMsg::ListProposals { start_after, limit } => {
+            query_list_proposals(deps, env, start_after, limit)
+        }
+        QueryMsg::ReverseProposals {
+            start_before,
+            limit,
+        } => query_reverse_proposals(deps, env, start_before, limit),
+        QueryMsg::NextProposalId {} => query_next_proposal_id(deps),
+        QueryMsg::ProposalCount {} => query_proposal_count(deps),
+        QueryMsg::Support { proposal_id, voter } => query_support(deps, proposal_id, voter),
+        QueryMsg::ListSupport {
+            voter,
+            start_after,
+            limit,
+        } => query_list_support(deps, voter, start_after, limit),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
+        QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+        QueryMsg::VotingPowerHookCallers { start_after, limit } => {
+            query_voting_power_hook_callers(deps, start_after, limit)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_json_binary(&config)
+}
+
+pub fn query_dao(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_json_binary(&config.dao)
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_json_binary(&proposal.into_response(deps, &config, &env.block, id)?)
+}
+
+pub fn query_creation_policy(deps: Deps) -> StdResult<Binary> {
+    let policy = CREATION_POLICY.load(deps.storage)?;
+    to_json_binary(&policy)
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let props: Vec<ProposalResponse> = PROPOSALS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<Result<Vec<(u64, ConvictionProposal)>, _>>()?
+        .into_iter()
+        .map(|(id, proposal)| proposal.into_response(deps, &config, &env.block, id))
+        .collect::<StdResult<Vec<ProposalResponse>>>()?;
+
+    to_json_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_reverse_proposals(
+    deps: Deps,
+    env: Env,
+    start_before: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let max = start_before.map(Bound::exclusive);
+    let props: Vec<ProposalResponse> = PROPOSALS
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit as usize)
+        .collect::<Result<Vec<(u64, ConvictionProposal)>, _>>()?
+        .into_iter()
+        .map(|(id, proposal)| proposal.into_response(deps, &config, &env.block, id))
+        .collect::<StdResult<Vec<ProposalResponse>>>()?;
+
+    to_json_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
+    let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
+    to_json_binary(&proposal_count)
+}
+
+pub fn query_next_proposal_id(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&next_proposal_id(deps.storage)?)
+}
+
+pub fn query_support(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let support = SUPPORT
+        .may_load(deps.storage, (&voter, proposal_id))?
+        .unwrap_or_default();
+    to_json_binary(&support)
+}
+
+pub fn query_list_support(
+    deps: Deps,
+    voter: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let support = SUPPORT
+        .prefix(&voter)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|entry| {
+            entry.map(|(proposal_id, amount)| SupportInfo {
+                proposal_id,
+                amount,
+            })
+        })
+        .collect::<StdResult<Vec<SupportInfo>>>()?;
+
+    to_json_binary(&SupportListResponse { support })
+}
+
+pub fn query_voting_power_hook_callers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    to_json_binary(&paginate_map_keys(
+        deps,
+        &VP_HOOK_CALLERS,
+        start_after.as_ref(),
+        limit,
+        Order::Ascending,
+    )?)
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_json_binary(&dao_interface::voting::InfoResponse { info })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = TaggedReplyId::new(msg.id)?;
+    match repl {
+        // Received whether or not execution failed.
+        TaggedReplyId::FailedProposalExecution(proposal_id) => {
+            let mut prop = PROPOSALS
+                .may_load(deps.storage, proposal_id)?
+                .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+            let response = match msg.result.into_result() {
+                Ok(_) => Response::new(),
+                Err(error) => {
+                    prop.status = Status::ExecutionFailed;
+                    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+                    Response::new()
+                        .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                        .add_attribute("error", error)
+                }
+            };
+
+            // Add prepropose / deposit module hook which will handle
+            // deposit refunds.
+            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            let proposal_completed_hooks = proposal_completed_hooks(
+                proposal_creation_policy,
+                proposal_id,
+                prop.status,
+                false,
+            )?;
+
+            Ok(response.add_submessages(proposal_completed_hooks))
+        }
+        TaggedReplyId::FailedProposalHook(idx) => {
+            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+        }
+        // This module does not fire vote hooks nor support
+        // execution plans.
+        TaggedReplyId::FailedVoteHook(_) | TaggedReplyId::ProposalBatchExecution { .. } => {
+            Err(ContractError::InvalidReplyID { id: msg.id })
+        }
+        TaggedReplyId::PreProposeModuleInstantiation => {
+            let res = parse_reply_instantiate_data(msg)?;
+
+            let module = deps.api.addr_validate(&res.contract_address)?;
+            CREATION_POLICY.save(
+                deps.storage,
+                &ProposalCreationPolicy::Module { addr: module },
+            )?;
+
+            match res.data {
+                Some(data) => Ok(Response::new()
+                    .add_attribute("update_pre_propose_module", res.contract_address)
+                    .set_data(data)),
+                None => Ok(Response::new()
+                    .add_attribute("update_pre_propose_module", res.contract_address)),
+            }
+        }
+        TaggedReplyId::FailedPreProposeModuleHook => {
+            let addr = match CREATION_POLICY.load(deps.storage)? {
+                ProposalCreationPolicy::Anyone {} => {
+                    // Something is off if we're getting this reply
+                    // and we don't have a pre-propose module
+                    // installed. This should be unreachable.
+                    return Err(ContractError::InvalidReplyID {
+                        id: failed_pre_propose_module_hook_id(),
+                    });
+                }
+                ProposalCreationPolicy::Module { addr } => {
+                    // Our pre-propose module has errored while
+                    // receiving a proposal hook. Fall back to
+                    // allowing anyone to propose.
+                    CREATION_POLICY.save(deps.storage, &ProposalCreationPolicy::Anyone {})?;
+                    addr
+                }
+            };
+            Ok(Response::new().add_attribute("failed_prepropose_hook", format!("{addr}")))
+        }
+    }
+}
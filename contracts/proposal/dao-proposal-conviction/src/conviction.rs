@@ -0,0 +1,172 @@
+use cosmwasm_std::{Decimal, StdError, StdResult, Uint128, Uint256};
+
+/// Computes the conviction of a proposal `blocks` blocks after it had
+/// `conviction`, given that `support` voting power was behind it the
+/// whole time. Each block conviction is updated as
+///
+/// ```text
+/// y(t + 1) = decay * y(t) + support
+/// ```
+///
+/// which, applied `blocks` times, has the closed form
+///
+/// ```text
+/// y(t) = decay^t * y(0) + support * (1 - decay^t) / (1 - decay)
+/// ```
+pub fn accrue_conviction(
+    conviction: Uint128,
+    support: Uint128,
+    decay: Decimal,
+    blocks: u64,
+) -> StdResult<Uint128> {
+    if blocks == 0 {
+        return Ok(conviction);
+    }
+    // `decay` is less than one so after u32::MAX blocks `decay^t` has
+    // long since rounded down to zero.
+    let blocks = u32::try_from(blocks).unwrap_or(u32::MAX);
+    let remaining = decay.checked_pow(blocks)?;
+    let growth = (Decimal::one() - remaining)
+        .checked_div(Decimal::one() - decay)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let accrued = support
+        .checked_mul_floor(growth)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    Ok(conviction.mul_floor(remaining).checked_add(accrued)?)
+}
+
+/// Computes the conviction a proposal requesting `requested` tokens
+/// from a treasury holding `treasury` tokens needs to pass:
+///
+/// ```text
+/// weight * total_power / ((1 - decay) * (max_ratio - requested / treasury)^2)
+/// ```
+///
+/// Returns `None` if the proposal can not pass, i.e. it requests
+/// `max_ratio` or more of the treasury or the threshold does not fit
+/// in a `Uint128`.
+pub fn conviction_threshold(
+    total_power: Uint128,
+    requested: Uint128,
+    treasury: Uint128,
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+) -> Option<Uint128> {
+    let share = Decimal::checked_from_ratio(requested, treasury).ok()?;
+    if share >= max_ratio {
+        return None;
+    }
+    let distance = max_ratio - share;
+    let denominator = (Decimal::one() - decay) * distance * distance;
+    if denominator.is_zero() {
+        return None;
+    }
+    // Work with the decimals' atomics so that no precision is lost
+    // dividing by a (potentially very small) denominator.
+    let threshold = total_power
+        .full_mul(weight.atomics())
+        .checked_div(Uint256::from(denominator.atomics()))
+        .ok()?;
+    Uint128::try_from(threshold).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accrue_conviction() {
+        let decay = Decimal::percent(50);
+
+        // No blocks, no change.
+        assert_eq!(
+            accrue_conviction(Uint128::new(10), Uint128::new(100), decay, 0).unwrap(),
+            Uint128::new(10)
+        );
+
+        // Matches applying the update rule block by block.
+        let mut conviction = Uint128::new(64);
+        for _ in 0..3 {
+            conviction = conviction.mul_floor(decay) + Uint128::new(100);
+        }
+        assert_eq!(conviction, Uint128::new(183));
+        assert_eq!(
+            accrue_conviction(Uint128::new(64), Uint128::new(100), decay, 3).unwrap(),
+            conviction
+        );
+
+        // Approaches support / (1 - decay) over time.
+        assert_eq!(
+            accrue_conviction(Uint128::zero(), Uint128::new(100), decay, u64::MAX).unwrap(),
+            Uint128::new(200)
+        );
+
+        // Decays without support.
+        assert_eq!(
+            accrue_conviction(Uint128::new(200), Uint128::zero(), decay, 200).unwrap(),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn test_conviction_threshold() {
+        let decay = Decimal::percent(90);
+        let max_ratio = Decimal::percent(20);
+        let weight = Decimal::permille(25);
+
+        // 0.025 * 1000 / (0.1 * (0.2 - 0.1)^2) = 25_000
+        assert_eq!(
+            conviction_threshold(
+                Uint128::new(1000),
+                Uint128::new(10),
+                Uint128::new(100),
+                decay,
+                max_ratio,
+                weight
+            ),
+            Some(Uint128::new(25_000))
+        );
+
+        // Smaller requests need less conviction.
+        // 0.025 * 1000 / (0.1 * 0.2^2) = 6_250
+        assert_eq!(
+            conviction_threshold(
+                Uint128::new(1000),
+                Uint128::zero(),
+                Uint128::new(100),
+                decay,
+                max_ratio,
+                weight
+            ),
+            Some(Uint128::new(6_250))
+        );
+
+        // Requests for max_ratio or more of the treasury may not pass.
+        assert_eq!(
+            conviction_threshold(
+                Uint128::new(1000),
+                Uint128::new(20),
+                Uint128::new(100),
+                decay,
+                max_ratio,
+                weight
+            ),
+            None
+        );
+
+        // Nor may requests from an empty treasury.
+        assert_eq!(
+            conviction_threshold(
+                Uint128::new(1000),
+                Uint128::new(20),
+                Uint128::zero(),
+                decay,
+                max_ratio,
+                weight
+            ),
+            None
+        );
+    }
+}
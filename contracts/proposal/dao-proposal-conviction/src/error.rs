@@ -0,0 +1,74 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use cw_denom::DenomError;
+use cw_hooks::HookError;
+use cw_utils::ParseReplyError;
+use dao_voting::reply::error::TagError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+
+    #[error(transparent)]
+    ParseReplyError(#[from] ParseReplyError),
+
+    #[error(transparent)]
+    HookError(#[from] HookError),
+
+    #[error(transparent)]
+    DenomError(#[from] DenomError),
+
+    #[error(transparent)]
+    Tag(#[from] TagError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("voting power changed hook sender incorrect")]
+    InvalidHookSender {},
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("proposal is ({size}) bytes, must be <= ({max}) bytes")]
+    ProposalTooLarge { size: u64, max: u64 },
+
+    #[error("the DAO is currently inactive, you cannot create proposals")]
+    InactiveDao {},
+
+    #[error(
+        "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
+    )]
+    InvalidProposer {},
+
+    #[error("proposals must request a non-zero amount")]
+    ZeroAmount {},
+
+    #[error("decay must be greater than zero and less than one")]
+    InvalidDecay {},
+
+    #[error("max ratio must be greater than zero and less than or equal to one")]
+    InvalidMaxRatio {},
+
+    #[error("weight must be greater than zero")]
+    ZeroWeight {},
+
+    #[error("proposal ({id}) is not open")]
+    NotOpen { id: u64 },
+
+    #[error("proposal is not in 'passed' state")]
+    NotPassed {},
+
+    #[error("support ({support}) exceeds available voting power ({available})")]
+    InsufficientVotingPower {
+        support: Uint128,
+        available: Uint128,
+    },
+
+    #[error("received a reply failure with an invalid ID: ({id})")]
+    InvalidReplyID { id: u64 },
+}
@@ -0,0 +1,170 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Uint128};
+use cw4::MemberChangedHookMsg;
+use cw_denom::UncheckedDenom;
+use dao_dao_macros::proposal_module_query;
+use dao_hooks::{nft_stake::NftStakeChangedHookMsg, stake::StakeChangedHookMsg};
+use dao_voting::{pre_propose::PreProposeInfo, proposal::ConvictionProposeMsg};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The token proposals request funding in.
+    pub denom: UncheckedDenom,
+    /// The fraction of a proposal's conviction that remains after
+    /// each block. Must be in (0, 1). Values closer to one cause
+    /// conviction to build up and decay more slowly.
+    pub decay: Decimal,
+    /// The maximum share of the treasury a single proposal may
+    /// request. Must be in (0, 1].
+    pub max_ratio: Decimal,
+    /// Scales the conviction proposals need to pass. Must be greater
+    /// than zero.
+    pub weight: Decimal,
+    /// Information about what addresses may create proposals.
+    pub pre_propose_info: PreProposeInfo,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain passed after execution
+    /// failure and may be executed again once the DAO's treasury is
+    /// large enough.
+    pub close_proposal_on_execution_failure: bool,
+}
+
+#[cw_serde]
+#[derive(cw_orch::ExecuteFns)]
+pub enum ExecuteMsg {
+    /// Creates a proposal in the module.
+    Propose(ConvictionProposeMsg),
+    /// Sets the voting power the sender has put behind a proposal,
+    /// replacing any previous support. Setting support to zero
+    /// withdraws it. The sender's total support across open
+    /// proposals may not exceed their current voting power.
+    Support {
+        /// The ID of the proposal to support.
+        proposal_id: u64,
+        /// The voting power to put behind the proposal.
+        amount: Uint128,
+    },
+    /// Sends the funds requested by a passed proposal from the DAO's
+    /// treasury to its beneficiary.
+    Execute {
+        /// The ID of the proposal to execute.
+        proposal_id: u64,
+    },
+    /// Closes an open proposal. Callable by the proposal's proposer
+    /// or the DAO. If applicable this will cause the proposal deposit
+    /// associated with said proposal to be returned.
+    Close {
+        /// The ID of the proposal to close.
+        proposal_id: u64,
+    },
+    /// Updates the governance module's config. Applies to all
+    /// proposals, including those already open.
+    UpdateConfig {
+        /// The token proposals request funding in.
+        denom: UncheckedDenom,
+        /// The fraction of a proposal's conviction that remains after
+        /// each block. Must be in (0, 1).
+        decay: Decimal,
+        /// The maximum share of the treasury a single proposal may
+        /// request. Must be in (0, 1].
+        max_ratio: Decimal,
+        /// Scales the conviction proposals need to pass. Must be
+        /// greater than zero.
+        weight: Decimal,
+        /// If set to true proposals will be closed if their execution
+        /// fails.
+        close_proposal_on_execution_failure: bool,
+    },
+    /// Update's the proposal creation policy used for this
+    /// module. Only the DAO may call this method.
+    UpdatePreProposeInfo { info: PreProposeInfo },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook { address: String },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String },
+    /// Updates the contracts allowed to call the voting power change
+    /// hooks. Only callable by the DAO.
+    UpdateVotingPowerHookCallers {
+        add: Option<Vec<String>>,
+        remove: Option<Vec<String>>,
+    },
+    /// Called when a member is added or removed
+    /// to a cw4-groups or cw721-roles contract.
+    MemberChangedHook(MemberChangedHookMsg),
+    /// Called when NFTs are staked or unstaked.
+    NftStakeChangeHook(NftStakeChangedHookMsg),
+    /// Called when tokens are staked or unstaked.
+    StakeChangeHook(StakeChangedHookMsg),
+}
+
+#[proposal_module_query]
+#[cw_serde]
+#[derive(QueryResponses, cw_orch::QueryFns)]
+pub enum QueryMsg {
+    /// Gets the proposal module's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets information about a proposal.
+    #[returns(crate::query::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been created in this
+    /// module.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposals {
+        /// The proposal ID to start listing proposals after. For
+        /// example, if this is set to 2 proposals with IDs 3 and
+        /// higher will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists all of the proposals that have been created in this
+    /// module in decending order of proposal ID.
+    #[returns(crate::query::ProposalListResponse)]
+    ReverseProposals {
+        /// The proposal ID to start listing proposals before. For
+        /// example, if this is set to 6 proposals with IDs 5 and
+        /// lower will be returned.
+        start_before: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Returns the number of proposals that have been created in
+    /// this module.
+    #[returns(::std::primitive::u64)]
+    ProposalCount {},
+    /// Gets the voting power an address has put behind a proposal.
+    #[returns(::cosmwasm_std::Uint128)]
+    Support { proposal_id: u64, voter: String },
+    /// Lists the proposals an address supports.
+    #[returns(crate::query::SupportListResponse)]
+    ListSupport {
+        voter: String,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+    /// Gets the current proposal creation policy for this module.
+    #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    ProposalHooks {},
+    /// Returns the contracts allowed to call the voting power change
+    /// hooks.
+    #[returns(Vec<::cosmwasm_std::Addr>)]
+    VotingPowerHookCallers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,122 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, Deps, StdResult, Storage, Uint128};
+use dao_voting::{status::Status, voting::get_total_power};
+
+use crate::{
+    conviction::{accrue_conviction, conviction_threshold},
+    query::ProposalResponse,
+    state::{Config, PROPOSAL_COUNT},
+};
+
+#[cw_serde]
+pub struct ConvictionProposal {
+    /// The title of the proposal.
+    pub title: String,
+    /// The main body of the proposal text.
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The address that will receive the requested funds if the
+    /// proposal passes.
+    pub beneficiary: Addr,
+    /// The amount of the module's funding denom requested.
+    pub amount: Uint128,
+    /// The block height at which this proposal was created.
+    pub start_height: u64,
+    pub status: Status,
+    /// The voting power currently supporting this proposal.
+    pub support: Uint128,
+    /// The proposal's conviction as of `last_updated`.
+    pub conviction: Uint128,
+    /// The block height at which `conviction` was last updated.
+    pub last_updated: u64,
+}
+
+pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
+    Ok(PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1)
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = next_proposal_id(store)?;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+impl ConvictionProposal {
+    /// Consumes the proposal and returns a version which may be used
+    /// in a query response. Conviction and status are only stored
+    /// when support for a proposal changes or it is executed, so
+    /// this brings both up to date with the current block.
+    pub fn into_response(
+        mut self,
+        deps: Deps,
+        config: &Config,
+        block: &BlockInfo,
+        id: u64,
+    ) -> StdResult<ProposalResponse> {
+        let threshold = self.threshold(deps, config, block)?;
+        self.update_status(config, block, threshold)?;
+        Ok(ProposalResponse {
+            id,
+            proposal: self,
+            threshold,
+        })
+    }
+
+    /// Brings the proposal's conviction up to date with the current
+    /// block.
+    pub fn accrue(&mut self, config: &Config, block: &BlockInfo) -> StdResult<()> {
+        self.conviction = accrue_conviction(
+            self.conviction,
+            self.support,
+            config.decay,
+            block.height.saturating_sub(self.last_updated),
+        )?;
+        self.last_updated = block.height;
+        Ok(())
+    }
+
+    /// Gets the conviction this proposal needs to pass given the
+    /// DAO's current total voting power and treasury. Returns `None`
+    /// if the proposal can not currently pass.
+    pub fn threshold(
+        &self,
+        deps: Deps,
+        config: &Config,
+        block: &BlockInfo,
+    ) -> StdResult<Option<Uint128>> {
+        let total_power = get_total_power(deps, &config.dao, Some(block.height))?;
+        let treasury = config.denom.query_balance(&deps.querier, &config.dao)?;
+        Ok(conviction_threshold(
+            total_power,
+            self.amount,
+            treasury,
+            config.decay,
+            config.max_ratio,
+            config.weight,
+        ))
+    }
+
+    /// Brings the proposal's conviction up to date and, if it is
+    /// open and its conviction has reached `threshold`, marks it as
+    /// passed.
+    pub fn update_status(
+        &mut self,
+        config: &Config,
+        block: &BlockInfo,
+        threshold: Option<Uint128>,
+    ) -> StdResult<()> {
+        if self.status != Status::Open {
+            return Ok(());
+        }
+        self.accrue(config, block)?;
+        if let Some(threshold) = threshold {
+            // A proposal with no conviction never passes, even if the
+            // DAO has no voting power and the threshold is zero.
+            if !self.conviction.is_zero() && self.conviction >= threshold {
+                self.status = Status::Passed;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use crate::proposal::ConvictionProposal;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+/// Information about a proposal returned by proposal queries.
+#[cw_serde]
+pub struct ProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    pub proposal: ConvictionProposal,
+    /// The conviction the proposal needs to pass given the DAO's
+    /// current total voting power and treasury. `None` if the
+    /// proposal can not currently pass.
+    pub threshold: Option<Uint128>,
+}
+
+/// A list of proposals returned by `ListProposals` and
+/// `ReverseProposals`.
+#[cw_serde]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+/// The voting power an address has put behind a proposal.
+#[cw_serde]
+pub struct SupportInfo {
+    /// The ID of the supported proposal.
+    pub proposal_id: u64,
+    /// The voting power behind the proposal.
+    pub amount: Uint128,
+}
+
+/// Information about the proposals an address supports.
+#[cw_serde]
+pub struct SupportListResponse {
+    pub support: Vec<SupportInfo>,
+}
@@ -0,0 +1,46 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cw_denom::CheckedDenom;
+use cw_hooks::Hooks;
+use cw_storage_plus::{Item, Map};
+use dao_voting::pre_propose::ProposalCreationPolicy;
+
+use crate::proposal::ConvictionProposal;
+
+/// The governance module's configuration.
+#[cw_serde]
+pub struct Config {
+    /// The address of the DAO that this governance module is
+    /// associated with.
+    pub dao: Addr,
+    /// The token proposals request funding in.
+    pub denom: CheckedDenom,
+    /// The fraction of a proposal's conviction that remains after
+    /// each block. Must be in (0, 1).
+    pub decay: Decimal,
+    /// The maximum share of the treasury a single proposal may
+    /// request. Must be in (0, 1].
+    pub max_ratio: Decimal,
+    /// Scales the conviction proposals need to pass. Must be greater
+    /// than zero.
+    pub weight: Decimal,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain passed after execution
+    /// failure and may be executed again once the DAO's treasury is
+    /// large enough.
+    pub close_proposal_on_execution_failure: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, ConvictionProposal> = Map::new("proposals");
+/// The voting power each address has put behind each proposal.
+pub const SUPPORT: Map<(&Addr, u64), Uint128> = Map::new("support");
+/// Contracts allowed to notify this module of voting power changes.
+pub const VP_HOOK_CALLERS: Map<&Addr, Empty> = Map::new("vp_hook_callers");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+/// The address of the pre-propose module associated with this
+/// proposal module (if any).
+pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
@@ -0,0 +1,563 @@
+use cosmwasm_std::{coins, to_json_binary, Addr, Decimal, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw_denom::UncheckedDenom;
+use cw_multi_test::{App, Executor};
+use dao_interface::state::{Admin, ModuleInstantiateInfo, ProposalModule};
+use dao_testing::{
+    contracts::{dao_pre_propose_conviction_contract, dao_proposal_conviction_contract},
+    helpers::instantiate_with_cw4_groups_governance,
+};
+use dao_voting::{
+    deposit::{DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    pre_propose::{PreProposeInfo, PreProposeSubmissionPolicy, ProposalCreationPolicy},
+    proposal::ConvictionProposeMsg,
+    status::Status,
+};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{ProposalResponse, SupportInfo, SupportListResponse},
+    ContractError,
+};
+
+const DENOM: &str = "ujuno";
+const WHALE: &str = "whale";
+
+struct Setup {
+    app: App,
+    dao: Addr,
+    module: Addr,
+}
+
+fn instantiate_msg(pre_propose_info: PreProposeInfo) -> InstantiateMsg {
+    // With 100 total voting power and a treasury of 1_000 tokens, a
+    // proposal requesting 100 tokens needs a conviction of
+    // 0.001 * 100 / (0.1 * (0.2 - 0.1)^2) = 100.
+    InstantiateMsg {
+        denom: UncheckedDenom::Native(DENOM.to_string()),
+        decay: Decimal::percent(90),
+        max_ratio: Decimal::percent(20),
+        weight: Decimal::permille(1),
+        pre_propose_info,
+        close_proposal_on_execution_failure: true,
+    }
+}
+
+/// Sets up a DAO with the conviction module. If `deposit_info` is
+/// set, proposals are created through a pre-propose module requiring
+/// that deposit.
+fn setup(deposit_info: Option<UncheckedDepositInfo>) -> Setup {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(WHALE), coins(10_000, DENOM))
+            .unwrap();
+    });
+    let code_id = app.store_code(dao_proposal_conviction_contract());
+    let pre_propose_info = match deposit_info {
+        Some(deposit_info) => PreProposeInfo::ModuleMayPropose {
+            info: ModuleInstantiateInfo {
+                code_id: app.store_code(dao_pre_propose_conviction_contract()),
+                msg: to_json_binary(&dao_pre_propose_conviction::InstantiateMsg {
+                    deposit_info: Some(deposit_info),
+                    submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                    extension: Empty::default(),
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "pre_propose_contract".to_string(),
+            },
+        },
+        None => PreProposeInfo::AnyoneMayPropose {},
+    };
+    let dao = instantiate_with_cw4_groups_governance(
+        &mut app,
+        code_id,
+        to_json_binary(&instantiate_msg(pre_propose_info)).unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(40),
+            },
+        ]),
+    );
+    app.send_tokens(Addr::unchecked(WHALE), dao.clone(), &coins(1_000, DENOM))
+        .unwrap();
+
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            &dao,
+            &dao_interface::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let module = modules.into_iter().next().unwrap().address;
+
+    Setup { app, dao, module }
+}
+
+fn propose(setup: &mut Setup, proposer: &str, amount: u128) -> u64 {
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked(proposer),
+            setup.module.clone(),
+            &ExecuteMsg::Propose(ConvictionProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                beneficiary: "carol".to_string(),
+                amount: Uint128::new(amount),
+                proposer: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    setup
+        .app
+        .wrap()
+        .query_wasm_smart(&setup.module, &QueryMsg::ProposalCount {})
+        .unwrap()
+}
+
+fn support(
+    setup: &mut Setup,
+    voter: &str,
+    proposal_id: u64,
+    amount: u128,
+) -> Result<(), ContractError> {
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked(voter),
+            setup.module.clone(),
+            &ExecuteMsg::Support {
+                proposal_id,
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+}
+
+fn execute(setup: &mut Setup, proposal_id: u64) -> Result<(), ContractError> {
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            setup.module.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+}
+
+fn close(setup: &mut Setup, sender: &str, proposal_id: u64) -> Result<(), ContractError> {
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked(sender),
+            setup.module.clone(),
+            &ExecuteMsg::Close { proposal_id },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+}
+
+fn query_proposal(setup: &Setup, proposal_id: u64) -> ProposalResponse {
+    setup
+        .app
+        .wrap()
+        .query_wasm_smart(&setup.module, &QueryMsg::Proposal { proposal_id })
+        .unwrap()
+}
+
+fn query_balance(setup: &Setup, address: &str) -> u128 {
+    setup
+        .app
+        .wrap()
+        .query_balance(address, DENOM)
+        .unwrap()
+        .amount
+        .u128()
+}
+
+fn advance_blocks(setup: &mut Setup, blocks: u64) {
+    setup.app.update_block(|b| b.height += blocks);
+}
+
+#[test]
+fn test_conviction_passes_and_executes() {
+    let mut setup = setup(None);
+    let id = propose(&mut setup, "bob", 100);
+
+    support(&mut setup, "alice", id, 60).unwrap();
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.support, Uint128::new(60));
+    assert_eq!(proposal.proposal.conviction, Uint128::zero());
+    assert_eq!(proposal.threshold, Some(Uint128::new(100)));
+
+    // Proposals may not be executed before they pass.
+    let err = execute(&mut setup, id).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+
+    advance_blocks(&mut setup, 1);
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.conviction, Uint128::new(60));
+
+    // 60 * (1 + 0.9) = 114 >= 100
+    advance_blocks(&mut setup, 1);
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(proposal.proposal.conviction, Uint128::new(114));
+
+    // Passed proposals no longer accept support.
+    let err = support(&mut setup, "bob", id, 40).unwrap_err();
+    assert_eq!(err, ContractError::NotOpen { id });
+
+    execute(&mut setup, id).unwrap();
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(query_balance(&setup, "carol"), 100);
+    assert_eq!(query_balance(&setup, setup.dao.as_str()), 900);
+
+    let err = execute(&mut setup, id).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+}
+
+#[test]
+fn test_conviction_decays() {
+    let mut setup = setup(None);
+    let id = propose(&mut setup, "bob", 100);
+
+    support(&mut setup, "alice", id, 60).unwrap();
+    advance_blocks(&mut setup, 1);
+    support(&mut setup, "alice", id, 0).unwrap();
+
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.support, Uint128::zero());
+    assert_eq!(proposal.proposal.conviction, Uint128::new(60));
+
+    advance_blocks(&mut setup, 1);
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.conviction, Uint128::new(54));
+}
+
+#[test]
+fn test_support_limited_by_voting_power() {
+    let mut setup = setup(None);
+    let first = propose(&mut setup, "bob", 100);
+    let second = propose(&mut setup, "bob", 100);
+
+    support(&mut setup, "alice", first, 40).unwrap();
+    let err = support(&mut setup, "alice", second, 30).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientVotingPower {
+            support: Uint128::new(70),
+            available: Uint128::new(60),
+        }
+    );
+    support(&mut setup, "alice", second, 20).unwrap();
+
+    // Updating support replaces the previous amount.
+    support(&mut setup, "alice", first, 10).unwrap();
+    support(&mut setup, "alice", second, 50).unwrap();
+
+    // Support for completed proposals is released.
+    close(&mut setup, "bob", first).unwrap();
+    support(&mut setup, "alice", second, 60).unwrap();
+
+    let list: SupportListResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.module,
+            &QueryMsg::ListSupport {
+                voter: "alice".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        list.support,
+        vec![SupportInfo {
+            proposal_id: second,
+            amount: Uint128::new(60),
+        }]
+    );
+
+    // Non-members have no voting power to lend.
+    let err = support(&mut setup, "carol", second, 1).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientVotingPower {
+            support: Uint128::new(1),
+            available: Uint128::zero(),
+        }
+    );
+}
+
+#[test]
+fn test_moving_stake_does_not_grow_conviction() {
+    let mut setup = setup(None);
+    let id = propose(&mut setup, "bob", 100);
+
+    let voting_module: Addr = setup
+        .app
+        .wrap()
+        .query_wasm_smart(&setup.dao, &dao_interface::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let group: Addr = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &dao_voting_cw4::msg::QueryMsg::GroupContract {},
+        )
+        .unwrap();
+
+    // Hook calls are only accepted from registered callers.
+    let err: ContractError = setup
+        .app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            setup.module.clone(),
+            &ExecuteMsg::MemberChangedHook(cw4::MemberChangedHookMsg { diffs: vec![] }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidHookSender {});
+
+    setup
+        .app
+        .execute_contract(
+            setup.dao.clone(),
+            setup.module.clone(),
+            &ExecuteMsg::UpdateVotingPowerHookCallers {
+                add: Some(vec![group.to_string()]),
+                remove: None,
+            },
+            &[],
+        )
+        .unwrap();
+    setup
+        .app
+        .execute_contract(
+            setup.dao.clone(),
+            group.clone(),
+            &cw4_group::msg::ExecuteMsg::AddHook {
+                addr: setup.module.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    support(&mut setup, "alice", id, 60).unwrap();
+    advance_blocks(&mut setup, 1);
+
+    // Alice's stake moves to dave, who supports the same proposal.
+    setup
+        .app
+        .execute_contract(
+            setup.dao.clone(),
+            group,
+            &cw4_group::msg::ExecuteMsg::UpdateMembers {
+                remove: vec!["alice".to_string()],
+                add: vec![cw4::Member {
+                    addr: "dave".to_string(),
+                    weight: 60,
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.support, Uint128::zero());
+    assert_eq!(proposal.proposal.conviction, Uint128::new(60));
+
+    advance_blocks(&mut setup, 1);
+    support(&mut setup, "dave", id, 60).unwrap();
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.support, Uint128::new(60));
+    assert_eq!(proposal.proposal.conviction, Uint128::new(54));
+
+    // Only 60 of support ever counts at once: 54 * 0.9 + 60 = 108.
+    // Had alice's support been kept, it would be 114 * 0.9 + 120.
+    advance_blocks(&mut setup, 1);
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.conviction, Uint128::new(108));
+
+    let list: SupportListResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.module,
+            &QueryMsg::ListSupport {
+                voter: "alice".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(list.support, vec![]);
+}
+
+#[test]
+fn test_large_requests_never_pass() {
+    let mut setup = setup(None);
+    // Requests max_ratio of the treasury.
+    let id = propose(&mut setup, "bob", 200);
+
+    support(&mut setup, "alice", id, 60).unwrap();
+    support(&mut setup, "bob", id, 40).unwrap();
+    advance_blocks(&mut setup, 1_000);
+
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.threshold, None);
+    let err = execute(&mut setup, id).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+}
+
+#[test]
+fn test_close() {
+    let mut setup = setup(None);
+    let id = propose(&mut setup, "bob", 100);
+
+    let err = close(&mut setup, "alice", id).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let dao = setup.dao.to_string();
+    close(&mut setup, &dao, id).unwrap();
+    let proposal = query_proposal(&setup, id);
+    assert_eq!(proposal.proposal.status, Status::Closed);
+
+    let err = close(&mut setup, "bob", id).unwrap_err();
+    assert_eq!(err, ContractError::NotOpen { id });
+    let err = support(&mut setup, "alice", id, 60).unwrap_err();
+    assert_eq!(err, ContractError::NotOpen { id });
+}
+
+#[test]
+fn test_invalid_params() {
+    let mut app = App::default();
+    let code_id = app.store_code(dao_proposal_conviction_contract());
+
+    let mut instantiate = |msg: InstantiateMsg| -> ContractError {
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked("dao"),
+            &msg,
+            &[],
+            "conviction",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+
+    let mut msg = instantiate_msg(PreProposeInfo::AnyoneMayPropose {});
+    msg.decay = Decimal::one();
+    assert_eq!(instantiate(msg), ContractError::InvalidDecay {});
+
+    let mut msg = instantiate_msg(PreProposeInfo::AnyoneMayPropose {});
+    msg.max_ratio = Decimal::zero();
+    assert_eq!(instantiate(msg), ContractError::InvalidMaxRatio {});
+
+    let mut msg = instantiate_msg(PreProposeInfo::AnyoneMayPropose {});
+    msg.weight = Decimal::zero();
+    assert_eq!(instantiate(msg), ContractError::ZeroWeight {});
+}
+
+#[test]
+fn test_pre_propose_deposit() {
+    let mut setup = setup(Some(UncheckedDepositInfo {
+        denom: DepositToken::Token {
+            denom: UncheckedDenom::Native(DENOM.to_string()),
+        },
+        amount: Uint128::new(10),
+        refund_policy: DepositRefundPolicy::OnlyPassed,
+        scaling: None,
+    }));
+    let pre_propose = match setup
+        .app
+        .wrap()
+        .query_wasm_smart(&setup.module, &QueryMsg::ProposalCreationPolicy {})
+        .unwrap()
+    {
+        ProposalCreationPolicy::Module { addr } => addr,
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+    };
+    setup
+        .app
+        .send_tokens(
+            Addr::unchecked(WHALE),
+            Addr::unchecked("bob"),
+            &coins(10, DENOM),
+        )
+        .unwrap();
+
+    // Proposals may no longer be created directly.
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("bob"),
+            setup.module.clone(),
+            &ExecuteMsg::Propose(ConvictionProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                beneficiary: "carol".to_string(),
+                amount: Uint128::new(100),
+                proposer: None,
+            }),
+            &[],
+        )
+        .unwrap_err();
+
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("bob"),
+            pre_propose,
+            &dao_pre_propose_conviction::ExecuteMsg::Propose {
+                msg: dao_pre_propose_conviction::ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    beneficiary: "carol".to_string(),
+                    amount: Uint128::new(100),
+                },
+            },
+            &coins(10, DENOM),
+        )
+        .unwrap();
+    assert_eq!(query_balance(&setup, "bob"), 0);
+
+    let proposal = query_proposal(&setup, 1);
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("bob"));
+
+    support(&mut setup, "alice", 1, 60).unwrap();
+    advance_blocks(&mut setup, 2);
+    execute(&mut setup, 1).unwrap();
+
+    // Executed proposals have their deposits refunded.
+    assert_eq!(query_balance(&setup, "bob"), 10);
+    assert_eq!(query_balance(&setup, "carol"), 100);
+}
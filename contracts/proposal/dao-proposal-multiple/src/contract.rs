@@ -2,7 +2,7 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     to_json_binary, Addr, Attribute, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdResult, Storage, SubMsg, WasmMsg,
+    Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 
 use cw2::set_contract_version;
@@ -10,12 +10,17 @@ use cw_hooks::Hooks;
 use cw_storage_plus::Bound;
 use cw_utils::{parse_reply_instantiate_data, Duration};
 use dao_hooks::proposal::{
-    new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
+    new_proposal_hooks, proposal_amended_hooks, proposal_completed_hooks,
+    proposal_status_changed_hooks,
 };
 use dao_hooks::vote::new_vote_hooks;
-use dao_interface::voting::IsActiveResponse;
+use dao_interface::{treasury::spending_cap_exceeded_event, voting::IsActiveResponse};
 use dao_voting::{
-    multiple_choice::{MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy},
+    amendment::{check_voting_open, AmendmentConfig, AmendmentError},
+    multiple_choice::{
+        MultipleChoiceOptions, MultipleChoiceSelection, MultipleChoiceVote, MultipleChoiceVotes,
+        RankedChoiceVotes, VotingStrategy,
+    },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     proposal::{MultipleChoiceProposeMsg as ProposeMsg, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
     reply::{
@@ -23,16 +28,24 @@ use dao_voting::{
     },
     status::Status,
     veto::{VetoConfig, VetoError},
-    voting::{get_total_power, get_voting_power, validate_voting_period},
+    voting::{
+        get_delegated_voting_power, get_delegations, get_total_power, get_voting_power,
+        validate_voting_period,
+    },
 };
 
 use crate::{msg::MigrateMsg, state::CREATION_POLICY};
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::{MultipleChoiceProposal, VoteResult},
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    query::{
+        ProposalListResponse, ProposalResponse, RevisionListResponse, VoteInfo, VoteListResponse,
+        VoteResponse,
+    },
     state::{
-        Ballot, Config, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS,
+        Ballot, Config, Revision, AMENDMENT_CONFIG, BALLOTS, CONFIG, DELEGATION_MODULE,
+        DELEGATION_OVERRIDES, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, RANKED_VOTES, REVISIONS,
+        VOTE_HOOKS,
     },
     ContractError,
 };
@@ -40,6 +53,11 @@ use crate::{
 pub const CONTRACT_NAME: &str = "crates.io:dao-proposal-multiple";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The default and maximum number of rankings counted by a single
+/// `TallyRankedVotes` message.
+const DEFAULT_RUNOFF_LIMIT: u32 = 100;
+const MAX_RUNOFF_LIMIT: u32 = 500;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -49,7 +67,7 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    msg.voting_strategy.validate()?;
+    validate_voting_strategy(&msg.voting_strategy)?;
 
     let dao = info.sender;
 
@@ -101,7 +119,63 @@ pub fn execute(
             proposal_id,
             vote,
             rationale,
-        } => execute_vote(deps, env, info.sender, proposal_id, vote, rationale),
+        } => execute_vote(
+            deps,
+            env,
+            info.sender,
+            proposal_id,
+            vote,
+            None,
+            None,
+            rationale,
+        ),
+        ExecuteMsg::RankedVote {
+            proposal_id,
+            ranking,
+            rationale,
+        } => {
+            let vote = MultipleChoiceVote {
+                option_id: ranking
+                    .first()
+                    .copied()
+                    .ok_or(ContractError::InvalidVote {})?,
+            };
+            execute_vote(
+                deps,
+                env,
+                info.sender,
+                proposal_id,
+                vote,
+                Some(ranking),
+                None,
+                rationale,
+            )
+        }
+        ExecuteMsg::ApprovalVote {
+            proposal_id,
+            selections,
+            rationale,
+        } => {
+            let vote = MultipleChoiceVote {
+                option_id: selections
+                    .first()
+                    .map(|selection| selection.option_id)
+                    .ok_or(ContractError::InvalidVote {})?,
+            };
+            execute_vote(
+                deps,
+                env,
+                info.sender,
+                proposal_id,
+                vote,
+                None,
+                Some(selections),
+                rationale,
+            )
+        }
+        ExecuteMsg::TallyRankedVotes { proposal_id, limit } => {
+            execute_tally_ranked_votes(deps, env, proposal_id, limit)
+        }
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
@@ -129,6 +203,12 @@ pub fn execute(
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
         }
+        ExecuteMsg::UpdateDelegationModule { module } => {
+            execute_update_delegation_module(deps, info, module)
+        }
+        ExecuteMsg::UpdateAmendmentConfig { config } => {
+            execute_update_amendment_config(deps, info, config)
+        }
         ExecuteMsg::AddProposalHook { address } => {
             execute_add_proposal_hook(deps, env, info, address)
         }
@@ -143,6 +223,12 @@ pub fn execute(
             proposal_id,
             rationale,
         } => execute_update_rationale(deps, info, proposal_id, rationale),
+        ExecuteMsg::Amend {
+            proposal_id,
+            title,
+            description,
+            choices,
+        } => execute_amend(deps, env, info, proposal_id, title, description, choices),
     }
 }
 
@@ -198,7 +284,16 @@ pub fn execute_propose(
     // Validate options.
     let checked_multiple_choice_options = choices.into_checked()?.options;
 
-    let expiration = config.max_voting_period.after(&env.block);
+    // If proposals have an amendment window voting opens, and the
+    // voting period begins, once it has passed.
+    let amendment_config = AMENDMENT_CONFIG.may_load(deps.storage)?;
+    if amendment_config.is_some() && vote.is_some() {
+        return Err(AmendmentError::AutoVoteNotAllowed {}.into());
+    }
+    let voting_start = amendment_config
+        .as_ref()
+        .map_or_else(|| env.block.clone(), |c| c.voting_start(&env.block));
+    let expiration = config.max_voting_period.after(&voting_start);
     let total_power = get_total_power(deps.as_ref(), &config.dao, None)?;
 
     let proposal = {
@@ -208,15 +303,26 @@ pub fn execute_propose(
             description,
             proposer: proposer.clone(),
             start_height: env.block.height,
-            min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+            min_voting_period: config.min_voting_period.map(|min| min.after(&voting_start)),
             expiration,
             voting_strategy: config.voting_strategy,
             total_power,
             status: Status::Open,
             votes: MultipleChoiceVotes::zero(checked_multiple_choice_options.len()),
+            ranked_votes: match config.voting_strategy {
+                VotingStrategy::RankedChoice { .. } => Some(RankedChoiceVotes::default()),
+                _ => None,
+            },
+            voted_power: match config.voting_strategy {
+                VotingStrategy::Approval { .. } => Some(Uint128::zero()),
+                _ => None,
+            },
             allow_revoting: config.allow_revoting,
             choices: checked_multiple_choice_options,
             veto: config.veto,
+            amendable_until: amendment_config
+                .as_ref()
+                .map(|c| c.window_expiration(&env.block)),
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -249,6 +355,24 @@ pub fn execute_propose(
 
     PROPOSALS.save(deps.storage, id, &proposal)?;
 
+    // Record the initial contents of amendable proposals as their
+    // first revision.
+    if proposal.amendable_until.is_some() {
+        REVISIONS.save(
+            deps.storage,
+            (id, 0),
+            &Revision {
+                revision: 0,
+                author: proposer.clone(),
+                height: env.block.height,
+                time: env.block.time,
+                title: proposal.title.clone(),
+                description: proposal.description.clone(),
+                choices: proposal.choices.clone(),
+            },
+        )?;
+    }
+
     let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
 
     let sender = info.sender.clone();
@@ -261,6 +385,8 @@ pub fn execute_propose(
             proposer.clone(),
             id,
             vote.vote,
+            None,
+            None,
             vote.rationale.clone(),
         )?;
         (
@@ -354,7 +480,7 @@ pub fn execute_veto(
     // Add prepropose / deposit module hook which will handle deposit refunds.
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
     let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status, false)?;
 
     Ok(Response::new()
         .add_attribute("action", "veto")
@@ -363,12 +489,15 @@ pub fn execute_veto(
         .add_submessages(proposal_completed_hooks))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_vote(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     sender: Addr,
     proposal_id: u64,
     vote: MultipleChoiceVote,
+    ranking: Option<Vec<u32>>,
+    selections: Option<Vec<MultipleChoiceSelection>>,
     rationale: Option<String>,
 ) -> Result<Response<Empty>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
@@ -381,6 +510,41 @@ pub fn execute_vote(
         return Err(ContractError::InvalidVote {});
     }
 
+    // Ballots on ranked choice proposals always carry a ranking and
+    // ballots on approval proposals always carry selections. A plain
+    // vote ranks or selects a single option.
+    if ranking.is_some() && !matches!(prop.voting_strategy, VotingStrategy::RankedChoice { .. }) {
+        return Err(ContractError::RankedVoteNotAllowed {});
+    }
+    if selections.is_some() && !matches!(prop.voting_strategy, VotingStrategy::Approval { .. }) {
+        return Err(ContractError::ApprovalVoteNotAllowed {});
+    }
+    let (ranking, selections) = match prop.voting_strategy {
+        VotingStrategy::SingleChoice { .. } => (None, None),
+        VotingStrategy::RankedChoice { .. } => {
+            let ranking = ranking.unwrap_or_else(|| vec![vote.option_id]);
+            RankedChoiceVotes::validate_ranking(&ranking, prop.choices.len())
+                .map_err(|_| ContractError::InvalidVote {})?;
+            (Some(ranking), None)
+        }
+        VotingStrategy::Approval { max_selections, .. } => {
+            let selections = selections.unwrap_or_else(|| {
+                vec![MultipleChoiceSelection {
+                    option_id: vote.option_id,
+                    weight: None,
+                }]
+            });
+            if selections.len() > max_selections as usize {
+                return Err(ContractError::TooManySelections {
+                    max: max_selections,
+                });
+            }
+            MultipleChoiceVotes::validate_selections(&selections, prop.choices.len())
+                .map_err(|_| ContractError::InvalidVote {})?;
+            (None, Some(selections))
+        }
+    };
+
     // Allow voting on proposals until they expire.
     // Voting on a non-open proposal will never change
     // their outcome as if an outcome has been determined,
@@ -391,49 +555,76 @@ pub fn execute_vote(
     if prop.expiration.is_expired(&env.block) {
         return Err(ContractError::Expired { id: proposal_id });
     }
+    check_voting_open(&prop.amendable_until, &env.block)?;
+
+    let delegation_module = DELEGATION_MODULE.may_load(deps.storage)?;
 
-    let vote_power = get_voting_power(
+    let mut vote_power = get_voting_power(
         deps.as_ref(),
         sender.clone(),
         &config.dao,
         Some(prop.start_height),
     )?;
+    if let Some(delegation_module) = &delegation_module {
+        // Delegates vote with the voting power delegated to them,
+        // less that of delegators who have already voted themselves.
+        let delegated_power = get_delegated_voting_power(
+            deps.as_ref(),
+            &sender,
+            delegation_module,
+            Some(prop.start_height),
+        )?;
+        let overridden_power = DELEGATION_OVERRIDES
+            .may_load(deps.storage, (proposal_id, &sender))?
+            .unwrap_or_default();
+        vote_power = vote_power.checked_add(delegated_power.saturating_sub(overridden_power))?;
+    }
     if vote_power.is_zero() {
         return Err(ContractError::NotRegistered {});
     }
 
-    BALLOTS.update(deps.storage, (proposal_id, &sender), |bal| match bal {
-        Some(current_ballot) => {
-            if prop.allow_revoting {
-                if current_ballot.vote == vote {
-                    // Don't allow casting the same vote more than
-                    // once. This seems liable to be confusing
-                    // behavior.
-                    Err(ContractError::AlreadyCast {})
-                } else {
-                    // Remove the old vote if this is a re-vote.
-                    prop.votes
-                        .remove_vote(current_ballot.vote, current_ballot.power)?;
-                    Ok(Ballot {
-                        power: vote_power,
-                        vote,
-                        rationale: rationale.clone(),
-                    })
-                }
-            } else {
-                Err(ContractError::AlreadyVoted {})
-            }
+    let current_ballot = BALLOTS.may_load(deps.storage, (proposal_id, &sender))?;
+    let first_vote = current_ballot.is_none();
+    if let Some(current_ballot) = current_ballot {
+        if !prop.allow_revoting {
+            return Err(ContractError::AlreadyVoted {});
         }
-        None => Ok(Ballot {
-            vote,
-            power: vote_power,
-            rationale: rationale.clone(),
-        }),
-    })?;
+        if current_ballot.vote == vote
+            && current_ballot.ranking == ranking
+            && current_ballot.selections == selections
+        {
+            // Don't allow casting the same vote more than
+            // once. This seems liable to be confusing
+            // behavior.
+            return Err(ContractError::AlreadyCast {});
+        }
+        // Remove the old vote if this is a re-vote.
+        remove_ballot(deps.storage, &mut prop, proposal_id, &current_ballot)?;
+    }
+    let ballot = Ballot {
+        vote,
+        power: vote_power,
+        rationale: rationale.clone(),
+        ranking: ranking.clone(),
+        selections: selections.clone(),
+    };
+    BALLOTS.save(deps.storage, (proposal_id, &sender), &ballot)?;
+
+    // A delegator voting for the first time overrides the votes their
+    // delegates cast on their behalf.
+    if let (true, Some(delegation_module)) = (first_vote, &delegation_module) {
+        override_delegate_votes(
+            deps.branch(),
+            &mut prop,
+            proposal_id,
+            &sender,
+            delegation_module,
+        )?;
+    }
 
     let old_status = prop.status;
 
-    prop.votes.add_vote(vote, vote_power)?;
+    add_ballot(deps.storage, &mut prop, proposal_id, &ballot)?;
     prop.update_status(&env.block)?;
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
     let new_status = prop.status;
@@ -444,12 +635,27 @@ pub fn execute_vote(
         old_status.to_string(),
         new_status.to_string(),
     )?;
+    // Ranked and approval votes are reported as a comma separated
+    // list of the ranked or selected options.
+    let position = match (&ranking, &selections) {
+        (Some(ranking), _) => ranking
+            .iter()
+            .map(|option_id| option_id.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+        (None, Some(selections)) => selections
+            .iter()
+            .map(|selection| selection.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+        (None, None) => vote.to_string(),
+    };
     let vote_hooks = new_vote_hooks(
         VOTE_HOOKS,
         deps.storage,
         proposal_id,
         sender.to_string(),
-        vote.to_string(),
+        position.clone(),
     )?;
     Ok(Response::default()
         .add_submessages(change_hooks)
@@ -457,7 +663,7 @@ pub fn execute_vote(
         .add_attribute("action", "vote")
         .add_attribute("sender", sender)
         .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("position", vote.to_string())
+        .add_attribute("position", position)
         .add_attribute(
             "rationale",
             rationale.unwrap_or_else(|| "_none".to_string()),
@@ -465,6 +671,181 @@ pub fn execute_vote(
         .add_attribute("status", prop.status.to_string()))
 }
 
+/// Removes the voting power `delegator` delegated as of the proposal's
+/// start height from their delegates' ballots, and records it so that
+/// delegates who have yet to vote do not count it.
+fn override_delegate_votes(
+    deps: DepsMut,
+    prop: &mut MultipleChoiceProposal,
+    proposal_id: u64,
+    delegator: &Addr,
+    delegation_module: &Addr,
+) -> Result<(), ContractError> {
+    let delegations = get_delegations(
+        deps.as_ref(),
+        delegator,
+        delegation_module,
+        Some(prop.start_height),
+    )?;
+    for delegation in delegations {
+        if delegation.power.is_zero() {
+            continue;
+        }
+        DELEGATION_OVERRIDES.update(
+            deps.storage,
+            (proposal_id, &delegation.delegate),
+            |overridden| -> StdResult<_> {
+                Ok(overridden
+                    .unwrap_or_default()
+                    .checked_add(delegation.power)?)
+            },
+        )?;
+        if let Some(mut ballot) =
+            BALLOTS.may_load(deps.storage, (proposal_id, &delegation.delegate))?
+        {
+            // Re-tally the whole ballot at its reduced power so that
+            // weighted selections round the same way as a fresh
+            // ballot would.
+            let removed = ballot.power.min(delegation.power);
+            remove_ballot(deps.storage, prop, proposal_id, &ballot)?;
+            ballot.power -= removed;
+            add_ballot(deps.storage, prop, proposal_id, &ballot)?;
+            BALLOTS.save(deps.storage, (proposal_id, &delegation.delegate), &ballot)?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds `ballot` to the proposal's tallies and, if it is ranked, its
+/// voting power to `RANKED_VOTES`.
+fn add_ballot(
+    storage: &mut dyn Storage,
+    prop: &mut MultipleChoiceProposal,
+    proposal_id: u64,
+    ballot: &Ballot,
+) -> StdResult<()> {
+    prop.add_vote(ballot)?;
+    match &ballot.ranking {
+        Some(ranking) if !ballot.power.is_zero() => {
+            let key = RankedChoiceVotes::ranking_key(ranking);
+            RANKED_VOTES.update(storage, (proposal_id, key.as_slice()), |power| {
+                power
+                    .unwrap_or_default()
+                    .checked_add(ballot.power)
+                    .map_err(StdError::overflow)
+            })?;
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Removes `ballot` from the proposal's tallies and, if it is ranked,
+/// its voting power from `RANKED_VOTES`.
+fn remove_ballot(
+    storage: &mut dyn Storage,
+    prop: &mut MultipleChoiceProposal,
+    proposal_id: u64,
+    ballot: &Ballot,
+) -> StdResult<()> {
+    prop.remove_vote(ballot)?;
+    if let Some(ranking) = &ballot.ranking {
+        let key = RankedChoiceVotes::ranking_key(ranking);
+        let power = RANKED_VOTES
+            .may_load(storage, (proposal_id, key.as_slice()))?
+            .unwrap_or_default()
+            .checked_sub(ballot.power)
+            .map_err(StdError::overflow)?;
+        if power.is_zero() {
+            RANKED_VOTES.remove(storage, (proposal_id, key.as_slice()));
+        } else {
+            RANKED_VOTES.save(storage, (proposal_id, key.as_slice()), &power)?;
+        }
+    }
+    Ok(())
+}
+
+/// Counts up to `limit` of an expired ranked choice proposal's
+/// rankings in its instant-runoff, continuing from where the previous
+/// call stopped. Rankings are counted a page at a time so that the
+/// runoff completes however many distinct rankings were cast.
+pub fn execute_tally_ranked_votes(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Only proposals without a first preference majority need a
+    // runoff, and it may only be counted once no more votes may be
+    // cast.
+    if !matches!(prop.calculate_vote_result()?, VoteResult::RunoffPending)
+        || prop.status != Status::Open
+    {
+        return Err(ContractError::NoRunoff { id: proposal_id });
+    }
+    if !prop.expiration.is_expired(&env.block) {
+        return Err(ContractError::NotExpired {});
+    }
+
+    let num_choices = prop.choices.len();
+    let mut ranked_votes = prop
+        .ranked_votes
+        .take()
+        .ok_or(ContractError::NoRunoff { id: proposal_id })?;
+    let mut remaining = limit.unwrap_or(DEFAULT_RUNOFF_LIMIT).min(MAX_RUNOFF_LIMIT) as usize;
+    let mut counted = 0;
+    while remaining > 0 && ranked_votes.outcome.is_none() {
+        let start = ranked_votes
+            .cursor
+            .as_ref()
+            .map(|cursor| Bound::exclusive(cursor.as_slice()));
+        let page = RANKED_VOTES
+            .prefix(proposal_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(remaining)
+            .collect::<StdResult<Vec<_>>>()?;
+        let round_complete = page.len() < remaining;
+        remaining -= page.len();
+        counted += page.len();
+        for (key, power) in page {
+            ranked_votes.count(
+                &RankedChoiceVotes::ranking_from_key(&key),
+                power,
+                num_choices,
+            )?;
+            ranked_votes.cursor = Some(key.into());
+        }
+        if round_complete {
+            ranked_votes.end_round(num_choices);
+        }
+    }
+    let complete = ranked_votes.outcome.is_some();
+    prop.ranked_votes = Some(ranked_votes);
+
+    let old_status = prop.status;
+    prop.update_status(&env.block)?;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    let change_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(change_hooks)
+        .add_attribute("action", "tally_ranked_votes")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("counted", counted.to_string())
+        .add_attribute("complete", complete.to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
 pub fn execute_execute(
     deps: DepsMut,
     env: Env,
@@ -536,7 +917,10 @@ pub fn execute_execute(
     let vote_result = prop.calculate_vote_result()?;
     match vote_result {
         VoteResult::Tie => Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
+        VoteResult::RunoffPending => Err(ContractError::NotPassed {}),
         VoteResult::SingleWinner(winning_choice) => {
+            let reports_execution =
+                !winning_choice.msgs.is_empty() && config.close_proposal_on_execution_failure;
             let response = if !winning_choice.msgs.is_empty() {
                 let execute_message = WasmMsg::Execute {
                     contract_addr: config.dao.to_string(),
@@ -546,9 +930,12 @@ pub fn execute_execute(
                     funds: vec![],
                 };
                 match config.close_proposal_on_execution_failure {
+                    // The reply reports whether execution failed to
+                    // the pre-propose module, so it is needed on
+                    // success too.
                     true => {
                         let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
-                        Response::default().add_submessage(SubMsg::reply_on_error(
+                        Response::default().add_submessage(SubMsg::reply_always(
                             execute_message,
                             masked_proposal_id,
                         ))
@@ -567,10 +954,15 @@ pub fn execute_execute(
                 prop.status.to_string(),
             )?;
 
-            // Add prepropose / deposit module hook which will handle deposit refunds.
-            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-            let proposal_completed_hooks =
-                proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+            // Add prepropose / deposit module hook which will handle
+            // deposit refunds. If the execution reply was requested it
+            // fires the hook once the outcome of execution is known.
+            let proposal_completed_hooks = if reports_execution {
+                vec![]
+            } else {
+                let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+                proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status, false)?
+            };
 
             Ok(response
                 .add_submessages(proposal_status_changed_hooks)
@@ -612,8 +1004,12 @@ pub fn execute_close(
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    let proposal_completed_hooks = proposal_completed_hooks(
+        proposal_creation_policy,
+        proposal_id,
+        prop.status,
+        prop.failed_quorum(),
+    )?;
 
     Ok(Response::default()
         .add_submessages(proposal_status_changed_hooks)
@@ -643,7 +1039,7 @@ pub fn execute_update_config(
         return Err(ContractError::Unauthorized {});
     }
 
-    voting_strategy.validate()?;
+    validate_voting_strategy(&voting_strategy)?;
 
     let dao = deps.api.addr_validate(&dao)?;
 
@@ -654,6 +1050,9 @@ pub fn execute_update_config(
     if let Some(veto_config) = &veto {
         veto_config.validate(&deps.as_ref(), &max_voting_period)?;
     };
+    if let Some(amendment_config) = AMENDMENT_CONFIG.may_load(deps.storage)? {
+        amendment_config.validate(&max_voting_period)?;
+    }
 
     CONFIG.save(
         deps.storage,
@@ -694,6 +1093,161 @@ pub fn execute_update_proposal_creation_policy(
         .add_attribute("new_policy", format!("{initial_policy:?}")))
 }
 
+pub fn execute_update_delegation_module(
+    deps: DepsMut,
+    info: MessageInfo,
+    module: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &module {
+        Some(module) => {
+            let module = deps.api.addr_validate(module)?;
+            DELEGATION_MODULE.save(deps.storage, &module)?;
+        }
+        None => DELEGATION_MODULE.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_delegation_module")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "delegation_module",
+            module.unwrap_or_else(|| "_none".to_string()),
+        ))
+}
+
+pub fn execute_update_amendment_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    amendment_config: Option<AmendmentConfig>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &amendment_config {
+        Some(amendment_config) => {
+            amendment_config.validate(&config.max_voting_period)?;
+            AMENDMENT_CONFIG.save(deps.storage, amendment_config)?;
+        }
+        None => AMENDMENT_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_amendment_config")
+        .add_attribute("sender", info.sender)
+        .add_attribute("amendments_enabled", amendment_config.is_some().to_string()))
+}
+
+pub fn execute_amend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    title: Option<String>,
+    description: Option<String>,
+    choices: Option<MultipleChoiceOptions>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block)?;
+    match prop.amendable_until {
+        Some(until) if prop.status == Status::Open && !until.is_expired(&env.block) => (),
+        _ => return Err(AmendmentError::WindowClosed {}.into()),
+    }
+
+    // Sponsors are determined by the current amendment config and
+    // their voting power when the proposal was created.
+    if info.sender != prop.proposer {
+        let is_sponsor = match AMENDMENT_CONFIG.may_load(deps.storage)? {
+            Some(amendment_config) => {
+                let power = get_voting_power(
+                    deps.as_ref(),
+                    info.sender.clone(),
+                    &config.dao,
+                    Some(prop.start_height),
+                )?;
+                amendment_config.is_sponsor(power, prop.total_power)
+            }
+            None => false,
+        };
+        if !is_sponsor {
+            return Err(AmendmentError::Unauthorized {}.into());
+        }
+    }
+
+    if title.is_none() && description.is_none() && choices.is_none() {
+        return Err(AmendmentError::NoChanges {}.into());
+    }
+    if let Some(title) = title {
+        prop.title = title;
+    }
+    if let Some(description) = description {
+        prop.description = description;
+    }
+    if let Some(choices) = choices {
+        // No votes may be cast during the amendment window so the
+        // tally is simply resized to the new options.
+        prop.choices = choices.into_checked()?.options;
+        prop.votes = MultipleChoiceVotes::zero(prop.choices.len());
+    }
+
+    // Amended proposals are subject to the same size limit as new
+    // ones. See `execute_propose`.
+    let proposal_size = cosmwasm_std::to_json_vec(&prop)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let revision = REVISIONS
+        .prefix(proposal_id)
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    REVISIONS.save(
+        deps.storage,
+        (proposal_id, revision),
+        &Revision {
+            revision,
+            author: info.sender.clone(),
+            height: env.block.height,
+            time: env.block.time,
+            title: prop.title,
+            description: prop.description,
+            choices: prop.choices,
+        },
+    )?;
+
+    let hooks = proposal_amended_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        revision,
+        info.sender.as_str(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "amend")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("revision", revision.to_string()))
+}
+
 pub fn execute_update_rationale(
     deps: DepsMut,
     info: MessageInfo,
@@ -834,6 +1388,17 @@ pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
     Ok(PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1)
 }
 
+fn validate_voting_strategy(voting_strategy: &VotingStrategy) -> Result<(), ContractError> {
+    voting_strategy.validate()?;
+    if let VotingStrategy::Approval {
+        max_selections: 0, ..
+    } = voting_strategy
+    {
+        return Err(ContractError::ZeroMaxSelections {});
+    }
+    Ok(())
+}
+
 pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
     let id: u64 = next_proposal_id(store)?;
     PROPOSAL_COUNT.save(store, &id)?;
@@ -864,6 +1429,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::DelegationModule {} => to_json_binary(&DELEGATION_MODULE.may_load(deps.storage)?),
+        QueryMsg::Turnout { proposal_id } => query_turnout(deps, env, proposal_id),
+        QueryMsg::AmendmentConfig {} => to_json_binary(&AMENDMENT_CONFIG.may_load(deps.storage)?),
+        QueryMsg::ListRevisions {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_revisions(deps, proposal_id, start_after, limit),
         QueryMsg::Dao {} => query_dao(deps),
     }
 }
@@ -943,6 +1516,8 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
         vote: ballot.vote,
         power: ballot.power,
         rationale: ballot.rationale,
+        ranking: ballot.ranking,
+        selections: ballot.selections,
     });
     to_json_binary(&VoteResponse { vote })
 }
@@ -970,6 +1545,8 @@ pub fn query_list_votes(
                 vote: ballot.vote,
                 power: ballot.power,
                 rationale: ballot.rationale,
+                ranking: ballot.ranking,
+                selections: ballot.selections,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -977,6 +1554,30 @@ pub fn query_list_votes(
     to_json_binary(&VoteListResponse { votes })
 }
 
+pub fn query_turnout(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_json_binary(&proposal.turnout(&env.block)?)
+}
+
+pub fn query_list_revisions(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let min = start_after.map(Bound::<u64>::exclusive);
+
+    let revisions = REVISIONS
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| item.map(|(_, revision)| revision))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&RevisionListResponse { revisions })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_json_binary(&dao_interface::voting::InfoResponse { info })
@@ -986,21 +1587,36 @@ pub fn query_info(deps: Deps) -> StdResult<Binary> {
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
+        // Received whether or not execution failed.
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
-            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
-                Some(mut prop) => {
+            let mut prop = PROPOSALS
+                .may_load(deps.storage, proposal_id)?
+                .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+            let response = match msg.result.into_result() {
+                Ok(_) => Response::new(),
+                Err(error) => {
                     prop.status = Status::ExecutionFailed;
-                    Ok(prop)
+                    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+                    Response::new()
+                        .add_attribute("proposal execution failed", proposal_id.to_string())
+                        .add_events(spending_cap_exceeded_event(proposal_id, &error))
+                        .add_attribute("error", error)
                 }
-                None => Err(ContractError::NoSuchProposal { id: proposal_id }),
-            })?;
+            };
 
-            Ok(Response::new()
-                .add_attribute("proposal execution failed", proposal_id.to_string())
-                .add_attribute(
-                    "error",
-                    msg.result.into_result().err().unwrap_or("None".to_string()),
-                ))
+            // Add prepropose / deposit module hook which will handle
+            // deposit refunds.
+            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            let proposal_completed_hooks = proposal_completed_hooks(
+                proposal_creation_policy,
+                proposal_id,
+                prop.status,
+                false,
+            )?;
+
+            Ok(response.add_submessages(proposal_completed_hooks))
         }
         TaggedReplyId::FailedProposalHook(idx) => {
             let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
@@ -1047,6 +1663,10 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             };
             Ok(Response::new().add_attribute("failed_prepropose_hook", format!("{addr}")))
         }
+        // This module does not support execution plans.
+        TaggedReplyId::ProposalBatchExecution { .. } => {
+            Err(ContractError::InvalidReplyID { id: msg.id })
+        }
     }
 }
 
@@ -1,7 +1,9 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{OverflowError, StdError};
 use cw_hooks::HookError;
 use cw_utils::ParseReplyError;
-use dao_voting::{reply::error::TagError, threshold::ThresholdError, veto::VetoError};
+use dao_voting::{
+    amendment::AmendmentError, reply::error::TagError, threshold::ThresholdError, veto::VetoError,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,6 +11,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+
     #[error(transparent)]
     ParseReplyError(#[from] ParseReplyError),
 
@@ -18,6 +23,9 @@ pub enum ContractError {
     #[error(transparent)]
     VetoError(#[from] VetoError),
 
+    #[error(transparent)]
+    AmendmentError(#[from] AmendmentError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -60,6 +68,9 @@ pub enum ContractError {
     #[error("Proposal is not expired.")]
     NotExpired {},
 
+    #[error("Proposal ({id}) has no runoff to count.")]
+    NoRunoff { id: u64 },
+
     #[error("Only rejected proposals may be closed.")]
     WrongCloseStatus {},
 
@@ -78,6 +89,18 @@ pub enum ContractError {
     #[error("Invalid vote selected.")]
     InvalidVote {},
 
+    #[error("Ranked votes may only be cast on ranked choice proposals.")]
+    RankedVoteNotAllowed {},
+
+    #[error("Approval votes may only be cast on approval proposals.")]
+    ApprovalVoteNotAllowed {},
+
+    #[error("At most ({max}) options may be selected.")]
+    TooManySelections { max: u32 },
+
+    #[error("Approval voting must allow at least one selection.")]
+    ZeroMaxSelections {},
+
     #[error("Must have voting power to propose.")]
     MustHaveVotingPower {},
 
@@ -2,7 +2,10 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cw_utils::Duration;
 use dao_dao_macros::proposal_module_query;
 use dao_voting::{
-    multiple_choice::{MultipleChoiceVote, VotingStrategy},
+    amendment::AmendmentConfig,
+    multiple_choice::{
+        MultipleChoiceOptions, MultipleChoiceSelection, MultipleChoiceVote, VotingStrategy,
+    },
     pre_propose::PreProposeInfo,
     proposal::MultipleChoiceProposeMsg,
     veto::VetoConfig,
@@ -64,6 +67,44 @@ pub enum ExecuteMsg {
         /// the vote.
         rationale: Option<String>,
     },
+    /// Votes on a ranked choice proposal by ranking options in order
+    /// of preference. Options may be left unranked. Voting power is
+    /// determined by the DAO's voting power module.
+    RankedVote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The option IDs in order of preference.
+        ranking: Vec<u32>,
+        /// An optional rationale for why this vote was cast. This can
+        /// be updated, set, or removed later by the address casting
+        /// the vote.
+        rationale: Option<String>,
+    },
+    /// Votes on an approval proposal by selecting one or more
+    /// options, optionally splitting voting power between them by
+    /// weight. Voting power is determined by the DAO's voting power
+    /// module.
+    ApprovalVote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The selected options.
+        selections: Vec<MultipleChoiceSelection>,
+        /// An optional rationale for why this vote was cast. This can
+        /// be updated, set, or removed later by the address casting
+        /// the vote.
+        rationale: Option<String>,
+    },
+    /// Counts the rankings of an expired ranked choice proposal
+    /// without a first preference majority in its instant-runoff, at
+    /// most `limit` at a time. May be called by anyone, and repeatedly
+    /// until the runoff completes and the proposal passes or is
+    /// rejected.
+    TallyRankedVotes {
+        /// The ID of the proposal to count.
+        proposal_id: u64,
+        /// The maximum number of rankings to count.
+        limit: Option<u32>,
+    },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
     Execute {
@@ -127,11 +168,36 @@ pub enum ExecuteMsg {
         proposal_id: u64,
         rationale: Option<String>,
     },
+    /// Revises a proposal during its amendment window. Callable by
+    /// the proposal's proposer, or by sponsors if the amendment
+    /// config sets a sponsor threshold. Fields left unset are not
+    /// changed.
+    Amend {
+        /// The ID of the proposal to amend.
+        proposal_id: u64,
+        title: Option<String>,
+        description: Option<String>,
+        choices: Option<MultipleChoiceOptions>,
+    },
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
     UpdatePreProposeInfo {
         info: PreProposeInfo,
     },
+    /// Updates the vote delegation module used to look up delegated
+    /// voting power. When set, delegates vote with the voting power
+    /// delegated to them and delegators may override their delegates
+    /// by voting themselves. Only the DAO may call this method.
+    UpdateDelegationModule {
+        module: Option<String>,
+    },
+    /// Updates the amendment window applied to proposals created
+    /// after the update. If unset, proposals may not be amended and
+    /// are open for voting once created. Only the DAO may call this
+    /// method.
+    UpdateAmendmentConfig {
+        config: Option<AmendmentConfig>,
+    },
     AddProposalHook {
         address: String,
     },
@@ -191,6 +257,25 @@ pub enum QueryMsg {
     /// Lists all of the consumers of vote hooks for this module.
     #[returns(::cw_hooks::HooksResponse)]
     VoteHooks {},
+    /// Gets the vote delegation module used by this module, if any.
+    #[returns(::std::option::Option<::cosmwasm_std::Addr>)]
+    DelegationModule {},
+    /// Gets the turnout on a proposal and the voting power needed to
+    /// decide it.
+    #[returns(crate::query::TurnoutResponse)]
+    Turnout { proposal_id: u64 },
+    /// Gets the amendment window applied to new proposals, if any.
+    #[returns(::std::option::Option<::dao_voting::amendment::AmendmentConfig>)]
+    AmendmentConfig {},
+    /// Lists the revisions of a proposal, starting with the contents
+    /// it was created with. Proposals created without an amendment
+    /// window have no revisions.
+    #[returns(crate::query::RevisionListResponse)]
+    ListRevisions {
+        proposal_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
 }
 
 #[cw_serde]
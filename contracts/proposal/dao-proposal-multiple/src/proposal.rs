@@ -5,14 +5,18 @@ use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Uint128};
 use cw_utils::Expiration;
 use dao_voting::{
     multiple_choice::{
-        CheckedMultipleChoiceOption, MultipleChoiceOptionType, MultipleChoiceVotes, VotingStrategy,
+        CheckedMultipleChoiceOption, MultipleChoiceOptionType, MultipleChoiceSelection,
+        MultipleChoiceVote, MultipleChoiceVotes, RankedChoiceVotes, RunoffOutcome, VotingStrategy,
     },
     status::Status,
     veto::VetoConfig,
-    voting::does_vote_count_pass,
+    voting::{does_vote_count_pass, least_power_needed, turnout},
 };
 
-use crate::query::ProposalResponse;
+use crate::{
+    query::{ProposalResponse, TurnoutResponse},
+    state::Ballot,
+};
 
 #[cw_serde]
 pub struct MultipleChoiceProposal {
@@ -43,6 +47,17 @@ pub struct MultipleChoiceProposal {
     pub total_power: Uint128,
     /// The vote tally.
     pub votes: MultipleChoiceVotes,
+    /// The progress of the instant-runoff. Only set for proposals
+    /// using the ranked choice voting strategy, in which case `votes`
+    /// holds the first preference tally and ballots are held in
+    /// `RANKED_VOTES`.
+    #[serde(default)]
+    pub ranked_votes: Option<RankedChoiceVotes>,
+    /// The total voting power of ballots cast. Only set for proposals
+    /// using the approval voting strategy, where a ballot may count
+    /// toward several options in `votes`.
+    #[serde(default)]
+    pub voted_power: Option<Uint128>,
     /// Whether DAO members are allowed to change their votes.
     /// When disabled, proposals can be executed as soon as they pass.
     /// When enabled, proposals can only be executed after the voting
@@ -51,11 +66,18 @@ pub struct MultipleChoiceProposal {
     /// Optional veto configuration. If set to `None`, veto option
     /// is disabled. Otherwise contains the configuration for veto flow.
     pub veto: Option<VetoConfig>,
+    /// If set, the proposal may be amended and may not be voted on
+    /// until this expires.
+    #[serde(default)]
+    pub amendable_until: Option<Expiration>,
 }
 
 pub enum VoteResult {
     SingleWinner(CheckedMultipleChoiceOption),
     Tie,
+    /// No option of a ranked choice proposal has a majority of first
+    /// preferences and the runoff has yet to be counted.
+    RunoffPending,
 }
 
 impl MultipleChoiceProposal {
@@ -105,6 +127,110 @@ impl MultipleChoiceProposal {
         }
     }
 
+    /// The total voting power of ballots cast on this proposal.
+    pub fn total_votes(&self) -> Uint128 {
+        self.voted_power.unwrap_or_else(|| self.votes.total())
+    }
+
+    /// Adds a ballot's voting power to the proposal's tallies. The
+    /// rankings of ranked ballots are stored separately, see
+    /// `contract::add_ballot`.
+    pub fn add_vote(&mut self, ballot: &Ballot) -> StdResult<()> {
+        match &ballot.selections {
+            Some(selections) => self.votes.add_selections(selections, ballot.power)?,
+            None => self.votes.add_vote(ballot.vote, ballot.power)?,
+        }
+        if let Some(voted_power) = &mut self.voted_power {
+            *voted_power = voted_power
+                .checked_add(ballot.power)
+                .map_err(StdError::overflow)?;
+        }
+        Ok(())
+    }
+
+    /// Removes a ballot's voting power from the proposal's tallies.
+    pub fn remove_vote(&mut self, ballot: &Ballot) -> StdResult<()> {
+        match &ballot.selections {
+            Some(selections) => self.votes.remove_selections(selections, ballot.power)?,
+            None => self.votes.remove_vote(ballot.vote, ballot.power)?,
+        }
+        if let Some(voted_power) = &mut self.voted_power {
+            *voted_power = voted_power
+                .checked_sub(ballot.power)
+                .map_err(StdError::overflow)?;
+        }
+        Ok(())
+    }
+
+    /// Computes the proposal's turnout and the voting power needed to
+    /// decide it given the votes cast so far.
+    pub fn turnout(&self, block: &BlockInfo) -> StdResult<TurnoutResponse> {
+        // No more votes may be cast once the proposal has expired.
+        let outstanding = if self.expiration.is_expired(block) {
+            Uint128::zero()
+        } else {
+            self.total_power.saturating_sub(self.total_votes())
+        };
+
+        let quorum = self.voting_strategy.get_quorum();
+        let quorum_needed = least_power_needed(outstanding, |power| {
+            does_vote_count_pass(self.total_votes() + power, self.total_power, quorum)
+        });
+
+        let leading_option = match self.calculate_vote_result()? {
+            VoteResult::SingleWinner(choice) => Some(choice),
+            VoteResult::Tie | VoteResult::RunoffPending => None,
+        };
+        let leader_needed_to_win = match &leading_option {
+            Some(leader) => {
+                // Project the result with the proposal's timing
+                // restrictions lifted, as they do not depend on votes.
+                let mut projection = self.clone();
+                projection.allow_revoting = false;
+                projection.min_voting_period = None;
+                for choice in projection.choices.iter_mut() {
+                    choice.msgs.clear();
+                }
+                least_power_needed(outstanding, |power| {
+                    let mut projection = projection.clone();
+                    let ballot = Ballot {
+                        power,
+                        vote: MultipleChoiceVote {
+                            option_id: leader.index,
+                        },
+                        rationale: None,
+                        ranking: projection.ranked_votes.as_ref().map(|_| vec![leader.index]),
+                        selections: projection.voted_power.map(|_| {
+                            vec![MultipleChoiceSelection {
+                                option_id: leader.index,
+                                weight: None,
+                            }]
+                        }),
+                    };
+                    // A projection that can not be computed is
+                    // treated as undecided.
+                    if projection.add_vote(&ballot).is_err() {
+                        return false;
+                    }
+                    match leader.option_type {
+                        MultipleChoiceOptionType::None => projection.is_rejected(block),
+                        MultipleChoiceOptionType::Standard => projection.is_passed(block),
+                    }
+                    .unwrap_or(false)
+                })
+            }
+            None => None,
+        };
+
+        Ok(TurnoutResponse {
+            turnout: turnout(self.total_votes(), self.total_power),
+            quorum_needed,
+            leading_option: leading_option.map(|choice| choice.index),
+            leader_needed_to_win,
+            locked: self.current_status(block)? != Status::Open,
+        })
+    }
+
     /// Sets a proposals status to its current status.
     pub fn update_status(&mut self, block: &BlockInfo) -> StdResult<()> {
         let new_status = self.current_status(block)?;
@@ -137,14 +263,15 @@ impl MultipleChoiceProposal {
 
         // Proposal can only pass if quorum has been met.
         if does_vote_count_pass(
-            self.votes.total(),
+            self.total_votes(),
             self.total_power,
             self.voting_strategy.get_quorum(),
         ) {
             let vote_result = self.calculate_vote_result()?;
             match vote_result {
-                // Proposal is not passed if there is a tie.
-                VoteResult::Tie => return Ok(false),
+                // Proposal is not passed if there is a tie or the
+                // runoff has yet to be counted.
+                VoteResult::Tie | VoteResult::RunoffPending => return Ok(false),
                 VoteResult::SingleWinner(winning_choice) => {
                     // Proposal is not passed if winning choice is None.
                     if winning_choice.option_type != MultipleChoiceOptionType::None {
@@ -163,6 +290,15 @@ impl MultipleChoiceProposal {
         Ok(false)
     }
 
+    /// Returns true if the proposal's votes did not reach its quorum.
+    pub fn failed_quorum(&self) -> bool {
+        !does_vote_count_pass(
+            self.total_votes(),
+            self.total_power,
+            self.voting_strategy.get_quorum(),
+        )
+    }
+
     pub fn is_rejected(&self, block: &BlockInfo) -> StdResult<bool> {
         // If re-voting is allowed and the proposal is not expired no
         // information is known.
@@ -176,13 +312,18 @@ impl MultipleChoiceProposal {
             // there is no voting power left.
             VoteResult::Tie => {
                 let rejected =
-                    self.expiration.is_expired(block) || self.total_power == self.votes.total();
+                    self.expiration.is_expired(block) || self.total_power == self.total_votes();
                 Ok(rejected)
             }
+            // The runoff is counted once the proposal has expired, at
+            // which point it is rejected only if quorum was not met.
+            VoteResult::RunoffPending => {
+                Ok(self.expiration.is_expired(block) && self.failed_quorum())
+            }
             VoteResult::SingleWinner(winning_choice) => {
                 match (
                     does_vote_count_pass(
-                        self.votes.total(),
+                        self.total_votes(),
                         self.total_power,
                         self.voting_strategy.get_quorum(),
                     ),
@@ -215,7 +356,7 @@ impl MultipleChoiceProposal {
     /// Find the option with the highest vote weight, and note if there is a tie.
     pub fn calculate_vote_result(&self) -> StdResult<VoteResult> {
         match self.voting_strategy {
-            VotingStrategy::SingleChoice { quorum: _ } => {
+            VotingStrategy::SingleChoice { quorum: _ } | VotingStrategy::Approval { .. } => {
                 // We expect to have at least 3 vote weights
                 if let Some(max_weight) = self.votes.vote_weights.iter().max_by(|&a, &b| a.cmp(b)) {
                     let top_choices: Vec<(usize, &Uint128)> = self
@@ -244,6 +385,35 @@ impl MultipleChoiceProposal {
                 }
                 Err(StdError::not_found("max vote weight"))
             }
+            VotingStrategy::RankedChoice { quorum: _ } => {
+                let ranked_votes = self
+                    .ranked_votes
+                    .as_ref()
+                    .ok_or_else(|| StdError::not_found("ranked votes"))?;
+                match ranked_votes.outcome {
+                    Some(RunoffOutcome::Winner(winner)) => Ok(VoteResult::SingleWinner(
+                        self.choices[winner as usize].clone(),
+                    )),
+                    Some(RunoffOutcome::Tie) => Ok(VoteResult::Tie),
+                    // An option with a majority of first preferences
+                    // wins the runoff's first round, so the runoff
+                    // only needs counting without one.
+                    None => {
+                        let total = self.votes.total();
+                        match self
+                            .votes
+                            .vote_weights
+                            .iter()
+                            .position(|weight| *weight > total - *weight)
+                        {
+                            Some(winner) => {
+                                Ok(VoteResult::SingleWinner(self.choices[winner].clone()))
+                            }
+                            None => Ok(VoteResult::RunoffPending),
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -254,6 +424,17 @@ impl MultipleChoiceProposal {
         winning_choice: &CheckedMultipleChoiceOption,
     ) -> StdResult<bool> {
         let winning_choice_power = self.votes.vote_weights[winning_choice.index as usize];
+        if let VotingStrategy::RankedChoice { .. } = self.voting_strategy {
+            // Later preferences may be transferred to any option during
+            // the runoff, so the result is only certain before
+            // expiration once the winner holds a majority of first
+            // preferences.
+            let others_power = self.total_power - winning_choice_power;
+            return Ok(match winning_choice.option_type {
+                MultipleChoiceOptionType::Standard => winning_choice_power > others_power,
+                MultipleChoiceOptionType::None => winning_choice_power >= others_power,
+            });
+        }
         if let Some(second_choice_power) = self
             .votes
             .vote_weights
@@ -262,7 +443,7 @@ impl MultipleChoiceProposal {
             .max_by(|&a, &b| a.cmp(b))
         {
             // Check if the remaining vote power can be used to overtake the current winning choice.
-            let remaining_vote_power = self.total_power - self.votes.total();
+            let remaining_vote_power = self.total_power - self.total_votes();
             match winning_choice.option_type {
                 MultipleChoiceOptionType::Standard => {
                     if winning_choice_power > *second_choice_power + remaining_vote_power {
@@ -333,9 +514,12 @@ mod tests {
             voting_strategy,
             total_power,
             votes,
+            ranked_votes: None,
+            voted_power: None,
             allow_revoting,
             min_voting_period: None,
             veto: None,
+            amendable_until: None,
         }
     }
 
@@ -875,4 +1059,141 @@ mod tests {
         // No quorum reached & proposal has expired => rejection
         assert!(prop.is_rejected(&env.block).unwrap());
     }
+
+    #[test]
+    fn test_ranked_choice_runoff() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::RankedChoice {
+            quorum: dao_voting::threshold::PercentageThreshold::Majority {},
+        };
+
+        // Option 1 leads on first preferences without a majority, so
+        // the result is pending until the runoff is counted.
+        let votes = MultipleChoiceVotes {
+            vote_weights: vec![Uint128::new(3), Uint128::new(4), Uint128::new(2)],
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy.clone(),
+            votes.clone(),
+            Uint128::new(9),
+            true,
+            false,
+        );
+        prop.ranked_votes = Some(RankedChoiceVotes::default());
+        assert!(matches!(
+            prop.calculate_vote_result().unwrap(),
+            VoteResult::RunoffPending
+        ));
+        assert!(!prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_rejected(&env.block).unwrap());
+
+        // Option 0 wins once none of the above is eliminated and its
+        // votes transfer.
+        prop.ranked_votes = Some(RankedChoiceVotes {
+            outcome: Some(RunoffOutcome::Winner(0)),
+            ..Default::default()
+        });
+        match prop.calculate_vote_result().unwrap() {
+            VoteResult::SingleWinner(choice) => assert_eq!(choice.index, 0),
+            _ => panic!("expected a winner"),
+        }
+        assert!(prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_rejected(&env.block).unwrap());
+
+        // A tied runoff rejects the proposal.
+        prop.ranked_votes = Some(RankedChoiceVotes {
+            outcome: Some(RunoffOutcome::Tie),
+            ..Default::default()
+        });
+        assert!(!prop.is_passed(&env.block).unwrap());
+        assert!(prop.is_rejected(&env.block).unwrap());
+
+        // An expired proposal that failed quorum is rejected without
+        // a runoff.
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy.clone(),
+            votes.clone(),
+            Uint128::new(100),
+            true,
+            false,
+        );
+        prop.ranked_votes = Some(RankedChoiceVotes::default());
+        assert!(prop.is_rejected(&env.block).unwrap());
+
+        // Before expiration the result is not final.
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy.clone(),
+            votes,
+            Uint128::new(10),
+            false,
+            false,
+        );
+        prop.ranked_votes = Some(RankedChoiceVotes::default());
+        assert!(!prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_rejected(&env.block).unwrap());
+
+        // A majority of first preferences passes early.
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes {
+                vote_weights: vec![Uint128::zero(), Uint128::new(6), Uint128::zero()],
+            },
+            Uint128::new(10),
+            false,
+            false,
+        );
+        prop.ranked_votes = Some(RankedChoiceVotes::default());
+        assert!(prop.is_passed(&env.block).unwrap());
+    }
+
+    #[test]
+    fn test_turnout() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::SingleChoice {
+            quorum: dao_voting::threshold::PercentageThreshold::Percent(
+                cosmwasm_std::Decimal::percent(30),
+            ),
+        };
+        let votes = MultipleChoiceVotes {
+            vote_weights: vec![Uint128::new(10), Uint128::new(5), Uint128::new(0)],
+        };
+        let prop = create_proposal(
+            &env.block,
+            voting_strategy.clone(),
+            votes,
+            Uint128::new(100),
+            false,
+            false,
+        );
+        let turnout = prop.turnout(&env.block).unwrap();
+        assert_eq!(turnout.turnout, cosmwasm_std::Decimal::percent(15));
+        assert_eq!(turnout.quorum_needed, Some(Uint128::new(15)));
+        assert_eq!(turnout.leading_option, Some(0));
+        // With 41 more votes the first option has 51 votes and the
+        // second may reach at most 50.
+        assert_eq!(turnout.leader_needed_to_win, Some(Uint128::new(41)));
+        assert!(!turnout.locked);
+
+        // Nothing is leading on a tie.
+        let votes = MultipleChoiceVotes {
+            vote_weights: vec![Uint128::new(5), Uint128::new(5), Uint128::new(0)],
+        };
+        let prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            votes,
+            Uint128::new(100),
+            true,
+            false,
+        );
+        let turnout = prop.turnout(&env.block).unwrap();
+        assert_eq!(turnout.quorum_needed, None);
+        assert_eq!(turnout.leading_option, None);
+        assert_eq!(turnout.leader_needed_to_win, None);
+        assert!(turnout.locked);
+    }
 }
@@ -1,14 +1,23 @@
-use crate::{proposal::MultipleChoiceProposal, state::Config};
+use crate::{
+    proposal::MultipleChoiceProposal,
+    state::{Config, Revision},
+};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128};
 
-use dao_voting::multiple_choice::MultipleChoiceVote;
+use dao_voting::multiple_choice::{MultipleChoiceSelection, MultipleChoiceVote};
 
 #[cw_serde]
 pub struct ProposalListResponse {
     pub proposals: Vec<ProposalResponse>,
 }
 
+/// The revision history of a proposal returned by `ListRevisions`.
+#[cw_serde]
+pub struct RevisionListResponse {
+    pub revisions: Vec<Revision>,
+}
+
 /// Information about a proposal returned by proposal queries.
 #[cw_serde]
 pub struct ProposalResponse {
@@ -16,6 +25,29 @@ pub struct ProposalResponse {
     pub proposal: MultipleChoiceProposal,
 }
 
+/// The turnout of a proposal returned by `Turnout`.
+#[cw_serde]
+pub struct TurnoutResponse {
+    /// The share of the proposal's total voting power that has been
+    /// cast.
+    pub turnout: Decimal,
+    /// The additional voting power that must be cast, for any option,
+    /// for the proposal to reach quorum. None if the outstanding
+    /// voting power can not reach it.
+    pub quorum_needed: Option<Uint128>,
+    /// The index of the option currently in the lead. None if there
+    /// is a tie.
+    pub leading_option: Option<u32>,
+    /// The additional voting power for the leading option that would
+    /// decide the proposal in its favor (rejecting it if the leading
+    /// option is "None of the above") however the rest of the
+    /// outstanding voting power votes. None if there is a tie or the
+    /// outstanding voting power would not.
+    pub leader_needed_to_win: Option<Uint128>,
+    /// Whether the outcome of the proposal is decided.
+    pub locked: bool,
+}
+
 /// Information about a vote that was cast.
 #[cw_serde]
 pub struct VoteInfo {
@@ -27,6 +59,11 @@ pub struct VoteInfo {
     pub power: Uint128,
     /// The rationale behind the vote.
     pub rationale: Option<String>,
+    /// The voter's ranking of options on ranked choice proposals, in
+    /// order of preference.
+    pub ranking: Option<Vec<u32>>,
+    /// The options selected by the voter on approval proposals.
+    pub selections: Option<Vec<MultipleChoiceSelection>>,
 }
 
 #[cw_serde]
@@ -1,11 +1,14 @@
 use crate::proposal::MultipleChoiceProposal;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Timestamp, Uint128};
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
 use dao_voting::{
-    multiple_choice::{MultipleChoiceVote, VotingStrategy},
+    amendment::AmendmentConfig,
+    multiple_choice::{
+        CheckedMultipleChoiceOption, MultipleChoiceSelection, MultipleChoiceVote, VotingStrategy,
+    },
     pre_propose::ProposalCreationPolicy,
     veto::VetoConfig,
 };
@@ -49,6 +52,23 @@ pub struct Config {
     pub veto: Option<VetoConfig>,
 }
 
+/// A revision of a proposal's contents. Revision zero holds the
+/// contents the proposal was created with.
+#[cw_serde]
+pub struct Revision {
+    /// The number of this revision.
+    pub revision: u64,
+    /// The address that made this revision.
+    pub author: Addr,
+    /// The block height at which this revision was made.
+    pub height: u64,
+    /// The time at which this revision was made.
+    pub time: Timestamp,
+    pub title: String,
+    pub description: String,
+    pub choices: Vec<CheckedMultipleChoiceOption>,
+}
+
 // Each ballot stores a chosen vote and corresponding voting power and rationale.
 #[cw_serde]
 pub struct Ballot {
@@ -58,6 +78,14 @@ pub struct Ballot {
     pub vote: MultipleChoiceVote,
     /// An optional rationale for why this vote was cast.
     pub rationale: Option<String>,
+    /// The voter's ranking of options on ranked choice proposals, in
+    /// order of preference. `vote` holds the first preference.
+    #[serde(default)]
+    pub ranking: Option<Vec<u32>>,
+    /// The options selected by the voter on approval proposals. `vote`
+    /// holds the first selection.
+    #[serde(default)]
+    pub selections: Option<Vec<MultipleChoiceSelection>>,
 }
 
 /// The current top level config for the module.
@@ -72,3 +100,18 @@ pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+/// The vote delegation module used to look up delegated voting power
+/// (if any).
+pub const DELEGATION_MODULE: Item<Addr> = Item::new("delegation_module");
+/// The voting power removed from a delegate's delegated voting power
+/// on a proposal by delegators who voted on it themselves.
+pub const DELEGATION_OVERRIDES: Map<(u64, &Addr), Uint128> = Map::new("delegation_overrides");
+/// The amendment window applied to new proposals (if any).
+pub const AMENDMENT_CONFIG: Item<AmendmentConfig> = Item::new("amendment_config");
+/// The revision history of proposals created with an amendment
+/// window, keyed by proposal ID and revision number.
+pub const REVISIONS: Map<(u64, u64), Revision> = Map::new("revisions");
+/// The voting power behind each distinct ranking cast on ranked
+/// choice proposals, keyed by proposal ID and
+/// `RankedChoiceVotes::ranking_key`.
+pub const RANKED_VOTES: Map<(u64, &[u8]), Uint128> = Map::new("ranked_votes");
@@ -276,6 +276,8 @@ where
                             _ => weight,
                         },
                         rationale: None,
+                        ranking: None,
+                        selections: None,
                     }),
                 };
                 assert_eq!(vote, expected)
@@ -42,6 +42,7 @@ pub fn make_proposal(
                     denom,
                     amount,
                     refund_policy: _,
+                    scaling: None,
                 }) => match denom {
                     CheckedDenom::Native(denom) => coins(amount.u128(), denom),
                     CheckedDenom::Cw20(addr) => {
@@ -76,6 +76,7 @@ pub fn _get_default_token_dao_proposal_module_instantiate(app: &mut App) -> Inst
                 },
                 amount: Uint128::new(10_000_000),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             false,
         ),
@@ -283,6 +284,7 @@ pub fn instantiate_with_native_staked_balances_governance(
                 },
                 unstaking_duration: None,
                 active_threshold: None,
+                power_transform: None,
             })
             .unwrap(),
             admin: None,
@@ -486,6 +488,7 @@ pub fn instantiate_with_staked_balances_governance(
                     unstaking_duration: Some(Duration::Height(6)),
                     initial_dao_balance: None,
                 },
+                power_transform: None,
             })
             .unwrap(),
             admin: None,
@@ -625,6 +628,7 @@ pub fn instantiate_with_multiple_staked_balances_governance(
                     unstaking_duration: Some(Duration::Height(6)),
                     initial_dao_balance: None,
                 },
+                power_transform: None,
             })
             .unwrap(),
             admin: None,
@@ -740,6 +744,7 @@ pub fn instantiate_with_staking_active_threshold(
                     initial_dao_balance: None,
                 },
                 active_threshold,
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -819,6 +824,7 @@ pub fn _instantiate_with_cw4_groups_governance(
                     cw4_group_code_id: cw4_id,
                     initial_members: initial_weights,
                 },
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -5,9 +5,10 @@ use cw20::Cw20Coin;
 use cw_denom::{CheckedDenom, UncheckedDenom};
 use cw_hooks::HooksResponse;
 use cw_multi_test::{next_block, App, BankSudo, Executor, SudoMsg};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_interface::state::ProposalModule;
 use dao_interface::state::{Admin, ModuleInstantiateInfo};
+use dao_voting::amendment::{AmendmentConfig, AmendmentError};
 use dao_voting::multiple_choice::MultipleChoiceAutoVote;
 use dao_voting::pre_propose::PreProposeSubmissionPolicy;
 use dao_voting::veto::{VetoConfig, VetoError};
@@ -18,8 +19,8 @@ use dao_voting::{
     },
     multiple_choice::{
         CheckedMultipleChoiceOption, MultipleChoiceOption, MultipleChoiceOptionType,
-        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
-        MAX_NUM_CHOICES,
+        MultipleChoiceOptions, MultipleChoiceSelection, MultipleChoiceVote, MultipleChoiceVotes,
+        VotingStrategy, MAX_NUM_CHOICES,
     },
     pre_propose::PreProposeInfo,
     proposal::MultipleChoiceProposeMsg as ProposeMsg,
@@ -32,7 +33,10 @@ use std::panic;
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::MultipleChoiceProposal,
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    query::{
+        ProposalListResponse, ProposalResponse, RevisionListResponse, VoteInfo, VoteListResponse,
+        VoteResponse,
+    },
     state::Config,
     testing::{
         do_votes::do_test_votes_cw20_balances,
@@ -56,7 +60,7 @@ use dao_proposal_multiple::ContractError;
 use dao_testing::{
     contracts::{
         cw20_base_contract, dao_pre_propose_multiple_contract, dao_proposal_multiple_contract,
-        dao_voting_cw20_balance_contract,
+        dao_vote_delegation_contract, dao_voting_cw20_balance_contract,
     },
     ShouldExecute,
 };
@@ -183,7 +187,10 @@ fn test_propose() {
         },
         allow_revoting: false,
         min_voting_period: None,
+        ranked_votes: None,
+        voted_power: None,
         veto: None,
+        amendable_until: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -393,7 +400,10 @@ fn test_propose_auto_vote_winner() {
         },
         allow_revoting: false,
         min_voting_period: None,
+        ranked_votes: None,
+        voted_power: None,
         veto: None,
+        amendable_until: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -484,7 +494,10 @@ fn test_propose_auto_vote_reject() {
         },
         allow_revoting: false,
         min_voting_period: None,
+        ranked_votes: None,
+        voted_power: None,
         veto: None,
+        amendable_until: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -976,6 +989,7 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             false,
         ),
@@ -1004,7 +1018,8 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
         Some(CheckedDepositInfo {
             denom: CheckedDenom::Cw20(token),
             amount: Uint128::new(1),
-            refund_policy: DepositRefundPolicy::OnlyPassed
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            scaling: None,
         })
     )
 }
@@ -1052,6 +1067,7 @@ fn test_different_token_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             false,
         ),
@@ -1114,6 +1130,7 @@ fn test_bad_token_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             false,
         ),
@@ -1147,6 +1164,7 @@ fn test_take_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             false,
         ),
@@ -1255,6 +1273,7 @@ fn test_take_native_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             false,
         ),
@@ -1349,6 +1368,7 @@ fn test_native_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::Always,
+                scaling: None,
             }),
             false,
         ),
@@ -1493,6 +1513,7 @@ fn test_deposit_return_on_execute() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            scaling: None,
         }),
         true,
     );
@@ -1636,12 +1657,16 @@ fn test_query_list_votes() {
             vote: MultipleChoiceVote { option_id: 0 },
             power: Uint128::new(10),
             rationale: None,
+            ranking: None,
+            selections: None,
         },
         VoteInfo {
             voter: Addr::unchecked("note"),
             vote: MultipleChoiceVote { option_id: 1 },
             power: Uint128::new(20),
             rationale: None,
+            ranking: None,
+            selections: None,
         },
     ];
 
@@ -1780,6 +1805,7 @@ fn test_cant_propose_zero_power() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::Always,
+                scaling: None,
             }),
             false,
         ),
@@ -1900,6 +1926,7 @@ fn test_cant_vote_not_registered() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -2233,7 +2260,10 @@ fn test_open_proposal_submission() {
         votes: MultipleChoiceVotes {
             vote_weights: vec![Uint128::zero(); 3],
         },
+        ranked_votes: None,
+        voted_power: None,
         veto: None,
+        amendable_until: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -2260,6 +2290,7 @@ fn test_close_open_proposal() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -2330,6 +2361,7 @@ fn test_no_refund_failed_proposal() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            scaling: None,
         }),
         false,
     );
@@ -2410,6 +2442,7 @@ fn test_deposit_return_on_close() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         }),
         false,
     );
@@ -2713,6 +2746,7 @@ fn test_no_return_if_no_refunds() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            scaling: None,
         }),
         true,
     );
@@ -2846,7 +2880,10 @@ fn test_query_list_proposals() {
             },
             allow_revoting: false,
             min_voting_period: None,
+            ranked_votes: None,
+            voted_power: None,
             veto: None,
+            amendable_until: None,
         },
     };
     assert_eq!(proposals_forward.proposals[0], expected);
@@ -2875,7 +2912,10 @@ fn test_query_list_proposals() {
             },
             allow_revoting: false,
             min_voting_period: None,
+            ranked_votes: None,
+            voted_power: None,
             veto: None,
+            amendable_until: None,
         },
     };
     assert_eq!(proposals_forward.proposals[0], expected);
@@ -3925,6 +3965,7 @@ fn test_return_deposit_to_dao_on_proposal_failure() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            scaling: None,
         }),
         false,
     );
@@ -4236,6 +4277,7 @@ fn test_no_double_refund_on_execute_fail_and_close() {
                 // that we don't get a second refund on close. Refunds on
                 // close only happen if this is true.
                 refund_policy: DepositRefundPolicy::Always,
+                scaling: None,
             }),
             false,
         ),
@@ -6022,3 +6064,844 @@ fn test_veto_only_members_execute_proposal() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_ranked_choice_voting() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(dao_proposal_multiple_contract());
+    let msg = InstantiateMsg {
+        voting_strategy: VotingStrategy::RankedChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: true,
+        allow_revoting: false,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        msg,
+        Some(vec![
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(35),
+            },
+            Cw20Coin {
+                address: "note".to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "green".to_string(),
+                amount: Uint128::new(25),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = (1..=3)
+        .map(|i| MultipleChoiceOption {
+            description: format!("multiple choice option {i}"),
+            msgs: vec![],
+            title: "title".to_string(),
+        })
+        .collect();
+    make_proposal(
+        &mut app,
+        &govmod,
+        "blue",
+        MultipleChoiceOptions { options },
+        None,
+    );
+
+    // Rankings must not contain duplicates.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("green"),
+            govmod.clone(),
+            &ExecuteMsg::RankedVote {
+                proposal_id: 1,
+                ranking: vec![2, 2],
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidVote {});
+
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::RankedVote {
+            proposal_id: 1,
+            ranking: vec![0],
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    // A plain vote ranks a single option.
+    app.execute_contract(
+        Addr::unchecked("note"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote { option_id: 1 },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Option 1 leads the votes cast so far, but the outstanding
+    // voting power could still change the result.
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    app.execute_contract(
+        Addr::unchecked("green"),
+        govmod.clone(),
+        &ExecuteMsg::RankedVote {
+            proposal_id: 1,
+            ranking: vec![2, 0],
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // No option has a majority of first preferences, so the result
+    // is not known until the runoff is counted after expiration.
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("green"),
+            govmod.clone(),
+            &ExecuteMsg::TallyRankedVotes {
+                proposal_id: 1,
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotExpired {});
+
+    app.update_block(|block| block.height += 10);
+
+    // The runoff may be counted in pages by anyone.
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        govmod.clone(),
+        &ExecuteMsg::TallyRankedVotes {
+            proposal_id: 1,
+            limit: Some(1),
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        govmod.clone(),
+        &ExecuteMsg::TallyRankedVotes {
+            proposal_id: 1,
+            limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Option 2 is eliminated and its votes transfer to option 0,
+    // which wins the runoff.
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            govmod.clone(),
+            &ExecuteMsg::TallyRankedVotes {
+                proposal_id: 1,
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoRunoff { id: 1 });
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![
+            Uint128::new(35),
+            Uint128::new(40),
+            Uint128::new(25),
+            Uint128::zero()
+        ]
+    );
+
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: "green".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vote.vote,
+        Some(VoteInfo {
+            voter: Addr::unchecked("green"),
+            vote: MultipleChoiceVote { option_id: 2 },
+            power: Uint128::new(25),
+            rationale: None,
+            ranking: Some(vec![2, 0]),
+            selections: None,
+        })
+    );
+}
+
+#[test]
+fn test_ranked_and_approval_votes_on_single_choice_proposal() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(dao_proposal_multiple_contract());
+    let msg = InstantiateMsg {
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: true,
+        allow_revoting: false,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        msg,
+        Some(vec![Cw20Coin {
+            address: "blue".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = vec![MultipleChoiceOption {
+        description: "multiple choice option 1".to_string(),
+        msgs: vec![],
+        title: "title".to_string(),
+    }];
+    make_proposal(
+        &mut app,
+        &govmod,
+        "blue",
+        MultipleChoiceOptions { options },
+        None,
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("blue"),
+            govmod.clone(),
+            &ExecuteMsg::RankedVote {
+                proposal_id: 1,
+                ranking: vec![0, 1],
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::RankedVoteNotAllowed {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("blue"),
+            govmod,
+            &ExecuteMsg::ApprovalVote {
+                proposal_id: 1,
+                selections: vec![MultipleChoiceSelection {
+                    option_id: 0,
+                    weight: None,
+                }],
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ApprovalVoteNotAllowed {});
+}
+
+#[test]
+fn test_approval_voting() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(dao_proposal_multiple_contract());
+    let msg = InstantiateMsg {
+        voting_strategy: VotingStrategy::Approval {
+            quorum: PercentageThreshold::Majority {},
+            max_selections: 2,
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: true,
+        allow_revoting: false,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        msg,
+        Some(vec![
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "note".to_string(),
+                amount: Uint128::new(50),
+            },
+            Cw20Coin {
+                address: "green".to_string(),
+                amount: Uint128::new(20),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = (1..=3)
+        .map(|i| MultipleChoiceOption {
+            description: format!("multiple choice option {i}"),
+            msgs: vec![],
+            title: "title".to_string(),
+        })
+        .collect();
+    make_proposal(
+        &mut app,
+        &govmod,
+        "blue",
+        MultipleChoiceOptions { options },
+        None,
+    );
+
+    let approve = |option_id| MultipleChoiceSelection {
+        option_id,
+        weight: None,
+    };
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("green"),
+            govmod.clone(),
+            &ExecuteMsg::ApprovalVote {
+                proposal_id: 1,
+                selections: vec![approve(0), approve(1), approve(2)],
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::TooManySelections { max: 2 });
+
+    // Each approved option receives blue's full voting power.
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::ApprovalVote {
+            proposal_id: 1,
+            selections: vec![approve(0), approve(1)],
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // Note splits their voting power between options.
+    let split = vec![
+        MultipleChoiceSelection {
+            option_id: 0,
+            weight: Some(Decimal::percent(20)),
+        },
+        MultipleChoiceSelection {
+            option_id: 1,
+            weight: Some(Decimal::percent(80)),
+        },
+    ];
+    app.execute_contract(
+        Addr::unchecked("note"),
+        govmod.clone(),
+        &ExecuteMsg::ApprovalVote {
+            proposal_id: 1,
+            selections: split.clone(),
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Option 1 can no longer be overtaken with green's outstanding
+    // voting power.
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![
+            Uint128::new(40),
+            Uint128::new(70),
+            Uint128::zero(),
+            Uint128::zero()
+        ]
+    );
+    assert_eq!(proposal.proposal.voted_power, Some(Uint128::new(80)));
+
+    let list_votes: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::ListVotes {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        list_votes.votes,
+        vec![
+            VoteInfo {
+                voter: Addr::unchecked("blue"),
+                vote: MultipleChoiceVote { option_id: 0 },
+                power: Uint128::new(30),
+                rationale: None,
+                ranking: None,
+                selections: Some(vec![approve(0), approve(1)]),
+            },
+            VoteInfo {
+                voter: Addr::unchecked("note"),
+                vote: MultipleChoiceVote { option_id: 0 },
+                power: Uint128::new(50),
+                rationale: None,
+                ranking: None,
+                selections: Some(split),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_delegate_override_weighted_selections() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(dao_proposal_multiple_contract());
+    let msg = InstantiateMsg {
+        voting_strategy: VotingStrategy::Approval {
+            quorum: PercentageThreshold::Majority {},
+            max_selections: 3,
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: true,
+        allow_revoting: false,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        msg,
+        Some(vec![
+            Cw20Coin {
+                address: "delegate".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "delegator".to_string(),
+                amount: Uint128::new(7),
+            },
+            Cw20Coin {
+                address: "whale".to_string(),
+                amount: Uint128::new(90),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    let (_, staking_contract) = query_cw20_token_staking_contracts(&app, &core_addr);
+
+    let delegation_id = app.store_code(dao_vote_delegation_contract());
+    let delegation = app
+        .instantiate_contract(
+            delegation_id,
+            core_addr.clone(),
+            &dao_vote_delegation::msg::InstantiateMsg {
+                dao: None,
+                vp_hook_callers: Some(vec![staking_contract.to_string()]),
+                max_delegations: None,
+            },
+            &[],
+            "delegation",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked("delegate"),
+        delegation.clone(),
+        &dao_vote_delegation::msg::ExecuteMsg::Register {},
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("delegator"),
+        delegation.clone(),
+        &dao_vote_delegation::msg::ExecuteMsg::Delegate {
+            delegate: "delegate".to_string(),
+            percent: Decimal::one(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        core_addr,
+        govmod.clone(),
+        &ExecuteMsg::UpdateDelegationModule {
+            module: Some(delegation.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let options = (1..=3)
+        .map(|i| MultipleChoiceOption {
+            description: format!("multiple choice option {i}"),
+            msgs: vec![],
+            title: "title".to_string(),
+        })
+        .collect();
+    make_proposal(
+        &mut app,
+        &govmod,
+        "whale",
+        MultipleChoiceOptions { options },
+        None,
+    );
+
+    // The delegate splits its own and the delegated voting power, 10
+    // in total, evenly over three options.
+    let third = Decimal::from_ratio(1u128, 3u128);
+    app.execute_contract(
+        Addr::unchecked("delegate"),
+        govmod.clone(),
+        &ExecuteMsg::ApprovalVote {
+            proposal_id: 1,
+            selections: vec![
+                MultipleChoiceSelection {
+                    option_id: 0,
+                    weight: Some(third),
+                },
+                MultipleChoiceSelection {
+                    option_id: 1,
+                    weight: Some(third),
+                },
+                MultipleChoiceSelection {
+                    option_id: 2,
+                    weight: Some(Decimal::one() - third - third),
+                },
+            ],
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![
+            Uint128::new(3),
+            Uint128::new(3),
+            Uint128::new(3),
+            Uint128::zero()
+        ]
+    );
+
+    // The delegator overrides the delegate. The delegate's ballot is
+    // re-tallied with its remaining 3 voting power, leaving no dust
+    // from the removed power.
+    app.execute_contract(
+        Addr::unchecked("delegator"),
+        govmod.clone(),
+        &ExecuteMsg::ApprovalVote {
+            proposal_id: 1,
+            selections: vec![MultipleChoiceSelection {
+                option_id: 1,
+                weight: None,
+            }],
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![
+            Uint128::zero(),
+            Uint128::new(7),
+            Uint128::new(1),
+            Uint128::zero()
+        ]
+    );
+    assert_eq!(proposal.proposal.voted_power, Some(Uint128::new(10)));
+}
+
+#[test]
+fn test_amend_proposal() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(dao_proposal_multiple_contract());
+    let msg = InstantiateMsg {
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: true,
+        allow_revoting: false,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        msg,
+        Some(vec![
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(80),
+            },
+            Cw20Coin {
+                address: "note".to_string(),
+                amount: Uint128::new(15),
+            },
+            Cw20Coin {
+                address: "green".to_string(),
+                amount: Uint128::new(5),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    // Windows must have the same units as the voting period.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            govmod.clone(),
+            &ExecuteMsg::UpdateAmendmentConfig {
+                config: Some(AmendmentConfig {
+                    window: Duration::Time(100),
+                    sponsor_threshold: None,
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::WindowUnitMismatch {})
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        govmod.clone(),
+        &ExecuteMsg::UpdateAmendmentConfig {
+            config: Some(AmendmentConfig {
+                window: Duration::Height(5),
+                sponsor_threshold: Some(Decimal::percent(10)),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let options = |n: u32| MultipleChoiceOptions {
+        options: (1..=n)
+            .map(|i| MultipleChoiceOption {
+                description: format!("multiple choice option {i}"),
+                msgs: vec![],
+                title: "title".to_string(),
+            })
+            .collect(),
+    };
+
+    // Proposers may not vote as voting has not opened yet.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("blue"),
+            govmod.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: options(2),
+                proposer: None,
+                vote: Some(MultipleChoiceAutoVote {
+                    vote: MultipleChoiceVote { option_id: 0 },
+                    rationale: None,
+                }),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::AutoVoteNotAllowed {})
+    );
+
+    let start_height = app.block_info().height;
+    let id = make_proposal(&mut app, &govmod, "blue", options(2), None);
+
+    // The voting period begins once the amendment window closes.
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, id);
+    assert_eq!(
+        proposal.proposal.amendable_until,
+        Some(Expiration::AtHeight(start_height + 5))
+    );
+    assert_eq!(
+        proposal.proposal.expiration,
+        Expiration::AtHeight(start_height + 15)
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("note"),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: id,
+                vote: MultipleChoiceVote { option_id: 0 },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::VotingNotOpen {})
+    );
+
+    // Holders below the sponsor threshold may not amend.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("green"),
+            govmod.clone(),
+            &ExecuteMsg::Amend {
+                proposal_id: id,
+                title: Some("typo".to_string()),
+                description: None,
+                choices: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::Unauthorized {})
+    );
+
+    app.execute_contract(
+        Addr::unchecked("note"),
+        govmod.clone(),
+        &ExecuteMsg::Amend {
+            proposal_id: id,
+            title: None,
+            description: None,
+            choices: Some(options(3)),
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, id);
+    assert_eq!(proposal.proposal.choices.len(), 4);
+    assert_eq!(proposal.proposal.votes.vote_weights.len(), 4);
+
+    let revisions: RevisionListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::ListRevisions {
+                proposal_id: id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(revisions.revisions.len(), 2);
+    assert_eq!(revisions.revisions[0].author, Addr::unchecked("blue"));
+    assert_eq!(revisions.revisions[0].choices.len(), 3);
+    assert_eq!(revisions.revisions[1].revision, 1);
+    assert_eq!(revisions.revisions[1].author, Addr::unchecked("note"));
+    assert_eq!(revisions.revisions[1].choices.len(), 4);
+
+    // Once the window closes the proposal may be voted on but not
+    // amended.
+    app.update_block(|block| block.height += 5);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("blue"),
+            govmod.clone(),
+            &ExecuteMsg::Amend {
+                proposal_id: id,
+                title: Some("late".to_string()),
+                description: None,
+                choices: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::WindowClosed {})
+    );
+
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: id,
+            vote: MultipleChoiceVote { option_id: 2 },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = query_proposal(&app, &govmod, id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+}
@@ -1,33 +1,43 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Attribute, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply,
-    Response, StdResult, Storage, SubMsg, WasmMsg,
+    to_json_binary, Addr, Attribute, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
+    Reply, Response, StdResult, Storage, SubMsg, SubMsgResult, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_hooks::Hooks;
 use cw_storage_plus::Bound;
 use cw_utils::{parse_reply_instantiate_data, Duration};
 use dao_hooks::proposal::{
-    new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
+    new_proposal_hooks, proposal_amended_hooks, proposal_completed_hooks,
+    proposal_status_changed_hooks,
 };
-use dao_hooks::vote::new_vote_hooks;
-use dao_interface::voting::IsActiveResponse;
+use dao_hooks::vote::{new_vote_hooks, new_weighted_vote_hooks};
+use dao_interface::{treasury::spending_cap_exceeded_event, voting::IsActiveResponse};
+use dao_voting::amendment::{check_voting_open, AmendmentConfig, AmendmentError};
+use dao_voting::execution_plan::{BatchFailurePolicy, BatchStatus, MAX_EXECUTION_BATCHES};
 use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
 use dao_voting::proposal::{
     SingleChoiceProposeMsg as ProposeMsg, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE,
 };
 use dao_voting::reply::{
-    failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
+    failed_pre_propose_module_hook_id, mask_proposal_batch_execution,
+    mask_proposal_execution_proposal_id, TaggedReplyId,
 };
 use dao_voting::status::Status;
 use dao_voting::threshold::Threshold;
 use dao_voting::veto::{VetoConfig, VetoError};
-use dao_voting::voting::{get_total_power, get_voting_power, validate_voting_period, Vote, Votes};
+use dao_voting::voting::{
+    get_delegated_voting_power, get_delegations, get_total_power, get_voting_power,
+    validate_voting_period, validate_weighted_votes, Vote, Votes, WeightedVote,
+};
 
 use crate::msg::MigrateMsg;
-use crate::proposal::{next_proposal_id, SingleChoiceProposal};
-use crate::state::{Config, CREATION_POLICY};
+use crate::proposal::{next_proposal_id, ScheduledBatch, SingleChoiceProposal};
+use crate::state::{
+    Config, Revision, AMENDMENT_CONFIG, CREATION_POLICY, DELEGATION_MODULE, DELEGATION_OVERRIDES,
+    REVISIONS,
+};
 use crate::v1_state::{
     v1_duration_to_v2, v1_expiration_to_v2, v1_status_to_v2, v1_threshold_to_v2, v1_votes_to_v2,
 };
@@ -36,7 +46,7 @@ use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::advance_proposal_id,
     query::ProposalListResponse,
-    query::{ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    query::{ProposalResponse, RevisionListResponse, VoteInfo, VoteListResponse, VoteResponse},
     state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
 };
 use cw_proposal_single_v1 as v1;
@@ -104,11 +114,40 @@ pub fn execute(
             proposal_id,
             vote,
             rationale,
-        } => execute_vote(deps, env, info.sender, proposal_id, vote, rationale),
+        } => execute_vote(deps, env, info.sender, proposal_id, vote, None, rationale),
+        ExecuteMsg::WeightedVote {
+            proposal_id,
+            votes,
+            rationale,
+        } => {
+            validate_weighted_votes(&votes).map_err(|_| ContractError::InvalidWeightedVote {})?;
+            // The ballot's position is the one given the largest
+            // share of voting power.
+            let vote = votes
+                .iter()
+                .max_by_key(|weighted| weighted.weight)
+                .map(|weighted| weighted.vote)
+                .ok_or(ContractError::InvalidWeightedVote {})?;
+            execute_vote(
+                deps,
+                env,
+                info.sender,
+                proposal_id,
+                vote,
+                Some(votes),
+                rationale,
+            )
+        }
         ExecuteMsg::UpdateRationale {
             proposal_id,
             rationale,
         } => execute_update_rationale(deps, info, proposal_id, rationale),
+        ExecuteMsg::Amend {
+            proposal_id,
+            title,
+            description,
+            msgs,
+        } => execute_amend(deps, env, info, proposal_id, title, description, msgs),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
@@ -135,6 +174,12 @@ pub fn execute(
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
         }
+        ExecuteMsg::UpdateDelegationModule { module } => {
+            execute_update_delegation_module(deps, info, module)
+        }
+        ExecuteMsg::UpdateAmendmentConfig { config } => {
+            execute_update_amendment_config(deps, info, config)
+        }
         ExecuteMsg::AddProposalHook { address } => {
             execute_add_proposal_hook(deps, env, info, address)
         }
@@ -159,6 +204,7 @@ pub fn execute_propose(
         msgs,
         proposer,
         vote,
+        execution_plan,
     }: ProposeMsg,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
@@ -182,6 +228,20 @@ pub fn execute_propose(
         _ => return Err(ContractError::InvalidProposer {}),
     };
 
+    if let Some(plan) = &execution_plan {
+        if !msgs.is_empty() {
+            return Err(ContractError::MsgsWithExecutionPlan {});
+        }
+        if plan.is_empty()
+            || plan.len() > MAX_EXECUTION_BATCHES
+            || plan.iter().any(|batch| batch.msgs.is_empty())
+        {
+            return Err(ContractError::InvalidExecutionPlan {
+                max: MAX_EXECUTION_BATCHES as u64,
+            });
+        }
+    }
+
     let voting_module: Addr = deps.querier.query_wasm_smart(
         config.dao.clone(),
         &dao_interface::msg::QueryMsg::VotingModule {},
@@ -198,7 +258,16 @@ pub fn execute_propose(
         return Err(ContractError::InactiveDao {});
     }
 
-    let expiration = config.max_voting_period.after(&env.block);
+    // If proposals have an amendment window voting opens, and the
+    // voting period begins, once it has passed.
+    let amendment_config = AMENDMENT_CONFIG.may_load(deps.storage)?;
+    if amendment_config.is_some() && vote.is_some() {
+        return Err(AmendmentError::AutoVoteNotAllowed {}.into());
+    }
+    let voting_start = amendment_config
+        .as_ref()
+        .map_or_else(|| env.block.clone(), |c| c.voting_start(&env.block));
+    let expiration = config.max_voting_period.after(&voting_start);
 
     let total_power = get_total_power(deps.as_ref(), &config.dao, Some(env.block.height))?;
 
@@ -209,7 +278,7 @@ pub fn execute_propose(
             description,
             proposer: proposer.clone(),
             start_height: env.block.height,
-            min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+            min_voting_period: config.min_voting_period.map(|min| min.after(&voting_start)),
             expiration,
             threshold: config.threshold,
             total_power,
@@ -218,6 +287,17 @@ pub fn execute_propose(
             votes: Votes::zero(),
             allow_revoting: config.allow_revoting,
             veto: config.veto,
+            execution_plan: execution_plan.map(|plan| {
+                plan.into_iter()
+                    .map(|batch| ScheduledBatch {
+                        batch,
+                        status: BatchStatus::Pending,
+                    })
+                    .collect()
+            }),
+            amendable_until: amendment_config
+                .as_ref()
+                .map(|c| c.window_expiration(&env.block)),
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -250,11 +330,37 @@ pub fn execute_propose(
 
     PROPOSALS.save(deps.storage, id, &proposal)?;
 
+    // Record the initial contents of amendable proposals as their
+    // first revision.
+    if proposal.amendable_until.is_some() {
+        REVISIONS.save(
+            deps.storage,
+            (id, 0),
+            &Revision {
+                revision: 0,
+                author: proposer.clone(),
+                height: env.block.height,
+                time: env.block.time,
+                title: proposal.title.clone(),
+                description: proposal.description.clone(),
+                msgs: proposal.msgs.clone(),
+            },
+        )?;
+    }
+
     let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
 
     // Auto cast vote if given.
     let (vote_hooks, vote_attributes) = if let Some(vote) = vote {
-        let response = execute_vote(deps, env, proposer, id, vote.vote, vote.rationale.clone())?;
+        let response = execute_vote(
+            deps,
+            env,
+            proposer,
+            id,
+            vote.vote,
+            None,
+            vote.rationale.clone(),
+        )?;
         (
             response.messages,
             vec![
@@ -346,7 +452,7 @@ pub fn execute_veto(
     // Add prepropose / deposit module hook which will handle deposit refunds.
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
     let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status, false)?;
 
     Ok(Response::new()
         .add_attribute("action", "veto")
@@ -415,6 +521,13 @@ pub fn execute_execute(
             // if veto timelocked, only allow execution if early_execute enabled
             veto_config.check_early_execute_enabled()?;
         }
+        // Proposals with an execution plan remain executable until
+        // each of their batches has been executed.
+        Status::Executed if prop.next_batch().is_some() => {
+            if !sender_can_execute {
+                return Err(ContractError::Unauthorized {});
+            }
+        }
         _ => {
             return Err(ContractError::NotPassed {});
         }
@@ -424,8 +537,50 @@ pub fn execute_execute(
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
+    let next_batch = prop
+        .next_batch()
+        .map(|(index, batch)| (index, batch.clone()));
+    // Proposals with an execution plan are completed once their plan
+    // finishes or is aborted. See `batch_execution_reply`.
+    let reports_execution = prop.execution_plan.is_some()
+        || (!prop.msgs.is_empty() && config.close_proposal_on_execution_failure);
+
+    // Status hooks are dispatched ahead of execution, so receivers
+    // hear that the proposal was executed before its reply reports
+    // any failure. They have already fired if this is a later batch
+    // of an execution plan.
+    let response = if old_status == Status::Executed {
+        Response::default()
+    } else {
+        Response::default().add_submessages(proposal_status_changed_hooks(
+            PROPOSAL_HOOKS,
+            deps.storage,
+            proposal_id,
+            old_status.to_string(),
+            prop.status.to_string(),
+        )?)
+    };
     let response = {
-        if !prop.msgs.is_empty() {
+        if let Some((index, batch)) = next_batch {
+            // Batches are executed one at a time. As each completes
+            // the next is dispatched from `reply` if it is due.
+            if let Some(not_before) = batch.not_before {
+                if env.block.time < not_before {
+                    return Err(ContractError::BatchNotReady {
+                        index: index as u64,
+                        not_before,
+                    });
+                }
+            }
+            response
+                .add_submessage(batch_execution_message(
+                    &config.dao,
+                    proposal_id,
+                    index,
+                    batch.msgs,
+                )?)
+                .add_attribute("batch", index.to_string())
+        } else if !prop.msgs.is_empty() {
             let execute_message = WasmMsg::Execute {
                 contract_addr: config.dao.to_string(),
                 msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
@@ -434,34 +589,39 @@ pub fn execute_execute(
                 funds: vec![],
             };
             match config.close_proposal_on_execution_failure {
+                // The reply reports whether execution failed to the
+                // pre-propose module, so it is needed on success too.
                 true => {
                     let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
-                    Response::default()
-                        .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+                    response
+                        .add_submessage(SubMsg::reply_always(execute_message, masked_proposal_id))
                 }
-                false => Response::default().add_message(execute_message),
+                false => response.add_message(execute_message),
             }
         } else {
-            Response::default()
+            response
         }
     };
 
-    // Add proposal status change hooks
-    let proposal_status_changed_hooks = proposal_status_changed_hooks(
-        PROPOSAL_HOOKS,
-        deps.storage,
-        proposal_id,
-        old_status.to_string(),
-        prop.status.to_string(),
-    )?;
+    if old_status == Status::Executed {
+        return Ok(response
+            .add_attribute("action", "execute")
+            .add_attribute("sender", info.sender)
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("dao", config.dao));
+    }
 
-    // Add prepropose / deposit module hook which will handle deposit refunds.
-    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    // Add prepropose / deposit module hook which will handle deposit
+    // refunds. If the execution reply was requested it fires the hook
+    // once the outcome of execution is known.
+    let proposal_completed_hooks = if reports_execution {
+        vec![]
+    } else {
+        let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status, false)?
+    };
 
     Ok(response
-        .add_submessages(proposal_status_changed_hooks)
         .add_submessages(proposal_completed_hooks)
         .add_attribute("action", "execute")
         .add_attribute("sender", info.sender)
@@ -469,12 +629,235 @@ pub fn execute_execute(
         .add_attribute("dao", config.dao))
 }
 
-pub fn execute_vote(
+/// Builds the message executing a batch of a proposal's execution
+/// plan. Its reply records the outcome of the batch.
+fn batch_execution_message(
+    dao: &Addr,
+    proposal_id: u64,
+    index: usize,
+    msgs: Vec<CosmosMsg>,
+) -> StdResult<SubMsg> {
+    Ok(SubMsg::reply_always(
+        WasmMsg::Execute {
+            contract_addr: dao.to_string(),
+            msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        },
+        mask_proposal_batch_execution(proposal_id, index as u64),
+    ))
+}
+
+/// Records the outcome of executing a batch of a proposal's
+/// execution plan according to the batch's failure policy. Unless the
+/// plan has stopped, the next batch is dispatched if it is due.
+fn batch_execution_reply(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+    index: usize,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let scheduled = prop
+        .execution_plan
+        .as_mut()
+        .and_then(|plan| plan.get_mut(index))
+        .ok_or(ContractError::NoSuchBatch {
+            id: proposal_id,
+            index: index as u64,
+        })?;
+
+    let error = result.into_result().err();
+    let continue_plan = match (&error, scheduled.batch.on_failure) {
+        (None, _) => {
+            scheduled.status = BatchStatus::Executed;
+            true
+        }
+        (Some(_), BatchFailurePolicy::Skip) => {
+            scheduled.status = BatchStatus::Failed;
+            true
+        }
+        // Leave the batch pending so that it is retried the next
+        // time the proposal is executed.
+        (Some(_), BatchFailurePolicy::Retry) => false,
+        (Some(_), BatchFailurePolicy::Abort) => {
+            scheduled.status = BatchStatus::Failed;
+            prop.status = Status::ExecutionFailed;
+            false
+        }
+    };
+
+    let mut response = Response::new()
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("batch", index.to_string())
+        .add_attribute("batch_status", scheduled.status.to_string());
+    if let Some(error) = error {
+        response = response
+            .add_events(spending_cap_exceeded_event(proposal_id, &error))
+            .add_attribute("error", error);
+    }
+
+    let mut finished = prop.status == Status::ExecutionFailed;
+    if continue_plan {
+        match prop.next_batch() {
+            Some((next, batch)) => {
+                if batch
+                    .not_before
+                    .map_or(true, |not_before| not_before <= env.block.time)
+                {
+                    response = response.add_submessage(batch_execution_message(
+                        &config.dao,
+                        proposal_id,
+                        next,
+                        batch.msgs.clone(),
+                    )?);
+                }
+            }
+            None => finished = true,
+        }
+    }
+
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    // The proposal's final status is only known once its plan has
+    // finished or been aborted, so the pre-propose module is told
+    // about it here rather than when the first batch is dispatched.
+    if finished {
+        if prop.status == Status::ExecutionFailed {
+            response = response.add_submessages(proposal_status_changed_hooks(
+                PROPOSAL_HOOKS,
+                deps.storage,
+                proposal_id,
+                Status::Executed.to_string(),
+                prop.status.to_string(),
+            )?);
+        }
+        let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+        response = response.add_submessages(proposal_completed_hooks(
+            proposal_creation_policy,
+            proposal_id,
+            prop.status,
+            false,
+        )?);
+    }
+
+    Ok(response)
+}
+
+pub fn execute_amend(
     deps: DepsMut,
     env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    title: Option<String>,
+    description: Option<String>,
+    msgs: Option<Vec<CosmosMsg>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block)?;
+    match prop.amendable_until {
+        Some(until) if prop.status == Status::Open && !until.is_expired(&env.block) => (),
+        _ => return Err(AmendmentError::WindowClosed {}.into()),
+    }
+
+    // Sponsors are determined by the current amendment config and
+    // their voting power when the proposal was created.
+    if info.sender != prop.proposer {
+        let is_sponsor = match AMENDMENT_CONFIG.may_load(deps.storage)? {
+            Some(amendment_config) => {
+                let power = get_voting_power(
+                    deps.as_ref(),
+                    info.sender.clone(),
+                    &config.dao,
+                    Some(prop.start_height),
+                )?;
+                amendment_config.is_sponsor(power, prop.total_power)
+            }
+            None => false,
+        };
+        if !is_sponsor {
+            return Err(AmendmentError::Unauthorized {}.into());
+        }
+    }
+
+    if title.is_none() && description.is_none() && msgs.is_none() {
+        return Err(AmendmentError::NoChanges {}.into());
+    }
+    if let Some(title) = title {
+        prop.title = title;
+    }
+    if let Some(description) = description {
+        prop.description = description;
+    }
+    if let Some(msgs) = msgs {
+        if prop.execution_plan.is_some() && !msgs.is_empty() {
+            return Err(ContractError::MsgsWithExecutionPlan {});
+        }
+        prop.msgs = msgs;
+    }
+
+    // Amended proposals are subject to the same size limit as new
+    // ones. See `execute_propose`.
+    let proposal_size = cosmwasm_std::to_json_vec(&prop)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let revision = REVISIONS
+        .prefix(proposal_id)
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    REVISIONS.save(
+        deps.storage,
+        (proposal_id, revision),
+        &Revision {
+            revision,
+            author: info.sender.clone(),
+            height: env.block.height,
+            time: env.block.time,
+            title: prop.title,
+            description: prop.description,
+            msgs: prop.msgs,
+        },
+    )?;
+
+    let hooks = proposal_amended_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        revision,
+        info.sender.as_str(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "amend")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("revision", revision.to_string()))
+}
+
+pub fn execute_vote(
+    mut deps: DepsMut,
+    env: Env,
     sender: Addr,
     proposal_id: u64,
     vote: Vote,
+    split: Option<Vec<WeightedVote>>,
     rationale: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
@@ -492,29 +875,47 @@ pub fn execute_vote(
     if prop.expiration.is_expired(&env.block) {
         return Err(ContractError::Expired { id: proposal_id });
     }
+    check_voting_open(&prop.amendable_until, &env.block)?;
+
+    let delegation_module = DELEGATION_MODULE.may_load(deps.storage)?;
 
-    let vote_power = get_voting_power(
+    let mut vote_power = get_voting_power(
         deps.as_ref(),
         sender.clone(),
         &config.dao,
         Some(prop.start_height),
     )?;
+    if let Some(delegation_module) = &delegation_module {
+        // Delegates vote with the voting power delegated to them,
+        // less that of delegators who have already voted themselves.
+        let delegated_power = get_delegated_voting_power(
+            deps.as_ref(),
+            &sender,
+            delegation_module,
+            Some(prop.start_height),
+        )?;
+        let overridden_power = DELEGATION_OVERRIDES
+            .may_load(deps.storage, (proposal_id, &sender))?
+            .unwrap_or_default();
+        vote_power = vote_power.checked_add(delegated_power.saturating_sub(overridden_power))?;
+    }
     if vote_power.is_zero() {
         return Err(ContractError::NotRegistered {});
     }
 
-    BALLOTS.update(deps.storage, (proposal_id, &sender), |bal| match bal {
+    let first_vote = !BALLOTS.has(deps.storage, (proposal_id, &sender));
+
+    let ballot = BALLOTS.update(deps.storage, (proposal_id, &sender), |bal| match bal {
         Some(current_ballot) => {
             if prop.allow_revoting {
-                if current_ballot.vote == vote {
+                if current_ballot.vote == vote && current_ballot.split == split {
                     // Don't allow casting the same vote more than
                     // once. This seems liable to be confusing
                     // behavior.
                     Err(ContractError::AlreadyCast {})
                 } else {
                     // Remove the old vote if this is a re-vote.
-                    prop.votes
-                        .remove_vote(current_ballot.vote, current_ballot.power);
+                    prop.remove_vote(&current_ballot);
                     Ok(Ballot {
                         power: vote_power,
                         vote,
@@ -522,6 +923,7 @@ pub fn execute_vote(
                         // you're changing your vote, you've also
                         // likely changed your thinking.
                         rationale: rationale.clone(),
+                        split: split.clone(),
                     })
                 }
             } else {
@@ -532,12 +934,25 @@ pub fn execute_vote(
             power: vote_power,
             vote,
             rationale: rationale.clone(),
+            split: split.clone(),
         }),
     })?;
 
+    // A delegator voting for the first time overrides the votes their
+    // delegates cast on their behalf.
+    if let (true, Some(delegation_module)) = (first_vote, &delegation_module) {
+        override_delegate_votes(
+            deps.branch(),
+            &mut prop,
+            proposal_id,
+            &sender,
+            delegation_module,
+        )?;
+    }
+
     let old_status = prop.status;
 
-    prop.votes.add_vote(vote, vote_power);
+    prop.add_vote(&ballot);
     prop.update_status(&env.block)?;
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
@@ -551,13 +966,32 @@ pub fn execute_vote(
         new_status.to_string(),
     )?;
 
-    let vote_hooks = new_vote_hooks(
-        VOTE_HOOKS,
-        deps.storage,
-        proposal_id,
-        sender.to_string(),
-        vote.to_string(),
-    )?;
+    let vote_hooks = match &split {
+        Some(split) => new_weighted_vote_hooks(
+            VOTE_HOOKS,
+            deps.storage,
+            proposal_id,
+            sender.to_string(),
+            split.clone(),
+        )?,
+        None => new_vote_hooks(
+            VOTE_HOOKS,
+            deps.storage,
+            proposal_id,
+            sender.to_string(),
+            vote.to_string(),
+        )?,
+    };
+    // Weighted votes are reported as a comma separated list of
+    // positions and their weights.
+    let position = match &split {
+        Some(split) => split
+            .iter()
+            .map(|weighted| weighted.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+        None => vote.to_string(),
+    };
 
     Ok(Response::default()
         .add_submessages(change_hooks)
@@ -565,7 +999,7 @@ pub fn execute_vote(
         .add_attribute("action", "vote")
         .add_attribute("sender", sender)
         .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("position", vote.to_string())
+        .add_attribute("position", position)
         .add_attribute(
             "rationale",
             rationale.unwrap_or_else(|| "_none".to_string()),
@@ -573,6 +1007,48 @@ pub fn execute_vote(
         .add_attribute("status", prop.status.to_string()))
 }
 
+/// Removes the voting power `delegator` delegated as of the proposal's
+/// start height from their delegates' ballots, and records it so that
+/// delegates who have yet to vote do not count it.
+fn override_delegate_votes(
+    deps: DepsMut,
+    prop: &mut SingleChoiceProposal,
+    proposal_id: u64,
+    delegator: &Addr,
+    delegation_module: &Addr,
+) -> Result<(), ContractError> {
+    let delegations = get_delegations(
+        deps.as_ref(),
+        delegator,
+        delegation_module,
+        Some(prop.start_height),
+    )?;
+    for delegation in delegations {
+        if delegation.power.is_zero() {
+            continue;
+        }
+        DELEGATION_OVERRIDES.update(
+            deps.storage,
+            (proposal_id, &delegation.delegate),
+            |overridden| -> StdResult<_> {
+                Ok(overridden
+                    .unwrap_or_default()
+                    .checked_add(delegation.power)?)
+            },
+        )?;
+        if let Some(mut ballot) =
+            BALLOTS.may_load(deps.storage, (proposal_id, &delegation.delegate))?
+        {
+            let removed = ballot.power.min(delegation.power);
+            prop.remove_vote(&ballot);
+            ballot.power -= removed;
+            prop.add_vote(&ballot);
+            BALLOTS.save(deps.storage, (proposal_id, &delegation.delegate), &ballot)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn execute_update_rationale(
     deps: DepsMut,
     info: MessageInfo,
@@ -634,8 +1110,12 @@ pub fn execute_close(
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    let proposal_completed_hooks = proposal_completed_hooks(
+        proposal_creation_policy,
+        proposal_id,
+        prop.status,
+        prop.failed_quorum(),
+    )?;
 
     Ok(Response::default()
         .add_submessages(proposal_status_changed_hooks)
@@ -673,6 +1153,9 @@ pub fn execute_update_config(
     // if veto is configured, validate its fields
     if let Some(veto_config) = &veto {
         veto_config.validate(&deps.as_ref(), &max_voting_period)?;
+    }
+    if let Some(amendment_config) = AMENDMENT_CONFIG.may_load(deps.storage)? {
+        amendment_config.validate(&max_voting_period)?;
     };
 
     CONFIG.save(
@@ -714,6 +1197,57 @@ pub fn execute_update_proposal_creation_policy(
         .add_attribute("new_policy", format!("{initial_policy:?}")))
 }
 
+pub fn execute_update_delegation_module(
+    deps: DepsMut,
+    info: MessageInfo,
+    module: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &module {
+        Some(module) => {
+            let module = deps.api.addr_validate(module)?;
+            DELEGATION_MODULE.save(deps.storage, &module)?;
+        }
+        None => DELEGATION_MODULE.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_delegation_module")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "delegation_module",
+            module.unwrap_or_else(|| "_none".to_string()),
+        ))
+}
+
+pub fn execute_update_amendment_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    amendment_config: Option<AmendmentConfig>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &amendment_config {
+        Some(amendment_config) => {
+            amendment_config.validate(&config.max_voting_period)?;
+            AMENDMENT_CONFIG.save(deps.storage, amendment_config)?;
+        }
+        None => AMENDMENT_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_amendment_config")
+        .add_attribute("sender", info.sender)
+        .add_attribute("amendments_enabled", amendment_config.is_some().to_string()))
+}
+
 pub fn add_hook(
     hooks: Hooks,
     storage: &mut dyn Storage,
@@ -845,6 +1379,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::DelegationModule {} => to_json_binary(&DELEGATION_MODULE.may_load(deps.storage)?),
+        QueryMsg::Turnout { proposal_id } => query_turnout(deps, env, proposal_id),
+        QueryMsg::AmendmentConfig {} => to_json_binary(&AMENDMENT_CONFIG.may_load(deps.storage)?),
+        QueryMsg::ListRevisions {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_revisions(deps, proposal_id, start_after, limit),
     }
 }
 
@@ -923,6 +1465,7 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
         vote: ballot.vote,
         power: ballot.power,
         rationale: ballot.rationale,
+        split: ballot.split,
     });
     to_json_binary(&VoteResponse { vote })
 }
@@ -950,6 +1493,7 @@ pub fn query_list_votes(
                 vote: ballot.vote,
                 power: ballot.power,
                 rationale: ballot.rationale,
+                split: ballot.split,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -957,6 +1501,30 @@ pub fn query_list_votes(
     to_json_binary(&VoteListResponse { votes })
 }
 
+pub fn query_turnout(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_json_binary(&proposal.turnout(&env.block)?)
+}
+
+pub fn query_list_revisions(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let min = start_after.map(Bound::<u64>::exclusive);
+
+    let revisions = REVISIONS
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| item.map(|(_, revision)| revision))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&RevisionListResponse { revisions })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_json_binary(&dao_interface::voting::InfoResponse { info })
@@ -1047,6 +1615,8 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                         votes: v1_votes_to_v2(prop.votes),
                         allow_revoting: prop.allow_revoting,
                         veto: None,
+                        execution_plan: None,
+                        amendable_until: None,
                     };
 
                     PROPOSALS
@@ -1066,25 +1636,50 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
+        // Received whether or not execution failed.
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
-            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
-                Some(mut prop) => {
-                    prop.status = Status::ExecutionFailed;
+            let mut prop = PROPOSALS
+                .may_load(deps.storage, proposal_id)?
+                .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
-                    Ok(prop)
+            let response = match msg.result.into_result() {
+                Ok(_) => Response::new(),
+                Err(error) => {
+                    prop.status = Status::ExecutionFailed;
+                    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+                    // Matches the hook fired when an execution plan is
+                    // aborted. See `batch_execution_reply`.
+                    let status_changed_hooks = proposal_status_changed_hooks(
+                        PROPOSAL_HOOKS,
+                        deps.storage,
+                        proposal_id,
+                        Status::Executed.to_string(),
+                        prop.status.to_string(),
+                    )?;
+
+                    Response::new()
+                        .add_submessages(status_changed_hooks)
+                        .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                        .add_events(spending_cap_exceeded_event(proposal_id, &error))
+                        .add_attribute("error", error)
                 }
-                None => Err(ContractError::NoSuchProposal { id: proposal_id }),
-            })?;
-
-            Ok(Response::new()
-                .add_attribute("proposal_execution_failed", proposal_id.to_string())
-                .add_attribute(
-                    "error",
-                    msg.result.into_result().err().unwrap_or("None".to_string()),
-                ))
+            };
+
+            // Add prepropose / deposit module hook which will handle
+            // deposit refunds.
+            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            let proposal_completed_hooks = proposal_completed_hooks(
+                proposal_creation_policy,
+                proposal_id,
+                prop.status,
+                false,
+            )?;
+
+            Ok(response.add_submessages(proposal_completed_hooks))
         }
         TaggedReplyId::FailedProposalHook(idx) => {
             let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
@@ -1137,5 +1732,8 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             };
             Ok(Response::new().add_attribute("failed_prepropose_hook", format!("{addr}")))
         }
+        TaggedReplyId::ProposalBatchExecution { proposal_id, batch } => {
+            batch_execution_reply(deps, env, proposal_id, batch as usize, msg.result)
+        }
     }
 }
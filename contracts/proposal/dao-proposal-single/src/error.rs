@@ -1,7 +1,7 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{OverflowError, StdError, Timestamp};
 use cw_hooks::HookError;
 use cw_utils::ParseReplyError;
-use dao_voting::{reply::error::TagError, veto::VetoError};
+use dao_voting::{amendment::AmendmentError, reply::error::TagError, veto::VetoError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,6 +9,9 @@ pub enum ContractError {
     #[error(transparent)]
     Std(#[from] StdError),
 
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+
     #[error(transparent)]
     ParseReplyError(#[from] ParseReplyError),
 
@@ -18,6 +21,9 @@ pub enum ContractError {
     #[error(transparent)]
     VetoError(#[from] VetoError),
 
+    #[error(transparent)]
+    AmendmentError(#[from] AmendmentError),
+
     #[error("unauthorized")]
     Unauthorized {},
 
@@ -48,9 +54,26 @@ pub enum ContractError {
     #[error("already cast a vote with that option. change your vote to revote")]
     AlreadyCast {},
 
+    #[error("weighted votes must split voting power between distinct positions with non-zero weights summing to one")]
+    InvalidWeightedVote {},
+
     #[error("proposal is not in 'passed' state")]
     NotPassed {},
 
+    #[error("proposals with an execution plan must not have msgs")]
+    MsgsWithExecutionPlan {},
+
+    #[error(
+        "execution plans must have between 1 and ({max}) batches, each with at least one message"
+    )]
+    InvalidExecutionPlan { max: u64 },
+
+    #[error("batch ({index}) of the execution plan may not be executed before ({not_before})")]
+    BatchNotReady { index: u64, not_before: Timestamp },
+
+    #[error("proposal ({id}) has no execution plan batch ({index})")]
+    NoSuchBatch { id: u64, index: u64 },
+
     #[error("proposal has already been executed")]
     AlreadyExecuted {},
 
@@ -1,9 +1,14 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
 use cw_utils::Duration;
 use dao_dao_macros::proposal_module_query;
 use dao_voting::{
-    pre_propose::PreProposeInfo, proposal::SingleChoiceProposeMsg, threshold::Threshold,
-    veto::VetoConfig, voting::Vote,
+    amendment::AmendmentConfig,
+    pre_propose::PreProposeInfo,
+    proposal::SingleChoiceProposeMsg,
+    threshold::Threshold,
+    veto::VetoConfig,
+    voting::{Vote, WeightedVote},
 };
 
 #[cw_serde]
@@ -63,12 +68,37 @@ pub enum ExecuteMsg {
         /// the vote.
         rationale: Option<String>,
     },
+    /// Votes on a proposal, splitting the sender's voting power
+    /// between positions by weight. Voting power is determined by the
+    /// DAO's voting power module.
+    WeightedVote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The share of voting power cast for each position. Weights
+        /// must sum to one.
+        votes: Vec<WeightedVote>,
+        /// An optional rationale for why this vote was cast. This can
+        /// be updated, set, or removed later by the address casting
+        /// the vote.
+        rationale: Option<String>,
+    },
     /// Updates the sender's rationale for their vote on the specified
     /// proposal. Errors if no vote vote has been cast.
     UpdateRationale {
         proposal_id: u64,
         rationale: Option<String>,
     },
+    /// Revises a proposal during its amendment window. Callable by
+    /// the proposal's proposer, or by sponsors if the amendment
+    /// config sets a sponsor threshold. Fields left unset are not
+    /// changed.
+    Amend {
+        /// The ID of the proposal to amend.
+        proposal_id: u64,
+        title: Option<String>,
+        description: Option<String>,
+        msgs: Option<Vec<CosmosMsg<Empty>>>,
+    },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
     Execute {
@@ -129,6 +159,16 @@ pub enum ExecuteMsg {
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
     UpdatePreProposeInfo { info: PreProposeInfo },
+    /// Updates the vote delegation module used to look up delegated
+    /// voting power. When set, delegates vote with the voting power
+    /// delegated to them and delegators may override their delegates
+    /// by voting themselves. Only the DAO may call this method.
+    UpdateDelegationModule { module: Option<String> },
+    /// Updates the amendment window applied to proposals created
+    /// after the update. If unset, proposals may not be amended and
+    /// are open for voting once created. Only the DAO may call this
+    /// method.
+    UpdateAmendmentConfig { config: Option<AmendmentConfig> },
     /// Adds an address as a consumer of proposal hooks. Consumers of
     /// proposal hooks have hook messages executed on them whenever
     /// the status of a proposal changes or a proposal is created. If
@@ -210,6 +250,30 @@ pub enum QueryMsg {
     /// Lists all of the consumers of vote hooks for this module.
     #[returns(::cw_hooks::HooksResponse)]
     VoteHooks {},
+    /// Gets the vote delegation module used by this module, if any.
+    #[returns(::std::option::Option<::cosmwasm_std::Addr>)]
+    DelegationModule {},
+    /// Gets the turnout on a proposal and the voting power needed to
+    /// pass or reject it.
+    #[returns(crate::query::TurnoutResponse)]
+    Turnout { proposal_id: u64 },
+    /// Gets the amendment window applied to new proposals, if any.
+    #[returns(::std::option::Option<::dao_voting::amendment::AmendmentConfig>)]
+    AmendmentConfig {},
+    /// Lists the revisions of a proposal, starting with the contents
+    /// it was created with. Proposals created without an amendment
+    /// window have no revisions.
+    #[returns(crate::query::RevisionListResponse)]
+    ListRevisions {
+        /// The proposal to list the revisions of.
+        proposal_id: u64,
+        /// The revision to start listing revisions after.
+        start_after: Option<u64>,
+        /// The maximum number of revisions to return in response to
+        /// this query. If no limit is specified a max of 30 are
+        /// returned.
+        limit: Option<u64>,
+    },
 }
 
 #[cw_serde]
@@ -1,14 +1,17 @@
 use std::ops::Add;
 
-use crate::query::ProposalResponse;
-use crate::state::PROPOSAL_COUNT;
+use crate::query::{ProposalResponse, TurnoutResponse};
+use crate::state::{Ballot, PROPOSAL_COUNT};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Decimal, Empty, StdResult, Storage, Uint128};
 use cw_utils::Expiration;
+use dao_voting::execution_plan::{BatchStatus, ExecutionBatch};
 use dao_voting::status::Status;
 use dao_voting::threshold::{PercentageThreshold, Threshold};
 use dao_voting::veto::VetoConfig;
-use dao_voting::voting::{does_vote_count_fail, does_vote_count_pass, Votes};
+use dao_voting::voting::{
+    does_vote_count_fail, does_vote_count_pass, least_power_needed, turnout, Votes,
+};
 
 #[cw_serde]
 pub struct SingleChoiceProposal {
@@ -46,6 +49,21 @@ pub struct SingleChoiceProposal {
     /// Optional veto configuration. If set to `None`, veto option
     /// is disabled. Otherwise contains the configuration for veto flow.
     pub veto: Option<VetoConfig>,
+    /// The batches to execute should this proposal pass, used instead
+    /// of `msgs` if set, and the progress of each.
+    #[serde(default)]
+    pub execution_plan: Option<Vec<ScheduledBatch>>,
+    /// If set, the proposal may be amended and may not be voted on
+    /// until this expires.
+    #[serde(default)]
+    pub amendable_until: Option<Expiration>,
+}
+
+/// A batch of a proposal's execution plan and its progress.
+#[cw_serde]
+pub struct ScheduledBatch {
+    pub batch: ExecutionBatch,
+    pub status: BatchStatus,
 }
 
 pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
@@ -105,6 +123,75 @@ impl SingleChoiceProposal {
         }
     }
 
+    /// Computes the proposal's turnout and the voting power needed to
+    /// decide it given the votes cast so far.
+    pub fn turnout(&self, block: &BlockInfo) -> StdResult<TurnoutResponse> {
+        let expired = self.expiration.is_expired(block);
+        // No more votes may be cast once the proposal has expired.
+        let outstanding = if expired {
+            Uint128::zero()
+        } else {
+            self.total_power.saturating_sub(self.votes.total())
+        };
+
+        let quorum_needed = match self.threshold {
+            Threshold::ThresholdQuorum { quorum, .. } => least_power_needed(outstanding, |power| {
+                does_vote_count_pass(self.votes.total() + power, self.total_power, quorum)
+            }),
+            _ => None,
+        };
+        let yes_needed_to_pass = least_power_needed(outstanding, |power| {
+            let votes = Votes {
+                yes: self.votes.yes + power,
+                ..self.votes.clone()
+            };
+            self.threshold_passed(&votes, expired)
+        });
+        let no_needed_to_fail = least_power_needed(outstanding, |power| {
+            let votes = Votes {
+                no: self.votes.no + power,
+                ..self.votes.clone()
+            };
+            self.threshold_rejected(&votes, expired)
+        });
+
+        Ok(TurnoutResponse {
+            turnout: turnout(self.votes.total(), self.total_power),
+            quorum_needed,
+            yes_needed_to_pass,
+            no_needed_to_fail,
+            locked: self.current_status(block)? != Status::Open,
+        })
+    }
+
+    /// Returns the index of the next batch of the proposal's
+    /// execution plan that has yet to be executed and the batch
+    /// itself, if any.
+    pub fn next_batch(&self) -> Option<(usize, &ExecutionBatch)> {
+        self.execution_plan
+            .as_ref()?
+            .iter()
+            .enumerate()
+            .find(|(_, scheduled)| scheduled.status == BatchStatus::Pending)
+            .map(|(index, scheduled)| (index, &scheduled.batch))
+    }
+
+    /// Adds a ballot's voting power to the proposal's tally.
+    pub fn add_vote(&mut self, ballot: &Ballot) {
+        match &ballot.split {
+            Some(split) => self.votes.add_weighted_vote(split, ballot.power),
+            None => self.votes.add_vote(ballot.vote, ballot.power),
+        }
+    }
+
+    /// Removes a ballot's voting power from the proposal's tally.
+    pub fn remove_vote(&mut self, ballot: &Ballot) {
+        match &ballot.split {
+            Some(split) => self.votes.remove_weighted_vote(split, ballot.power),
+            None => self.votes.remove_vote(ballot.vote, ballot.power),
+        }
+    }
+
     /// Sets a proposals status to its current status.
     pub fn update_status(&mut self, block: &BlockInfo) -> StdResult<()> {
         let new_status = self.current_status(block)?;
@@ -132,29 +219,43 @@ impl SingleChoiceProposal {
             }
         }
 
+        self.threshold_passed(&self.votes, self.expiration.is_expired(block))
+    }
+
+    /// Returns true iff `votes` meet the proposal's threshold. If
+    /// `expired` is false this is only the case if no sequence of
+    /// outstanding votes could cause the proposal to fail.
+    fn threshold_passed(&self, votes: &Votes, expired: bool) -> bool {
         match self.threshold {
             Threshold::AbsolutePercentage { percentage } => {
-                let options = self.total_power - self.votes.abstain;
-                does_vote_count_pass(self.votes.yes, options, percentage)
+                let options = self.total_power - votes.abstain;
+                does_vote_count_pass(votes.yes, options, percentage)
             }
             Threshold::ThresholdQuorum { threshold, quorum } => {
-                if !does_vote_count_pass(self.votes.total(), self.total_power, quorum) {
+                if !does_vote_count_pass(votes.total(), self.total_power, quorum) {
                     return false;
                 }
 
-                if self.expiration.is_expired(block) {
+                if expired {
                     // If the quorum is met and the proposal is
                     // expired the number of votes needed to pass a
                     // proposal is compared to the number of votes on
                     // the proposal.
-                    let options = self.votes.total() - self.votes.abstain;
-                    does_vote_count_pass(self.votes.yes, options, threshold)
+                    let options = votes.total() - votes.abstain;
+                    does_vote_count_pass(votes.yes, options, threshold)
                 } else {
-                    let options = self.total_power - self.votes.abstain;
-                    does_vote_count_pass(self.votes.yes, options, threshold)
+                    let options = self.total_power - votes.abstain;
+                    does_vote_count_pass(votes.yes, options, threshold)
                 }
             }
-            Threshold::AbsoluteCount { threshold } => self.votes.yes >= threshold,
+            Threshold::AbsoluteCount { threshold } => votes.yes >= threshold,
+            Threshold::Optimistic {
+                objection_threshold,
+            } => {
+                // Optimistic proposals pass once voting has closed
+                // without enough objections to reject them.
+                expired && !does_vote_count_pass(votes.no, self.total_power, objection_threshold)
+            }
         }
     }
 
@@ -167,11 +268,28 @@ impl SingleChoiceProposal {
             return false;
         }
 
+        self.threshold_rejected(&self.votes, self.expiration.is_expired(block))
+    }
+
+    /// Returns true if the proposal has a quorum that its votes did
+    /// not reach.
+    pub fn failed_quorum(&self) -> bool {
+        match self.threshold {
+            Threshold::ThresholdQuorum { quorum, .. } => {
+                !does_vote_count_pass(self.votes.total(), self.total_power, quorum)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true iff `votes` can no longer meet the proposal's
+    /// threshold. As above for `threshold_passed`.
+    fn threshold_rejected(&self, votes: &Votes, expired: bool) -> bool {
         match self.threshold {
             Threshold::AbsolutePercentage {
                 percentage: percentage_needed,
             } => {
-                let options = self.total_power - self.votes.abstain;
+                let options = self.total_power - votes.abstain;
 
                 // If there is a 100% passing threshold..
                 if percentage_needed == PercentageThreshold::Percent(Decimal::percent(100)) {
@@ -189,22 +307,22 @@ impl SingleChoiceProposal {
                         // threshold`) we get a 0% requirement for no
                         // votes. Zero no votes do indeed meet a 0%
                         // threshold.
-                        return self.votes.no >= Uint128::new(1);
+                        return votes.no >= Uint128::new(1);
                     }
                 }
 
-                does_vote_count_fail(self.votes.no, options, percentage_needed)
+                does_vote_count_fail(votes.no, options, percentage_needed)
             }
             Threshold::ThresholdQuorum { threshold, quorum } => {
                 match (
-                    does_vote_count_pass(self.votes.total(), self.total_power, quorum),
-                    self.expiration.is_expired(block),
+                    does_vote_count_pass(votes.total(), self.total_power, quorum),
+                    expired,
                 ) {
                     // Has met quorum and is expired.
                     (true, true) => {
                         // => consider only votes cast and see if no
                         //    votes meet threshold.
-                        let options = self.votes.total() - self.votes.abstain;
+                        let options = votes.total() - votes.abstain;
 
                         // If there is a 100% passing threshold..
                         if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
@@ -223,17 +341,17 @@ impl SingleChoiceProposal {
                                 // threshold`) we get a 0% requirement
                                 // for no votes. Zero no votes do
                                 // indeed meet a 0% threshold.
-                                return self.votes.no >= Uint128::new(1);
+                                return votes.no >= Uint128::new(1);
                             }
                         }
-                        does_vote_count_fail(self.votes.no, options, threshold)
+                        does_vote_count_fail(votes.no, options, threshold)
                     }
                     // Has met quorum and is not expired.
                     // | Hasn't met quorum and is not expired.
                     (true, false) | (false, false) => {
                         // => consider all possible votes and see if
                         //    no votes meet threshold.
-                        let options = self.total_power - self.votes.abstain;
+                        let options = self.total_power - votes.abstain;
 
                         // If there is a 100% passing threshold..
                         if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
@@ -252,11 +370,11 @@ impl SingleChoiceProposal {
                                 // get a 0% requirement for no
                                 // votes. Zero no votes do indeed meet
                                 // a 0% threshold.
-                                return self.votes.no >= Uint128::new(1);
+                                return votes.no >= Uint128::new(1);
                             }
                         }
 
-                        does_vote_count_fail(self.votes.no, options, threshold)
+                        does_vote_count_fail(votes.no, options, threshold)
                     }
                     // Hasn't met quorum requirement and voting has closed => rejected.
                     (false, true) => true,
@@ -265,9 +383,12 @@ impl SingleChoiceProposal {
             Threshold::AbsoluteCount { threshold } => {
                 // If all the outstanding votes voting yes would not
                 // cause this proposal to pass then it is rejected.
-                let outstanding_votes = self.total_power - self.votes.total();
-                self.votes.yes + outstanding_votes < threshold
+                let outstanding_votes = self.total_power - votes.total();
+                votes.yes + outstanding_votes < threshold
             }
+            Threshold::Optimistic {
+                objection_threshold,
+            } => does_vote_count_pass(votes.no, self.total_power, objection_threshold),
         }
     }
 }
@@ -312,6 +433,8 @@ mod test {
             veto: None,
             total_power,
             votes,
+            execution_plan: None,
+            amendable_until: None,
         };
         (prop, block)
     }
@@ -1126,6 +1249,87 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_optimistic() {
+        let threshold = Threshold::Optimistic {
+            objection_threshold: PercentageThreshold::Percent(Decimal::percent(20)),
+        };
+        let votes = Votes {
+            yes: Uint128::zero(),
+            no: Uint128::new(2),
+            abstain: Uint128::new(1),
+        };
+
+        // Does not pass before expiration even with no objections.
+        assert!(!check_is_passed(
+            threshold.clone(),
+            Votes::zero(),
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+        // Passes at expiration without enough objections.
+        assert!(check_is_passed(
+            threshold.clone(),
+            votes.clone(),
+            Uint128::new(15),
+            true,
+            true,
+            false
+        ));
+        assert!(!check_is_rejected(
+            threshold.clone(),
+            votes,
+            Uint128::new(15),
+            true,
+            true,
+            false
+        ));
+
+        // 3 of 15 is 20% of voting power objecting. Rejected before
+        // expiration.
+        let objected = Votes {
+            yes: Uint128::new(10),
+            no: Uint128::new(3),
+            abstain: Uint128::zero(),
+        };
+        assert!(check_is_rejected(
+            threshold.clone(),
+            objected.clone(),
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+        assert!(!check_is_passed(
+            threshold.clone(),
+            objected.clone(),
+            Uint128::new(15),
+            true,
+            true,
+            false
+        ));
+
+        // With revoting enabled objections only count at expiration.
+        assert!(!check_is_rejected(
+            threshold.clone(),
+            objected.clone(),
+            Uint128::new(15),
+            false,
+            true,
+            true
+        ));
+        assert!(check_is_rejected(
+            threshold,
+            objected,
+            Uint128::new(15),
+            true,
+            true,
+            true
+        ));
+    }
+
     #[test]
     fn test_proposal_ids_advance() {
         // do they advance, lets find out!
@@ -1142,4 +1346,47 @@ mod test {
         let now = advance_proposal_id(storage).unwrap();
         assert_eq!(now, next);
     }
+
+    #[test]
+    fn test_turnout() {
+        let threshold = Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(30)),
+        };
+        let votes = Votes {
+            yes: Uint128::new(10),
+            no: Uint128::new(5),
+            abstain: Uint128::new(5),
+        };
+        let (prop, block) = setup_prop(
+            threshold.clone(),
+            votes,
+            Uint128::new(100),
+            false,
+            true,
+            false,
+        );
+        let turnout = prop.turnout(&block).unwrap();
+        assert_eq!(turnout.turnout, Decimal::percent(20));
+        assert_eq!(turnout.quorum_needed, Some(Uint128::new(10)));
+        // A majority of the 95 non-abstaining votes is 48 votes.
+        assert_eq!(turnout.yes_needed_to_pass, Some(Uint128::new(38)));
+        // Half of the 95 non-abstaining votes rounded up is 48 votes.
+        assert_eq!(turnout.no_needed_to_fail, Some(Uint128::new(43)));
+        assert!(!turnout.locked);
+
+        // Once expired no more votes may be cast.
+        let votes = Votes {
+            yes: Uint128::new(60),
+            no: Uint128::new(10),
+            abstain: Uint128::zero(),
+        };
+        let (prop, block) = setup_prop(threshold, votes, Uint128::new(100), true, true, false);
+        let turnout = prop.turnout(&block).unwrap();
+        assert_eq!(turnout.turnout, Decimal::percent(70));
+        assert_eq!(turnout.quorum_needed, Some(Uint128::zero()));
+        assert_eq!(turnout.yes_needed_to_pass, Some(Uint128::zero()));
+        assert_eq!(turnout.no_needed_to_fail, None);
+        assert!(turnout.locked);
+    }
 }
@@ -1,7 +1,7 @@
-use crate::proposal::SingleChoiceProposal;
+use crate::{proposal::SingleChoiceProposal, state::Revision};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use dao_voting::voting::Vote;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use dao_voting::voting::{Vote, WeightedVote};
 
 /// Information about a proposal returned by proposal queries.
 #[cw_serde]
@@ -22,6 +22,9 @@ pub struct VoteInfo {
     pub power: Uint128,
     /// Address-specified rationale for the vote.
     pub rationale: Option<String>,
+    /// How the voting power was split between positions if this is a
+    /// weighted vote.
+    pub split: Option<Vec<WeightedVote>>,
 }
 
 /// Information about a vote.
@@ -37,6 +40,39 @@ pub struct VoteListResponse {
     pub votes: Vec<VoteInfo>,
 }
 
+/// Turnout on a proposal and the voting power needed to decide it,
+/// returned by `Turnout`.
+#[cw_serde]
+pub struct TurnoutResponse {
+    /// The share of the proposal's total voting power that has been
+    /// cast, including abstentions.
+    pub turnout: Decimal,
+    /// The additional voting power that must be cast, in any
+    /// position, for the proposal to reach quorum. None if the
+    /// proposal's threshold has no quorum or the outstanding voting
+    /// power can not reach it.
+    pub quorum_needed: Option<Uint128>,
+    /// The additional yes voting power that would pass the proposal
+    /// however the rest of the outstanding voting power votes. None if
+    /// the outstanding voting power voting yes would not.
+    pub yes_needed_to_pass: Option<Uint128>,
+    /// The additional no voting power that would reject the proposal
+    /// however the rest of the outstanding voting power votes. None if
+    /// the outstanding voting power voting no would not.
+    pub no_needed_to_fail: Option<Uint128>,
+    /// Whether the outcome of the proposal is decided. This may be
+    /// false while the power needed to pass or fail is zero if the
+    /// proposal's minimum voting period has not passed or revoting is
+    /// allowed.
+    pub locked: bool,
+}
+
+/// The revision history of a proposal returned by `ListRevisions`.
+#[cw_serde]
+pub struct RevisionListResponse {
+    pub revisions: Vec<Revision>,
+}
+
 /// A list of proposals returned by `ListProposals` and
 /// `ReverseProposals`.
 #[cw_serde]
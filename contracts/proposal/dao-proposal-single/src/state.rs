@@ -1,10 +1,14 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, CosmosMsg, Empty, Timestamp, Uint128};
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
 use dao_voting::{
-    pre_propose::ProposalCreationPolicy, threshold::Threshold, veto::VetoConfig, voting::Vote,
+    amendment::AmendmentConfig,
+    pre_propose::ProposalCreationPolicy,
+    threshold::Threshold,
+    veto::VetoConfig,
+    voting::{Vote, WeightedVote},
 };
 
 use crate::proposal::SingleChoiceProposal;
@@ -22,6 +26,28 @@ pub struct Ballot {
     /// we deserialize into None (i.e. Option::default()).
     #[serde(default)]
     pub rationale: Option<String>,
+    /// How the voting power was split between positions if this is a
+    /// weighted vote. `vote` then holds the position with the largest
+    /// share.
+    #[serde(default)]
+    pub split: Option<Vec<WeightedVote>>,
+}
+
+/// A revision of a proposal's contents. Revision zero holds the
+/// contents the proposal was created with.
+#[cw_serde]
+pub struct Revision {
+    /// The number of this revision.
+    pub revision: u64,
+    /// The address that made this revision.
+    pub author: Addr,
+    /// The block height at which this revision was made.
+    pub height: u64,
+    /// The time at which this revision was made.
+    pub time: Timestamp,
+    pub title: String,
+    pub description: String,
+    pub msgs: Vec<CosmosMsg<Empty>>,
 }
 
 /// The governance module's configuration.
@@ -77,3 +103,14 @@ pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+/// The vote delegation module used to look up delegated voting power
+/// (if any).
+pub const DELEGATION_MODULE: Item<Addr> = Item::new("delegation_module");
+/// The voting power removed from a delegate's delegated voting power
+/// on a proposal by delegators who voted on it themselves.
+pub const DELEGATION_OVERRIDES: Map<(u64, &Addr), Uint128> = Map::new("delegation_overrides");
+/// The amendment window applied to new proposals (if any).
+pub const AMENDMENT_CONFIG: Item<AmendmentConfig> = Item::new("amendment_config");
+/// The revision history of proposals created with an amendment
+/// window, keyed by proposal ID and revision number.
+pub const REVISIONS: Map<(u64, u64), Revision> = Map::new("revisions");
@@ -177,6 +177,7 @@ pub fn test_executed_prop_state_remains_after_vote_swing() {
                 },
                 amount: Uint128::new(10_000_000),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             false,
         ),
@@ -276,6 +277,7 @@ pub fn test_passed_prop_state_remains_after_vote_swing() {
                 },
                 amount: Uint128::new(10_000_000),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             false,
         ),
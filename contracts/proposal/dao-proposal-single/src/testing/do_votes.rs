@@ -208,6 +208,7 @@ where
                 description: "This is a simple text proposal".to_string(),
                 msgs: vec![],
                 vote: None,
+                execution_plan: None,
             },
         },
         &funds,
@@ -250,6 +251,7 @@ where
                 let expected = VoteResponse {
                     vote: Some(VoteInfo {
                         rationale: None,
+                        split: None,
                         voter: Addr::unchecked(&voter),
                         vote: position,
                         power: match deposit_config.deposit_info {
@@ -8,7 +8,7 @@ use dao_voting::{
     deposit::CheckedDepositInfo,
     pre_propose::ProposalCreationPolicy,
     proposal::SingleChoiceProposeMsg as ProposeMsg,
-    voting::{SingleChoiceAutoVote, Vote},
+    voting::{SingleChoiceAutoVote, Vote, WeightedVote},
 };
 
 use crate::{
@@ -46,6 +46,7 @@ pub(crate) fn make_proposal(
                     denom,
                     amount,
                     refund_policy: _,
+                    scaling: None,
                 }) => match denom {
                     CheckedDenom::Native(denom) => coins(amount.u128(), denom),
                     CheckedDenom::Cw20(addr) => {
@@ -81,6 +82,7 @@ pub(crate) fn make_proposal(
                     msgs: msgs.clone(),
                     proposer: None,
                     vote,
+                    execution_plan: None,
                 }),
                 &[],
             )
@@ -95,6 +97,7 @@ pub(crate) fn make_proposal(
                         description: "description".to_string(),
                         msgs: msgs.clone(),
                         vote,
+                        execution_plan: None,
                     },
                 },
                 &funds,
@@ -160,6 +163,48 @@ pub(crate) fn vote_on_proposal_should_fail(
     .unwrap()
 }
 
+pub(crate) fn weighted_vote_on_proposal(
+    app: &mut App,
+    proposal_single: &Addr,
+    sender: &str,
+    proposal_id: u64,
+    votes: Vec<WeightedVote>,
+) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_single.clone(),
+        &ExecuteMsg::WeightedVote {
+            proposal_id,
+            votes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+pub(crate) fn weighted_vote_on_proposal_should_fail(
+    app: &mut App,
+    proposal_single: &Addr,
+    sender: &str,
+    proposal_id: u64,
+    votes: Vec<WeightedVote>,
+) -> ContractError {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_single.clone(),
+        &ExecuteMsg::WeightedVote {
+            proposal_id,
+            votes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap()
+}
+
 pub(crate) fn execute_proposal_should_fail(
     app: &mut App,
     proposal_single: &Addr,
@@ -75,6 +75,7 @@ pub(crate) fn get_default_token_dao_proposal_module_instantiate(app: &mut App) -
                 },
                 amount: Uint128::new(10_000_000),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             false,
         ),
@@ -284,6 +285,7 @@ pub(crate) fn instantiate_with_native_staked_balances_governance(
                 },
                 unstaking_duration: None,
                 active_threshold: None,
+                power_transform: None,
             })
             .unwrap(),
             admin: None,
@@ -405,6 +407,7 @@ pub(crate) fn instantiate_with_staked_balances_governance(
                     unstaking_duration: Some(Duration::Height(6)),
                     initial_dao_balance: None,
                 },
+                power_transform: None,
             })
             .unwrap(),
             admin: None,
@@ -520,6 +523,7 @@ pub(crate) fn instantiate_with_staking_active_threshold(
                     initial_dao_balance: None,
                 },
                 active_threshold,
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -599,6 +603,7 @@ pub(crate) fn instantiate_with_cw4_groups_governance(
                     cw4_group_code_id: cw4_id,
                     initial_members: initial_weights,
                 },
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -89,6 +89,7 @@ fn test_v1_v2_full_migration() {
                             unstaking_duration: None,
                             initial_dao_balance: Some(Uint128::new(100)),
                         },
+                        power_transform: None,
                     })
                     .unwrap(),
                     admin: cw_core_v1::msg::Admin::CoreContract {},
@@ -312,6 +313,7 @@ fn test_v1_v2_full_migration() {
             },
             amount: Uint128::new(1),
             refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            scaling: None,
         }),
         false,
     );
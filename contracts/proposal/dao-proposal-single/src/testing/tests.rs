@@ -11,17 +11,23 @@ use cw20::Cw20Coin;
 use cw_denom::CheckedDenom;
 use cw_hooks::{HookError, HooksResponse};
 use cw_multi_test::{next_block, App, Executor};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_interface::{
     state::{Admin, ModuleInstantiateInfo},
     voting::InfoResponse,
 };
+use dao_pre_propose_single as cppbps;
 use dao_testing::{
-    contracts::{dao_pre_propose_single_contract, dao_proposal_single_contract},
+    contracts::{
+        dao_pre_propose_single_contract, dao_proposal_hook_counter_contract,
+        dao_proposal_single_contract,
+    },
     ShouldExecute, TestSingleChoiceVote,
 };
 use dao_voting::{
+    amendment::{AmendmentConfig, AmendmentError},
     deposit::{CheckedDepositInfo, UncheckedDepositInfo, VotingModuleTokenType},
+    execution_plan::{BatchFailurePolicy, BatchStatus, ExecutionBatch},
     pre_propose::{PreProposeInfo, PreProposeSubmissionPolicy, ProposalCreationPolicy},
     proposal::{SingleChoiceProposeMsg as ProposeMsg, MAX_PROPOSAL_SIZE},
     reply::{
@@ -31,14 +37,14 @@ use dao_voting::{
     status::Status,
     threshold::{ActiveThreshold, PercentageThreshold, Threshold},
     veto::{VetoConfig, VetoError},
-    voting::{SingleChoiceAutoVote, Vote, Votes},
+    voting::{SingleChoiceAutoVote, Vote, Votes, WeightedVote},
 };
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     proposal::SingleChoiceProposal,
-    query::{ProposalResponse, VoteInfo},
+    query::{ProposalResponse, RevisionListResponse, VoteInfo},
     state::Config,
     testing::{
         execute::{
@@ -48,6 +54,7 @@ use crate::{
             make_proposal, mint_cw20s, mint_natives, remove_proposal_hook,
             remove_proposal_hook_should_fail, remove_vote_hook, remove_vote_hook_should_fail,
             update_rationale, vote_on_proposal, vote_on_proposal_should_fail,
+            weighted_vote_on_proposal, weighted_vote_on_proposal_should_fail,
         },
         instantiate::{
             get_default_non_token_dao_proposal_module_instantiate,
@@ -133,6 +140,8 @@ fn test_simple_propose_staked_balances() {
         status: Status::Open,
         veto: None,
         votes: Votes::zero(),
+        execution_plan: None,
+        amendable_until: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -148,7 +157,8 @@ fn test_simple_propose_staked_balances() {
         Some(CheckedDepositInfo {
             denom: cw_denom::CheckedDenom::Cw20(gov_token),
             amount: Uint128::new(10_000_000),
-            refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed
+            refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            scaling: None,
         })
     );
 }
@@ -183,6 +193,8 @@ fn test_simple_proposal_cw4_voting() {
         status: Status::Open,
         veto: None,
         votes: Votes::zero(),
+        execution_plan: None,
+        amendable_until: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -239,6 +251,8 @@ fn test_simple_proposal_auto_vote_yes() {
             no: Uint128::zero(),
             abstain: Uint128::zero(),
         },
+        execution_plan: None,
+        amendable_until: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -288,6 +302,8 @@ fn test_simple_proposal_auto_vote_no() {
             no: Uint128::new(1),
             abstain: Uint128::zero(),
         },
+        execution_plan: None,
+        amendable_until: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -356,6 +372,7 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
             },
             amount: Uint128::new(10_000_000),
             refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            scaling: None,
         }),
         false,
     );
@@ -386,6 +403,8 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
         status: Status::Open,
         votes: Votes::zero(),
         veto: None,
+        execution_plan: None,
+        amendable_until: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -401,7 +420,8 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
         Some(CheckedDepositInfo {
             denom: cw_denom::CheckedDenom::Cw20(alt_cw20),
             amount: Uint128::new(10_000_000),
-            refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed
+            refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            scaling: None,
         })
     );
 }
@@ -2246,7 +2266,9 @@ fn test_anyone_may_propose_and_proposal_listing() {
                     no: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                veto: None
+                veto: None,
+                execution_plan: None,
+                amendable_until: None,
             }
         }
     )
@@ -2430,6 +2452,7 @@ fn test_active_threshold_absolute() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                execution_plan: None,
             }),
             &[],
         )
@@ -2469,6 +2492,7 @@ fn test_active_threshold_absolute() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                execution_plan: None,
             }),
             &[],
         )
@@ -2513,6 +2537,7 @@ fn test_active_threshold_percent() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                execution_plan: None,
             }),
             &[],
         )
@@ -2553,6 +2578,7 @@ fn test_active_threshold_percent() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                execution_plan: None,
             }),
             &[],
         )
@@ -3183,6 +3209,8 @@ pub fn test_migrate_updates_version() {
 //                 unstaking_duration: Some(Duration::Height(6)),
 //                 initial_dao_balance: None,
 //             },
+//,
+//             power_transform: None,
 //         })
 //         .unwrap(),
 //         admin: None,
@@ -3500,14 +3528,135 @@ fn test_execution_failed() {
     assert_eq!(balance, Uint128::zero());
 }
 
+/// Instantiates a proposal hook counter and registers it with the
+/// proposal module. Returns the counter's address.
+fn add_hook_counter(app: &mut App, core_addr: &Addr, proposal_module: &Addr) -> Addr {
+    let counter_id = app.store_code(dao_proposal_hook_counter_contract());
+    let counter = app
+        .instantiate_contract(
+            counter_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &dao_proposal_hook_counter::msg::InstantiateMsg {
+                should_error: false,
+            },
+            &[],
+            "hook counter",
+            None,
+        )
+        .unwrap();
+    add_proposal_hook(app, proposal_module, core_addr.as_str(), counter.as_str());
+    counter
+}
+
+fn query_status_changed_count(app: &App, counter: &Addr) -> u64 {
+    let count: dao_proposal_hook_counter::msg::CountResponse = app
+        .wrap()
+        .query_wasm_smart(
+            counter,
+            &dao_proposal_hook_counter::msg::QueryMsg::StatusChangedCounter {},
+        )
+        .unwrap();
+    count.count
+}
+
+#[test]
+fn test_execution_failed_status_hooks() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token: _,
+        proposal_id,
+    } = setup_test(vec![BankMsg::Send {
+        to_address: "ekez".to_string(),
+        amount: coins(10, "ujuno"),
+    }
+    .into()]);
+    let counter = add_hook_counter(&mut app, &core_addr, &proposal_module);
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    assert_eq!(query_status_changed_count(&app, &counter), 1);
+
+    // Hooks hear that the proposal was executed and then that its
+    // execution failed.
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::ExecutionFailed);
+    assert_eq!(query_status_changed_count(&app, &counter), 3);
+}
+
+#[test]
+fn test_execution_succeeded_with_close_on_failure() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token,
+        proposal_id,
+    } = setup_test(vec![BankMsg::Send {
+        to_address: "ekez".to_string(),
+        amount: coins(10, "ujuno"),
+    }
+    .into()]);
+    mint_natives(&mut app, core_addr.as_str(), coins(10, "ujuno"));
+    let counter = add_hook_counter(&mut app, &core_addr, &proposal_module);
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    let res = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .unwrap();
+
+    // The execution reply is requested on success too, but leaves the
+    // proposal executed and reports no failure.
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert!(!res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key == "proposal_execution_failed")));
+    assert_eq!(
+        query_balance_native(&app, "ekez", "ujuno"),
+        Uint128::new(10)
+    );
+    assert_eq!(query_status_changed_count(&app, &counter), 2);
+
+    // The deposit is refunded once the outcome of execution is known.
+    let balance = query_balance_cw20(&app, &gov_token, CREATOR_ADDR);
+    assert_eq!(balance, Uint128::new(10_000_000));
+
+    // Executed is an end state.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert_eq!(err, ContractError::NotPassed {});
+}
+
 #[test]
 fn test_reply_proposal_mock() {
     use crate::contract::reply;
-    use crate::state::PROPOSALS;
+    use crate::state::{CREATION_POLICY, PROPOSALS};
 
     let mut deps = mock_dependencies();
     let env = mock_env();
 
+    CREATION_POLICY
+        .save(deps.as_mut().storage, &ProposalCreationPolicy::Anyone {})
+        .unwrap();
     let m_proposal_id = mask_proposal_execution_proposal_id(1);
     PROPOSALS
         .save(
@@ -3529,6 +3678,8 @@ fn test_reply_proposal_mock() {
                 status: Status::Open,
                 veto: None,
                 votes: Votes::zero(),
+                execution_plan: None,
+                amendable_until: None,
             },
         )
         .unwrap();
@@ -3569,6 +3720,7 @@ fn test_proposal_too_large() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                execution_plan: None,
             }),
             &[],
         )
@@ -3621,6 +3773,7 @@ fn test_proposal_creation_permissions() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                execution_plan: None,
             }),
             &[],
         )
@@ -3647,6 +3800,7 @@ fn test_proposal_creation_permissions() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                execution_plan: None,
             }),
             &[],
         )
@@ -3678,6 +3832,7 @@ fn test_proposal_creation_permissions() {
                 msgs: vec![],
                 proposer: Some("ekez".to_string()),
                 vote: None,
+                execution_plan: None,
             }),
             &[],
         )
@@ -3863,30 +4018,35 @@ fn test_query_list_votes() {
         vec![
             VoteInfo {
                 rationale: None,
+                split: None,
                 voter: Addr::unchecked("five"),
                 vote: Vote::Yes,
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                split: None,
                 voter: Addr::unchecked("four"),
                 vote: Vote::Yes,
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                split: None,
                 voter: Addr::unchecked("one"),
                 vote: Vote::Yes,
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                split: None,
                 voter: Addr::unchecked("three"),
                 vote: Vote::No,
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                split: None,
                 voter: Addr::unchecked("two"),
                 vote: Vote::No,
                 power: Uint128::new(1)
@@ -3906,12 +4066,14 @@ fn test_query_list_votes() {
         vec![
             VoteInfo {
                 rationale: None,
+                split: None,
                 voter: Addr::unchecked("one"),
                 vote: Vote::Yes,
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                split: None,
                 voter: Addr::unchecked("three"),
                 vote: Vote::No,
                 power: Uint128::new(1)
@@ -3959,6 +4121,7 @@ fn test_update_pre_propose_module() {
                                 },
                                 amount: Uint128::new(1),
                                 refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+                                scaling: None,
                             }),
                             submission_policy: PreProposeSubmissionPolicy::Specific {
                                 dao_members: true,
@@ -4014,6 +4177,7 @@ fn test_update_pre_propose_module() {
                 denom: CheckedDenom::Cw20(gov_token.clone()),
                 amount: Uint128::new(1),
                 refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+                scaling: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Specific {
                 dao_members: true,
@@ -4251,3 +4415,679 @@ fn test_proposal_count_goes_up() {
     let next = query_next_proposal_id(&app, &proposal_module);
     assert_eq!(next, 3);
 }
+
+#[test]
+fn test_weighted_vote() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.allow_revoting = true;
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+
+    let weighted = |vote, percent| WeightedVote {
+        vote,
+        weight: Decimal::percent(percent),
+    };
+
+    // Weights must sum to one.
+    let err = weighted_vote_on_proposal_should_fail(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        vec![weighted(Vote::Yes, 60), weighted(Vote::No, 30)],
+    );
+    assert!(matches!(err, ContractError::InvalidWeightedVote {}));
+
+    let split = vec![
+        weighted(Vote::Yes, 60),
+        weighted(Vote::No, 30),
+        weighted(Vote::Abstain, 10),
+    ];
+    weighted_vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        split.clone(),
+    );
+
+    let vote = query_vote(&app, &proposal_module, CREATOR_ADDR, proposal_id)
+        .vote
+        .unwrap();
+    assert_eq!(vote.vote, Vote::Yes);
+    assert_eq!(vote.split, Some(split.clone()));
+
+    // The voter's full voting power is split between positions.
+    let power = vote.power;
+    let yes = power.mul_floor(Decimal::percent(60));
+    let no = power.mul_floor(Decimal::percent(30));
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes,
+            no,
+            abstain: power - yes - no,
+        }
+    );
+
+    // Can't cast the same split more than once.
+    let err = weighted_vote_on_proposal_should_fail(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        split,
+    );
+    assert!(matches!(err, ContractError::AlreadyCast {}));
+
+    // Revoting replaces the split.
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::No,
+    );
+    let vote = query_vote(&app, &proposal_module, CREATOR_ADDR, proposal_id)
+        .vote
+        .unwrap();
+    assert_eq!(vote.split, None);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::zero(),
+            no: power,
+            abstain: Uint128::zero(),
+        }
+    );
+
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+}
+
+#[test]
+fn test_optimistic_threshold() -> anyhow::Result<()> {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let veto_config = VetoConfig {
+        timelock_duration: Duration::Time(100),
+        vetoer: "oversight".to_string(),
+        early_execute: false,
+        veto_before_passed: false,
+    };
+    instantiate.threshold = Threshold::Optimistic {
+        objection_threshold: PercentageThreshold::Percent(Decimal::percent(20)),
+    };
+    instantiate.veto = Some(veto_config.clone());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(15),
+            },
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(85),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let unopposed = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let opposed = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+
+    // Objections below the threshold do not reject the proposal.
+    vote_on_proposal(&mut app, &proposal_module, "ekez", unopposed, Vote::No);
+    let proposal = query_proposal(&app, &proposal_module, unopposed);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // Objections reaching the threshold reject the proposal
+    // immediately.
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, opposed, Vote::No);
+    let proposal = query_proposal(&app, &proposal_module, opposed);
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+
+    // Without enough objections the proposal passes at expiration,
+    // subject to the veto timelock.
+    app.update_block(|block| block.time = block.time.plus_seconds(604800));
+    let proposal = query_proposal(&app, &proposal_module, unopposed);
+    assert_eq!(
+        proposal.proposal.status,
+        Status::VetoTimelock {
+            expiration: proposal
+                .proposal
+                .expiration
+                .add(veto_config.timelock_duration)?,
+        }
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let proposal = query_proposal(&app, &proposal_module, unopposed);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, unopposed);
+    let proposal = query_proposal(&app, &proposal_module, unopposed);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+
+    Ok(())
+}
+
+#[test]
+fn test_execution_plan() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, core_addr.as_str(), coins(100, "ujuno"));
+
+    let send = |to: &str, amount: u128| -> Vec<CosmosMsg> {
+        vec![BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()]
+    };
+    let unlocks_at = app.block_info().time.plus_seconds(1_209_600);
+    let plan = vec![
+        ExecutionBatch {
+            msgs: send("alice", 10),
+            not_before: None,
+            on_failure: BatchFailurePolicy::Abort,
+        },
+        // Exceeds the treasury and is skipped.
+        ExecutionBatch {
+            msgs: send("bob", 1_000),
+            not_before: None,
+            on_failure: BatchFailurePolicy::Skip,
+        },
+        ExecutionBatch {
+            msgs: send("carol", 10),
+            not_before: Some(unlocks_at),
+            on_failure: BatchFailurePolicy::Abort,
+        },
+        // Exceeds the treasury and is left pending.
+        ExecutionBatch {
+            msgs: send("dave", 100),
+            not_before: None,
+            on_failure: BatchFailurePolicy::Retry,
+        },
+    ];
+
+    // Plans may not be combined with messages.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: send("alice", 10),
+                proposer: None,
+                vote: None,
+                execution_plan: Some(plan.clone()),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::MsgsWithExecutionPlan {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                vote: None,
+                execution_plan: Some(vec![]),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidExecutionPlan { max: 64 });
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose(ProposeMsg {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: vec![],
+            proposer: None,
+            vote: Some(SingleChoiceAutoVote {
+                vote: Vote::Yes,
+                rationale: None,
+            }),
+            execution_plan: Some(plan),
+        }),
+        &[],
+    )
+    .unwrap();
+    let id = query_next_proposal_id(&app, &proposal_module) - 1;
+
+    let batch_statuses = |app: &App| -> Vec<BatchStatus> {
+        query_proposal(app, &proposal_module, id)
+            .proposal
+            .execution_plan
+            .unwrap()
+            .into_iter()
+            .map(|scheduled| scheduled.status)
+            .collect()
+    };
+
+    // Executing runs batches until one is not yet due.
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, id);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(
+        batch_statuses(&app),
+        vec![
+            BatchStatus::Executed,
+            BatchStatus::Failed,
+            BatchStatus::Pending,
+            BatchStatus::Pending
+        ]
+    );
+    assert_eq!(
+        query_balance_native(&app, "alice", "ujuno"),
+        Uint128::new(10)
+    );
+    assert_eq!(query_balance_native(&app, "bob", "ujuno"), Uint128::zero());
+
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, id);
+    assert_eq!(
+        err,
+        ContractError::BatchNotReady {
+            index: 2,
+            not_before: unlocks_at,
+        }
+    );
+
+    // Only members may execute later batches.
+    app.update_block(|block| block.time = unlocks_at);
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, "stranger", id);
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The retried batch stays pending after failing.
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, id);
+    assert_eq!(
+        batch_statuses(&app),
+        vec![
+            BatchStatus::Executed,
+            BatchStatus::Failed,
+            BatchStatus::Executed,
+            BatchStatus::Pending
+        ]
+    );
+    assert_eq!(
+        query_balance_native(&app, "carol", "ujuno"),
+        Uint128::new(10)
+    );
+
+    mint_natives(&mut app, core_addr.as_str(), coins(100, "ujuno"));
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, id);
+    assert_eq!(
+        batch_statuses(&app),
+        vec![
+            BatchStatus::Executed,
+            BatchStatus::Failed,
+            BatchStatus::Executed,
+            BatchStatus::Executed
+        ]
+    );
+    assert_eq!(
+        query_balance_native(&app, "dave", "ujuno"),
+        Uint128::new(100)
+    );
+
+    // Once every batch has run the proposal may not be executed again.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, id);
+    assert_eq!(err, ContractError::NotPassed {});
+}
+
+#[test]
+fn test_execution_plan_abort() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let plan = vec![
+        ExecutionBatch {
+            msgs: vec![BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(10, "ujuno"),
+            }
+            .into()],
+            not_before: None,
+            on_failure: BatchFailurePolicy::Abort,
+        },
+        ExecutionBatch {
+            msgs: vec![BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: coins(10, "ujuno"),
+            }
+            .into()],
+            not_before: None,
+            on_failure: BatchFailurePolicy::Skip,
+        },
+    ];
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose(ProposeMsg {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: vec![],
+            proposer: None,
+            vote: Some(SingleChoiceAutoVote {
+                vote: Vote::Yes,
+                rationale: None,
+            }),
+            execution_plan: Some(plan),
+        }),
+        &[],
+    )
+    .unwrap();
+    let id = query_next_proposal_id(&app, &proposal_module) - 1;
+
+    // The treasury is empty so the first batch fails and the plan is
+    // aborted.
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, id);
+    let proposal = query_proposal(&app, &proposal_module, id).proposal;
+    assert_eq!(proposal.status, Status::ExecutionFailed);
+    let statuses: Vec<BatchStatus> = proposal
+        .execution_plan
+        .unwrap()
+        .into_iter()
+        .map(|scheduled| scheduled.status)
+        .collect();
+    assert_eq!(statuses, vec![BatchStatus::Failed, BatchStatus::Pending]);
+
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, id);
+    assert_eq!(err, ContractError::NotPassed {});
+}
+
+#[test]
+fn test_execution_plan_abort_deposit() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = get_pre_propose_info(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: dao_voting::deposit::DepositToken::Token {
+                denom: cw_denom::UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: dao_voting::deposit::DepositRefundPolicy::ByOutcome {
+                rejected: Decimal::zero(),
+                failed_quorum: Decimal::zero(),
+                vetoed: Decimal::zero(),
+                execution_failed: Decimal::percent(50),
+                slash_destination: dao_voting::deposit::SlashDestination::Dao,
+            },
+            scaling: None,
+        }),
+        false,
+    );
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let (_, pre_propose) = query_deposit_config_and_pre_propose_module(&app, &proposal_module);
+    mint_natives(&mut app, CREATOR_ADDR, coins(10, "ujuno"));
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        pre_propose,
+        &cppbps::ExecuteMsg::Propose {
+            msg: cppbps::ProposeMessage::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                vote: Some(SingleChoiceAutoVote {
+                    vote: Vote::Yes,
+                    rationale: None,
+                }),
+                execution_plan: Some(vec![ExecutionBatch {
+                    msgs: vec![BankMsg::Send {
+                        to_address: "alice".to_string(),
+                        amount: coins(10, "ujuno"),
+                    }
+                    .into()],
+                    not_before: None,
+                    on_failure: BatchFailurePolicy::Abort,
+                }]),
+            },
+        },
+        &coins(10, "ujuno"),
+    )
+    .unwrap();
+    let id = query_next_proposal_id(&app, &proposal_module) - 1;
+
+    // The treasury is empty so the plan is aborted. The deposit is
+    // refunded according to the final status of the proposal, not
+    // the status it had when its first batch was dispatched.
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, id);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.status, Status::ExecutionFailed);
+    assert_eq!(
+        query_balance_native(&app, CREATOR_ADDR, "ujuno"),
+        Uint128::new(5)
+    );
+    assert_eq!(
+        query_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(5)
+    );
+}
+
+#[test]
+fn test_amend_proposal() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(80),
+            },
+            Cw20Coin {
+                address: "sponsor".to_string(),
+                amount: Uint128::new(15),
+            },
+            Cw20Coin {
+                address: "member".to_string(),
+                amount: Uint128::new(5),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let amendment_config = AmendmentConfig {
+        window: Duration::Time(86400),
+        sponsor_threshold: Some(Decimal::percent(10)),
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateAmendmentConfig {
+                config: Some(amendment_config.clone()),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateAmendmentConfig {
+            config: Some(amendment_config.clone()),
+        },
+        &[],
+    )
+    .unwrap();
+    let config: Option<AmendmentConfig> = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::AmendmentConfig {})
+        .unwrap();
+    assert_eq!(config, Some(amendment_config));
+
+    // Proposers may not vote as voting has not opened yet.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                vote: Some(SingleChoiceAutoVote {
+                    vote: Vote::Yes,
+                    rationale: None,
+                }),
+                execution_plan: None,
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::AutoVoteNotAllowed {})
+    );
+
+    let id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+    let created = app.block_info();
+
+    // The voting period begins once the amendment window closes.
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(
+        proposal.proposal.amendable_until,
+        Some(Expiration::AtTime(created.time.plus_seconds(86400)))
+    );
+    assert_eq!(
+        proposal.proposal.expiration,
+        Expiration::AtTime(created.time.plus_seconds(86400 + 604800))
+    );
+
+    let err = vote_on_proposal_should_fail(&mut app, &proposal_module, "sponsor", id, Vote::Yes);
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::VotingNotOpen {})
+    );
+
+    let amend = |app: &mut App, sender: &str, title: Option<&str>, msgs: Option<Vec<CosmosMsg>>| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            proposal_module.clone(),
+            &ExecuteMsg::Amend {
+                proposal_id: id,
+                title: title.map(str::to_string),
+                description: None,
+                msgs,
+            },
+            &[],
+        )
+    };
+
+    // Holders below the sponsor threshold may not amend.
+    let err: ContractError = amend(&mut app, "member", Some("typo"), None)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::Unauthorized {})
+    );
+    let err: ContractError = amend(&mut app, CREATOR_ADDR, None, None)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::NoChanges {})
+    );
+
+    let payout: Vec<CosmosMsg> = vec![BankMsg::Send {
+        to_address: "member".to_string(),
+        amount: coins(10, "ujuno"),
+    }
+    .into()];
+    amend(&mut app, CREATOR_ADDR, Some("fixed title"), None).unwrap();
+    amend(&mut app, "sponsor", None, Some(payout.clone())).unwrap();
+
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.title, "fixed title");
+    assert_eq!(proposal.proposal.msgs, payout);
+
+    let revisions: RevisionListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ListRevisions {
+                proposal_id: id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let history: Vec<(u64, Addr, String, usize)> = revisions
+        .revisions
+        .into_iter()
+        .map(|r| (r.revision, r.author, r.title, r.msgs.len()))
+        .collect();
+    assert_eq!(
+        history,
+        vec![
+            (0, Addr::unchecked(CREATOR_ADDR), "title".to_string(), 0),
+            (
+                1,
+                Addr::unchecked(CREATOR_ADDR),
+                "fixed title".to_string(),
+                0
+            ),
+            (2, Addr::unchecked("sponsor"), "fixed title".to_string(), 1),
+        ]
+    );
+
+    // Once the window closes the proposal may be voted on but not
+    // amended.
+    app.update_block(|block| block.time = block.time.plus_seconds(86400));
+    let err: ContractError = amend(&mut app, CREATOR_ADDR, Some("late"), None)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AmendmentError(AmendmentError::WindowClosed {})
+    );
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, id, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+}
@@ -74,6 +74,7 @@ pub fn execute_proposal_hook(
             count = count.checked_add(1).unwrap_or_default();
             STATUS_CHANGED_COUNTER.save(deps.storage, &count)?;
         }
+        ProposalHookMsg::ProposalAmended { .. } => (),
     }
 
     Ok(Response::new().add_attribute("action", "proposal_hook"))
@@ -108,7 +109,7 @@ pub fn execute_vote_hook(
     vote_hook: VoteHookMsg,
 ) -> Result<Response, ContractError> {
     match vote_hook {
-        VoteHookMsg::NewVote { .. } => {
+        VoteHookMsg::NewVote { .. } | VoteHookMsg::NewWeightedVote { .. } => {
             let mut count = VOTE_COUNTER.load(deps.storage)?;
             count = count.checked_add(1).unwrap_or_default();
             VOTE_COUNTER.save(deps.storage, &count)?;
@@ -216,6 +216,7 @@ fn test_counters() {
             msgs: vec![],
             proposer: None,
             vote: None,
+            execution_plan: None,
         }),
         &[],
     )
@@ -317,6 +318,7 @@ fn test_counters() {
             msgs: vec![],
             proposer: None,
             vote: None,
+            execution_plan: None,
         }),
         &[],
     )
@@ -2,11 +2,12 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response,
-    StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+    StdResult, Storage, SubMsg, Uint128, Uint256, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw20::{Cw20Coin, TokenInfoResponse};
 use cw_utils::parse_reply_instantiate_data;
+use dao_hooks::stake::StakeChangedHookMsg;
 use dao_interface::voting::IsActiveResponse;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 use std::convert::TryInto;
@@ -14,8 +15,8 @@ use std::convert::TryInto;
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StakingInfo, TokenInfo};
 use crate::state::{
-    ACTIVE_THRESHOLD, DAO, STAKING_CONTRACT, STAKING_CONTRACT_CODE_ID,
-    STAKING_CONTRACT_UNSTAKING_DURATION, TOKEN,
+    ACTIVE_THRESHOLD, DAO, POWER_TRANSFORM, STAKING_CONTRACT, STAKING_CONTRACT_CODE_ID,
+    STAKING_CONTRACT_UNSTAKING_DURATION, TOKEN, TRANSFORMED_TOTAL,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-cw20-staked";
@@ -47,6 +48,19 @@ pub fn instantiate(
         ACTIVE_THRESHOLD.save(deps.storage, active_threshold)?;
     }
 
+    if let Some(power_transform) = msg.power_transform.as_ref() {
+        if let TokenInfo::Existing {
+            staking_contract: StakingInfo::Existing { .. },
+            ..
+        } = &msg.token_info
+        {
+            return Err(ContractError::PowerTransformRequiresNewStakingContract {});
+        }
+        power_transform.validate()?;
+        POWER_TRANSFORM.save(deps.storage, power_transform)?;
+        TRANSFORMED_TOTAL.save(deps.storage, &Uint128::zero(), env.block.height)?;
+    }
+
     match msg.token_info {
         TokenInfo::Existing {
             address,
@@ -90,7 +104,7 @@ pub fn instantiate(
                         admin: Some(info.sender.to_string()),
                         label: env.contract.address.to_string(),
                         msg: to_json_binary(&cw20_stake::msg::InstantiateMsg {
-                            owner: Some(info.sender.to_string()),
+                            owner: Some(staking_owner(deps.storage, &env, &info.sender)?),
                             unstaking_duration,
                             token_address: address.to_string(),
                         })?,
@@ -165,6 +179,17 @@ pub fn instantiate(
     }
 }
 
+/// The initial owner of a new staking contract. With a power
+/// transform this contract owns the staking contract until it has
+/// added itself as a hook, after which ownership is offered to the
+/// DAO. See `reply`.
+fn staking_owner(storage: &dyn Storage, env: &Env, dao: &Addr) -> StdResult<String> {
+    Ok(match POWER_TRANSFORM.may_load(storage)? {
+        Some(_) => env.contract.address.to_string(),
+        None => dao.to_string(),
+    })
+}
+
 pub fn assert_valid_absolute_count_threshold(
     deps: Deps,
     token_addr: &Addr,
@@ -193,9 +218,48 @@ pub fn execute(
         ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
             execute_update_active_threshold(deps, env, info, new_threshold)
         }
+        ExecuteMsg::StakeChangeHook(msg) => execute_stake_changed(deps, env, info, msg),
     }
 }
 
+pub fn execute_stake_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: StakeChangedHookMsg,
+) -> Result<Response, ContractError> {
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    if info.sender != staking_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(power_transform) = POWER_TRANSFORM.may_load(deps.storage)? {
+        let addr = match &msg {
+            StakeChangedHookMsg::Stake { addr, .. } => addr,
+            StakeChangedHookMsg::Unstake { addr, .. } => addr,
+        };
+        // The staking contract snapshots balances at the height they
+        // change, so the new balance is only visible at the next one.
+        let res: cw20_stake::msg::StakedBalanceAtHeightResponse = deps.querier.query_wasm_smart(
+            staking_contract,
+            &cw20_stake::msg::QueryMsg::StakedBalanceAtHeight {
+                address: addr.to_string(),
+                height: Some(env.block.height + 1),
+            },
+        )?;
+        let new_balance = res.balance;
+        let old_balance = match msg {
+            StakeChangedHookMsg::Stake { amount, .. } => new_balance.checked_sub(amount)?,
+            StakeChangedHookMsg::Unstake { amount, .. } => new_balance.checked_add(amount)?,
+        };
+        TRANSFORMED_TOTAL.update(deps.storage, env.block.height, |total| {
+            power_transform.update_total(total.unwrap_or_default(), old_balance, new_balance)
+        })?;
+    }
+
+    Ok(Response::new().add_attribute("action", "stake_changed"))
+}
+
 pub fn execute_update_active_threshold(
     deps: DepsMut,
     _env: Env,
@@ -240,6 +304,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::IsActive {} => query_is_active(deps),
         QueryMsg::ActiveThreshold {} => query_active_threshold(deps),
+        QueryMsg::PowerTransform {} => to_json_binary(&POWER_TRANSFORM.may_load(deps.storage)?),
     }
 }
 
@@ -268,17 +333,29 @@ pub fn query_voting_power_at_height(
             height,
         },
     )?;
+    let power = match POWER_TRANSFORM.may_load(deps.storage)? {
+        Some(power_transform) => power_transform.apply(res.balance)?,
+        None => res.balance,
+    };
     to_json_binary(&dao_interface::voting::VotingPowerAtHeightResponse {
-        power: res.balance,
+        power,
         height: res.height,
     })
 }
 
-pub fn query_total_power_at_height(
-    deps: Deps,
-    _env: Env,
-    height: Option<u64>,
-) -> StdResult<Binary> {
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    if POWER_TRANSFORM.may_load(deps.storage)?.is_some() {
+        let power = match height {
+            Some(height) => TRANSFORMED_TOTAL.may_load_at_height(deps.storage, height)?,
+            None => TRANSFORMED_TOTAL.may_load(deps.storage)?,
+        }
+        .unwrap_or_default();
+        return to_json_binary(&dao_interface::voting::TotalPowerAtHeightResponse {
+            power,
+            height: height.unwrap_or(env.block.height),
+        });
+    }
+
     let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
     let res: cw20_stake::msg::TotalStakedAtHeightResponse = deps.querier.query_wasm_smart(
         staking_contract,
@@ -414,7 +491,7 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                         admin: Some(dao.to_string()),
                         label: env.contract.address.to_string(),
                         msg: to_json_binary(&cw20_stake::msg::InstantiateMsg {
-                            owner: Some(dao.to_string()),
+                            owner: Some(staking_owner(deps.storage, &env, &dao)?),
                             unstaking_duration,
                             token_address: token.to_string(),
                         })?,
@@ -440,7 +517,33 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
 
                     STAKING_CONTRACT.save(deps.storage, &staking_contract_addr)?;
 
-                    Ok(Response::new().add_attribute("staking_contract", staking_contract_addr))
+                    // Subscribe to stake changes so that total power
+                    // stays up to date, then offer ownership of the
+                    // staking contract to the DAO. The DAO must accept
+                    // it before it can manage the staking contract.
+                    let mut response =
+                        Response::new().add_attribute("staking_contract", &staking_contract_addr);
+                    if POWER_TRANSFORM.may_load(deps.storage)?.is_some() {
+                        let dao = DAO.load(deps.storage)?;
+                        for msg in [
+                            cw20_stake::msg::ExecuteMsg::AddHook {
+                                addr: env.contract.address.to_string(),
+                            },
+                            cw20_stake::msg::ExecuteMsg::UpdateOwnership(
+                                cw_ownable::Action::TransferOwnership {
+                                    new_owner: dao.to_string(),
+                                    expiry: None,
+                                },
+                            ),
+                        ] {
+                            response = response.add_message(WasmMsg::Execute {
+                                contract_addr: staking_contract_addr.to_string(),
+                                msg: to_json_binary(&msg)?,
+                                funds: vec![],
+                            });
+                        }
+                    }
+                    Ok(response)
                 }
                 Err(_) => Err(ContractError::StakingInstantiateError {}),
             }
@@ -1,11 +1,14 @@
 use cosmwasm_std::StdError;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error(transparent)]
+    PowerTransformError(#[from] dao_voting::power_transform::PowerTransformError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -30,6 +33,9 @@ pub enum ContractError {
     #[error("Can not change the contract's staking contract after it has been set")]
     DuplicateStakingContract {},
 
+    #[error("Power transforms may only be used with a new staking contract")]
+    PowerTransformRequiresNewStakingContract {},
+
     #[error("Active threshold percentage must be greater than 0 and less than 1")]
     InvalidActivePercentage {},
 
@@ -5,6 +5,8 @@ use cw20_base::msg::InstantiateMarketingInfo;
 use cw_utils::Duration;
 
 use dao_dao_macros::{active_query, cw20_token_query, voting_module_query};
+use dao_hooks::stake::StakeChangedHookMsg;
+use dao_voting::power_transform::PowerTransform;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
 /// Information about the staking contract to be used with this voting
@@ -58,6 +60,12 @@ pub struct InstantiateMsg {
     /// The number or percentage of tokens that must be staked
     /// for the DAO to be active
     pub active_threshold: Option<ActiveThreshold>,
+    /// An optional curve applied to staked balances to compute voting
+    /// power. Only supported when a new staking contract is created,
+    /// as this module must receive the staking contract's stake
+    /// change hooks from the start to track total power. May not be
+    /// changed after instantiation.
+    pub power_transform: Option<PowerTransform>,
 }
 
 #[cw_serde]
@@ -69,6 +77,10 @@ pub enum ExecuteMsg {
     UpdateActiveThreshold {
         new_threshold: Option<ActiveThreshold>,
     },
+    /// Called by the staking contract when tokens are staked or
+    /// unstaked. Keeps total power up to date if a power transform is
+    /// set.
+    StakeChangeHook(StakeChangedHookMsg),
 }
 
 #[voting_module_query]
@@ -83,6 +95,8 @@ pub enum QueryMsg {
     StakingContract {},
     #[returns(ActiveThresholdResponse)]
     ActiveThreshold {},
+    #[returns(Option<dao_voting::power_transform::PowerTransform>)]
+    PowerTransform {},
 }
 
 #[cw_serde]
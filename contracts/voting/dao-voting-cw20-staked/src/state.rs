@@ -1,6 +1,7 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, SnapshotItem, Strategy};
 use cw_utils::Duration;
+use dao_voting::power_transform::PowerTransform;
 use dao_voting::threshold::ActiveThreshold;
 
 pub const ACTIVE_THRESHOLD: Item<ActiveThreshold> = Item::new("active_threshold");
@@ -10,3 +11,16 @@ pub const STAKING_CONTRACT: Item<Addr> = Item::new("staking_contract");
 pub const STAKING_CONTRACT_UNSTAKING_DURATION: Item<Option<Duration>> =
     Item::new("staking_contract_unstaking_duration");
 pub const STAKING_CONTRACT_CODE_ID: Item<u64> = Item::new("staking_contract_code_id");
+
+/// The curve applied to staked balances to compute voting power, if
+/// any
+pub const POWER_TRANSFORM: Item<PowerTransform> = Item::new("power_transform");
+
+/// Keeps track of the sum of transformed staked balances over time.
+/// Only used if a power transform is set.
+pub const TRANSFORMED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
+    "transformed_total",
+    "transformed_total__checkpoints",
+    "transformed_total__changelog",
+    Strategy::EveryBlock,
+);
@@ -5,14 +5,21 @@ use cosmwasm_std::{
 use cw2::ContractVersion;
 use cw20::{BalanceResponse, Cw20Coin, MinterResponse, TokenInfoResponse};
 use cw_multi_test::{next_block, App, Executor};
-use dao_interface::voting::{InfoResponse, IsActiveResponse, VotingPowerAtHeightResponse};
+use dao_hooks::stake::StakeChangedHookMsg;
+use dao_interface::voting::{
+    InfoResponse, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
 use dao_testing::contracts::{
     cw20_base_contract, cw20_stake_contract, dao_voting_cw20_staked_contract,
 };
-use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
+use dao_voting::{
+    power_transform::PowerTransform,
+    threshold::{ActiveThreshold, ActiveThresholdResponse},
+};
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
+    error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StakingInfo},
 };
 
@@ -68,6 +75,7 @@ fn test_instantiate_zero_supply() {
                 initial_dao_balance: Some(Uint128::zero()),
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 }
@@ -96,6 +104,7 @@ fn test_instantiate_no_balances() {
                 initial_dao_balance: Some(Uint128::zero()),
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 }
@@ -129,6 +138,7 @@ fn test_instantiate_zero_active_threshold_count() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(0),
             }),
+            power_transform: None,
         },
     );
 }
@@ -160,6 +170,7 @@ fn test_contract_info() {
                 initial_dao_balance: Some(Uint128::zero()),
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -211,6 +222,7 @@ fn test_new_cw20() {
                 initial_dao_balance: Some(Uint128::from(10u64)),
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -385,6 +397,7 @@ fn test_existing_cw20_new_staking() {
                 },
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -536,6 +549,7 @@ fn test_existing_cw20_existing_staking() {
                 },
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -574,6 +588,7 @@ fn test_existing_cw20_existing_staking() {
                 },
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -689,6 +704,7 @@ fn test_existing_cw20_existing_staking() {
                 },
             },
             active_threshold: None,
+            power_transform: None,
         },
         &[],
         "voting module",
@@ -737,6 +753,7 @@ fn test_different_heights() {
                 },
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -926,6 +943,7 @@ fn test_active_threshold_absolute_count() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(100),
             }),
+            power_transform: None,
         },
     );
 
@@ -986,6 +1004,7 @@ fn test_active_threshold_percent() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(20),
             }),
+            power_transform: None,
         },
     );
 
@@ -1046,6 +1065,7 @@ fn test_active_threshold_percent_rounds_up() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(50),
             }),
+            power_transform: None,
         },
     );
 
@@ -1119,6 +1139,7 @@ fn test_active_threshold_none() {
                 initial_dao_balance: Some(Uint128::from(100u64)),
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -1157,6 +1178,7 @@ fn test_update_active_threshold() {
                 initial_dao_balance: Some(Uint128::from(100u64)),
             },
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -1227,6 +1249,7 @@ fn test_active_threshold_percentage_gt_100() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(120),
             }),
+            power_transform: None,
         },
     );
 }
@@ -1261,6 +1284,7 @@ fn test_active_threshold_percentage_lte_0() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(0),
             }),
+            power_transform: None,
         },
     );
 }
@@ -1295,6 +1319,7 @@ fn test_active_threshold_absolute_count_invalid() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(10000),
             }),
+            power_transform: None,
         },
     );
 }
@@ -1327,6 +1352,7 @@ fn test_migrate() {
                     initial_dao_balance: Some(Uint128::zero()),
                 },
                 active_threshold: None,
+                power_transform: None,
             },
             &[],
             "voting module",
@@ -1371,3 +1397,165 @@ pub fn test_migrate_update_version() {
     assert_eq!(version.version, CONTRACT_VERSION);
     assert_eq!(version.contract, CONTRACT_NAME);
 }
+
+#[test]
+fn test_power_transform() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_base_contract());
+    let voting_id = app.store_code(dao_voting_cw20_staked_contract());
+    let staking_contract_id = app.store_code(cw20_stake_contract());
+
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            token_info: crate::msg::TokenInfo::New {
+                code_id: cw20_id,
+                label: "DAO DAO voting".to_string(),
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: CREATOR_ADDR.to_string(),
+                        amount: Uint128::new(100),
+                    },
+                    Cw20Coin {
+                        address: "ekez".to_string(),
+                        amount: Uint128::new(4),
+                    },
+                ],
+                marketing: None,
+                unstaking_duration: None,
+                staking_code_id: staking_contract_id,
+                initial_dao_balance: None,
+            },
+            active_threshold: None,
+            power_transform: Some(PowerTransform::Quadratic {}),
+        },
+    );
+
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+
+    // Ownership of the staking contract has been offered to the DAO.
+    let ownership: cw20_stake::msg::Ownership<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            staking_addr.clone(),
+            &cw20_stake::msg::QueryMsg::Ownership {},
+        )
+        .unwrap();
+    assert_eq!(ownership.owner, Some(voting_addr.clone()));
+    assert_eq!(ownership.pending_owner, Some(Addr::unchecked(DAO_ADDR)));
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        staking_addr.clone(),
+        &cw20_stake::msg::ExecuteMsg::UpdateOwnership(cw_ownable::Action::AcceptOwnership),
+        &[],
+    )
+    .unwrap();
+
+    let power = |app: &App, address: &str, height: Option<u64>| -> Uint128 {
+        let res: VotingPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::VotingPowerAtHeight {
+                    address: address.to_string(),
+                    height,
+                },
+            )
+            .unwrap();
+        res.power
+    };
+    let total = |app: &App, height: Option<u64>| -> Uint128 {
+        let res: TotalPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::TotalPowerAtHeight { height },
+            )
+            .unwrap();
+        res.power
+    };
+
+    stake_tokens(
+        &mut app,
+        staking_addr.clone(),
+        token_addr.clone(),
+        CREATOR_ADDR,
+        100,
+    );
+    stake_tokens(&mut app, staking_addr.clone(), token_addr, "ekez", 4);
+    app.update_block(next_block);
+    assert_eq!(power(&app, CREATOR_ADDR, None), Uint128::new(10));
+    assert_eq!(power(&app, "ekez", None), Uint128::new(2));
+    assert_eq!(total(&app, None), Uint128::new(12));
+
+    // Total power follows unstaking.
+    let height = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        staking_addr,
+        &cw20_stake::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(19),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(power(&app, CREATOR_ADDR, None), Uint128::new(9));
+    assert_eq!(total(&app, None), Uint128::new(11));
+    assert_eq!(total(&app, Some(height)), Uint128::new(12));
+
+    // Only the staking contract may report stake changes.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            voting_addr,
+            &ExecuteMsg::StakeChangeHook(StakeChangedHookMsg::Stake {
+                addr: Addr::unchecked(CREATOR_ADDR),
+                amount: Uint128::new(1),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Existing staking contracts may not be used with a power
+    // transform as their total can not be tracked.
+    let err: ContractError = app
+        .instantiate_contract(
+            voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                token_info: crate::msg::TokenInfo::Existing {
+                    address: "token".to_string(),
+                    staking_contract: StakingInfo::Existing {
+                        staking_contract_address: "staking".to_string(),
+                    },
+                },
+                active_threshold: None,
+                power_transform: Some(PowerTransform::Quadratic {}),
+            },
+            &[],
+            "voting module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::PowerTransformRequiresNewStakingContract {}
+    );
+}
@@ -5,12 +5,12 @@ use cosmwasm_std::{
     Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
-use cw4::{MemberListResponse, MemberResponse, TotalWeightResponse};
+use cw4::{MemberChangedHookMsg, MemberListResponse, MemberResponse, TotalWeightResponse};
 use cw_utils::parse_reply_instantiate_data;
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, GroupContract, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{DAO, GROUP_CONTRACT};
+use crate::state::{DAO, GROUP_CONTRACT, POWER_TRANSFORM, TRANSFORMED_TOTAL};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-cw4";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -28,6 +28,11 @@ pub fn instantiate(
 
     DAO.save(deps.storage, &info.sender)?;
 
+    if let Some(power_transform) = msg.power_transform.as_ref() {
+        power_transform.validate()?;
+        POWER_TRANSFORM.save(deps.storage, power_transform)?;
+    }
+
     match msg.group_contract {
         GroupContract::New {
             cw4_group_code_id,
@@ -61,6 +66,21 @@ pub fn instantiate(
                 return Err(ContractError::ZeroTotalWeight {});
             }
 
+            // With a power transform this contract is the group's
+            // admin until it has added itself as a hook, after which
+            // it hands the group over to the DAO. See `reply`.
+            let group_admin = match msg.power_transform {
+                Some(power_transform) => {
+                    let mut total = Uint128::zero();
+                    for member in initial_members.iter() {
+                        total = total.checked_add(power_transform.apply(member.weight.into())?)?;
+                    }
+                    TRANSFORMED_TOTAL.save(deps.storage, &total, env.block.height)?;
+                    env.contract.address.to_string()
+                }
+                None => info.sender.to_string(),
+            };
+
             // Instantiate group contract, set DAO as admin.
             // Voting module contracts are instantiated by the main dao-dao-core
             // contract, so the Admin is set to info.sender.
@@ -68,7 +88,7 @@ pub fn instantiate(
                 admin: Some(info.sender.to_string()),
                 code_id: cw4_group_code_id,
                 msg: to_json_binary(&cw4_group::msg::InstantiateMsg {
-                    admin: Some(info.sender.to_string()),
+                    admin: Some(group_admin),
                     members: initial_members,
                 })?,
                 funds: vec![],
@@ -82,6 +102,9 @@ pub fn instantiate(
                 .add_submessage(msg))
         }
         GroupContract::Existing { address } => {
+            if msg.power_transform.is_some() {
+                return Err(ContractError::PowerTransformRequiresNewGroup {});
+            }
             let group_contract = deps.api.addr_validate(&address)?;
 
             // Validate valid group contract that has at least one member.
@@ -108,12 +131,40 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    _msg: ExecuteMsg,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    Err(ContractError::NoExecute {})
+    match msg {
+        ExecuteMsg::MemberChangedHook(msg) => execute_member_changed_hook(deps, env, info, msg),
+    }
+}
+
+pub fn execute_member_changed_hook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: MemberChangedHookMsg,
+) -> Result<Response, ContractError> {
+    let group_contract = GROUP_CONTRACT.load(deps.storage)?;
+    if info.sender != group_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(power_transform) = POWER_TRANSFORM.may_load(deps.storage)? {
+        for diff in msg.diffs {
+            TRANSFORMED_TOTAL.update(deps.storage, env.block.height, |total| {
+                power_transform.update_total(
+                    total.unwrap_or_default(),
+                    diff.old.unwrap_or_default().into(),
+                    diff.new.unwrap_or_default().into(),
+                )
+            })?;
+        }
+    }
+
+    Ok(Response::new().add_attribute("action", "member_changed_hook"))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -126,6 +177,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::GroupContract {} => to_json_binary(&GROUP_CONTRACT.load(deps.storage)?),
         QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+        QueryMsg::PowerTransform {} => to_json_binary(&POWER_TRANSFORM.may_load(deps.storage)?),
     }
 }
 
@@ -145,13 +197,30 @@ pub fn query_voting_power_at_height(
         },
     )?;
 
+    let power = Uint128::from(res.weight.unwrap_or(0));
+    let power = match POWER_TRANSFORM.may_load(deps.storage)? {
+        Some(power_transform) => power_transform.apply(power)?,
+        None => power,
+    };
+
     to_json_binary(&dao_interface::voting::VotingPowerAtHeightResponse {
-        power: res.weight.unwrap_or(0).into(),
+        power,
         height: height.unwrap_or(env.block.height),
     })
 }
 
 pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    if POWER_TRANSFORM.may_load(deps.storage)?.is_some() {
+        let power = match height {
+            Some(height) => TRANSFORMED_TOTAL.may_load_at_height(deps.storage, height)?,
+            None => TRANSFORMED_TOTAL.may_load(deps.storage)?,
+        };
+        return to_json_binary(&dao_interface::voting::TotalPowerAtHeightResponse {
+            power: power.unwrap_or_default(),
+            height: height.unwrap_or(env.block.height),
+        });
+    }
+
     let group_contract = GROUP_CONTRACT.load(deps.storage)?;
     let res: TotalWeightResponse = deps.querier.query_wasm_smart(
         group_contract,
@@ -182,7 +251,7 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         INSTANTIATE_GROUP_REPLY_ID => {
             let res = parse_reply_instantiate_data(msg);
@@ -194,7 +263,30 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
                     }
                     let group_contract = deps.api.addr_validate(&res.contract_address)?;
                     GROUP_CONTRACT.save(deps.storage, &group_contract)?;
-                    Ok(Response::default().add_attribute("group_contract", group_contract))
+
+                    // Subscribe to membership changes so that total
+                    // power stays up to date, then give the DAO
+                    // control of the group.
+                    let mut response =
+                        Response::default().add_attribute("group_contract", &group_contract);
+                    if POWER_TRANSFORM.may_load(deps.storage)?.is_some() {
+                        let dao = DAO.load(deps.storage)?;
+                        for msg in [
+                            cw4_group::msg::ExecuteMsg::AddHook {
+                                addr: env.contract.address.to_string(),
+                            },
+                            cw4_group::msg::ExecuteMsg::UpdateAdmin {
+                                admin: Some(dao.to_string()),
+                            },
+                        ] {
+                            response = response.add_message(WasmMsg::Execute {
+                                contract_addr: group_contract.to_string(),
+                                msg: to_json_binary(&msg)?,
+                                funds: vec![],
+                            });
+                        }
+                    }
+                    Ok(response)
                 }
                 Err(_) => Err(ContractError::GroupContractInstantiateError {}),
             }
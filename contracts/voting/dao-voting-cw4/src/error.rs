@@ -6,6 +6,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error(transparent)]
+    PowerTransformError(#[from] dao_voting::power_transform::PowerTransformError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -24,6 +27,9 @@ pub enum ContractError {
     #[error("Cannot instantiate or use a group contract with no initial members")]
     NoMembers {},
 
+    #[error("Power transforms may only be used with a new group contract")]
+    PowerTransformRequiresNewGroup {},
+
     #[error("Got a submessage reply with unknown id: {id}")]
     UnknownReplyId { id: u64 },
 
@@ -1,5 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw4::MemberChangedHookMsg;
 use dao_dao_macros::voting_module_query;
+use dao_voting::power_transform::PowerTransform;
 
 #[cw_serde]
 pub enum GroupContract {
@@ -15,11 +17,21 @@ pub enum GroupContract {
 #[cw_serde]
 pub struct InstantiateMsg {
     pub group_contract: GroupContract,
+    /// An optional curve applied to member weights to compute voting
+    /// power. Only supported when a new group contract is created, as
+    /// this module must receive the group's membership change hooks
+    /// from the start to track total power. May not be changed after
+    /// instantiation.
+    pub power_transform: Option<PowerTransform>,
 }
 
 #[cw_serde]
 #[derive(cw_orch::ExecuteFns)]
-pub enum ExecuteMsg {}
+pub enum ExecuteMsg {
+    /// Called by the group contract when its members change. Keeps
+    /// total power up to date if a power transform is set.
+    MemberChangedHook(MemberChangedHookMsg),
+}
 
 #[voting_module_query]
 #[cw_serde]
@@ -27,6 +39,8 @@ pub enum ExecuteMsg {}
 pub enum QueryMsg {
     #[returns(cosmwasm_std::Addr)]
     GroupContract {},
+    #[returns(Option<dao_voting::power_transform::PowerTransform>)]
+    PowerTransform {},
 }
 
 #[cw_serde]
@@ -1,5 +1,19 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, SnapshotItem, Strategy};
+use dao_voting::power_transform::PowerTransform;
 
 pub const GROUP_CONTRACT: Item<Addr> = Item::new("group_contract");
 pub const DAO: Item<Addr> = Item::new("dao_address");
+
+/// The curve applied to member weights to compute voting power, if
+/// any
+pub const POWER_TRANSFORM: Item<PowerTransform> = Item::new("power_transform");
+
+/// Keeps track of the sum of transformed member weights over time.
+/// Only used if a power transform is set.
+pub const TRANSFORMED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
+    "transformed_total",
+    "transformed_total__checkpoints",
+    "transformed_total__changelog",
+    Strategy::EveryBlock,
+);
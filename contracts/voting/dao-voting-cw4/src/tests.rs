@@ -8,10 +8,11 @@ use dao_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 use dao_testing::contracts::{cw4_group_contract, dao_voting_cw4_contract};
+use dao_voting::power_transform::PowerTransform;
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{GroupContract, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{ExecuteMsg, GroupContract, InstantiateMsg, MigrateMsg, QueryMsg},
 };
 use dao_voting_cw4::ContractError;
 
@@ -63,6 +64,7 @@ fn setup_test_case(app: &mut App) -> Addr {
                 cw4_group_code_id: cw4_id,
                 initial_members: members,
             },
+            power_transform: None,
         },
     )
 }
@@ -81,6 +83,7 @@ fn test_instantiate() {
             cw4_group_code_id: cw4_id,
             initial_members: [].into(),
         },
+        power_transform: None,
     };
     let _err = app
         .instantiate_contract(
@@ -112,6 +115,7 @@ fn test_instantiate() {
                 },
             ],
         },
+        power_transform: None,
     };
     let _err = app
         .instantiate_contract(
@@ -155,6 +159,7 @@ pub fn test_instantiate_existing_contract() {
                 group_contract: GroupContract::Existing {
                     address: cw4_addr.to_string(),
                 },
+                power_transform: None,
             },
             &[],
             "voting module",
@@ -187,6 +192,7 @@ pub fn test_instantiate_existing_contract() {
         group_contract: GroupContract::Existing {
             address: cw4_addr.to_string(),
         },
+        power_transform: None,
     };
     let _err = app
         .instantiate_contract(
@@ -561,6 +567,7 @@ fn test_migrate() {
             cw4_group_code_id: cw4_id,
             initial_members,
         },
+        power_transform: None,
     };
     let voting_addr = app
         .instantiate_contract(
@@ -638,6 +645,7 @@ fn test_duplicate_member() {
                 },
             ],
         },
+        power_transform: None,
     };
     // Previous versions voting power was 100, due to no dedup.
     // Now we error
@@ -722,3 +730,133 @@ pub fn test_migrate_update_version() {
     assert_eq!(version.version, CONTRACT_VERSION);
     assert_eq!(version.contract, CONTRACT_NAME);
 }
+
+#[test]
+fn test_power_transform() {
+    let mut app = App::default();
+    let cw4_id = app.store_code(cw4_group_contract());
+    let voting_id = app.store_code(dao_voting_cw4_contract());
+
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            group_contract: GroupContract::New {
+                cw4_group_code_id: cw4_id,
+                initial_members: vec![
+                    cw4::Member {
+                        addr: ADDR1.to_string(),
+                        weight: 100,
+                    },
+                    cw4::Member {
+                        addr: ADDR2.to_string(),
+                        weight: 4,
+                    },
+                ],
+            },
+            power_transform: Some(PowerTransform::Quadratic {}),
+        },
+    );
+    let cw4_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::GroupContract {})
+        .unwrap();
+
+    // The DAO is left as the admin of the group.
+    let admin: cw4::AdminResponse = app
+        .wrap()
+        .query_wasm_smart(cw4_addr.clone(), &cw4_group::msg::QueryMsg::Admin {})
+        .unwrap();
+    assert_eq!(admin.admin, Some(DAO_ADDR.to_string()));
+
+    let power = |app: &App, address: &str, height: Option<u64>| -> Uint128 {
+        let res: VotingPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::VotingPowerAtHeight {
+                    address: address.to_string(),
+                    height,
+                },
+            )
+            .unwrap();
+        res.power
+    };
+    let total = |app: &App, height: Option<u64>| -> Uint128 {
+        let res: TotalPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::TotalPowerAtHeight { height },
+            )
+            .unwrap();
+        res.power
+    };
+
+    app.update_block(next_block);
+    assert_eq!(power(&app, ADDR1, None), Uint128::new(10));
+    assert_eq!(power(&app, ADDR2, None), Uint128::new(2));
+    assert_eq!(total(&app, None), Uint128::new(12));
+
+    // Total power follows membership changes made by the DAO.
+    let height = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        cw4_addr,
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: vec![ADDR1.to_string()],
+            add: vec![
+                cw4::Member {
+                    addr: ADDR2.to_string(),
+                    weight: 9,
+                },
+                cw4::Member {
+                    addr: ADDR3.to_string(),
+                    weight: 16,
+                },
+            ],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(power(&app, ADDR1, None), Uint128::zero());
+    assert_eq!(power(&app, ADDR2, None), Uint128::new(3));
+    assert_eq!(power(&app, ADDR3, None), Uint128::new(4));
+    assert_eq!(total(&app, None), Uint128::new(7));
+    assert_eq!(total(&app, Some(height)), Uint128::new(12));
+
+    // Only the group may report membership changes.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr,
+            &ExecuteMsg::MemberChangedHook(cw4::MemberChangedHookMsg { diffs: vec![] }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Existing groups may not be used with a power transform as their
+    // total can not be tracked.
+    let err: ContractError = app
+        .instantiate_contract(
+            voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                group_contract: GroupContract::Existing {
+                    address: "group".to_string(),
+                },
+                power_transform: Some(PowerTransform::Quadratic {}),
+            },
+            &[],
+            "voting module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::PowerTransformRequiresNewGroup {});
+}
@@ -3,7 +3,8 @@ use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
     coins, from_json, to_json_binary, BankMsg, BankQuery, Binary, Coin, CosmosMsg, Deps, DepsMut,
-    Env, MessageInfo, Order, Reply, Response, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+    Env, MessageInfo, Order, Reply, Response, StdResult, Storage, SubMsg, Uint128, Uint256,
+    WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_controllers::ClaimsResponse;
@@ -40,8 +41,9 @@ use crate::msg::{
     StakerBalanceResponse, TokenInfo,
 };
 use crate::state::{
-    Config, ACTIVE_THRESHOLD, CLAIMS, CONFIG, DAO, DENOM, HOOKS, MAX_CLAIMS, STAKED_BALANCES,
-    STAKED_TOTAL, TOKEN_INSTANTIATION_INFO, TOKEN_ISSUER_CONTRACT,
+    Config, ACTIVE_THRESHOLD, CLAIMS, CONFIG, DAO, DENOM, HOOKS, MAX_CLAIMS, POWER_TRANSFORM,
+    STAKED_BALANCES, STAKED_TOTAL, TOKEN_INSTANTIATION_INFO, TOKEN_ISSUER_CONTRACT,
+    TRANSFORMED_TOTAL,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-token-staked";
@@ -76,6 +78,11 @@ pub fn instantiate(
     CONFIG.save(deps.storage, &config)?;
     DAO.save(deps.storage, &info.sender)?;
 
+    if let Some(power_transform) = msg.power_transform.as_ref() {
+        power_transform.validate()?;
+        POWER_TRANSFORM.save(deps.storage, power_transform)?;
+    }
+
     // Validate Active Threshold
     if let Some(active_threshold) = msg.active_threshold.as_ref() {
         // Only check active threshold percentage as new tokens don't exist yet
@@ -186,12 +193,13 @@ pub fn execute_stake(
     let denom = DENOM.load(deps.storage)?;
     let amount = must_pay(&info, &denom)?;
 
-    STAKED_BALANCES.update(
+    let balance = STAKED_BALANCES.update(
         deps.storage,
         &info.sender,
         env.block.height,
         |balance| -> StdResult<Uint128> { Ok(balance.unwrap_or_default().checked_add(amount)?) },
     )?;
+    update_transformed_total(deps.storage, env.block.height, balance - amount, balance)?;
     STAKED_TOTAL.update(
         deps.storage,
         env.block.height,
@@ -218,7 +226,7 @@ pub fn execute_unstake(
         return Err(ContractError::ZeroUnstake {});
     }
 
-    STAKED_BALANCES.update(
+    let balance = STAKED_BALANCES.update(
         deps.storage,
         &info.sender,
         env.block.height,
@@ -229,6 +237,7 @@ pub fn execute_unstake(
                 .map_err(|_e| ContractError::InvalidUnstakeAmount {})
         },
     )?;
+    update_transformed_total(deps.storage, env.block.height, balance + amount, balance)?;
     STAKED_TOTAL.update(
         deps.storage,
         env.block.height,
@@ -281,6 +290,23 @@ pub fn execute_unstake(
     }
 }
 
+/// Updates the snapshot of the sum of transformed staked balances
+/// after a staker's balance changed from `old_balance` to
+/// `new_balance`. Does nothing if no power transform is set.
+fn update_transformed_total(
+    storage: &mut dyn Storage,
+    height: u64,
+    old_balance: Uint128,
+    new_balance: Uint128,
+) -> StdResult<()> {
+    if let Some(power_transform) = POWER_TRANSFORM.may_load(storage)? {
+        TRANSFORMED_TOTAL.update(storage, height, |total| -> StdResult<Uint128> {
+            power_transform.update_total(total.unwrap_or_default(), old_balance, new_balance)
+        })?;
+    }
+    Ok(())
+}
+
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -416,6 +442,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TokenContract {} => {
             to_json_binary(&TOKEN_ISSUER_CONTRACT.may_load(deps.storage)?)
         }
+        QueryMsg::PowerTransform {} => to_json_binary(&POWER_TRANSFORM.may_load(deps.storage)?),
     }
 }
 
@@ -430,6 +457,10 @@ pub fn query_voting_power_at_height(
     let power = STAKED_BALANCES
         .may_load_at_height(deps.storage, &address, height)?
         .unwrap_or_default();
+    let power = match POWER_TRANSFORM.may_load(deps.storage)? {
+        Some(power_transform) => power_transform.apply(power)?,
+        None => power,
+    };
     Ok(VotingPowerAtHeightResponse { power, height })
 }
 
@@ -439,9 +470,11 @@ pub fn query_total_power_at_height(
     height: Option<u64>,
 ) -> StdResult<TotalPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
-    let power = STAKED_TOTAL
-        .may_load_at_height(deps.storage, height)?
-        .unwrap_or_default();
+    let power = match POWER_TRANSFORM.may_load(deps.storage)? {
+        Some(_) => TRANSFORMED_TOTAL.may_load_at_height(deps.storage, height)?,
+        None => STAKED_TOTAL.may_load_at_height(deps.storage, height)?,
+    }
+    .unwrap_or_default();
     Ok(TotalPowerAtHeightResponse { power, height })
 }
 
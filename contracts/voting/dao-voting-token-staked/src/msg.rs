@@ -3,6 +3,7 @@ use cosmwasm_std::{Binary, Uint128};
 use cw_utils::Duration;
 use dao_dao_macros::{active_query, native_token_query, voting_module_query};
 use dao_interface::token::NewTokenInfo;
+use dao_voting::power_transform::PowerTransform;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
 #[cw_serde]
@@ -33,6 +34,10 @@ pub struct InstantiateMsg {
     /// The number or percentage of tokens that must be staked
     /// for the DAO to be active
     pub active_threshold: Option<ActiveThreshold>,
+    /// An optional curve applied to staked balances to compute voting
+    /// power, for example to dampen the influence of large holders.
+    /// May not be changed after instantiation.
+    pub power_transform: Option<PowerTransform>,
 }
 
 #[cw_serde]
@@ -79,6 +84,8 @@ pub enum QueryMsg {
     GetHooks {},
     #[returns(Option<cosmwasm_std::Addr>)]
     TokenContract {},
+    #[returns(Option<dao_voting::power_transform::PowerTransform>)]
+    PowerTransform {},
 }
 
 #[cw_serde]
@@ -4,6 +4,7 @@ use cw_controllers::Claims;
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
+use dao_voting::power_transform::PowerTransform;
 use dao_voting::threshold::ActiveThreshold;
 
 use crate::msg::TokenInfo;
@@ -38,6 +39,19 @@ pub const STAKED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
     Strategy::EveryBlock,
 );
 
+/// The curve applied to staked balances to compute voting power, if
+/// any
+pub const POWER_TRANSFORM: Item<PowerTransform> = Item::new("power_transform");
+
+/// Keeps track of the sum of transformed staked balances over time.
+/// Only used if a power transform is set.
+pub const TRANSFORMED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
+    "transformed_total",
+    "transformed_total__checkpoints",
+    "transformed_total__changelog",
+    Strategy::EveryBlock,
+);
+
 /// The maximum number of claims that may be outstanding.
 pub const MAX_CLAIMS: u64 = 100;
 
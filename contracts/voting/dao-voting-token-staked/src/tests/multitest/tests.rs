@@ -16,6 +16,7 @@ use dao_interface::voting::{
 use dao_testing::contracts::{
     dao_proposal_hook_counter_contract, dao_voting_token_staked_contract,
 };
+use dao_voting::power_transform::PowerTransform;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
 const DAO_ADDR: &str = "dao";
@@ -197,6 +198,7 @@ fn test_instantiate_existing() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -210,6 +212,7 @@ fn test_instantiate_existing() {
             },
             unstaking_duration: None,
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -244,6 +247,7 @@ fn test_instantiate_invalid_unstaking_duration_height() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(1),
             }),
+            power_transform: None,
         },
     );
 }
@@ -267,6 +271,7 @@ fn test_instantiate_invalid_unstaking_duration_time() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(1),
             }),
+            power_transform: None,
         },
     );
 }
@@ -286,6 +291,7 @@ fn test_stake_invalid_denom() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -307,6 +313,7 @@ fn test_stake_valid_denom() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -330,6 +337,7 @@ fn test_unstake_none_staked() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -351,6 +359,7 @@ fn test_unstake_zero_tokens() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -372,6 +381,7 @@ fn test_unstake_invalid_balance() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -397,6 +407,7 @@ fn test_unstake() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -434,6 +445,7 @@ fn test_unstake_no_unstaking_duration() {
             },
             unstaking_duration: None,
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -473,6 +485,7 @@ fn test_claim_no_claims() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -494,6 +507,7 @@ fn test_claim_claim_not_reached() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -523,6 +537,7 @@ fn test_claim() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -576,6 +591,7 @@ fn test_update_config_invalid_sender() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -597,6 +613,7 @@ fn test_update_config_as_owner() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -627,6 +644,7 @@ fn test_update_config_invalid_duration() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -648,6 +666,7 @@ fn test_query_dao() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -670,6 +689,7 @@ fn test_query_info() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -692,6 +712,7 @@ fn test_query_claims() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -730,6 +751,7 @@ fn test_query_get_config() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -756,6 +778,7 @@ fn test_voting_power_queries() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -850,6 +873,90 @@ fn test_voting_power_queries() {
     assert_eq!(resp.power, Uint128::new(50));
 }
 
+#[test]
+fn test_power_transform() {
+    let mut app = mock_app();
+
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+            power_transform: Some(PowerTransform::Quadratic {}),
+        },
+    );
+
+    let transform: Option<PowerTransform> = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::PowerTransform {})
+        .unwrap();
+    assert_eq!(transform, Some(PowerTransform::Quadratic {}));
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 10000, DENOM).unwrap();
+    stake_tokens(&mut app, addr.clone(), ADDR2, 100, DENOM).unwrap();
+    app.update_block(next_block);
+
+    // Voting power is the square root of the staked balance and
+    // total power is the sum of voting powers.
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(100));
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(10));
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(resp.power, Uint128::new(110));
+
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 7500).unwrap();
+    app.update_block(next_block);
+    let prev_height = app.block_info().height - 1;
+
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(50));
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(resp.power, Uint128::new(60));
+    let resp = get_total_power_at_height(&mut app, addr.clone(), Some(prev_height));
+    assert_eq!(resp.power, Uint128::new(110));
+
+    // Staked balances are unaffected.
+    let stakers: ListStakersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::ListStakers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(stakers.stakers[0].balance, Uint128::new(2500));
+}
+
+#[test]
+#[should_panic(expected = "Capped power transforms must have a non-zero cap")]
+fn test_instantiate_invalid_power_transform() {
+    let mut app = mock_app();
+
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+            power_transform: Some(PowerTransform::Capped {
+                cap: Uint128::zero(),
+            }),
+        },
+    );
+}
+
 #[test]
 fn test_query_list_stakers() {
     let mut app = mock_app();
@@ -864,6 +971,7 @@ fn test_query_list_stakers() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -953,6 +1061,7 @@ fn test_instantiate_zero_active_threshold_count() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::zero(),
             }),
+            power_transform: None,
         },
     );
 }
@@ -974,6 +1083,7 @@ fn test_active_threshold_absolute_count() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(100),
             }),
+            power_transform: None,
         },
     );
 
@@ -1012,6 +1122,7 @@ fn test_active_threshold_percent() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(20),
             }),
+            power_transform: None,
         },
     );
 
@@ -1050,6 +1161,7 @@ fn test_active_threshold_percent_rounds_up() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(50),
             }),
+            power_transform: None,
         },
     );
 
@@ -1095,6 +1207,7 @@ fn test_active_threshold_none() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -1120,6 +1233,7 @@ fn test_update_active_threshold() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -1174,6 +1288,7 @@ fn test_active_threshold_percentage_gt_100() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(120),
             }),
+            power_transform: None,
         },
     );
 }
@@ -1197,6 +1312,7 @@ fn test_active_threshold_percentage_lte_0() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(0),
             }),
+            power_transform: None,
         },
     );
 }
@@ -1218,6 +1334,7 @@ fn test_active_threshold_absolute_count_invalid() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(30001),
             }),
+            power_transform: None,
         },
     );
 }
@@ -1237,6 +1354,7 @@ fn test_add_remove_hooks() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -1313,6 +1431,7 @@ fn test_staking_hooks() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            power_transform: None,
         },
     );
 
@@ -132,6 +132,7 @@ fn test_instantiate_no_dao_balance() {
                 }),
                 unstaking_duration: None,
                 active_threshold: None,
+                power_transform: None,
             },
             dao,
         )
@@ -185,6 +186,7 @@ fn test_instantiate_no_metadata() {
             }),
             unstaking_duration: None,
             active_threshold: None,
+            power_transform: None,
         },
         dao,
     )
@@ -226,6 +228,7 @@ fn test_instantiate_invalid_metadata_fails() {
             }),
             unstaking_duration: None,
             active_threshold: None,
+            power_transform: None,
         },
         dao,
     )
@@ -270,6 +273,7 @@ fn test_instantiate_invalid_active_threshold_count_fails() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(1000),
                 }),
+                power_transform: None,
             },
             dao,
         )
@@ -314,6 +318,7 @@ fn test_instantiate_no_initial_balances_fails() {
                 }),
                 unstaking_duration: None,
                 active_threshold: None,
+                power_transform: None,
             },
             dao,
         )
@@ -377,6 +382,7 @@ fn test_factory() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(75),
                 }),
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -487,6 +493,7 @@ fn test_factory_funds_pass_through() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(75),
                 }),
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -561,6 +568,7 @@ fn test_factory_funds_pass_through() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(75),
             }),
+            power_transform: None,
         })
         .unwrap(),
         admin: Some(Admin::CoreModule {}),
@@ -613,6 +621,7 @@ fn test_factory_no_callback() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(75),
                 }),
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -694,6 +703,7 @@ fn test_factory_wrong_callback() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(75),
                 }),
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
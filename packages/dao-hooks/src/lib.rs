@@ -4,6 +4,7 @@ mod all_hooks;
 pub mod nft_stake;
 pub mod proposal;
 pub mod stake;
+pub mod stream;
 pub mod vote;
 
 pub use all_hooks::DaoHooks;
@@ -9,7 +9,9 @@ use dao_voting::{
 
 /// An enum representing proposal hook messages.
 /// Either a new propsoal hook, fired when a new proposal is created,
-/// or a proposal status hook, fired when a proposal changes status.
+/// a proposal status hook, fired when a proposal changes status, or a
+/// proposal amended hook, fired when a proposal is revised during its
+/// amendment window.
 #[cw_serde]
 pub enum ProposalHookMsg {
     NewProposal {
@@ -21,6 +23,11 @@ pub enum ProposalHookMsg {
         old_status: String,
         new_status: String,
     },
+    ProposalAmended {
+        id: u64,
+        revision: u64,
+        amended_by: String,
+    },
 }
 
 /// Prepares new proposal hook messages. These messages reply on error
@@ -92,15 +99,52 @@ pub fn proposal_status_changed_hooks(
     Ok(messages)
 }
 
+/// Prepares proposal amended hook messages. These messages reply on
+/// error and have even reply IDs.
+/// IDs are set to even numbers to then be interleaved with the vote hooks.
+pub fn proposal_amended_hooks(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    id: u64,
+    revision: u64,
+    amended_by: &str,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_json_binary(&ProposalHookExecuteMsg::ProposalHook(
+        ProposalHookMsg::ProposalAmended {
+            id,
+            revision,
+            amended_by: amended_by.to_string(),
+        },
+    ))?;
+
+    let mut index: u64 = 0;
+    let messages = hooks.prepare_hooks(storage, |a| {
+        let execute = WasmMsg::Execute {
+            contract_addr: a.to_string(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        let masked_index = mask_proposal_hook_index(index);
+        let tmp = SubMsg::reply_on_error(execute, masked_index);
+        index += 1;
+        Ok(tmp)
+    })?;
+
+    Ok(messages)
+}
+
 /// Message type used for firing hooks to a proposal module's pre-propose
 /// module, if one is installed.
 pub type PreProposeHookMsg = dao_pre_propose_base::msg::ExecuteMsg<Empty, Empty>;
 
 /// Adds prepropose / deposit module hook which will handle deposit refunds.
+/// `failed_quorum` should be true if a closed proposal was rejected
+/// because it did not reach quorum.
 pub fn proposal_completed_hooks(
     proposal_creation_policy: ProposalCreationPolicy,
     proposal_id: u64,
     new_status: Status,
+    failed_quorum: bool,
 ) -> StdResult<Vec<SubMsg>> {
     let mut hooks: Vec<SubMsg> = vec![];
     match proposal_creation_policy {
@@ -109,6 +153,7 @@ pub fn proposal_completed_hooks(
             let msg = to_json_binary(&PreProposeHookMsg::ProposalCompletedHook {
                 proposal_id,
                 new_status,
+                failed_quorum,
             })?;
             hooks.push(SubMsg::reply_on_error(
                 WasmMsg::Execute {
@@ -0,0 +1,56 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, StdResult, Storage, SubMsg, Uint128, WasmMsg};
+use cw_hooks::Hooks;
+
+/// An enum representing payment stream hooks, fired when a stream is
+/// created, funded, claimed from or changed.
+#[cw_serde]
+pub enum StreamChangedHookMsg {
+    Created {
+        id: u64,
+        recipient: Addr,
+    },
+    Funded {
+        id: u64,
+        amount: Uint128,
+    },
+    Claimed {
+        id: u64,
+        recipient: Addr,
+        amount: Uint128,
+    },
+    Paused {
+        id: u64,
+    },
+    Resumed {
+        id: u64,
+    },
+    Updated {
+        id: u64,
+    },
+    Cancelled {
+        id: u64,
+    },
+}
+
+/// Prepares StreamChangedHookMsg hook SubMsgs.
+pub fn stream_hook_msgs(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    hook: StreamChangedHookMsg,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_json_binary(&StreamChangedExecuteMsg::StreamChangeHook(hook))?;
+    hooks.prepare_hooks(storage, |a| {
+        let execute = WasmMsg::Execute {
+            contract_addr: a.to_string(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        Ok(SubMsg::new(execute))
+    })
+}
+
+#[cw_serde]
+pub enum StreamChangedExecuteMsg {
+    StreamChangeHook(StreamChangedHookMsg),
+}
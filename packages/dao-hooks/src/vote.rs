@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, StdResult, Storage, SubMsg, WasmMsg};
+use cosmwasm_std::{to_json_binary, Binary, StdResult, Storage, SubMsg, WasmMsg};
 use cw_hooks::Hooks;
-use dao_voting::reply::mask_vote_hook_index;
+use dao_voting::{reply::mask_vote_hook_index, voting::WeightedVote};
 
 /// An enum representing vote hooks, fired when new votes are cast.
 #[cw_serde]
@@ -11,6 +11,13 @@ pub enum VoteHookMsg {
         voter: String,
         vote: String,
     },
+    /// Fired when a voter splits their voting power between
+    /// positions on a single choice proposal.
+    NewWeightedVote {
+        proposal_id: u64,
+        voter: String,
+        votes: Vec<WeightedVote>,
+    },
 }
 
 /// Prepares new vote hook messages. These messages reply on error
@@ -28,6 +35,29 @@ pub fn new_vote_hooks(
         voter,
         vote,
     }))?;
+    prepare_vote_hooks(hooks, storage, msg)
+}
+
+/// Prepares new weighted vote hook messages. These messages reply on
+/// error and have even reply IDs, like those of `new_vote_hooks`.
+pub fn new_weighted_vote_hooks(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    proposal_id: u64,
+    voter: String,
+    votes: Vec<WeightedVote>,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_json_binary(&VoteHookExecuteMsg::VoteHook(
+        VoteHookMsg::NewWeightedVote {
+            proposal_id,
+            voter,
+            votes,
+        },
+    ))?;
+    prepare_vote_hooks(hooks, storage, msg)
+}
+
+fn prepare_vote_hooks(hooks: Hooks, storage: &dyn Storage, msg: Binary) -> StdResult<Vec<SubMsg>> {
     let mut index: u64 = 0;
     hooks.prepare_hooks(storage, |a| {
         let execute = WasmMsg::Execute {
@@ -0,0 +1,45 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+/// Queries implemented by vote delegation modules that proposal modules
+/// rely on to account for delegated voting power.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum Query {
+    /// Returns the voting power delegated to a delegate at a given
+    /// height. This does not include the delegate's own voting power.
+    #[returns(DelegatedVotingPowerResponse)]
+    DelegatedVotingPower {
+        delegate: ::std::string::String,
+        height: ::std::option::Option<::std::primitive::u64>,
+    },
+    /// Returns the delegations made by a delegator at a given height.
+    #[returns(DelegationsResponse)]
+    Delegations {
+        delegator: ::std::string::String,
+        height: ::std::option::Option<::std::primitive::u64>,
+    },
+}
+
+#[cw_serde]
+pub struct Delegation {
+    /// The delegate that voting power is delegated to.
+    pub delegate: Addr,
+    /// The percent of the delegator's voting power that is delegated.
+    pub percent: Decimal,
+    /// The voting power contributed to the delegate by this
+    /// delegation.
+    pub power: Uint128,
+}
+
+#[cw_serde]
+pub struct DelegationsResponse {
+    pub delegations: Vec<Delegation>,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct DelegatedVotingPowerResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
@@ -1,12 +1,17 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
+pub mod delegation;
 pub mod migrate_msg;
 pub mod msg;
 pub mod nft;
 pub mod proposal;
 pub mod query;
+pub mod remote;
+pub mod role;
 pub mod state;
+pub mod timelock;
 pub mod token;
+pub mod treasury;
 pub mod voting;
 
 pub use msg::ExecuteMsgFns as CoreExecuteMsgFns;
@@ -1,8 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Empty};
+use cosmwasm_std::{Binary, CosmosMsg, Empty, Uint128};
+use cw_denom::UncheckedDenom;
 use cw_utils::Duration;
 
+use crate::role::Role;
 use crate::state::Config;
+use crate::timelock::Timelock;
+use crate::treasury::{SpendingCap, TreasuryAssetType, TreasuryBalanceKey, TreasuryHolding};
 use crate::{migrate_msg::MigrateParams, query::SubDao, state::ModuleInstantiateInfo};
 
 /// Information about an item to be stored in the items list.
@@ -66,6 +70,18 @@ pub enum ExecuteMsg {
     Pause { duration: Duration },
     /// Unpauses the DAO
     Unpause {},
+    /// Pauses the DAO for a duration no longer than the guardian's
+    /// `max_duration`. Only callable by the guardian, and not while
+    /// the DAO is paused or within `max_duration` of the guardian's
+    /// last pause ending.
+    GuardianPause { duration: Duration },
+    /// Stops a proposal module from executing proposals for a
+    /// duration no longer than the guardian's `max_duration`. Only
+    /// callable by the guardian.
+    GuardianSuspendModule { address: String, duration: Duration },
+    /// Lifts a guardian's suspension of a proposal module. Only
+    /// callable by the DAO or its admin.
+    LiftModuleSuspension { address: String },
     /// Executed when the contract receives a cw20 token. Depending on
     /// the contract's configuration the contract will automatically
     /// add the token to its treasury.
@@ -80,6 +96,25 @@ pub enum ExecuteMsg {
     /// item already exists the existing value is overridden. If the
     /// item does not exist a new item is added.
     SetItem { key: String, value: String },
+    /// Sets an item in a namespace, overriding any existing value.
+    /// `value` must be JSON. Callable by the core contract and by the
+    /// namespace's writers.
+    SetNamespacedItem {
+        namespace: String,
+        key: String,
+        value: Binary,
+    },
+    /// Removes an item from a namespace. Callable by the core
+    /// contract and by the namespace's writers.
+    RemoveNamespacedItem { namespace: String, key: String },
+    /// Callable by the core contract. Allows the addresses in
+    /// `to_add`, for example proposal modules, to write to a
+    /// namespace and disallows those in `to_remove`.
+    UpdateNamespaceWriters {
+        namespace: String,
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
     /// Callable by the admin of the contract. If ADMIN is None the
     /// admin is set as the contract itself so that it may be updated
     /// later by vote. If ADMIN is Some a new admin is proposed and
@@ -127,11 +162,97 @@ pub enum ExecuteMsg {
     /// voting module with a new one instantiated by the governance
     /// contract.
     UpdateVotingModule { module: ModuleInstantiateInfo },
+    /// Callable by the core contract. Instantiates `new_module` to
+    /// replace the enabled proposal module at `address`. The old
+    /// module is left draining: it is no longer active, but may
+    /// execute proposals so that its open proposals may close.
+    UpgradeProposalModule {
+        address: String,
+        new_module: ModuleInstantiateInfo,
+        drain_duration: Duration,
+        /// If set, the old module's config is copied to the new one
+        /// once it is instantiated. Only supported between single
+        /// and multiple choice proposal modules of the same kind.
+        copy_config: bool,
+    },
+    /// Disables a draining proposal module once `drain_duration` has
+    /// passed and none of its proposals are open. Callable by anyone.
+    /// Looks through at most `limit` proposals per call; if it runs
+    /// out before reaching the module's last proposal, the module
+    /// stays draining and the next call resumes where it stopped.
+    FinishDraining { address: String, limit: Option<u64> },
     /// Update the core module to add/remove SubDAOs and their charters
     UpdateSubDaos {
         to_add: Vec<SubDao>,
         to_remove: Vec<String>,
     },
+    /// Callable by the core contract. Creates a role, or replaces the
+    /// permissions of an existing one. Holders of a replaced role
+    /// keep it and its spending in the current period is kept.
+    SetRole { role: Role },
+    /// Callable by the core contract. Removes a role and revokes it
+    /// from all of its holders.
+    RemoveRole { name: String },
+    /// Callable by the core contract. Grants a role to the addresses
+    /// in `to_add` and revokes it from those in `to_remove`.
+    UpdateRoleHolders {
+        name: String,
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by holders of a role. The DAO will execute the
+    /// messages in order if they are within the role's permissions.
+    ExecuteRoleMsgs {
+        role: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Callable by the core contract. Sets the caps on spending by
+    /// proposals for the tokens in `to_set` and removes the caps for
+    /// the denoms in `to_remove`.
+    UpdateSpendingCaps {
+        to_set: Vec<SpendingCap>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Adds and removes contracts that
+    /// hold tokens on the DAO's behalf, whose balances are reported by
+    /// the `TreasuryBalances` query.
+    UpdateTreasuryHoldings {
+        to_add: Vec<TreasuryHolding>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Sets the total amount of a
+    /// token a SubDAO may draw from the DAO's treasury. Amounts the
+    /// SubDAO has already drawn count towards the new total.
+    SetSubDaoBudget {
+        sub_dao: String,
+        denom: UncheckedDenom,
+        amount: Uint128,
+    },
+    /// Callable by SubDAOs. Sends `amount` of a token from the DAO's
+    /// treasury to the SubDAO if it is within the SubDAO's budget.
+    DrawSubDaoBudget {
+        denom: UncheckedDenom,
+        amount: Uint128,
+    },
+    /// Callable by the core contract. Vetoes a proposal in one of the
+    /// proposal modules of a SubDAO. The proposal module must support
+    /// vetoing and have this DAO as its vetoer.
+    VetoSubDaoProposal {
+        sub_dao: String,
+        proposal_module: String,
+        proposal_id: u64,
+    },
+    /// Callable by the core contract. Sets or removes the DAO's
+    /// timelock. While a timelock is set, messages updating it are
+    /// always queued.
+    UpdateTimelock { timelock: Option<Timelock> },
+    /// Executes messages in the timelock queue once their delay has
+    /// passed. Callable by anyone.
+    ExecuteQueued { id: u64 },
+    /// Callable by the core contract, the admin and the guardian.
+    /// Removes messages from the timelock queue without executing
+    /// them.
+    CancelQueued { id: u64 },
 }
 
 #[cw_serde]
@@ -184,6 +305,28 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Gets an item in a namespace. If `height` is set, gets the
+    /// value the item had at the start of that block.
+    #[returns(crate::query::GetNamespacedItemResponse)]
+    GetNamespacedItem {
+        namespace: String,
+        key: String,
+        height: Option<u64>,
+    },
+    /// Lists the items in a namespace and their values.
+    #[returns(Vec<(String, cosmwasm_std::Binary)>)]
+    ListNamespacedItems {
+        namespace: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the addresses that may write to a namespace.
+    #[returns(Vec<cosmwasm_std::Addr>)]
+    ListNamespaceWriters {
+        namespace: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Returns contract version info
     #[returns(crate::voting::InfoResponse)]
     Info {},
@@ -211,6 +354,13 @@ pub enum QueryMsg {
     /// Gets the contract's voting module.
     #[returns(cosmwasm_std::Addr)]
     VotingModule {},
+    /// Lists proposal modules with the versions recorded when they
+    /// were added and the versions they currently report with cw2.
+    #[returns(Vec<crate::query::ProposalModuleVersion>)]
+    ProposalModuleVersions {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Returns all SubDAOs with their charters in a vec.
     /// start_after is bound exclusive and asks for a string address.
     #[returns(Vec<crate::query::SubDao>)]
@@ -218,9 +368,72 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Returns the DAO's SubDAOs and, recursively, their SubDAOs up
+    /// to `max_depth` levels below this DAO. Each SubDAO is checked to
+    /// have its parent as its admin.
+    #[returns(Vec<crate::query::SubDaoNode>)]
+    SubDaoTree { max_depth: Option<u32> },
+    /// Lists the budgets the DAO has delegated to a SubDAO and how
+    /// much of each the SubDAO has drawn.
+    #[returns(Vec<crate::treasury::SubDaoBudget>)]
+    SubDaoBudgets { sub_dao: String },
     /// Implements the DAO Star standard: <https://daostar.one/EIP>
     #[returns(crate::query::DaoURIResponse)]
     DaoURI {},
+    /// Gets a role and the spending under it in its current period.
+    #[returns(crate::query::RoleResponse)]
+    GetRole { name: String },
+    /// Lists the roles the DAO has created.
+    #[returns(Vec<crate::role::Role>)]
+    ListRoles {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the addresses holding a role.
+    #[returns(Vec<cosmwasm_std::Addr>)]
+    ListRoleHolders {
+        name: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Reports the DAO's native balances, the balances of its tracked
+    /// cw20s and cw721s, and the tokens held for it by its tracked
+    /// vesting and staking contracts. Balances are ordered by asset
+    /// type and then by ID, and may be filtered by asset type.
+    #[returns(Vec<crate::treasury::TreasuryBalance>)]
+    TreasuryBalances {
+        asset_type: Option<TreasuryAssetType>,
+        start_after: Option<TreasuryBalanceKey>,
+        limit: Option<u32>,
+    },
+    /// Lists the contracts that hold tokens on the DAO's behalf.
+    #[returns(Vec<crate::treasury::TreasuryHolding>)]
+    TreasuryHoldings {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the caps on spending by proposals.
+    #[returns(Vec<crate::treasury::SpendingCap>)]
+    SpendingCaps {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets how much of a token proposals may still spend under its
+    /// spending cap.
+    #[returns(crate::query::RemainingBudgetResponse)]
+    RemainingBudget { denom: String },
+    /// Gets the DAO's timelock, if any.
+    #[returns(Option<crate::timelock::Timelock>)]
+    Timelock {},
+    /// Gets messages in the timelock queue.
+    #[returns(crate::timelock::QueuedExecution)]
+    QueuedExecution { id: u64 },
+    /// Lists the messages in the timelock queue.
+    #[returns(Vec<crate::timelock::QueuedExecution>)]
+    QueuedExecutions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     /// Returns the voting power for an address at a given height.
     #[returns(crate::voting::VotingPowerAtHeightResponse)]
     VotingPowerAtHeight {
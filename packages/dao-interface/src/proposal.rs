@@ -21,6 +21,13 @@ pub enum Query {
     NextProposalId {},
 }
 
+/// Implemented by proposal modules that allow a vetoer to veto their
+/// proposals.
+#[cw_serde]
+pub enum VetoMsg {
+    Veto { proposal_id: u64 },
+}
+
 mod tests {
     /// Make sure the enum has all of the fields we expect. This will
     /// fail to compile if not.
@@ -1,9 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Uint128};
 use cw2::ContractVersion;
 use cw_utils::Expiration;
 
-use crate::state::{Config, ProposalModule};
+use crate::role::{Role, RoleSpending};
+use crate::state::{Config, ProposalModule, ProposalModuleStatus};
+use crate::treasury::SubDaoBudget;
 
 /// Relevant state for the governance module. Returned by the
 /// `DumpState` query.
@@ -26,6 +28,9 @@ pub struct DumpStateResponse {
     pub active_proposal_module_count: u32,
     /// The total number of proposal modules.
     pub total_proposal_module_count: u32,
+    /// Actions taken by the DAO's guardian that are still in effect.
+    #[serde(default)]
+    pub guardian_actions: Vec<GuardianAction>,
 }
 
 /// Information about if the contract is currently paused.
@@ -35,6 +40,38 @@ pub enum PauseInfoResponse {
     Unpaused {},
 }
 
+/// An emergency action taken by the DAO's guardian.
+#[cw_serde]
+pub enum GuardianAction {
+    /// The guardian paused the DAO.
+    Pause { expiration: Expiration },
+    /// The guardian suspended a proposal module. Proposals from the
+    /// module may not be executed until the suspension expires.
+    SuspendModule {
+        address: Addr,
+        expiration: Expiration,
+    },
+}
+
+/// Returned by the `ProposalModuleVersions` query.
+#[cw_serde]
+pub struct ProposalModuleVersion {
+    pub address: Addr,
+    pub status: ProposalModuleStatus,
+    /// The version recorded when the module was added.
+    pub recorded: Option<ContractVersion>,
+    /// The version the module currently reports, which differs from
+    /// the recorded one if the module has been migrated.
+    pub current: Option<ContractVersion>,
+}
+
+/// Returned by the `GetNamespacedItem` query.
+#[cw_serde]
+pub struct GetNamespacedItemResponse {
+    /// The item's JSON value, or `None` if it was not set.
+    pub value: Option<Binary>,
+}
+
 /// Returned by the `GetItem` query.
 #[cw_serde]
 pub struct GetItemResponse {
@@ -68,6 +105,23 @@ pub struct SubDao {
     pub charter: Option<String>,
 }
 
+/// A SubDAO and, recursively, its own SubDAOs. Returned by the
+/// `SubDaoTree` query.
+#[cw_serde]
+pub struct SubDaoNode {
+    /// The contract address of the SubDAO.
+    pub addr: String,
+    /// The purpose/constitution for the SubDAO.
+    pub charter: Option<String>,
+    /// True if the SubDAO's admin is its parent DAO.
+    pub verified: bool,
+    /// The budgets its parent has delegated to the SubDAO.
+    pub budgets: Vec<SubDaoBudget>,
+    /// The SubDAO's own SubDAOs. Empty if the depth limit of the
+    /// query was reached.
+    pub children: Vec<SubDaoNode>,
+}
+
 #[cw_serde]
 pub struct DaoURIResponse {
     pub dao_uri: Option<String>,
@@ -80,3 +134,23 @@ pub struct ProposalModuleCountResponse {
     /// The total number of proposal modules.
     pub total_proposal_module_count: u32,
 }
+
+/// Information about a role returned by `GetRole`.
+#[cw_serde]
+pub struct RoleResponse {
+    pub role: Role,
+    /// The amount of each token spent under the role in its current
+    /// period.
+    pub spending: Vec<RoleSpending>,
+}
+
+/// The remaining budget for a token returned by `RemainingBudget`.
+#[cw_serde]
+pub struct RemainingBudgetResponse {
+    pub denom: String,
+    /// The most the next proposal execution may spend, if limited.
+    pub per_execution: Option<Uint128>,
+    /// The amount that may still be spent within the current window,
+    /// if limited.
+    pub window_remaining: Option<Uint128>,
+}
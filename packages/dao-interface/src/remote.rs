@@ -0,0 +1,27 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{CosmosMsg, Empty};
+
+/// The IBC channel version used by `dao-remote-note` and
+/// `dao-remote-voice`.
+pub const REMOTE_VERSION: &str = "dao-remote-1";
+
+/// The data of a packet sent by a note, asking the voice on the other
+/// end of the channel to execute messages with the sender's proxy.
+#[cw_serde]
+pub struct RemotePacket {
+    /// The note's ID for the execution, used to match
+    /// acknowledgements to it.
+    pub id: u64,
+    /// The address that controls the proxy on the note's chain.
+    pub sender: String,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+}
+
+/// The acknowledgement a voice writes for a packet.
+#[cw_serde]
+pub enum RemoteAck {
+    /// The messages were executed by the proxy at `proxy`.
+    Success { proxy: String },
+    /// Executing the messages failed, and none of them took effect.
+    Error { error: String },
+}
@@ -0,0 +1,64 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+use cw_utils::{Duration, Expiration};
+
+/// A kind of message that holders of a role may execute on behalf of
+/// the DAO.
+#[cw_serde]
+pub enum RoleMsgType {
+    /// `BankMsg::Send`. Its target is the recipient.
+    BankSend,
+    /// `BankMsg::Burn`. Has no target.
+    BankBurn,
+    /// `WasmMsg::Execute`. Its target is the contract executed.
+    WasmExecute,
+    /// `WasmMsg::Instantiate`. Has no target.
+    WasmInstantiate,
+}
+
+/// A limit on how much of a token holders of a role may spend on
+/// behalf of the DAO each period.
+#[cw_serde]
+pub struct SpendLimit {
+    /// A native denom, or the address of a cw20 token or cw721
+    /// collection. Amounts in cw20 `Transfer`, `Send`,
+    /// `IncreaseAllowance` and `Burn` messages count as spending the
+    /// token. Each cw721 `TransferNft`, `SendNft`, `Approve` and
+    /// `Burn` spends one token of the collection.
+    pub denom: String,
+    /// The amount that may be spent each period.
+    pub amount: Uint128,
+    /// The length of a period. A period starts with the first spend
+    /// after the previous one ended.
+    pub period: Duration,
+}
+
+/// A named set of permissions that the DAO may grant to addresses.
+/// Holders of a role may execute messages on behalf of the DAO
+/// without a proposal so long as they are within its permissions.
+#[cw_serde]
+pub struct Role {
+    /// The name of the role.
+    pub name: String,
+    /// The kinds of messages holders of the role may execute.
+    pub msg_types: Vec<RoleMsgType>,
+    /// If set, the only addresses that messages executed by holders
+    /// of the role may target. Messages of a kind with no target are
+    /// unaffected. Messages may never target the DAO itself.
+    pub targets: Option<Vec<String>>,
+    /// Limits on the tokens holders of the role may spend. Tokens
+    /// without a limit may not be spent.
+    pub spend_limits: Vec<SpendLimit>,
+    /// When the role expires. Holders of an expired role may not
+    /// execute messages.
+    pub expiration: Expiration,
+}
+
+/// The amount of a token spent under a role in its current period.
+#[cw_serde]
+pub struct RoleSpending {
+    pub denom: String,
+    pub spent: Uint128,
+    /// When the current period ends and spending resets.
+    pub resets: Expiration,
+}
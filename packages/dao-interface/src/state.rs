@@ -1,5 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, WasmMsg};
+use cw2::ContractVersion;
+use cw_utils::{Duration, Expiration};
 
 /// Top level config type for core module.
 #[cw_serde]
@@ -19,6 +21,22 @@ pub struct Config {
     /// The URI for the DAO as defined by the DAOstar standard
     /// <https://daostar.one/EIP>
     pub dao_uri: Option<String>,
+    /// An address, for example a multisig, that may pause the DAO or
+    /// suspend its proposal modules in an emergency without a
+    /// proposal.
+    #[serde(default)]
+    pub guardian: Option<Guardian>,
+}
+
+/// An address that may act quickly in an emergency. The guardian may
+/// pause the DAO or suspend individual proposal modules for up to
+/// `max_duration`, but may not execute messages on the DAO's behalf.
+#[cw_serde]
+pub struct Guardian {
+    pub addr: String,
+    /// The longest the guardian may pause the DAO or suspend a
+    /// proposal module for. Guardian actions must use the same units.
+    pub max_duration: Duration,
 }
 
 /// Top level type describing a proposal module.
@@ -31,6 +49,11 @@ pub struct ProposalModule {
     pub prefix: String,
     /// The status of the proposal module, e.g. 'Enabled' or 'Disabled.'
     pub status: ProposalModuleStatus,
+    /// The contract name and version of the module, read with cw2
+    /// when the module was added. `None` for modules added before
+    /// versions were tracked or that do not set a cw2 version.
+    #[serde(default)]
+    pub version: Option<ContractVersion>,
 }
 
 /// The status of a proposal module.
@@ -38,6 +61,14 @@ pub struct ProposalModule {
 pub enum ProposalModuleStatus {
     Enabled,
     Disabled,
+    /// The module has been replaced by `successor` and is no longer
+    /// active. It may still execute proposals so that its open
+    /// proposals may close. It may be disabled once `until` has
+    /// passed and none of its proposals are open.
+    Draining {
+        until: Expiration,
+        successor: Addr,
+    },
 }
 
 /// Information about the CosmWasm level admin of a contract. Used in
@@ -0,0 +1,49 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CosmosMsg, Empty, Uint128};
+use cw_utils::{Duration, Expiration};
+
+/// A kind of message that the DAO's timelock delays.
+#[cw_serde]
+pub enum TimelockCriterion {
+    /// `WasmMsg::Migrate` of any contract.
+    Migrate {},
+    /// `WasmMsg::UpdateAdmin` and `WasmMsg::ClearAdmin` of any
+    /// contract, and `NominateAdmin` on the DAO.
+    UpdateAdmin {},
+    /// `UpdateVotingModule` on the DAO.
+    UpdateVotingModule {},
+    /// Spending more than `amount` of a token from the DAO's
+    /// treasury. Spends are totalled across all of the messages
+    /// executed together.
+    Transfer {
+        /// A native denom, or the address of a cw20 token. Counted
+        /// as for spending caps.
+        denom: String,
+        amount: Uint128,
+    },
+}
+
+/// Delays high-impact messages executed by proposals and the DAO's
+/// admin. If any message executed together matches a criterion, all
+/// of them are queued and may only be executed once `delay` has
+/// passed.
+#[cw_serde]
+pub struct Timelock {
+    pub delay: Duration,
+    pub criteria: Vec<TimelockCriterion>,
+}
+
+/// Messages waiting in the DAO's timelock queue.
+#[cw_serde]
+pub struct QueuedExecution {
+    pub id: u64,
+    /// The proposal module or admin that executed the messages.
+    pub sender: Addr,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// When the messages may be executed.
+    pub ready: Expiration,
+    /// Whether the messages count towards the DAO's spending caps,
+    /// as they do when executed by a proposal module. Spending is
+    /// charged when the messages are executed, not when queued.
+    pub capped: bool,
+}
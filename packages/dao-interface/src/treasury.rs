@@ -0,0 +1,123 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Event, Uint128};
+use cw_denom::CheckedDenom;
+use cw_utils::Duration;
+
+/// Limits on how much of a token proposals may spend from the DAO's
+/// treasury.
+#[cw_serde]
+pub struct SpendingCap {
+    /// A native denom, or the address of a cw20 token. Amounts in
+    /// cw20 `Transfer`, `Send`, `IncreaseAllowance` and `Burn`
+    /// messages count as spending the token.
+    pub denom: String,
+    /// If set, the most that the messages in a single
+    /// `ExecuteProposalHook` call may spend. A proposal whose messages
+    /// are executed over several calls, such as a `dao-proposal-single`
+    /// execution plan run in batches, may spend this much in each;
+    /// `per_window` bounds what they spend in total.
+    pub per_execution: Option<Uint128>,
+    /// If set, the most that proposals may spend within any window
+    /// of time.
+    pub per_window: Option<WindowCap>,
+}
+
+/// A limit on spending within a rolling window.
+#[cw_serde]
+pub struct WindowCap {
+    /// The most that may be spent within the window.
+    pub amount: Uint128,
+    /// The length of the window.
+    pub window: Duration,
+}
+
+/// The type of the event proposal modules emit when a proposal's
+/// execution fails because it would exceed one of the DAO's spending
+/// caps.
+pub const SPENDING_CAP_EXCEEDED_EVENT: &str = "spending_cap_exceeded";
+
+/// Returns a `spending_cap_exceeded` event naming the token and its
+/// remaining budget if `error`, the error a proposal's execution
+/// failed with, is dao-dao-core's `SpendingCapExceeded` error. Errors
+/// reach proposal modules as strings, possibly wrapped by the chain,
+/// so it is found by its message.
+pub fn spending_cap_exceeded_event(proposal_id: u64, error: &str) -> Option<Event> {
+    let (_, rest) = error.split_once("would exceed the spending cap for (")?;
+    let (denom, rest) = rest.split_once("). Remaining budget: ")?;
+    let remaining: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    let remaining: Uint128 = remaining.parse().ok()?;
+    Some(
+        Event::new(SPENDING_CAP_EXCEEDED_EVENT)
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("denom", denom)
+            .add_attribute("remaining", remaining.to_string()),
+    )
+}
+
+/// A budget the DAO has delegated to one of its SubDAOs. The SubDAO
+/// may draw up to `amount` of the token from the DAO's treasury
+/// without a proposal.
+#[cw_serde]
+pub struct SubDaoBudget {
+    pub denom: CheckedDenom,
+    /// The total amount the SubDAO may draw.
+    pub amount: Uint128,
+    /// The amount the SubDAO has drawn so far.
+    pub drawn: Uint128,
+}
+
+/// A kind of asset reported by the `TreasuryBalances` query. Balances
+/// are reported in the order of these kinds.
+#[cw_serde]
+#[derive(Copy, Eq, PartialOrd, Ord)]
+pub enum TreasuryAssetType {
+    /// Native tokens held by the DAO.
+    Native,
+    /// cw20 tokens in the DAO's cw20 list.
+    Cw20,
+    /// cw721 collections in the DAO's cw721 list. The amount is the
+    /// number of NFTs the DAO holds.
+    Cw721,
+    /// Tokens vesting to the DAO in its tracked cw-vesting contracts.
+    Vesting,
+    /// Tokens the DAO has staked in its tracked cw20-stake contracts.
+    Staked,
+}
+
+/// An asset held by the DAO. Returned by the `TreasuryBalances` query.
+#[cw_serde]
+pub struct TreasuryBalance {
+    pub asset_type: TreasuryAssetType,
+    /// The native denom, or the address of the cw20 token, cw721
+    /// collection, or vesting or staking contract.
+    pub id: String,
+    /// The native denom or cw20 address of the tokens held. The
+    /// collection's address for cw721 collections.
+    pub denom: String,
+    pub amount: Uint128,
+}
+
+/// Identifies a balance returned by the `TreasuryBalances` query, to
+/// paginate from.
+#[cw_serde]
+pub struct TreasuryBalanceKey {
+    pub asset_type: TreasuryAssetType,
+    pub id: String,
+}
+
+/// A contract that holds tokens on the DAO's behalf.
+#[cw_serde]
+pub struct TreasuryHolding {
+    pub addr: String,
+    pub kind: TreasuryHoldingKind,
+}
+
+#[cw_serde]
+pub enum TreasuryHoldingKind {
+    /// A cw-vesting contract. Tokens that have yet to be claimed are
+    /// reported if the DAO is its recipient.
+    CwVesting,
+    /// A cw20-stake contract. Tokens the DAO has staked in it are
+    /// reported.
+    Cw20Stake,
+}
@@ -1,11 +1,12 @@
 use cosmwasm_std::StdError;
 use cw_denom::DenomError;
-use cw_utils::ParseReplyError;
+use cw_utils::{Expiration, ParseReplyError};
 use thiserror::Error;
 
 use cw_hooks::HookError;
 use dao_voting::{
-    deposit::DepositError, pre_propose::PreProposeSubmissionPolicyError, status::Status,
+    approval::ApprovalError, deposit::DepositError, pre_propose::PreProposeSubmissionPolicyError,
+    status::Status,
 };
 
 #[derive(Error, Debug, PartialEq)]
@@ -19,6 +20,9 @@ pub enum PreProposeError {
     #[error(transparent)]
     Deposit(#[from] DepositError),
 
+    #[error(transparent)]
+    Approval(#[from] ApprovalError),
+
     #[error(transparent)]
     Hooks(#[from] HookError),
 
@@ -55,6 +59,18 @@ pub enum PreProposeError {
     #[error("Unsupported")]
     Unsupported {},
 
+    #[error("Rate limits must be non-zero")]
+    ZeroRateLimit {},
+
+    #[error("Proposer has reached the limit of ({limit}) open proposals")]
+    ProposerOpenProposalLimit { limit: u64 },
+
+    #[error("Module has reached the limit of ({limit}) open proposals")]
+    ModuleOpenProposalLimit { limit: u64 },
+
+    #[error("Proposer may not submit another proposal until ({available})")]
+    SubmissionCooldown { available: Expiration },
+
     #[error("Cannot migrate contract version {actual}. Requires: {required}")]
     CannotMigrateVersion { required: String, actual: String },
 }
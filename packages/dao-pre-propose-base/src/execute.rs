@@ -1,10 +1,11 @@
 use cosmwasm_schema::{cw_serde, schemars::JsonSchema};
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
-    SubMsg, Uint128, WasmMsg,
+    to_json_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
+use cw_utils::{Duration, Expiration};
 
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use semver::{Version, VersionReq};
 
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
@@ -21,8 +22,11 @@ use serde::Serialize;
 use crate::{
     error::PreProposeError,
     helpers::add_and_remove_addresses,
-    msg::{DepositInfoResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    state::{Config, PreProposeContract},
+    msg::{
+        DepositInfoResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, ProposalQuotaResponse,
+        QueryMsg,
+    },
+    state::{Config, OpenProposal, PreProposeContract, RateLimits},
 };
 
 const CONTRACT_NAME: &str = "crates.io::dao-pre-propose-base";
@@ -110,6 +114,9 @@ where
                 allowlist_add,
                 allowlist_remove,
             ),
+            ExecuteMsg::UpdateRateLimits { rate_limits } => {
+                self.execute_update_rate_limits(deps, info, rate_limits)
+            }
             ExecuteMsg::Withdraw { denom } => {
                 self.execute_withdraw(deps.as_ref(), env, info, denom)
             }
@@ -122,7 +129,14 @@ where
             ExecuteMsg::ProposalCompletedHook {
                 proposal_id,
                 new_status,
-            } => self.execute_proposal_completed_hook(deps.as_ref(), info, proposal_id, new_status),
+                failed_quorum,
+            } => self.execute_proposal_completed_hook(
+                deps,
+                info,
+                proposal_id,
+                new_status,
+                failed_quorum,
+            ),
 
             ExecuteMsg::Extension { .. } => Ok(Response::default()),
         }
@@ -135,11 +149,10 @@ where
         info: MessageInfo,
         msg: ProposalMessage,
     ) -> Result<Response, PreProposeError> {
-        self.check_can_submit(deps.as_ref(), info.sender.clone())?;
+        self.check_can_submit(deps.as_ref(), &env.block, info.sender.clone())?;
 
-        let config = self.config.load(deps.storage)?;
-
-        let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
+        let deposit_info = self.resolve_deposit(deps.as_ref(), &info.sender)?;
+        let deposit_messages = if let Some(ref deposit_info) = deposit_info {
             deposit_info.check_native_deposit_paid(&info)?;
             deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
         } else {
@@ -154,10 +167,13 @@ where
             &proposal_module,
             &dao_interface::proposal::Query::NextProposalId {},
         )?;
-        self.deposits.save(
+        self.deposits
+            .save(deps.storage, next_id, &(deposit_info, info.sender.clone()))?;
+        self.add_open_proposal(
             deps.storage,
-            next_id,
-            &(config.deposit_info, info.sender.clone()),
+            &env.block,
+            &info.sender,
+            OpenProposal::Created(next_id),
         )?;
 
         let propose_messsage = WasmMsg::Execute {
@@ -307,6 +323,43 @@ where
             .add_attribute("sender", info.sender))
     }
 
+    pub fn execute_update_rate_limits(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        rate_limits: RateLimits,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        rate_limits.validate()?;
+        self.rate_limits.save(deps.storage, &rate_limits)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "update_rate_limits")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "max_open_per_proposer",
+                rate_limits
+                    .max_open_per_proposer
+                    .map_or_else(|| "None".to_string(), |limit| limit.to_string()),
+            )
+            .add_attribute(
+                "min_submission_interval",
+                rate_limits
+                    .min_submission_interval
+                    .map_or_else(|| "None".to_string(), |interval| interval.to_string()),
+            )
+            .add_attribute(
+                "max_open_proposals",
+                rate_limits
+                    .max_open_proposals
+                    .map_or_else(|| "None".to_string(), |limit| limit.to_string()),
+            ))
+    }
+
     pub fn execute_withdraw(
         &self,
         deps: Deps,
@@ -384,10 +437,11 @@ where
 
     pub fn execute_proposal_completed_hook(
         &self,
-        deps: Deps,
+        deps: DepsMut,
         info: MessageInfo,
         id: u64,
         new_status: Status,
+        failed_quorum: bool,
     ) -> Result<Response, PreProposeError> {
         let proposal_module = self.proposal_module.load(deps.storage)?;
         if info.sender != proposal_module {
@@ -401,6 +455,7 @@ where
         // module and open proposal submission to anyone.
         if new_status != Status::Closed
             && new_status != Status::Executed
+            && new_status != Status::ExecutionFailed
             && new_status != Status::Vetoed
         {
             return Err(PreProposeError::NotCompleted { status: new_status });
@@ -408,27 +463,19 @@ where
 
         match self.deposits.may_load(deps.storage, id)? {
             Some((deposit_info, proposer)) => {
+                self.remove_open_proposal(deps.storage, &proposer, OpenProposal::Created(id))?;
+
                 let messages = if let Some(ref deposit_info) = deposit_info {
-                    // Determine if refund can be issued
-                    let should_refund_to_proposer =
-                        match (new_status, deposit_info.clone().refund_policy) {
-                            // If policy is refund only passed props, refund for executed status
-                            (Status::Executed, DepositRefundPolicy::OnlyPassed) => true,
-                            // Don't refund other statuses for OnlyPassed policy
-                            (_, DepositRefundPolicy::OnlyPassed) => false,
-                            // Refund if the refund policy is always refund
-                            (_, DepositRefundPolicy::Always) => true,
-                            // Don't refund if the refund is never refund
-                            (_, DepositRefundPolicy::Never) => false,
-                        };
-
-                    if should_refund_to_proposer {
-                        deposit_info.get_return_deposit_message(&proposer)?
-                    } else {
-                        // If the proposer doesn't get the deposit, the DAO does.
-                        let dao = self.dao.load(deps.storage)?;
-                        deposit_info.get_return_deposit_message(&dao)?
-                    }
+                    // The refund policy determines how much of the
+                    // deposit the proposer gets back. Whatever they
+                    // don't get is slashed, by default to the DAO.
+                    let dao = self.dao.load(deps.storage)?;
+                    deposit_info.get_completed_deposit_messages(
+                        &proposer,
+                        &dao,
+                        new_status,
+                        failed_quorum,
+                    )?
                 } else {
                     // No deposit info for this proposal. Nothing to do.
                     vec![]
@@ -451,7 +498,197 @@ where
         }
     }
 
-    pub fn check_can_submit(&self, deps: Deps, who: Addr) -> Result<(), PreProposeError> {
+    /// Resolves the deposit `proposer` must pay to create a proposal
+    /// now. Returns `None` if no deposit is required.
+    pub fn resolve_deposit(
+        &self,
+        deps: Deps,
+        proposer: &Addr,
+    ) -> Result<Option<CheckedDepositInfo>, PreProposeError> {
+        let config = self.config.load(deps.storage)?;
+        match config.deposit_info {
+            Some(deposit_info) => {
+                let dao = self.dao.load(deps.storage)?;
+                let open_proposals = self
+                    .open_proposals
+                    .may_load(deps.storage, proposer.clone())?
+                    .unwrap_or_default();
+                Ok(Some(deposit_info.resolve(
+                    deps,
+                    &dao,
+                    proposer,
+                    open_proposals,
+                )?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// The map that marks `proposal` as counted, and its key there.
+    fn counted(&self, proposal: OpenProposal) -> (&Map<'static, u64, Empty>, u64) {
+        match proposal {
+            OpenProposal::Created(id) => (&self.counted_proposals, id),
+            OpenProposal::Pending(id) => (&self.counted_pending_proposals, id),
+        }
+    }
+
+    /// Counts `proposal`, created by `proposer`, as open and records
+    /// when it was submitted.
+    pub fn add_open_proposal(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        proposer: &Addr,
+        proposal: OpenProposal,
+    ) -> StdResult<()> {
+        let (counted, id) = self.counted(proposal);
+        counted.save(storage, id, &Empty {})?;
+        self.open_proposals
+            .update(storage, proposer.clone(), |count| -> StdResult<_> {
+                Ok(count.unwrap_or_default() + 1)
+            })?;
+        let total = self
+            .total_open_proposals
+            .may_load(storage)?
+            .unwrap_or_default();
+        self.total_open_proposals.save(storage, &(total + 1))?;
+        self.last_submissions
+            .save(storage, proposer.clone(), &(block.height, block.time))?;
+        Ok(())
+    }
+
+    /// Counts `proposal`, created by `proposer`, as no longer open.
+    /// Proposals that were not counted, such as those created before
+    /// open proposals were counted, are ignored.
+    pub fn remove_open_proposal(
+        &self,
+        storage: &mut dyn Storage,
+        proposer: &Addr,
+        proposal: OpenProposal,
+    ) -> StdResult<()> {
+        let (counted, id) = self.counted(proposal);
+        if !counted.has(storage, id) {
+            return Ok(());
+        }
+        counted.remove(storage, id);
+
+        let count = self
+            .open_proposals
+            .may_load(storage, proposer.clone())?
+            .unwrap_or_default();
+        if count > 1 {
+            self.open_proposals
+                .save(storage, proposer.clone(), &(count - 1))?;
+        } else {
+            self.open_proposals.remove(storage, proposer.clone());
+        }
+        if count > 0 {
+            let total = self
+                .total_open_proposals
+                .may_load(storage)?
+                .unwrap_or_default();
+            self.total_open_proposals
+                .save(storage, &total.saturating_sub(1))?;
+        }
+        Ok(())
+    }
+
+    /// Moves the count of a pending proposal to the proposal created
+    /// from it in the proposal module, so that it is counted as no
+    /// longer open once that proposal completes.
+    pub fn create_pending_proposal(
+        &self,
+        storage: &mut dyn Storage,
+        pending_id: u64,
+        proposal_id: u64,
+    ) -> StdResult<()> {
+        if self.counted_pending_proposals.has(storage, pending_id) {
+            self.counted_pending_proposals.remove(storage, pending_id);
+            self.counted_proposals
+                .save(storage, proposal_id, &Empty {})?;
+        }
+        Ok(())
+    }
+
+    /// Returns when `who` may next submit a proposal under the
+    /// minimum submission interval, or `None` if they may submit one
+    /// now.
+    pub fn next_submission(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        who: &Addr,
+    ) -> StdResult<Option<Expiration>> {
+        let interval = self
+            .rate_limits
+            .may_load(storage)?
+            .unwrap_or_default()
+            .min_submission_interval;
+        let last = self.last_submissions.may_load(storage, who.clone())?;
+        Ok(match (interval, last) {
+            (Some(interval), Some((height, time))) => {
+                let next = match interval {
+                    Duration::Height(blocks) => Expiration::AtHeight(height + blocks),
+                    Duration::Time(seconds) => Expiration::AtTime(time.plus_seconds(seconds)),
+                };
+                if next.is_expired(block) {
+                    None
+                } else {
+                    Some(next)
+                }
+            }
+            _ => None,
+        })
+    }
+
+    /// Checks that `who` may submit a proposal, both under the
+    /// submission policy and the rate limits.
+    pub fn check_can_submit(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        who: Addr,
+    ) -> Result<(), PreProposeError> {
+        self.check_submission_policy(deps, who.clone())?;
+        self.check_rate_limits(deps, block, &who)
+    }
+
+    fn check_rate_limits(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        who: &Addr,
+    ) -> Result<(), PreProposeError> {
+        let rate_limits = self.rate_limits.may_load(deps.storage)?.unwrap_or_default();
+
+        if let Some(limit) = rate_limits.max_open_per_proposer {
+            let open = self
+                .open_proposals
+                .may_load(deps.storage, who.clone())?
+                .unwrap_or_default();
+            if open >= limit {
+                return Err(PreProposeError::ProposerOpenProposalLimit { limit });
+            }
+        }
+
+        if let Some(limit) = rate_limits.max_open_proposals {
+            let open = self
+                .total_open_proposals
+                .may_load(deps.storage)?
+                .unwrap_or_default();
+            if open >= limit {
+                return Err(PreProposeError::ModuleOpenProposalLimit { limit });
+            }
+        }
+
+        if let Some(available) = self.next_submission(deps.storage, block, who)? {
+            return Err(PreProposeError::SubmissionCooldown { available });
+        }
+
+        Ok(())
+    }
+
+    fn check_submission_policy(&self, deps: Deps, who: Addr) -> Result<(), PreProposeError> {
         let config = self.config.load(deps.storage)?;
 
         match config.submission_policy {
@@ -497,7 +734,7 @@ where
         ))
     }
 
-    pub fn query(&self, deps: Deps, _env: Env, msg: QueryMsg<QueryExt>) -> StdResult<Binary> {
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg<QueryExt>) -> StdResult<Binary> {
         match msg {
             QueryMsg::ProposalModule {} => {
                 to_json_binary(&self.proposal_module.load(deps.storage)?)
@@ -514,14 +751,51 @@ where
                     proposer,
                 })
             }
+            QueryMsg::RequiredDeposit { proposer } => {
+                let proposer = deps.api.addr_validate(&proposer)?;
+                to_json_binary(
+                    &self
+                        .resolve_deposit(deps, &proposer)
+                        .map_err(|e| StdError::generic_err(e.to_string()))?,
+                )
+            }
+            QueryMsg::RateLimits {} => {
+                to_json_binary(&self.rate_limits.may_load(deps.storage)?.unwrap_or_default())
+            }
+            QueryMsg::ProposalQuota { address } => {
+                let addr = deps.api.addr_validate(&address)?;
+                let rate_limits = self.rate_limits.may_load(deps.storage)?.unwrap_or_default();
+                let open_proposals = self
+                    .open_proposals
+                    .may_load(deps.storage, addr.clone())?
+                    .unwrap_or_default();
+                let module_open_proposals = self
+                    .total_open_proposals
+                    .may_load(deps.storage)?
+                    .unwrap_or_default();
+                to_json_binary(&ProposalQuotaResponse {
+                    open_proposals,
+                    remaining_open_proposals: rate_limits
+                        .max_open_per_proposer
+                        .map(|limit| limit.saturating_sub(open_proposals)),
+                    module_open_proposals,
+                    module_remaining_open_proposals: rate_limits
+                        .max_open_proposals
+                        .map(|limit| limit.saturating_sub(module_open_proposals)),
+                    next_submission: self.next_submission(deps.storage, &env.block, &addr)?,
+                })
+            }
             QueryMsg::CanPropose { address } => {
                 let addr = deps.api.addr_validate(&address)?;
-                match self.check_can_submit(deps, addr) {
+                match self.check_can_submit(deps, &env.block, addr) {
                     Ok(_) => to_json_binary(&true),
                     Err(err) => match err {
                         PreProposeError::SubmissionPolicy(
                             PreProposeSubmissionPolicyError::Unauthorized {},
-                        ) => to_json_binary(&false),
+                        )
+                        | PreProposeError::ProposerOpenProposalLimit { .. }
+                        | PreProposeError::ModuleOpenProposalLimit { .. }
+                        | PreProposeError::SubmissionCooldown { .. } => to_json_binary(&false),
                         PreProposeError::Std(err) => Err(err),
                         _ => Err(StdError::generic_err(format!(
                             "unexpected error: {:?}",
@@ -642,6 +916,7 @@ where
                             DepositRefundPolicyV241::Never => DepositRefundPolicy::Never,
                             DepositRefundPolicyV241::OnlyPassed => DepositRefundPolicy::OnlyPassed,
                         },
+                        scaling: None,
                     });
 
                 self.config.save(
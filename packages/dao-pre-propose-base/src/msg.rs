@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, schemars::JsonSchema, QueryResponses};
 use cw_denom::UncheckedDenom;
+use cw_utils::Expiration;
 use dao_interface::proposal::InfoResponse;
 use dao_voting::{
     deposit::{CheckedDepositInfo, UncheckedDepositInfo},
@@ -52,6 +53,12 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
         allowlist_remove: Option<Vec<String>>,
     },
 
+    /// Updates the limits on how often proposals may be submitted.
+    /// Only the DAO may execute this message.
+    UpdateRateLimits {
+        rate_limits: crate::state::RateLimits,
+    },
+
     /// Withdraws funds inside of this contract to the message
     /// sender. The contracts entire balance for the specifed DENOM is
     /// withdrawn to the message sender. Only the DAO may call this
@@ -103,6 +110,10 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     ProposalCompletedHook {
         proposal_id: u64,
         new_status: Status,
+        /// Whether the proposal was closed because it did not reach
+        /// quorum. Only set by proposal modules that have a quorum.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        failed_quorum: bool,
     },
 }
 
@@ -130,6 +141,18 @@ where
     /// PROPOSAL_ID.
     #[returns(DepositInfoResponse)]
     DepositInfo { proposal_id: u64 },
+    /// Returns the deposit PROPOSER would have to pay to create a
+    /// proposal now, with any deposit scaling resolved. Returns `None`
+    /// if no deposit is required.
+    #[returns(Option<CheckedDepositInfo>)]
+    RequiredDeposit { proposer: String },
+    /// Gets the limits on how often proposals may be submitted.
+    #[returns(crate::state::RateLimits)]
+    RateLimits {},
+    /// Returns how many more proposals ADDRESS may submit under the
+    /// module's rate limits, and when it may next submit one.
+    #[returns(ProposalQuotaResponse)]
+    ProposalQuota { address: String },
     /// Returns whether or not the address can submit proposals.
     #[returns(bool)]
     CanPropose { address: String },
@@ -150,6 +173,23 @@ pub struct DepositInfoResponse {
     pub proposer: cosmwasm_std::Addr,
 }
 
+#[cw_serde]
+pub struct ProposalQuotaResponse {
+    /// The number of open proposals the address has created.
+    pub open_proposals: u64,
+    /// How many more proposals the address may have open at once.
+    /// `None` if there is no per-proposer limit.
+    pub remaining_open_proposals: Option<u64>,
+    /// The number of open proposals created through the module.
+    pub module_open_proposals: u64,
+    /// How many more proposals may be open in the module at once.
+    /// `None` if there is no module-wide limit.
+    pub module_remaining_open_proposals: Option<u64>,
+    /// When the address may next submit a proposal. `None` if it may
+    /// submit one now.
+    pub next_submission: Option<Expiration>,
+}
+
 #[cw_serde]
 pub enum MigrateMsg<MigrateExt>
 where
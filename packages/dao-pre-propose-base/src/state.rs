@@ -1,12 +1,15 @@
 use std::marker::PhantomData;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Empty, Timestamp};
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
 
 use dao_voting::{deposit::CheckedDepositInfo, pre_propose::PreProposeSubmissionPolicy};
 
+use crate::error::PreProposeError;
+
 #[cw_serde]
 pub struct Config {
     /// Information about the deposit required to create a
@@ -16,6 +19,49 @@ pub struct Config {
     pub submission_policy: PreProposeSubmissionPolicy,
 }
 
+/// Limits on how often proposals may be submitted. Checked in
+/// addition to the submission policy.
+#[cw_serde]
+#[derive(Default)]
+pub struct RateLimits {
+    /// The most proposals one address may have open at once. If
+    /// `None`, there is no limit.
+    pub max_open_per_proposer: Option<u64>,
+    /// The least time that must pass between two submissions by the
+    /// same address. If `None`, there is no limit.
+    pub min_submission_interval: Option<Duration>,
+    /// The most proposals that may be open in this module at once. If
+    /// `None`, there is no limit.
+    pub max_open_proposals: Option<u64>,
+}
+
+impl RateLimits {
+    pub fn validate(&self) -> Result<(), PreProposeError> {
+        let zero_interval = matches!(
+            self.min_submission_interval,
+            Some(Duration::Height(0) | Duration::Time(0))
+        );
+        if self.max_open_per_proposer == Some(0)
+            || self.max_open_proposals == Some(0)
+            || zero_interval
+        {
+            return Err(PreProposeError::ZeroRateLimit {});
+        }
+        Ok(())
+    }
+}
+
+/// A proposal counted in `open_proposals` and `total_open_proposals`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpenProposal {
+    /// A proposal in the proposal module, by its ID there.
+    Created(u64),
+    /// A proposal held by an extension before it is created in the
+    /// proposal module, for example while it awaits approval, by the
+    /// extension's ID for it.
+    Pending(u64),
+}
+
 pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage> {
     /// The proposal module that this module is associated with.
     pub proposal_module: Item<'static, Addr>,
@@ -28,6 +74,22 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt,
     pub deposits: Map<'static, u64, (Option<CheckedDepositInfo>, Addr)>,
     /// Consumers of proposal submitted hooks.
     pub proposal_submitted_hooks: Hooks<'static>,
+    /// The number of open proposals each proposer has created through
+    /// this module. Used to scale deposits.
+    pub open_proposals: Map<'static, Addr, u64>,
+    /// The limits on proposal submission.
+    pub rate_limits: Item<'static, RateLimits>,
+    /// The number of open proposals created through this module.
+    pub total_open_proposals: Item<'static, u64>,
+    /// The height and time of each proposer's last submission.
+    pub last_submissions: Map<'static, Addr, (u64, Timestamp)>,
+    /// Proposals in the proposal module that are counted as open.
+    /// Proposals created before open proposals were counted are
+    /// absent, so completing them leaves the counts alone.
+    pub counted_proposals: Map<'static, u64, Empty>,
+    /// As `counted_proposals`, for pending proposals held by an
+    /// extension.
+    pub counted_pending_proposals: Map<'static, u64, Empty>,
 
     // These types are used in associated functions, but not
     // assocaited data. To stop the compiler complaining about unused
@@ -42,12 +104,19 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt,
 impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
     PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
 {
+    #[allow(clippy::too_many_arguments)]
     const fn new(
         proposal_key: &'static str,
         dao_key: &'static str,
         config_key: &'static str,
         deposits_key: &'static str,
         proposal_submitted_hooks_key: &'static str,
+        open_proposals_key: &'static str,
+        rate_limits_key: &'static str,
+        total_open_proposals_key: &'static str,
+        last_submissions_key: &'static str,
+        counted_proposals_key: &'static str,
+        counted_pending_proposals_key: &'static str,
     ) -> Self {
         Self {
             proposal_module: Item::new(proposal_key),
@@ -55,6 +124,12 @@ impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
             config: Item::new(config_key),
             deposits: Map::new(deposits_key),
             proposal_submitted_hooks: Hooks::new(proposal_submitted_hooks_key),
+            open_proposals: Map::new(open_proposals_key),
+            rate_limits: Item::new(rate_limits_key),
+            total_open_proposals: Item::new(total_open_proposals_key),
+            last_submissions: Map::new(last_submissions_key),
+            counted_proposals: Map::new(counted_proposals_key),
+            counted_pending_proposals: Map::new(counted_pending_proposals_key),
             execute_type: PhantomData,
             instantiate_type: PhantomData,
             query_type: PhantomData,
@@ -77,6 +152,12 @@ impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage> Default
             "config",
             "deposits",
             "proposal_submitted_hooks",
+            "open_proposals",
+            "rate_limits",
+            "total_open_proposals",
+            "last_submissions",
+            "counted_proposals",
+            "counted_pending_proposals",
         )
     }
 }
@@ -4,12 +4,13 @@ use cosmwasm_std::{
     to_json_binary, Addr, Binary, ContractResult, Empty, Response, SubMsg, WasmMsg,
 };
 use cw_hooks::HooksResponse;
+use cw_utils::{Duration, Expiration};
 use dao_voting::{pre_propose::PreProposeSubmissionPolicy, status::Status};
 
 use crate::{
     error::PreProposeError,
-    msg::{ExecuteMsg, QueryMsg},
-    state::{Config, PreProposeContract},
+    msg::{ExecuteMsg, ProposalQuotaResponse, QueryMsg},
+    state::{Config, OpenProposal, PreProposeContract, RateLimits},
 };
 
 type Contract = PreProposeContract<Empty, Empty, Empty, Empty, Empty>;
@@ -33,6 +34,7 @@ fn test_completed_hook_status_invariant() {
         ExecuteMsg::ProposalCompletedHook {
             proposal_id: 1,
             new_status: Status::Passed,
+            failed_quorum: false,
         },
     );
 
@@ -62,6 +64,7 @@ fn test_completed_hook_auth() {
         ExecuteMsg::ProposalCompletedHook {
             proposal_id: 1,
             new_status: Status::Passed,
+            failed_quorum: false,
         },
     );
 
@@ -200,3 +203,191 @@ fn test_execute_ext_does_nothing() {
         .unwrap();
     assert_eq!(res, Response::default())
 }
+
+#[test]
+fn test_rate_limits() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    module
+        .dao
+        .save(&mut deps.storage, &Addr::unchecked("d"))
+        .unwrap();
+    module
+        .proposal_module
+        .save(&mut deps.storage, &Addr::unchecked("pm"))
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: None,
+                submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            },
+        )
+        .unwrap();
+    deps.querier.update_wasm(|_| {
+        // for responding to the next proposal ID query that gets fired by propose.
+        cosmwasm_std::SystemResult::Ok(ContractResult::Ok(to_json_binary(&1u64).unwrap()))
+    });
+
+    let rate_limits = RateLimits {
+        max_open_per_proposer: Some(1),
+        min_submission_interval: Some(Duration::Height(10)),
+        max_open_proposals: Some(2),
+    };
+
+    // Only the DAO may update the rate limits, and they may not be
+    // zero.
+    let err = module
+        .execute_update_rate_limits(deps.as_mut(), mock_info("n", &[]), rate_limits.clone())
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::NotDao {});
+    let err = module
+        .execute_update_rate_limits(
+            deps.as_mut(),
+            mock_info("d", &[]),
+            RateLimits {
+                max_open_proposals: Some(0),
+                ..rate_limits.clone()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::ZeroRateLimit {});
+    let res = module
+        .execute_update_rate_limits(deps.as_mut(), mock_info("d", &[]), rate_limits.clone())
+        .unwrap();
+    assert_eq!(
+        res.attributes
+            .iter()
+            .skip(2)
+            .map(|attr| (attr.key.as_str(), attr.value.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("max_open_per_proposer", "1"),
+            ("min_submission_interval", "height: 10"),
+            ("max_open_proposals", "2"),
+        ]
+    );
+    let res: RateLimits = from_json(
+        module
+            .query(deps.as_ref(), mock_env(), QueryMsg::RateLimits {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, rate_limits);
+
+    let propose = |deps: cosmwasm_std::DepsMut, env, sender: &str| {
+        module.execute(
+            deps,
+            env,
+            mock_info(sender, &[]),
+            ExecuteMsg::Propose {
+                msg: Empty::default(),
+            },
+        )
+    };
+    let quota = |deps: cosmwasm_std::Deps, env, address: &str| -> ProposalQuotaResponse {
+        from_json(
+            module
+                .query(
+                    deps,
+                    env,
+                    QueryMsg::ProposalQuota {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    let env = mock_env();
+    propose(deps.as_mut(), env.clone(), "a").unwrap();
+
+    // The proposer may not have another proposal open.
+    let err = propose(deps.as_mut(), env.clone(), "a").unwrap_err();
+    assert_eq!(err, PreProposeError::ProposerOpenProposalLimit { limit: 1 });
+    assert_eq!(
+        quota(deps.as_ref(), env.clone(), "a"),
+        ProposalQuotaResponse {
+            open_proposals: 1,
+            remaining_open_proposals: Some(0),
+            module_open_proposals: 1,
+            module_remaining_open_proposals: Some(1),
+            next_submission: Some(Expiration::AtHeight(env.block.height + 10)),
+        }
+    );
+    let can_propose: bool = from_json(
+        module
+            .query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::CanPropose {
+                    address: "a".to_string(),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(!can_propose);
+
+    // The module may not have more than two proposals open.
+    propose(deps.as_mut(), env.clone(), "b").unwrap();
+    let err = propose(deps.as_mut(), env.clone(), "c").unwrap_err();
+    assert_eq!(err, PreProposeError::ModuleOpenProposalLimit { limit: 2 });
+
+    // Once its proposal closes, the proposer must still wait for the
+    // submission interval to pass.
+    module
+        .remove_open_proposal(
+            &mut deps.storage,
+            &Addr::unchecked("a"),
+            OpenProposal::Created(1),
+        )
+        .unwrap();
+    let err = propose(deps.as_mut(), env.clone(), "a").unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::SubmissionCooldown {
+            available: Expiration::AtHeight(env.block.height + 10)
+        }
+    );
+
+    let mut env = env;
+    env.block.height += 10;
+    assert_eq!(
+        quota(deps.as_ref(), env.clone(), "a"),
+        ProposalQuotaResponse {
+            open_proposals: 0,
+            remaining_open_proposals: Some(1),
+            module_open_proposals: 1,
+            module_remaining_open_proposals: Some(1),
+            next_submission: None,
+        }
+    );
+    propose(deps.as_mut(), env.clone(), "a").unwrap();
+
+    // Completing a proposal created before open proposals were
+    // counted leaves the counts alone.
+    module
+        .deposits
+        .save(&mut deps.storage, 0, &(None, Addr::unchecked("b")))
+        .unwrap();
+    module
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("pm", &[]),
+            ExecuteMsg::ProposalCompletedHook {
+                proposal_id: 0,
+                new_status: Status::Closed,
+                failed_quorum: false,
+            },
+        )
+        .unwrap();
+    let res = quota(deps.as_ref(), env, "b");
+    assert_eq!(res.open_proposals, 1);
+    assert_eq!(res.module_open_proposals, 2);
+}
@@ -110,6 +110,27 @@ pub fn dao_pre_propose_single_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn dao_proposal_conviction_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_proposal_conviction::contract::execute,
+        dao_proposal_conviction::contract::instantiate,
+        dao_proposal_conviction::contract::query,
+    )
+    .with_reply(dao_proposal_conviction::contract::reply)
+    .with_migrate(dao_proposal_conviction::contract::migrate);
+    Box::new(contract)
+}
+
+pub fn dao_pre_propose_conviction_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_pre_propose_conviction::contract::execute,
+        dao_pre_propose_conviction::contract::instantiate,
+        dao_pre_propose_conviction::contract::query,
+    )
+    .with_migrate(dao_pre_propose_conviction::contract::migrate);
+    Box::new(contract)
+}
+
 pub fn dao_pre_propose_multiple_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         dao_pre_propose_multiple::contract::execute,
@@ -130,6 +151,16 @@ pub fn dao_pre_propose_approval_single_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn dao_pre_propose_sponsored_single_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_pre_propose_sponsored_single::contract::execute,
+        dao_pre_propose_sponsored_single::contract::instantiate,
+        dao_pre_propose_sponsored_single::contract::query,
+    )
+    .with_migrate(dao_pre_propose_sponsored_single::contract::migrate);
+    Box::new(contract)
+}
+
 pub fn dao_voting_cw4_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         dao_voting_cw4::contract::execute,
@@ -254,6 +285,16 @@ pub fn dao_rewards_distributor_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn dao_vote_delegation_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_vote_delegation::contract::execute,
+        dao_vote_delegation::contract::instantiate,
+        dao_vote_delegation::contract::query,
+    )
+    .with_migrate(dao_vote_delegation::contract::migrate);
+    Box::new(contract)
+}
+
 pub fn btsg_ft_factory_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         btsg_ft_factory::contract::execute,
@@ -276,6 +317,15 @@ pub fn cw_admin_factory_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn cw_payment_streams_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_payment_streams::contract::execute,
+        cw_payment_streams::contract::instantiate,
+        cw_payment_streams::contract::query,
+    );
+    Box::new(contract)
+}
+
 pub fn cw_payroll_factory_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         cw_payroll_factory::contract::execute,
@@ -324,3 +374,31 @@ pub fn dao_proposal_hook_counter_contract() -> Box<dyn Contract<Empty>> {
     );
     Box::new(contract)
 }
+
+pub fn dao_remote_note_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_remote_note::contract::execute,
+        dao_remote_note::contract::instantiate,
+        dao_remote_note::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn dao_remote_proxy_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_remote_proxy::contract::execute,
+        dao_remote_proxy::contract::instantiate,
+        dao_remote_proxy::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn dao_remote_voice_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_remote_voice::contract::execute,
+        dao_remote_voice::contract::instantiate,
+        dao_remote_voice::contract::query,
+    )
+    .with_reply(dao_remote_voice::contract::reply);
+    Box::new(contract)
+}
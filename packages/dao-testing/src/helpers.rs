@@ -151,6 +151,7 @@ pub fn instantiate_with_staked_balances_governance(
                     unstaking_duration: Some(Duration::Height(6)),
                     initial_dao_balance: None,
                 },
+                power_transform: None,
             })
             .unwrap(),
             admin: None,
@@ -272,6 +273,7 @@ pub fn instantiate_with_staking_active_threshold(
                     initial_dao_balance: None,
                 },
                 active_threshold,
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -346,6 +348,7 @@ pub fn instantiate_with_cw4_groups_governance(
                     cw4_group_code_id: cw4_id,
                     initial_members: initial_weights,
                 },
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -67,6 +67,7 @@ impl<'a> DaoTestingSuite<Cw4DaoExtra> for DaoTestingSuiteCw4<'a> {
                     cw4_group_code_id: self.base.cw4_group_id,
                     initial_members: self.members.clone(),
                 },
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(dao_interface::state::Admin::CoreModule {}),
@@ -145,6 +145,7 @@ impl<'a> DaoTestingSuite<TokenDaoExtra> for DaoTestingSuiteToken<'a> {
                 },
                 unstaking_duration: self.unstaking_duration,
                 active_threshold: self.active_threshold.clone(),
+                power_transform: None,
             })
             .unwrap(),
             admin: Some(dao_interface::state::Admin::CoreModule {}),
@@ -0,0 +1,181 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{BlockInfo, Decimal, StdError, Uint128};
+use cw_utils::{Duration, Expiration};
+use thiserror::Error;
+
+use crate::voting::{compare_vote_count, VoteCmp};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AmendmentError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("The amendment window must be non-zero.")]
+    ZeroWindow {},
+
+    #[error("The amendment window must have the same units as the max_voting_period of the proposal (height or time).")]
+    WindowUnitMismatch {},
+
+    #[error("The sponsor threshold must be greater than zero and at most one.")]
+    InvalidSponsorThreshold {},
+
+    #[error("The amendment window for this proposal has closed.")]
+    WindowClosed {},
+
+    #[error("Voting on this proposal opens once its amendment window closes.")]
+    VotingNotOpen {},
+
+    #[error("Proposals with an amendment window may not be voted on when they are created.")]
+    AutoVoteNotAllowed {},
+
+    #[error("Only the proposer or a sponsor may amend this proposal.")]
+    Unauthorized {},
+
+    #[error("An amendment must change at least one field of the proposal.")]
+    NoChanges {},
+}
+
+/// Configures a period after a proposal is created during which it
+/// may be revised and may not be voted on.
+#[cw_serde]
+pub struct AmendmentConfig {
+    /// How long after creation a proposal may be amended. Voting
+    /// opens once the window has passed, and the voting period is
+    /// measured from then.
+    pub window: Duration,
+    /// If set, holders whose voting power when a proposal was created
+    /// is at least this share of the total voting power may amend it
+    /// along with its proposer.
+    pub sponsor_threshold: Option<Decimal>,
+}
+
+impl AmendmentConfig {
+    pub fn validate(&self, max_voting_period: &Duration) -> Result<(), AmendmentError> {
+        match (self.window, max_voting_period) {
+            (Duration::Time(0), _) | (Duration::Height(0), _) => {
+                return Err(AmendmentError::ZeroWindow {})
+            }
+            (Duration::Time(_), Duration::Time(_)) => (),
+            (Duration::Height(_), Duration::Height(_)) => (),
+            _ => return Err(AmendmentError::WindowUnitMismatch {}),
+        };
+
+        if let Some(threshold) = self.sponsor_threshold {
+            if threshold.is_zero() || threshold > Decimal::one() {
+                return Err(AmendmentError::InvalidSponsorThreshold {});
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The expiration of the amendment window of a proposal created
+    /// at `block`.
+    pub fn window_expiration(&self, block: &BlockInfo) -> Expiration {
+        self.window.after(block)
+    }
+
+    /// The block at which voting opens on a proposal created at
+    /// `block`. Voting periods should be measured from this block.
+    pub fn voting_start(&self, block: &BlockInfo) -> BlockInfo {
+        let mut start = block.clone();
+        match self.window {
+            Duration::Height(height) => start.height += height,
+            Duration::Time(seconds) => start.time = start.time.plus_seconds(seconds),
+        }
+        start
+    }
+
+    /// Whether an address with `power` voting power out of
+    /// `total_power` may sponsor amendments.
+    pub fn is_sponsor(&self, power: Uint128, total_power: Uint128) -> bool {
+        match self.sponsor_threshold {
+            Some(threshold) if !power.is_zero() => {
+                compare_vote_count(power, VoteCmp::Geq, total_power, threshold)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Errors if a proposal with the given amendment window may not be
+/// voted on yet.
+pub fn check_voting_open(
+    amendable_until: &Option<Expiration>,
+    block: &BlockInfo,
+) -> Result<(), AmendmentError> {
+    match amendable_until {
+        Some(until) if !until.is_expired(block) => Err(AmendmentError::VotingNotOpen {}),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let config = AmendmentConfig {
+            window: Duration::Time(100),
+            sponsor_threshold: Some(Decimal::percent(10)),
+        };
+        config.validate(&Duration::Time(1000)).unwrap();
+        assert_eq!(
+            config.validate(&Duration::Height(1000)),
+            Err(AmendmentError::WindowUnitMismatch {})
+        );
+
+        let config = AmendmentConfig {
+            window: Duration::Height(0),
+            sponsor_threshold: None,
+        };
+        assert_eq!(
+            config.validate(&Duration::Height(1000)),
+            Err(AmendmentError::ZeroWindow {})
+        );
+
+        let config = AmendmentConfig {
+            window: Duration::Height(10),
+            sponsor_threshold: Some(Decimal::percent(101)),
+        };
+        assert_eq!(
+            config.validate(&Duration::Height(1000)),
+            Err(AmendmentError::InvalidSponsorThreshold {})
+        );
+    }
+
+    #[test]
+    fn test_voting_start() {
+        let block = mock_env().block;
+        let config = AmendmentConfig {
+            window: Duration::Height(10),
+            sponsor_threshold: None,
+        };
+        let start = config.voting_start(&block);
+        assert_eq!(start.height, block.height + 10);
+        assert_eq!(start.time, block.time);
+        assert!(check_voting_open(&Some(config.window_expiration(&block)), &block).is_err());
+        assert!(check_voting_open(&Some(config.window_expiration(&block)), &start).is_ok());
+        assert!(check_voting_open(&None, &block).is_ok());
+    }
+
+    #[test]
+    fn test_is_sponsor() {
+        let config = AmendmentConfig {
+            window: Duration::Height(10),
+            sponsor_threshold: Some(Decimal::percent(10)),
+        };
+        assert!(config.is_sponsor(Uint128::new(10), Uint128::new(100)));
+        assert!(!config.is_sponsor(Uint128::new(9), Uint128::new(100)));
+        assert!(!config.is_sponsor(Uint128::zero(), Uint128::new(100)));
+
+        let config = AmendmentConfig {
+            window: Duration::Height(10),
+            sponsor_threshold: None,
+        };
+        assert!(!config.is_sponsor(Uint128::new(100), Uint128::new(100)));
+    }
+}
@@ -1,8 +1,31 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Api, StdError};
+use cw_utils::{Duration, Expiration};
+use thiserror::Error;
 
 use crate::deposit::CheckedDepositInfo;
 
+#[derive(Error, Debug, PartialEq)]
+pub enum ApprovalError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error("approver set threshold must be between one and the number of members")]
+    InvalidThreshold {},
+
+    #[error("({approver}) appears in the approver set more than once")]
+    DuplicateApprover { approver: String },
+
+    #[error("({approver}) has already approved or rejected this proposal")]
+    AlreadyDecided { approver: Addr },
+
+    #[error("proposal has expired")]
+    Expired {},
+
+    #[error("proposal has not expired")]
+    NotExpired {},
+}
+
 #[cw_serde]
 pub enum ApproverProposeMessage {
     Propose {
@@ -14,12 +37,75 @@ pub enum ApproverProposeMessage {
 
 #[cw_serde]
 pub enum ApprovalExecuteExt {
-    /// Approve a proposal, only callable by approver
+    /// Approve a proposal, only callable by the approver or a member
+    /// of the proposal's approver set
     Approve { id: u64 },
-    /// Reject a proposal, only callable by approver
+    /// Reject a proposal, only callable by the approver or a member
+    /// of the proposal's approver set
     Reject { id: u64 },
     /// Updates the approver, can only be called the current approver
     UpdateApprover { address: String },
+    /// Updates the approver set and how long proposals may remain
+    /// pending. Proposals that are already pending keep the set and
+    /// expiration they were submitted with. Only callable by the
+    /// approver or the DAO.
+    UpdateApprovalConfig {
+        approver_set: Option<ApproverSet>,
+        pending_duration: Option<Duration>,
+    },
+    /// Completes a pending proposal that has expired, handling its
+    /// deposit as if it had been rejected. Callable by anyone.
+    Expire { id: u64 },
+}
+
+/// A committee that may approve or reject proposals together, in
+/// addition to the approver, who may still decide alone.
+#[cw_serde]
+pub struct ApproverSet {
+    /// The members of the committee.
+    pub members: Vec<String>,
+    /// The number of members that must approve a proposal for it to
+    /// be approved. A proposal is rejected once enough members reject
+    /// it that this can no longer happen.
+    pub threshold: u32,
+}
+
+/// Counterpart to `ApproverSet` with validated addresses. Built by
+/// calling `into_checked` on an `ApproverSet`.
+#[cw_serde]
+pub struct CheckedApproverSet {
+    pub members: Vec<Addr>,
+    pub threshold: u32,
+}
+
+impl ApproverSet {
+    pub fn into_checked(self, api: &dyn Api) -> Result<CheckedApproverSet, ApprovalError> {
+        if self.threshold == 0 || self.threshold as usize > self.members.len() {
+            return Err(ApprovalError::InvalidThreshold {});
+        }
+        let mut members: Vec<Addr> = Vec::with_capacity(self.members.len());
+        for member in self.members {
+            let member = api.addr_validate(&member)?;
+            if members.contains(&member) {
+                return Err(ApprovalError::DuplicateApprover {
+                    approver: member.into_string(),
+                });
+            }
+            members.push(member);
+        }
+        Ok(CheckedApproverSet {
+            members,
+            threshold: self.threshold,
+        })
+    }
+}
+
+/// A decision made on a proposal by the approver or a member of its
+/// approver set.
+#[cw_serde]
+pub struct ApprovalDecision {
+    pub approver: Addr,
+    pub approved: bool,
 }
 
 #[cw_serde]
@@ -33,6 +119,8 @@ pub enum ApprovalProposalStatus {
     },
     /// The proposal has been rejected.
     Rejected {},
+    /// The proposal was not approved or rejected before it expired.
+    Expired {},
 }
 
 #[cw_serde]
@@ -51,4 +139,59 @@ pub struct ApprovalProposal<ProposeMsg> {
     /// Snapshot of the deposit info at the time of proposal
     /// submission.
     pub deposit: Option<CheckedDepositInfo>,
+    /// Snapshot of the approver set at the time of proposal
+    /// submission.
+    #[serde(default)]
+    pub approver_set: Option<CheckedApproverSet>,
+    /// The decisions made on this proposal so far.
+    #[serde(default)]
+    pub decisions: Vec<ApprovalDecision>,
+    /// When this proposal expires if it is still pending.
+    #[serde(default)]
+    pub expiration: Expiration,
+}
+
+impl<ProposeMsg> ApprovalProposal<ProposeMsg> {
+    /// Whether `addr` may approve or reject this proposal.
+    pub fn is_approver(&self, addr: &Addr) -> bool {
+        self.approver == *addr
+            || self
+                .approver_set
+                .as_ref()
+                .map_or(false, |set| set.members.contains(addr))
+    }
+
+    /// Records a decision by `approver`, returning whether it decides
+    /// the proposal. The approver decides alone, while members of the
+    /// approver set decide once enough of them agree.
+    pub fn decide(&mut self, approver: &Addr, approved: bool) -> Result<bool, ApprovalError> {
+        if self.decisions.iter().any(|d| d.approver == *approver) {
+            return Err(ApprovalError::AlreadyDecided {
+                approver: approver.clone(),
+            });
+        }
+        self.decisions.push(ApprovalDecision {
+            approver: approver.clone(),
+            approved,
+        });
+
+        if self.approver == *approver {
+            return Ok(true);
+        }
+        Ok(match &self.approver_set {
+            Some(set) => {
+                let count = self
+                    .decisions
+                    .iter()
+                    .filter(|d| d.approved == approved && set.members.contains(&d.approver))
+                    .count() as u32;
+                if approved {
+                    count >= set.threshold
+                } else {
+                    count > set.members.len() as u32 - set.threshold
+                }
+            }
+            None => false,
+        })
+    }
 }
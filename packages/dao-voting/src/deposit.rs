@@ -1,14 +1,17 @@
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    to_json_binary, Addr, CosmosMsg, Deps, MessageInfo, StdError, StdResult, Uint128, WasmMsg,
+    coins, to_json_binary, Addr, BankMsg, CosmosMsg, Decimal, Deps, MessageInfo, StdError,
+    StdResult, Uint128, WasmMsg,
 };
 use cw_utils::{must_pay, PaymentError};
 
-use dao_interface::voting::DenomResponse;
+use dao_interface::voting::{DenomResponse, VotingPowerAtHeightResponse};
 use thiserror::Error;
 
 use cw_denom::{CheckedDenom, DenomError, UncheckedDenom};
 
+use crate::status::Status;
+
 /// Error type for deposit methods.
 #[derive(Error, Debug, PartialEq)]
 pub enum DepositError {
@@ -26,6 +29,15 @@ pub enum DepositError {
 
     #[error("invalid deposit amount. got ({actual}), expected ({expected})")]
     InvalidDeposit { actual: Uint128, expected: Uint128 },
+
+    #[error("invalid refund percentage ({percentage}). must be at most 100%")]
+    InvalidRefundPercentage { percentage: Decimal },
+
+    #[error("invalid zero voting power percentage for deposit scaling")]
+    ZeroVotingPowerPercentage,
+
+    #[error("price source returned a zero price for ({denom})")]
+    ZeroPrice { denom: String },
 }
 
 // The voting module token type to expect.
@@ -61,6 +73,61 @@ pub struct UncheckedDepositInfo {
     pub amount: Uint128,
     /// The policy used for refunding deposits on proposal completion.
     pub refund_policy: DepositRefundPolicy,
+    /// How the deposit scales with the circumstances of a proposal.
+    /// If `None`, the deposit is always `amount`.
+    #[serde(default)]
+    pub scaling: Option<DepositScaling>,
+}
+
+/// Ways a deposit may change from its fixed `amount` when a proposal
+/// is created. The deposit is resolved to a fixed amount when a
+/// proposal is created, and that amount is what is refunded.
+#[cw_serde]
+pub enum DepositScaling {
+    /// The deposit is `amount` plus `increment` for each proposal the
+    /// proposer already has open, making it costly to flood the DAO
+    /// with proposals.
+    OpenProposals { increment: Uint128 },
+    /// The deposit is `percentage` of the proposer's voting power, but
+    /// at least `amount`.
+    VotingPower { percentage: Decimal },
+    /// `amount` is denominated in a reference value, for example US
+    /// cents, rather than the deposit token. The deposit is the amount
+    /// of the deposit token worth `amount`, according to the price
+    /// source contract, which must implement `PriceSourceQuery`.
+    ReferenceValue { price_source: String },
+}
+
+/// The query a price source used for `DepositScaling::ReferenceValue`
+/// must implement.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum PriceSourceQuery {
+    /// Returns the value of one unit of `denom`, a native denom or
+    /// cw20 contract address, in the reference value.
+    #[returns(PriceResponse)]
+    Price { denom: String },
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub price: Decimal,
+}
+
+impl DepositScaling {
+    /// Validates the scaling, normalizing the price source address if
+    /// there is one.
+    pub fn into_checked(self, deps: Deps) -> Result<Self, DepositError> {
+        match self {
+            DepositScaling::VotingPower { percentage } if percentage.is_zero() => {
+                Err(DepositError::ZeroVotingPowerPercentage)
+            }
+            DepositScaling::ReferenceValue { price_source } => Ok(DepositScaling::ReferenceValue {
+                price_source: deps.api.addr_validate(&price_source)?.into_string(),
+            }),
+            scaling => Ok(scaling),
+        }
+    }
 }
 
 #[cw_serde]
@@ -71,6 +138,105 @@ pub enum DepositRefundPolicy {
     OnlyPassed,
     /// Deposits should never be refunded.
     Never,
+    /// Deposits are refunded in full for executed proposals. For
+    /// other outcomes only the given percentage of the deposit is
+    /// refunded and the rest is slashed. This allows spam proposals
+    /// to cost something without punishing honest proposals that fail
+    /// a vote.
+    ByOutcome {
+        /// The percentage refunded for proposals that were rejected
+        /// after reaching quorum.
+        rejected: Decimal,
+        /// The percentage refunded for proposals that were rejected
+        /// because they did not reach quorum.
+        failed_quorum: Decimal,
+        /// The percentage refunded for proposals that were vetoed.
+        vetoed: Decimal,
+        /// The percentage refunded for proposals that passed but
+        /// whose execution failed.
+        execution_failed: Decimal,
+        /// Where slashed deposits are sent.
+        slash_destination: SlashDestination,
+    },
+}
+
+/// Where the part of a deposit that is not refunded is sent.
+#[cw_serde]
+pub enum SlashDestination {
+    /// Slashed deposits are sent to the DAO's treasury.
+    Dao,
+    /// Slashed deposits are burned.
+    Burn,
+    /// Slashed deposits are sent to an address, for example a
+    /// community pool.
+    Address { address: String },
+}
+
+impl DepositRefundPolicy {
+    /// Validates the policy, normalizing the slash destination
+    /// address if there is one.
+    pub fn into_checked(self, deps: Deps) -> Result<Self, DepositError> {
+        match self {
+            DepositRefundPolicy::ByOutcome {
+                rejected,
+                failed_quorum,
+                vetoed,
+                execution_failed,
+                slash_destination,
+            } => {
+                for percentage in [rejected, failed_quorum, vetoed, execution_failed] {
+                    if percentage > Decimal::one() {
+                        return Err(DepositError::InvalidRefundPercentage { percentage });
+                    }
+                }
+                let slash_destination = match slash_destination {
+                    SlashDestination::Address { address } => SlashDestination::Address {
+                        address: deps.api.addr_validate(&address)?.into_string(),
+                    },
+                    destination => destination,
+                };
+                Ok(DepositRefundPolicy::ByOutcome {
+                    rejected,
+                    failed_quorum,
+                    vetoed,
+                    execution_failed,
+                    slash_destination,
+                })
+            }
+            policy => Ok(policy),
+        }
+    }
+
+    /// The percentage of a deposit refunded to the proposer of a
+    /// proposal that completed with `status`. `failed_quorum` is true
+    /// if the proposal was closed because it did not reach quorum.
+    pub fn refund_percentage(&self, status: Status, failed_quorum: bool) -> Decimal {
+        match (self, status) {
+            (DepositRefundPolicy::Always, _) => Decimal::one(),
+            (DepositRefundPolicy::Never, _) => Decimal::zero(),
+            // A proposal whose execution failed still passed.
+            (DepositRefundPolicy::OnlyPassed, Status::Executed | Status::ExecutionFailed) => {
+                Decimal::one()
+            }
+            (DepositRefundPolicy::OnlyPassed, _) => Decimal::zero(),
+            (DepositRefundPolicy::ByOutcome { .. }, Status::Executed) => Decimal::one(),
+            (
+                DepositRefundPolicy::ByOutcome {
+                    execution_failed, ..
+                },
+                Status::ExecutionFailed,
+            ) => *execution_failed,
+            (DepositRefundPolicy::ByOutcome { vetoed, .. }, Status::Vetoed) => *vetoed,
+            (
+                DepositRefundPolicy::ByOutcome {
+                    failed_quorum: percentage,
+                    ..
+                },
+                _,
+            ) if failed_quorum => *percentage,
+            (DepositRefundPolicy::ByOutcome { rejected, .. }, _) => *rejected,
+        }
+    }
 }
 
 /// Counterpart to the `DepositInfo` struct which has been
@@ -89,6 +255,11 @@ pub struct CheckedDepositInfo {
     pub amount: Uint128,
     /// The policy used for refunding proposal deposits.
     pub refund_policy: DepositRefundPolicy,
+    /// How the deposit scales with the circumstances of a
+    /// proposal. Deposits snapshotted for a proposal are always
+    /// resolved and have no scaling.
+    #[serde(default)]
+    pub scaling: Option<DepositScaling>,
 }
 
 impl UncheckedDepositInfo {
@@ -98,6 +269,7 @@ impl UncheckedDepositInfo {
             denom,
             amount,
             refund_policy,
+            scaling,
         } = self;
         // Check that the deposit is non-zero. Modules should make
         // deposit information optional and consumers should provide
@@ -105,6 +277,8 @@ impl UncheckedDepositInfo {
         if amount.is_zero() {
             return Err(DepositError::ZeroDeposit);
         }
+        let refund_policy = refund_policy.into_checked(deps)?;
+        let scaling = scaling.map(|s| s.into_checked(deps)).transpose()?;
 
         let denom = match denom {
             DepositToken::Token { denom } => denom.into_checked(deps),
@@ -147,11 +321,68 @@ impl UncheckedDepositInfo {
             denom,
             amount,
             refund_policy,
+            scaling,
         })
     }
 }
 
 impl CheckedDepositInfo {
+    /// Resolves the deposit `proposer` must pay to create a proposal
+    /// in `dao` now, given the number of proposals they already have
+    /// open. The returned deposit has a fixed amount and no scaling.
+    pub fn resolve(
+        &self,
+        deps: Deps,
+        dao: &Addr,
+        proposer: &Addr,
+        open_proposals: u64,
+    ) -> Result<CheckedDepositInfo, DepositError> {
+        let amount = match self.scaling {
+            None => self.amount,
+            Some(DepositScaling::OpenProposals { increment }) => increment
+                .checked_mul(Uint128::from(open_proposals))
+                .and_then(|extra| self.amount.checked_add(extra))
+                .map_err(StdError::from)?,
+            Some(DepositScaling::VotingPower { percentage }) => {
+                let power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+                    dao,
+                    &dao_interface::voting::Query::VotingPowerAtHeight {
+                        address: proposer.to_string(),
+                        height: None,
+                    },
+                )?;
+                power.power.mul_ceil(percentage).max(self.amount)
+            }
+            Some(DepositScaling::ReferenceValue { ref price_source }) => {
+                let denom = match &self.denom {
+                    CheckedDenom::Native(denom) => denom.clone(),
+                    CheckedDenom::Cw20(address) => address.to_string(),
+                };
+                let price: PriceResponse = deps.querier.query_wasm_smart(
+                    price_source,
+                    &PriceSourceQuery::Price {
+                        denom: denom.clone(),
+                    },
+                )?;
+                if price.price.is_zero() {
+                    return Err(DepositError::ZeroPrice { denom });
+                }
+                // Round up so that the deposit is worth at least
+                // `amount`.
+                self.amount
+                    .checked_div_ceil(price.price)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?
+            }
+        };
+
+        Ok(CheckedDepositInfo {
+            denom: self.denom.clone(),
+            amount,
+            refund_policy: self.refund_policy.clone(),
+            scaling: None,
+        })
+    }
+
     pub fn check_native_deposit_paid(&self, info: &MessageInfo) -> Result<(), DepositError> {
         if let Self {
             amount,
@@ -221,11 +452,66 @@ impl CheckedDepositInfo {
         let message = self.denom.get_transfer_to_message(depositor, self.amount)?;
         Ok(vec![message])
     }
+
+    /// Gets the messages that settle the deposit of a proposal that
+    /// completed with `status`: the refund to `depositor` according
+    /// to the refund policy, and the slashed remainder to the policy's
+    /// slash destination. Slashed deposits go to `dao` unless the
+    /// policy says otherwise.
+    pub fn get_completed_deposit_messages(
+        &self,
+        depositor: &Addr,
+        dao: &Addr,
+        status: Status,
+        failed_quorum: bool,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let refund = self
+            .amount
+            .mul_floor(self.refund_policy.refund_percentage(status, failed_quorum));
+        let slashed = self.amount - refund;
+
+        let mut messages = vec![];
+        if !refund.is_zero() {
+            messages.push(self.denom.get_transfer_to_message(depositor, refund)?);
+        }
+        if !slashed.is_zero() {
+            let destination = match self.refund_policy {
+                DepositRefundPolicy::ByOutcome {
+                    ref slash_destination,
+                    ..
+                } => slash_destination,
+                _ => &SlashDestination::Dao,
+            };
+            messages.push(match destination {
+                SlashDestination::Dao => self.denom.get_transfer_to_message(dao, slashed)?,
+                SlashDestination::Address { address } => self
+                    .denom
+                    .get_transfer_to_message(&Addr::unchecked(address), slashed)?,
+                SlashDestination::Burn => match &self.denom {
+                    CheckedDenom::Native(denom) => BankMsg::Burn {
+                        amount: coins(slashed.u128(), denom),
+                    }
+                    .into(),
+                    CheckedDenom::Cw20(address) => WasmMsg::Execute {
+                        contract_addr: address.to_string(),
+                        msg: to_json_binary(&cw20::Cw20ExecuteMsg::Burn { amount: slashed })?,
+                        funds: vec![],
+                    }
+                    .into(),
+                },
+            });
+        }
+        Ok(messages)
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use cosmwasm_std::{coin, coins, testing::mock_info, BankMsg};
+    use cosmwasm_std::{
+        coin,
+        testing::{mock_dependencies, mock_info},
+        ContractResult, SystemResult,
+    };
 
     use super::*;
 
@@ -239,6 +525,7 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         };
         deposit_info.check_native_deposit_paid(&info).unwrap();
 
@@ -261,6 +548,7 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         };
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
         assert_eq!(
@@ -279,6 +567,7 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         };
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
         assert_eq!(
@@ -299,6 +588,7 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         };
 
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
@@ -312,6 +602,7 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         };
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
         assert_eq!(err, DepositError::Payment(PaymentError::NoFunds {}));
@@ -324,6 +615,7 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         };
         let messages = deposit_info
             .get_take_deposit_messages(&Addr::unchecked("ekez"), &Addr::unchecked(CW20))
@@ -364,6 +656,7 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         };
         let messages = deposit_info
             .get_return_deposit_message(&Addr::unchecked("ekez"))
@@ -384,12 +677,271 @@ pub mod tests {
         assert_eq!(messages, vec![]);
     }
 
+    #[test]
+    fn test_refund_percentage() {
+        let policy = DepositRefundPolicy::ByOutcome {
+            rejected: Decimal::percent(50),
+            failed_quorum: Decimal::percent(25),
+            vetoed: Decimal::zero(),
+            execution_failed: Decimal::percent(75),
+            slash_destination: SlashDestination::Dao,
+        };
+        assert_eq!(
+            policy.refund_percentage(Status::Executed, false),
+            Decimal::one()
+        );
+        assert_eq!(
+            policy.refund_percentage(Status::ExecutionFailed, false),
+            Decimal::percent(75)
+        );
+        assert_eq!(
+            policy.refund_percentage(Status::Vetoed, false),
+            Decimal::zero()
+        );
+        assert_eq!(
+            policy.refund_percentage(Status::Closed, false),
+            Decimal::percent(50)
+        );
+        assert_eq!(
+            policy.refund_percentage(Status::Closed, true),
+            Decimal::percent(25)
+        );
+
+        // Proposals whose execution failed still passed.
+        assert_eq!(
+            DepositRefundPolicy::OnlyPassed.refund_percentage(Status::ExecutionFailed, false),
+            Decimal::one()
+        );
+        assert_eq!(
+            DepositRefundPolicy::OnlyPassed.refund_percentage(Status::Closed, true),
+            Decimal::zero()
+        );
+    }
+
+    #[test]
+    fn test_get_completed_deposit_messages() {
+        let dao = Addr::unchecked("dao");
+        let ekez = Addr::unchecked("ekez");
+        let mut deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            scaling: None,
+        };
+
+        // Existing policies slash to the DAO.
+        let messages = deposit_info
+            .get_completed_deposit_messages(&ekez, &dao, Status::Closed, false)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao".to_string(),
+                amount: coins(10, NATIVE_DENOM)
+            })]
+        );
+
+        deposit_info.refund_policy = DepositRefundPolicy::ByOutcome {
+            rejected: Decimal::percent(33),
+            failed_quorum: Decimal::zero(),
+            vetoed: Decimal::zero(),
+            execution_failed: Decimal::one(),
+            slash_destination: SlashDestination::Burn,
+        };
+        let messages = deposit_info
+            .get_completed_deposit_messages(&ekez, &dao, Status::Closed, false)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "ekez".to_string(),
+                    amount: coins(3, NATIVE_DENOM)
+                }),
+                CosmosMsg::Bank(BankMsg::Burn {
+                    amount: coins(7, NATIVE_DENOM)
+                })
+            ]
+        );
+
+        // Nothing is slashed if the deposit is refunded in full.
+        deposit_info.denom = CheckedDenom::Cw20(Addr::unchecked(CW20));
+        let messages = deposit_info
+            .get_completed_deposit_messages(&ekez, &dao, Status::ExecutionFailed, false)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW20.to_string(),
+                msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                    recipient: "ekez".to_string(),
+                    amount: Uint128::new(10)
+                })
+                .unwrap(),
+                funds: vec![]
+            })]
+        );
+
+        // Nothing is refunded if the proposal failed quorum.
+        let messages = deposit_info
+            .get_completed_deposit_messages(&ekez, &dao, Status::Closed, true)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW20.to_string(),
+                msg: to_json_binary(&cw20::Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(10)
+                })
+                .unwrap(),
+                funds: vec![]
+            })]
+        );
+    }
+
+    #[test]
+    fn test_resolve_open_proposals() {
+        let deps = mock_dependencies();
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+            scaling: Some(DepositScaling::OpenProposals {
+                increment: Uint128::new(5),
+            }),
+        };
+        let resolved = deposit_info
+            .resolve(
+                deps.as_ref(),
+                &Addr::unchecked("dao"),
+                &Addr::unchecked("ekez"),
+                2,
+            )
+            .unwrap();
+        assert_eq!(
+            resolved,
+            CheckedDepositInfo {
+                denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+                amount: Uint128::new(20),
+                refund_policy: DepositRefundPolicy::Always,
+                scaling: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_voting_power() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&VotingPowerAtHeightResponse {
+                    power: Uint128::new(1000),
+                    height: 1,
+                })
+                .unwrap(),
+            ))
+        });
+        let mut deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(5),
+            refund_policy: DepositRefundPolicy::Always,
+            scaling: Some(DepositScaling::VotingPower {
+                percentage: Decimal::percent(1),
+            }),
+        };
+        let dao = Addr::unchecked("dao");
+        let ekez = Addr::unchecked("ekez");
+        let resolved = deposit_info.resolve(deps.as_ref(), &dao, &ekez, 0).unwrap();
+        assert_eq!(resolved.amount, Uint128::new(10));
+
+        // The deposit is never less than `amount`.
+        deposit_info.amount = Uint128::new(50);
+        let resolved = deposit_info.resolve(deps.as_ref(), &dao, &ekez, 0).unwrap();
+        assert_eq!(resolved.amount, Uint128::new(50));
+    }
+
+    #[test]
+    fn test_resolve_reference_value() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&PriceResponse {
+                    price: Decimal::percent(300),
+                })
+                .unwrap(),
+            ))
+        });
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Cw20(Addr::unchecked(CW20)),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+            scaling: Some(DepositScaling::ReferenceValue {
+                price_source: "oracle".to_string(),
+            }),
+        };
+        // 10 / 3 rounded up.
+        let resolved = deposit_info
+            .resolve(
+                deps.as_ref(),
+                &Addr::unchecked("dao"),
+                &Addr::unchecked("ekez"),
+                0,
+            )
+            .unwrap();
+        assert_eq!(resolved.amount, Uint128::new(4));
+        assert_eq!(resolved.scaling, None);
+
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&PriceResponse {
+                    price: Decimal::zero(),
+                })
+                .unwrap(),
+            ))
+        });
+        let err = deposit_info
+            .resolve(
+                deps.as_ref(),
+                &Addr::unchecked("dao"),
+                &Addr::unchecked("ekez"),
+                0,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DepositError::ZeroPrice {
+                denom: CW20.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_refund_percentage() {
+        let deps = mock_dependencies();
+        let err = DepositRefundPolicy::ByOutcome {
+            rejected: Decimal::percent(101),
+            failed_quorum: Decimal::zero(),
+            vetoed: Decimal::zero(),
+            execution_failed: Decimal::one(),
+            slash_destination: SlashDestination::Dao,
+        }
+        .into_checked(deps.as_ref())
+        .unwrap_err();
+        assert_eq!(
+            err,
+            DepositError::InvalidRefundPercentage {
+                percentage: Decimal::percent(101)
+            }
+        );
+    }
+
     #[test]
     fn test_get_return_deposit_message_cw20() {
         let mut deposit_info = CheckedDepositInfo {
             denom: CheckedDenom::Cw20(Addr::unchecked(CW20)),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            scaling: None,
         };
         let messages = deposit_info
             .get_return_deposit_message(&Addr::unchecked("ekez"))
@@ -0,0 +1,53 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{CosmosMsg, Empty, Timestamp};
+
+/// The maximum number of batches in a proposal's execution plan.
+pub const MAX_EXECUTION_BATCHES: usize = 64;
+
+/// What to do when a batch of an execution plan fails to execute.
+#[cw_serde]
+#[derive(Copy)]
+pub enum BatchFailurePolicy {
+    /// Stop executing the plan. The proposal's status is set to
+    /// `ExecutionFailed` and later batches are never executed.
+    Abort,
+    /// Mark the batch as failed and continue with the next batch.
+    Skip,
+    /// Leave the batch pending and stop. Executing the proposal again
+    /// retries the batch.
+    Retry,
+}
+
+/// A group of messages executed together as one step of a
+/// proposal's execution plan.
+#[cw_serde]
+pub struct ExecutionBatch {
+    /// The messages executed by the DAO when this batch is executed.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// If set, the batch may not be executed before this time.
+    pub not_before: Option<Timestamp>,
+    /// What to do if executing the batch fails.
+    pub on_failure: BatchFailurePolicy,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum BatchStatus {
+    /// The batch has not been executed yet.
+    Pending,
+    /// The batch has been executed.
+    Executed,
+    /// The batch failed to execute and was skipped or aborted the
+    /// plan.
+    Failed,
+}
+
+impl std::fmt::Display for BatchStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BatchStatus::Pending => write!(f, "pending"),
+            BatchStatus::Executed => write!(f, "executed"),
+            BatchStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
@@ -1,10 +1,13 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
+pub mod amendment;
 pub mod approval;
 pub mod deposit;
 pub mod duration;
 pub mod error;
+pub mod execution_plan;
 pub mod multiple_choice;
+pub mod power_transform;
 pub mod pre_propose;
 pub mod proposal;
 pub mod reply;
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{CosmosMsg, Empty, StdError, StdResult, Uint128};
+use cosmwasm_std::{Binary, CosmosMsg, Decimal, Empty, StdError, StdResult, Uint128};
 
 use crate::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
 
@@ -11,19 +11,39 @@ const NONE_OPTION_DESCRIPTION: &str = "None of the above";
 /// Determines how many choices may be selected.
 #[cw_serde]
 pub enum VotingStrategy {
-    SingleChoice { quorum: PercentageThreshold },
+    SingleChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Voters rank the options in order of preference and the winner
+    /// is resolved by instant-runoff. Quorum is measured against the
+    /// voting power of all ballots cast.
+    RankedChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Voters may select up to `max_selections` options. Each
+    /// selected option receives the voter's full voting power unless
+    /// the ballot splits it between options by weight. Quorum is
+    /// measured against the voting power of all ballots cast.
+    Approval {
+        quorum: PercentageThreshold,
+        max_selections: u32,
+    },
 }
 
 impl VotingStrategy {
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
             VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum),
+            VotingStrategy::RankedChoice { quorum } => validate_quorum(quorum),
+            VotingStrategy::Approval { quorum, .. } => validate_quorum(quorum),
         }
     }
 
     pub fn get_quorum(&self) -> PercentageThreshold {
         match self {
             VotingStrategy::SingleChoice { quorum } => *quorum,
+            VotingStrategy::RankedChoice { quorum } => *quorum,
+            VotingStrategy::Approval { quorum, .. } => *quorum,
         }
     }
 }
@@ -42,6 +62,27 @@ impl std::fmt::Display for MultipleChoiceVote {
     }
 }
 
+/// An option selected on a multi-select ballot.
+#[cw_serde]
+#[derive(Copy)]
+pub struct MultipleChoiceSelection {
+    pub option_id: u32,
+    /// The share of the voter's voting power given to this option.
+    /// Either every selection on a ballot has a weight, in which case
+    /// the weights must sum to one, or none do, in which case each
+    /// selected option receives the voter's full voting power.
+    pub weight: Option<Decimal>,
+}
+
+impl std::fmt::Display for MultipleChoiceSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.weight {
+            Some(weight) => write!(f, "{}:{}", self.option_id, weight),
+            None => write!(f, "{}", self.option_id),
+        }
+    }
+}
+
 // Holds the vote weights for each option
 #[cw_serde]
 pub struct MultipleChoiceVotes {
@@ -72,6 +113,92 @@ impl MultipleChoiceVotes {
         Ok(())
     }
 
+    /// Validates that a multi-select ballot is non-empty, only
+    /// contains valid option IDs, selects each option at most once,
+    /// and either weights every selection with weights summing to one
+    /// or weights none of them.
+    pub fn validate_selections(
+        selections: &[MultipleChoiceSelection],
+        num_choices: usize,
+    ) -> StdResult<()> {
+        if selections.is_empty() {
+            return Err(StdError::generic_err("Selections must not be empty"));
+        }
+        for (i, selection) in selections.iter().enumerate() {
+            if selection.option_id as usize >= num_choices {
+                return Err(StdError::generic_err("Invalid option in selections"));
+            }
+            if selections[..i]
+                .iter()
+                .any(|s| s.option_id == selection.option_id)
+            {
+                return Err(StdError::generic_err("Duplicate option in selections"));
+            }
+        }
+        let weights: Vec<Decimal> = selections.iter().filter_map(|s| s.weight).collect();
+        if !weights.is_empty() {
+            if weights.len() != selections.len() {
+                return Err(StdError::generic_err(
+                    "Either all or no selections must be weighted",
+                ));
+            }
+            if weights.iter().any(|w| w.is_zero()) {
+                return Err(StdError::generic_err("Selection weights must be non-zero"));
+            }
+            let total = weights
+                .into_iter()
+                .try_fold(Decimal::zero(), |total, w| total.checked_add(w))
+                .map_err(StdError::overflow)?;
+            if total != Decimal::one() {
+                return Err(StdError::generic_err("Selection weights must sum to one"));
+            }
+        }
+        Ok(())
+    }
+
+    /// The voting power a multi-select ballot gives each selected
+    /// option.
+    fn selection_weights(
+        selections: &[MultipleChoiceSelection],
+        power: Uint128,
+    ) -> impl Iterator<Item = (usize, Uint128)> + '_ {
+        selections.iter().map(move |selection| {
+            let weight = match selection.weight {
+                Some(weight) => power.mul_floor(weight),
+                None => power,
+            };
+            (selection.option_id as usize, weight)
+        })
+    }
+
+    // Add a multi-select vote to the tally
+    pub fn add_selections(
+        &mut self,
+        selections: &[MultipleChoiceSelection],
+        power: Uint128,
+    ) -> StdResult<()> {
+        for (option, weight) in Self::selection_weights(selections, power) {
+            self.vote_weights[option] = self.vote_weights[option]
+                .checked_add(weight)
+                .map_err(StdError::overflow)?;
+        }
+        Ok(())
+    }
+
+    // Remove a multi-select vote from the tally
+    pub fn remove_selections(
+        &mut self,
+        selections: &[MultipleChoiceSelection],
+        power: Uint128,
+    ) -> StdResult<()> {
+        for (option, weight) in Self::selection_weights(selections, power) {
+            self.vote_weights[option] = self.vote_weights[option]
+                .checked_sub(weight)
+                .map_err(StdError::overflow)?;
+        }
+        Ok(())
+    }
+
     // Default tally of zero for all multiple choice options
     pub fn zero(num_choices: usize) -> Self {
         Self {
@@ -80,6 +207,123 @@ impl MultipleChoiceVotes {
     }
 }
 
+/// The result of an instant-runoff.
+#[cw_serde]
+pub enum RunoffOutcome {
+    /// The option with this ID won.
+    Winner(u32),
+    /// The remaining options are tied.
+    Tie,
+}
+
+/// The progress of the instant-runoff count of a ranked choice
+/// proposal. Ballots are not held here: the proposal module stores
+/// the voting power behind each distinct ranking on its own and
+/// counts it a page at a time, so neither voting nor the runoff ever
+/// loads every ranking at once.
+#[cw_serde]
+#[derive(Default)]
+pub struct RankedChoiceVotes {
+    /// Options eliminated in earlier rounds.
+    pub eliminated: Vec<u32>,
+    /// Voting power counted toward each option in the current
+    /// round. Empty until the round's first ranking is counted.
+    pub round_tally: Vec<Uint128>,
+    /// The key of the last ranking counted in the current round.
+    pub cursor: Option<Binary>,
+    /// Set once the runoff has completed.
+    pub outcome: Option<RunoffOutcome>,
+}
+
+impl RankedChoiceVotes {
+    /// Validates that a ranking is non-empty, only contains valid
+    /// option IDs, and ranks each option at most once.
+    pub fn validate_ranking(ranking: &[u32], num_choices: usize) -> StdResult<()> {
+        if ranking.is_empty() {
+            return Err(StdError::generic_err("Ranking must not be empty"));
+        }
+        for (i, option_id) in ranking.iter().enumerate() {
+            if *option_id as usize >= num_choices {
+                return Err(StdError::generic_err("Invalid option in ranking"));
+            }
+            if ranking[..i].contains(option_id) {
+                return Err(StdError::generic_err("Duplicate option in ranking"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes a valid ranking as a storage key. Option IDs are at
+    /// most `MAX_NUM_CHOICES`, so each fits in a single byte.
+    pub fn ranking_key(ranking: &[u32]) -> Vec<u8> {
+        ranking.iter().map(|option_id| *option_id as u8).collect()
+    }
+
+    /// Decodes a ranking from its storage key.
+    pub fn ranking_from_key(key: &[u8]) -> Vec<u32> {
+        key.iter().map(|option_id| *option_id as u32).collect()
+    }
+
+    /// Counts ballots cast with `ranking` in the current round,
+    /// toward their highest ranked option that has not been
+    /// eliminated. Ballots ranking only eliminated options are
+    /// exhausted and not counted.
+    pub fn count(&mut self, ranking: &[u32], power: Uint128, num_choices: usize) -> StdResult<()> {
+        if self.round_tally.is_empty() {
+            self.round_tally = vec![Uint128::zero(); num_choices];
+        }
+        if let Some(option_id) = ranking
+            .iter()
+            .find(|option_id| !self.eliminated.contains(option_id))
+        {
+            let tally = &mut self.round_tally[*option_id as usize];
+            *tally = tally.checked_add(power).map_err(StdError::overflow)?;
+        }
+        Ok(())
+    }
+
+    /// Ends the current round once every ranking has been
+    /// counted. An option with a majority of the counted voting power
+    /// wins; otherwise the option(s) with the least voting power are
+    /// eliminated and the next round starts from the first ranking.
+    /// If every remaining option is tied, the runoff ends in a tie.
+    pub fn end_round(&mut self, num_choices: usize) {
+        let mut tally = std::mem::take(&mut self.round_tally);
+        tally.resize(num_choices, Uint128::zero());
+        self.cursor = None;
+
+        let total: Uint128 = tally.iter().sum();
+        let remaining: Vec<u32> = (0..num_choices as u32)
+            .filter(|option_id| !self.eliminated.contains(option_id))
+            .collect();
+        if let Some(winner) = remaining
+            .iter()
+            .find(|i| tally[**i as usize] > total - tally[**i as usize])
+        {
+            self.outcome = Some(RunoffOutcome::Winner(*winner));
+            return;
+        }
+
+        let fewest = remaining
+            .iter()
+            .map(|i| tally[*i as usize])
+            .min()
+            .unwrap_or_default();
+        let lowest: Vec<u32> = remaining
+            .iter()
+            .copied()
+            .filter(|i| tally[*i as usize] == fewest)
+            .collect();
+        // If every remaining option is tied, no option can be
+        // eliminated.
+        if lowest.len() == remaining.len() {
+            self.outcome = Some(RunoffOutcome::Tie);
+            return;
+        }
+        self.eliminated.extend(lowest);
+    }
+}
+
 /// Represents the type of Multiple choice option. "None of the above" has a special
 /// type for example.
 #[cw_serde]
@@ -216,6 +460,201 @@ mod test {
         assert_eq!(votes, MultipleChoiceVotes::zero(2))
     }
 
+    #[test]
+    fn test_display_multiple_choice_selection() {
+        let selection = MultipleChoiceSelection {
+            option_id: 1,
+            weight: None,
+        };
+        assert_eq!("1", selection.to_string());
+        let selection = MultipleChoiceSelection {
+            option_id: 1,
+            weight: Some(Decimal::percent(25)),
+        };
+        assert_eq!("1:0.25", selection.to_string())
+    }
+
+    #[test]
+    fn test_multiple_choice_selections() {
+        let mut votes = MultipleChoiceVotes::zero(3);
+        let approval = vec![
+            MultipleChoiceSelection {
+                option_id: 0,
+                weight: None,
+            },
+            MultipleChoiceSelection {
+                option_id: 2,
+                weight: None,
+            },
+        ];
+        votes.add_selections(&approval, Uint128::new(10)).unwrap();
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::new(10), Uint128::zero(), Uint128::new(10)]
+        );
+
+        let split = vec![
+            MultipleChoiceSelection {
+                option_id: 0,
+                weight: Some(Decimal::percent(25)),
+            },
+            MultipleChoiceSelection {
+                option_id: 1,
+                weight: Some(Decimal::percent(75)),
+            },
+        ];
+        votes.add_selections(&split, Uint128::new(8)).unwrap();
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::new(12), Uint128::new(6), Uint128::new(10)]
+        );
+
+        votes
+            .remove_selections(&approval, Uint128::new(10))
+            .unwrap();
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::new(2), Uint128::new(6), Uint128::zero()]
+        );
+        votes
+            .remove_selections(&approval, Uint128::new(10))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_validate_selections() {
+        let selection = |option_id, weight| MultipleChoiceSelection { option_id, weight };
+
+        MultipleChoiceVotes::validate_selections(&[selection(0, None), selection(2, None)], 3)
+            .unwrap();
+        MultipleChoiceVotes::validate_selections(
+            &[
+                selection(0, Some(Decimal::percent(40))),
+                selection(1, Some(Decimal::percent(60))),
+            ],
+            3,
+        )
+        .unwrap();
+
+        MultipleChoiceVotes::validate_selections(&[], 3).unwrap_err();
+        MultipleChoiceVotes::validate_selections(&[selection(3, None)], 3).unwrap_err();
+        MultipleChoiceVotes::validate_selections(&[selection(1, None), selection(1, None)], 3)
+            .unwrap_err();
+        // Mixed weighted and unweighted selections.
+        MultipleChoiceVotes::validate_selections(
+            &[selection(0, Some(Decimal::one())), selection(1, None)],
+            3,
+        )
+        .unwrap_err();
+        // Weights must sum to one.
+        MultipleChoiceVotes::validate_selections(
+            &[
+                selection(0, Some(Decimal::percent(40))),
+                selection(1, Some(Decimal::percent(40))),
+            ],
+            3,
+        )
+        .unwrap_err();
+        MultipleChoiceVotes::validate_selections(
+            &[
+                selection(0, Some(Decimal::one())),
+                selection(1, Some(Decimal::zero())),
+            ],
+            3,
+        )
+        .unwrap_err();
+    }
+
+    /// Runs the runoff to completion over `rankings`, counting one
+    /// ranking at a time as the proposal module does.
+    fn runoff(rankings: &[(&[u32], u128)], num_choices: usize) -> RunoffOutcome {
+        let mut votes = RankedChoiceVotes::default();
+        loop {
+            for (ranking, power) in rankings {
+                votes
+                    .count(ranking, Uint128::new(*power), num_choices)
+                    .unwrap();
+                votes.cursor = Some(RankedChoiceVotes::ranking_key(ranking).into());
+            }
+            votes.end_round(num_choices);
+            if let Some(outcome) = votes.outcome.take() {
+                return outcome;
+            }
+            assert_eq!(votes.cursor, None);
+        }
+    }
+
+    #[test]
+    fn test_ranking_key() {
+        let ranking = vec![20, 0, 3];
+        let key = RankedChoiceVotes::ranking_key(&ranking);
+        assert_eq!(key, vec![20, 0, 3]);
+        assert_eq!(RankedChoiceVotes::ranking_from_key(&key), ranking);
+    }
+
+    #[test]
+    fn test_ranked_choice_count() {
+        let mut votes = RankedChoiceVotes::default();
+        votes.count(&[0, 1], Uint128::new(10), 3).unwrap();
+        votes.count(&[1], Uint128::new(3), 3).unwrap();
+        assert_eq!(
+            votes.round_tally,
+            vec![Uint128::new(10), Uint128::new(3), Uint128::zero()]
+        );
+
+        // Option 2 has the fewest votes and is eliminated.
+        votes.end_round(3);
+        assert_eq!(votes.eliminated, vec![2]);
+        assert!(votes.round_tally.is_empty());
+        assert_eq!(votes.outcome, None);
+
+        // Ballots ranking only eliminated options are exhausted.
+        votes.count(&[2], Uint128::new(20), 3).unwrap();
+        votes.count(&[2, 1], Uint128::new(8), 3).unwrap();
+        assert_eq!(
+            votes.round_tally,
+            vec![Uint128::zero(), Uint128::new(8), Uint128::zero()]
+        );
+        votes.end_round(3);
+        assert_eq!(votes.outcome, Some(RunoffOutcome::Winner(1)));
+    }
+
+    #[test]
+    fn test_validate_ranking() {
+        RankedChoiceVotes::validate_ranking(&[2, 0, 1], 3).unwrap();
+        RankedChoiceVotes::validate_ranking(&[1], 3).unwrap();
+        RankedChoiceVotes::validate_ranking(&[], 3).unwrap_err();
+        RankedChoiceVotes::validate_ranking(&[0, 3], 3).unwrap_err();
+        RankedChoiceVotes::validate_ranking(&[0, 1, 0], 3).unwrap_err();
+    }
+
+    #[test]
+    fn test_instant_runoff() {
+        // First round majority.
+        assert_eq!(
+            runoff(&[(&[0, 1], 6), (&[1, 0], 4)], 3),
+            RunoffOutcome::Winner(0)
+        );
+
+        // Option 2 is eliminated and its ballots transfer to option 1.
+        assert_eq!(
+            runoff(&[(&[0], 4), (&[1, 0], 3), (&[2, 1], 2)], 4),
+            RunoffOutcome::Winner(1)
+        );
+
+        // Exhausted ballots do not count in later rounds.
+        assert_eq!(
+            runoff(&[(&[0], 4), (&[1], 3), (&[2], 2)], 3),
+            RunoffOutcome::Winner(0)
+        );
+
+        // Remaining options tied.
+        assert_eq!(runoff(&[(&[0, 2], 4), (&[1, 2], 4)], 3), RunoffOutcome::Tie);
+
+        // No votes.
+        assert_eq!(runoff(&[], 3), RunoffOutcome::Tie);
+    }
+
     #[test]
     fn test_into_checked() {
         let options = vec![
@@ -0,0 +1,198 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128, Uint256};
+use thiserror::Error;
+
+/// The number of fractional bits computed for logarithmic transforms.
+const LOG2_PRECISION: u32 = 40;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PowerTransformError {
+    #[error("Capped power transforms must have a non-zero cap")]
+    ZeroCap {},
+
+    #[error("Logarithmic power transforms must have a non-zero unit")]
+    ZeroUnit {},
+}
+
+/// A curve applied to raw voting power, for example a staked balance,
+/// to dampen the influence of large holders.
+#[cw_serde]
+pub enum PowerTransform {
+    /// Voting power is the square root of raw power.
+    Quadratic {},
+    /// Voting power is raw power, up to `cap`.
+    Capped { cap: Uint128 },
+    /// Voting power is `unit * log2(1 + raw / unit)`. Holders with up
+    /// to about `unit` raw power are barely affected, while each
+    /// doubling of raw power beyond that adds only `unit` voting power.
+    Logarithmic { unit: Uint128 },
+}
+
+impl PowerTransform {
+    pub fn validate(&self) -> Result<(), PowerTransformError> {
+        match self {
+            PowerTransform::Quadratic {} => Ok(()),
+            PowerTransform::Capped { cap } if cap.is_zero() => Err(PowerTransformError::ZeroCap {}),
+            PowerTransform::Capped { .. } => Ok(()),
+            PowerTransform::Logarithmic { unit } if unit.is_zero() => {
+                Err(PowerTransformError::ZeroUnit {})
+            }
+            PowerTransform::Logarithmic { .. } => Ok(()),
+        }
+    }
+
+    /// Applies the transform to `power`.
+    pub fn apply(&self, power: Uint128) -> StdResult<Uint128> {
+        Ok(match self {
+            PowerTransform::Quadratic {} => isqrt(power),
+            PowerTransform::Capped { cap } => power.min(*cap),
+            PowerTransform::Logarithmic { unit } => {
+                let log = log2_ratio(Uint256::from(power) + Uint256::from(*unit), (*unit).into());
+                unit.checked_mul_floor(log)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?
+            }
+        })
+    }
+
+    /// Applies the transform to both the old and new raw power of a
+    /// holder and returns the new transformed total given the old
+    /// transformed total. Used to keep a snapshot of the sum of
+    /// transformed powers up to date as raw powers change.
+    pub fn update_total(
+        &self,
+        total: Uint128,
+        old_power: Uint128,
+        new_power: Uint128,
+    ) -> StdResult<Uint128> {
+        Ok(total
+            .checked_sub(self.apply(old_power)?)?
+            .checked_add(self.apply(new_power)?)?)
+    }
+}
+
+/// The square root of `n`, rounded down.
+fn isqrt(n: Uint128) -> Uint128 {
+    if n.is_zero() {
+        return n;
+    }
+    // Newton's method. Iterates decrease monotonically towards the
+    // root from the first step on.
+    let mut x = n;
+    let mut y = n / Uint128::new(2) + n % Uint128::new(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / Uint128::new(2);
+    }
+    x
+}
+
+/// `log2(numerator / denominator)` for `numerator >= denominator > 0`.
+fn log2_ratio(numerator: Uint256, denominator: Uint256) -> Decimal {
+    let two = Uint256::from(2u8);
+
+    // The integer part is the number of times the denominator may be
+    // doubled without exceeding the numerator.
+    let mut bound = denominator;
+    let mut log = Decimal::zero();
+    while bound * two <= numerator {
+        bound = bound * two;
+        log = log + Decimal::one();
+    }
+
+    // The remaining ratio is in [1, 2). Squaring it shifts the next
+    // binary digit of its logarithm into the integer part.
+    let mut y = Decimal256::from_ratio(numerator, bound);
+    let two = Decimal256::percent(200);
+    for bit in 1..=LOG2_PRECISION {
+        y = y * y;
+        if y >= two {
+            y = y / two;
+            log = log + Decimal::from_ratio(1u128, 2u128.pow(bit));
+        }
+    }
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadratic() {
+        let transform = PowerTransform::Quadratic {};
+        assert_eq!(transform.apply(Uint128::zero()).unwrap(), Uint128::zero());
+        assert_eq!(transform.apply(Uint128::one()).unwrap(), Uint128::one());
+        assert_eq!(transform.apply(Uint128::new(99)).unwrap(), Uint128::new(9));
+        assert_eq!(
+            transform.apply(Uint128::new(100)).unwrap(),
+            Uint128::new(10)
+        );
+        assert_eq!(
+            transform.apply(Uint128::MAX).unwrap(),
+            Uint128::new(u64::MAX as u128)
+        );
+    }
+
+    #[test]
+    fn test_capped() {
+        let transform = PowerTransform::Capped {
+            cap: Uint128::new(10),
+        };
+        assert_eq!(transform.apply(Uint128::new(5)).unwrap(), Uint128::new(5));
+        assert_eq!(transform.apply(Uint128::new(50)).unwrap(), Uint128::new(10));
+        assert_eq!(
+            PowerTransform::Capped {
+                cap: Uint128::zero()
+            }
+            .validate(),
+            Err(PowerTransformError::ZeroCap {})
+        );
+    }
+
+    #[test]
+    fn test_logarithmic() {
+        let transform = PowerTransform::Logarithmic {
+            unit: Uint128::new(1_000_000),
+        };
+        assert_eq!(transform.apply(Uint128::zero()).unwrap(), Uint128::zero());
+        // 1 + 1 = 2^1
+        assert_eq!(
+            transform.apply(Uint128::new(1_000_000)).unwrap(),
+            Uint128::new(1_000_000)
+        );
+        // 1 + 3 = 2^2
+        assert_eq!(
+            transform.apply(Uint128::new(3_000_000)).unwrap(),
+            Uint128::new(2_000_000)
+        );
+        // log2(1.5) = 0.58496...
+        assert_eq!(
+            transform.apply(Uint128::new(500_000)).unwrap(),
+            Uint128::new(584_962)
+        );
+        assert!(transform.apply(Uint128::MAX).is_ok());
+        assert_eq!(
+            PowerTransform::Logarithmic {
+                unit: Uint128::zero()
+            }
+            .validate(),
+            Err(PowerTransformError::ZeroUnit {})
+        );
+    }
+
+    #[test]
+    fn test_update_total() {
+        let transform = PowerTransform::Quadratic {};
+        let total = transform
+            .update_total(Uint128::zero(), Uint128::zero(), Uint128::new(100))
+            .unwrap();
+        let total = transform
+            .update_total(total, Uint128::zero(), Uint128::new(16))
+            .unwrap();
+        assert_eq!(total, Uint128::new(14));
+        let total = transform
+            .update_total(total, Uint128::new(100), Uint128::new(25))
+            .unwrap();
+        assert_eq!(total, Uint128::new(9));
+    }
+}
@@ -1,7 +1,8 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{CosmosMsg, Empty};
+use cosmwasm_std::{CosmosMsg, Empty, Uint128};
 
 use crate::{
+    execution_plan::ExecutionBatch,
     multiple_choice::{MultipleChoiceAutoVote, MultipleChoiceOptions},
     voting::SingleChoiceAutoVote,
 };
@@ -36,8 +37,15 @@ pub struct SingleChoiceProposeMsg {
     /// pre-propose module is attached, this must be Some and will
     /// set the proposer of the proposal it creates.
     pub proposer: Option<String>,
-    /// An optional vote cast by the proposer.
+    /// An optional vote cast by the proposer. Must be None if the
+    /// proposal module has an amendment config, as voting does not
+    /// open until the amendment window closes.
     pub vote: Option<SingleChoiceAutoVote>,
+    /// An optional plan for executing the proposal's messages in
+    /// ordered batches, each of which may be scheduled and has its
+    /// own failure policy. If set, `msgs` must be empty.
+    #[serde(default)]
+    pub execution_plan: Option<Vec<ExecutionBatch>>,
 }
 
 /// The contents of a message to create a proposal in the multiple
@@ -65,6 +73,34 @@ pub struct MultipleChoiceProposeMsg {
     /// pre-propose module is attached, this must be Some and will
     /// set the proposer of the proposal it creates.
     pub proposer: Option<String>,
-    /// An optional vote cast by the proposer.
+    /// An optional vote cast by the proposer. Must be None if the
+    /// proposal module has an amendment config, as voting does not
+    /// open until the amendment window closes.
     pub vote: Option<MultipleChoiceAutoVote>,
 }
+
+/// The contents of a message to create a proposal in the conviction
+/// voting proposal module.
+///
+/// We move this type to this package for the same reasons as
+/// `SingleChoiceProposeMsg`: pre-propose modules can then type check
+/// their propose messages without importing dao-proposal-conviction.
+#[cw_serde]
+pub struct ConvictionProposeMsg {
+    /// The title of the proposal.
+    pub title: String,
+    /// A description of the proposal.
+    pub description: String,
+    /// The address that will receive the requested funds if the
+    /// proposal passes.
+    pub beneficiary: String,
+    /// The amount of the module's funding denom being requested from
+    /// the DAO's treasury.
+    pub amount: Uint128,
+    /// The address creating the proposal. If no pre-propose
+    /// module is attached to this module this must always be None
+    /// as the proposer is the sender of the propose message. If a
+    /// pre-propose module is attached, this must be Some and will
+    /// set the proposer of the proposal it creates.
+    pub proposer: Option<String>,
+}
@@ -10,6 +10,13 @@ const FAILED_VOTE_HOOK_MASK: u64 = 0b010;
 const PRE_PROPOSE_MODULE_INSTANTIATION_ID: u64 = 0b011;
 const FAILED_PRE_PROPOSE_MODULE_HOOK_ID: u64 = 0b100;
 
+/// Replies to execution plan batches carry both the proposal ID and
+/// the index of the batch, which is stored in the bits above the
+/// reply type.
+const PROPOSAL_BATCH_EXECUTION_MASK: u64 = 0b101;
+const BITS_RESERVED_FOR_BATCH_INDEX: u8 = 16;
+const BATCH_INDEX_MASK: u64 = (1 << BITS_RESERVED_FOR_BATCH_INDEX) - 1;
+
 const BITS_RESERVED_FOR_REPLY_TYPE: u8 = 3;
 const REPLY_TYPE_MASK: u64 = (1 << BITS_RESERVED_FOR_REPLY_TYPE) - 1;
 
@@ -32,6 +39,9 @@ pub enum TaggedReplyId {
     FailedPreProposeModuleHook,
     /// Fired when a pre-propose module is successfully instantiated.
     PreProposeModuleInstantiation,
+    /// Fired when a batch of a proposal's execution plan has been
+    /// executed, successfully or not.
+    ProposalBatchExecution { proposal_id: u64, batch: u64 },
 }
 
 impl TaggedReplyId {
@@ -50,6 +60,10 @@ impl TaggedReplyId {
             FAILED_VOTE_HOOK_MASK => Ok(TaggedReplyId::FailedVoteHook(id_after_shift)),
             PRE_PROPOSE_MODULE_INSTANTIATION_ID => Ok(TaggedReplyId::PreProposeModuleInstantiation),
             FAILED_PRE_PROPOSE_MODULE_HOOK_ID => Ok(TaggedReplyId::FailedPreProposeModuleHook),
+            PROPOSAL_BATCH_EXECUTION_MASK => Ok(TaggedReplyId::ProposalBatchExecution {
+                proposal_id: id_after_shift >> BITS_RESERVED_FOR_BATCH_INDEX,
+                batch: id_after_shift & BATCH_INDEX_MASK,
+            }),
             _ => Err(error::TagError::UnknownReplyId { id }),
         }
     }
@@ -68,6 +82,15 @@ pub const fn mask_vote_hook_index(index: u64) -> u64 {
     FAILED_VOTE_HOOK_MASK | (index << BITS_RESERVED_FOR_REPLY_TYPE)
 }
 
+/// This function can drop bits, if you have more than
+/// `u(64-[`BITS_RESERVED_FOR_REPLY_TYPE`]-[`BITS_RESERVED_FOR_BATCH_INDEX`])`
+/// proposals or more than `u16` batches.
+pub const fn mask_proposal_batch_execution(proposal_id: u64, batch: u64) -> u64 {
+    PROPOSAL_BATCH_EXECUTION_MASK
+        | ((batch & BATCH_INDEX_MASK) << BITS_RESERVED_FOR_REPLY_TYPE)
+        | (proposal_id << (BITS_RESERVED_FOR_REPLY_TYPE + BITS_RESERVED_FOR_BATCH_INDEX))
+}
+
 pub const fn pre_propose_module_instantiation_id() -> u64 {
     PRE_PROPOSE_MODULE_INSTANTIATION_ID
 }
@@ -100,6 +123,9 @@ mod test {
         let m_proposal_id = mask_proposal_execution_proposal_id(proposal_id_max);
         let m_proposal_hook_idx = mask_proposal_hook_index(proposal_hook_idx);
         let m_vote_hook_idx = mask_vote_hook_index(vote_hook_idx);
+        // max u45
+        let batch_proposal_id_max: u64 = 2_u64.pow(45) - 1;
+        let m_batch = mask_proposal_batch_execution(batch_proposal_id_max, 63);
 
         assert_eq!(
             TaggedReplyId::new(m_proposal_id).unwrap(),
@@ -113,6 +139,13 @@ mod test {
             TaggedReplyId::new(m_vote_hook_idx).unwrap(),
             TaggedReplyId::FailedVoteHook(vote_hook_idx)
         );
+        assert_eq!(
+            TaggedReplyId::new(m_batch).unwrap(),
+            TaggedReplyId::ProposalBatchExecution {
+                proposal_id: batch_proposal_id_max,
+                batch: 63,
+            }
+        );
         assert_eq!(
             TaggedReplyId::new(0b110).unwrap_err(),
             error::TagError::UnknownReplyId { id: 0b110 }
@@ -118,6 +118,14 @@ pub enum Threshold {
     /// An absolute number of votes needed for something to cross the
     /// threshold. Useful for multisig style voting.
     AbsoluteCount { threshold: Uint128 },
+
+    /// Proposals pass when they expire unless a percentage of the
+    /// total weight >= `objection_threshold` votes No, in which case
+    /// they are rejected. Useful for routine proposals that should
+    /// not need active participation to pass.
+    Optimistic {
+        objection_threshold: PercentageThreshold,
+    },
 }
 
 /// Asserts that the 0.0 < percent <= 1.0
@@ -155,6 +163,7 @@ impl Threshold {
     /// - Quorums must never be over 100%.
     /// - Passing thresholds must never be over 100%, nor be 0%.
     /// - Absolute count thresholds must be non-zero.
+    /// - Objection thresholds must never be over 100%, nor be 0%.
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
             Threshold::AbsolutePercentage {
@@ -171,6 +180,9 @@ impl Threshold {
                     Ok(())
                 }
             }
+            Threshold::Optimistic {
+                objection_threshold,
+            } => validate_percentage(objection_threshold),
         }
     }
 }
@@ -231,5 +243,14 @@ mod tests {
             t.validate().unwrap_err(),
             ThresholdError::UnreachableThreshold {}
         );
+        let t = Threshold::Optimistic {
+            objection_threshold: p!(0),
+        };
+        assert_eq!(t.validate().unwrap_err(), ThresholdError::ZeroThreshold {});
+
+        let t = Threshold::Optimistic {
+            objection_threshold: PercentageThreshold::Majority {},
+        };
+        t.validate().unwrap();
     }
 }
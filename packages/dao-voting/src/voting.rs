@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Deps, StdResult, Uint128, Uint256};
+use cosmwasm_std::{Addr, Decimal, Deps, StdError, StdResult, Uint128, Uint256};
 use cw_utils::Duration;
-use dao_interface::voting;
+use dao_interface::{delegation, voting};
 
 use crate::threshold::PercentageThreshold;
 
@@ -29,6 +29,17 @@ pub enum Vote {
     Abstain,
 }
 
+/// A share of a voter's voting power cast for a single position on a
+/// split vote.
+#[cw_serde]
+#[derive(Copy)]
+pub struct WeightedVote {
+    /// The position.
+    pub vote: Vote,
+    /// The share of the voter's voting power cast for this position.
+    pub weight: Decimal,
+}
+
 #[cw_serde]
 pub struct SingleChoiceAutoVote {
     /// The proposer's position on the proposal.
@@ -136,6 +147,36 @@ pub fn does_vote_count_fail(
     }
 }
 
+/// Returns the least amount of voting power, no greater than `max`,
+/// for which `decided` holds. `decided` must be monotonic: if it holds
+/// for some amount of voting power it must hold for all larger
+/// amounts. Returns None if it does not hold for `max`.
+pub fn least_power_needed(max: Uint128, decided: impl Fn(Uint128) -> bool) -> Option<Uint128> {
+    if !decided(max) {
+        return None;
+    }
+    let (mut low, mut high) = (Uint128::zero(), max);
+    while low < high {
+        let mid = low + (high - low) / Uint128::new(2);
+        if decided(mid) {
+            high = mid;
+        } else {
+            low = mid + Uint128::one();
+        }
+    }
+    Some(low)
+}
+
+/// The share of `total_power` that `voted` represents, or zero if
+/// there is no voting power.
+pub fn turnout(voted: Uint128, total_power: Uint128) -> Decimal {
+    if total_power.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(voted, total_power)
+    }
+}
+
 impl Votes {
     /// Constructs an zero'd out votes struct.
     pub fn zero() -> Self {
@@ -177,6 +218,23 @@ impl Votes {
         }
     }
 
+    /// Adds a split vote to the votes, dividing `power` between
+    /// positions by weight.
+    pub fn add_weighted_vote(&mut self, votes: &[WeightedVote], power: Uint128) {
+        for (vote, power) in split_voting_power(votes, power) {
+            self.add_vote(vote, power)
+        }
+    }
+
+    /// Removes a split vote from the votes. The split vote being
+    /// removed must have been previously added with the same power or
+    /// this method will cause an overflow.
+    pub fn remove_weighted_vote(&mut self, votes: &[WeightedVote], power: Uint128) {
+        for (vote, power) in split_voting_power(votes, power) {
+            self.remove_vote(vote, power)
+        }
+    }
+
     /// Computes the total number of votes cast.
     ///
     /// NOTE: The total number of votes avaliable from a voting module
@@ -189,6 +247,52 @@ impl Votes {
     }
 }
 
+/// Divides `power` between the positions of a split vote. Any power
+/// lost to rounding is given to the last position so that the full
+/// voting power is always cast.
+fn split_voting_power(votes: &[WeightedVote], power: Uint128) -> Vec<(Vote, Uint128)> {
+    let mut remaining = power;
+    let mut split = Vec::with_capacity(votes.len());
+    for (i, weighted) in votes.iter().enumerate() {
+        let share = if i == votes.len() - 1 {
+            remaining
+        } else {
+            power.mul_floor(weighted.weight).min(remaining)
+        };
+        remaining -= share;
+        split.push((weighted.vote, share));
+    }
+    split
+}
+
+/// Validates that a split vote casts non-zero shares of voting power
+/// for distinct positions, and that the shares sum to one.
+pub fn validate_weighted_votes(votes: &[WeightedVote]) -> StdResult<()> {
+    if votes.is_empty() {
+        return Err(StdError::generic_err("Weighted vote must not be empty"));
+    }
+    let mut total = Decimal::zero();
+    for (i, weighted) in votes.iter().enumerate() {
+        if weighted.weight.is_zero() {
+            return Err(StdError::generic_err("Vote weights must be non-zero"));
+        }
+        if votes[..i].iter().any(|w| w.vote == weighted.vote) {
+            return Err(StdError::generic_err("Duplicate position in weighted vote"));
+        }
+        total = total.checked_add(weighted.weight)?;
+    }
+    if total != Decimal::one() {
+        return Err(StdError::generic_err("Vote weights must sum to one"));
+    }
+    Ok(())
+}
+
+impl std::fmt::Display for WeightedVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.vote, self.weight)
+    }
+}
+
 impl std::fmt::Display for Vote {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -216,6 +320,43 @@ pub fn get_voting_power(
     Ok(response.power)
 }
 
+/// Returns the voting power delegated to `delegate` via the vote
+/// delegation module `delegation_module` at `height`. This does not
+/// include the delegate's own voting power.
+pub fn get_delegated_voting_power(
+    deps: Deps,
+    delegate: &Addr,
+    delegation_module: &Addr,
+    height: Option<u64>,
+) -> StdResult<Uint128> {
+    let response: delegation::DelegatedVotingPowerResponse = deps.querier.query_wasm_smart(
+        delegation_module,
+        &delegation::Query::DelegatedVotingPower {
+            delegate: delegate.to_string(),
+            height,
+        },
+    )?;
+    Ok(response.power)
+}
+
+/// Returns the delegations made by `delegator` in the vote delegation
+/// module `delegation_module` at `height`.
+pub fn get_delegations(
+    deps: Deps,
+    delegator: &Addr,
+    delegation_module: &Addr,
+    height: Option<u64>,
+) -> StdResult<Vec<delegation::Delegation>> {
+    let response: delegation::DelegationsResponse = deps.querier.query_wasm_smart(
+        delegation_module,
+        &delegation::Query::Delegations {
+            delegator: delegator.to_string(),
+            height,
+        },
+    )?;
+    Ok(response.delegations)
+}
+
 /// A height of None will query for the current block height.
 pub fn get_total_power(deps: Deps, dao: &Addr, height: Option<u64>) -> StdResult<Uint128> {
     let response: voting::TotalPowerAtHeightResponse = deps
@@ -265,6 +406,51 @@ mod test {
         assert_eq!(votes.abstain, Uint128::new(40));
     }
 
+    #[test]
+    fn count_weighted_votes() {
+        let split = vec![
+            WeightedVote {
+                vote: Vote::Yes,
+                weight: Decimal::percent(60),
+            },
+            WeightedVote {
+                vote: Vote::No,
+                weight: Decimal::percent(30),
+            },
+            WeightedVote {
+                vote: Vote::Abstain,
+                weight: Decimal::percent(10),
+            },
+        ];
+        let mut votes = Votes::zero();
+        votes.add_weighted_vote(&split, Uint128::new(33));
+
+        // Power lost to rounding goes to the last position.
+        assert_eq!(votes.yes, Uint128::new(19));
+        assert_eq!(votes.no, Uint128::new(9));
+        assert_eq!(votes.abstain, Uint128::new(5));
+        assert_eq!(votes.total(), Uint128::new(33));
+
+        votes.remove_weighted_vote(&split, Uint128::new(33));
+        assert_eq!(votes, Votes::zero());
+    }
+
+    #[test]
+    fn validate_weighted_vote() {
+        let weighted = |vote, percent| WeightedVote {
+            vote,
+            weight: Decimal::percent(percent),
+        };
+
+        validate_weighted_votes(&[weighted(Vote::Yes, 70), weighted(Vote::No, 30)]).unwrap();
+        validate_weighted_votes(&[weighted(Vote::Abstain, 100)]).unwrap();
+
+        validate_weighted_votes(&[]).unwrap_err();
+        validate_weighted_votes(&[weighted(Vote::Yes, 70), weighted(Vote::No, 20)]).unwrap_err();
+        validate_weighted_votes(&[weighted(Vote::Yes, 50), weighted(Vote::Yes, 50)]).unwrap_err();
+        validate_weighted_votes(&[weighted(Vote::Yes, 100), weighted(Vote::No, 0)]).unwrap_err();
+    }
+
     #[test]
     fn vote_comparisons() {
         assert!(!compare_vote_count(
@@ -461,4 +647,40 @@ mod test {
             Decimal::percent(0)
         ))
     }
+
+    #[test]
+    fn least_power_needed_majority() {
+        // A majority of 100 requires strictly more than 50.
+        let needed = least_power_needed(Uint128::new(100), |power| {
+            does_vote_count_pass(power, Uint128::new(100), PercentageThreshold::Majority {})
+        });
+        assert_eq!(needed, Some(Uint128::new(51)));
+
+        let needed = least_power_needed(Uint128::new(100), |power| {
+            does_vote_count_pass(
+                power,
+                Uint128::new(100),
+                PercentageThreshold::Percent(Decimal::percent(50)),
+            )
+        });
+        assert_eq!(needed, Some(Uint128::new(50)));
+
+        let needed = least_power_needed(Uint128::new(40), |power| {
+            does_vote_count_pass(power, Uint128::new(100), PercentageThreshold::Majority {})
+        });
+        assert_eq!(needed, None);
+        assert_eq!(
+            least_power_needed(Uint128::zero(), |_| true),
+            Some(Uint128::zero())
+        );
+    }
+
+    #[test]
+    fn turnout_of_zero_power() {
+        assert_eq!(turnout(Uint128::zero(), Uint128::zero()), Decimal::zero());
+        assert_eq!(
+            turnout(Uint128::new(1), Uint128::new(4)),
+            Decimal::percent(25)
+        );
+    }
 }